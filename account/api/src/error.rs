@@ -23,6 +23,15 @@ pub enum AccountError {
 
     #[error("invalid public key: {0:?}")]
     InvalidPublicKey(starcoin_crypto::CryptoMaterialError),
+
+    #[error("invalid mnemonic: {0:?}")]
+    InvalidMnemonic(anyhow::Error),
+    #[error("hd wallet seed already exists")]
+    HDSeedAlreadyExist,
+    #[error("hd wallet seed not exists, import a mnemonic first")]
+    HDSeedNotExist,
+    #[error("invalid password, cannot decrypt hd wallet seed")]
+    InvalidHDSeedPassword,
     // logic error
     #[error("transaction sign error, {0:?}")]
     TransactionSignError(anyhow::Error),
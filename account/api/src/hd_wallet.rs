@@ -0,0 +1,84 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hierarchical deterministic accounts.
+//!
+//! The wallet seed is generated from a BIP39 mnemonic, and the Ed25519 private key of every
+//! account is derived from the seed by SLIP-0010, with the path `m/44'/101010'/{index}'/0'/0'`.
+
+use anyhow::{ensure, format_err, Result};
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha512;
+use starcoin_crypto::ed25519::Ed25519PrivateKey;
+use std::convert::TryFrom;
+
+/// The SLIP-0044 coin type of Starcoin.
+pub const STARCOIN_COIN_TYPE: u32 = 101010;
+/// The entropy size of the generated mnemonic, 32 bytes for 24 words.
+pub const MNEMONIC_ENTROPY_SIZE: usize = 32;
+
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+const ED25519_CURVE_SEED: &[u8] = b"ed25519 seed";
+
+/// Generate a new random 24 words BIP39 mnemonic.
+pub fn generate_mnemonic() -> String {
+    let mut entropy = [0u8; MNEMONIC_ENTROPY_SIZE];
+    rand::rngs::OsRng.fill_bytes(&mut entropy);
+    Mnemonic::from_entropy(&entropy)
+        .expect("entropy of 32 bytes should be valid")
+        .to_string()
+}
+
+/// Convert the BIP39 `mnemonic` to the 64 bytes wallet seed, with an empty passphrase.
+pub fn mnemonic_to_seed(mnemonic: &str) -> Result<Vec<u8>> {
+    let words: Vec<String> = mnemonic
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect();
+    let mnemonic = Mnemonic::parse_normalized(words.join(" ").as_str())
+        .map_err(|e| format_err!("Invalid mnemonic: {}", e))?;
+    Ok(mnemonic.to_seed_normalized("").to_vec())
+}
+
+/// The derivation path of the account with `index`, all the levels are hardened.
+pub fn derivation_path(index: u32) -> Vec<u32> {
+    vec![44, STARCOIN_COIN_TYPE, index, 0, 0]
+}
+
+/// Derive the private key of the account with `index` from the wallet `seed`.
+pub fn derive_private_key(seed: &[u8], index: u32) -> Result<Ed25519PrivateKey> {
+    let key = derive_ed25519_key(seed, &derivation_path(index))?;
+    Ed25519PrivateKey::try_from(key.as_slice())
+        .map_err(|e| format_err!("Invalid derived private key: {:?}", e))
+}
+
+/// SLIP-0010 Ed25519 key derivation, only hardened child keys are supported by Ed25519,
+/// every index in the `path` is hardened.
+pub fn derive_ed25519_key(seed: &[u8], path: &[u32]) -> Result<[u8; 32]> {
+    let (mut key, mut chain_code) = hmac_sha512(ED25519_CURVE_SEED, &[seed]);
+    for index in path {
+        ensure!(
+            *index < HARDENED_OFFSET,
+            "Derivation index {} is out of range",
+            index
+        );
+        let hardened_index = (*index | HARDENED_OFFSET).to_be_bytes();
+        (key, chain_code) = hmac_sha512(&chain_code, &[&[0u8], &key, &hardened_index]);
+    }
+    Ok(key)
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC can take key of any size");
+    for d in data {
+        mac.update(d);
+    }
+    let result = mac.finalize().into_bytes();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&result[..32]);
+    right.copy_from_slice(&result[32..]);
+    (left, right)
+}
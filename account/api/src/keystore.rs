@@ -0,0 +1,148 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The portable JSON keystore file, the private key is encrypted by the password,
+//! so that the account can be moved between machines without handling the raw private key.
+
+use anyhow::{ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_decrypt::{
+    EncryptedData, EncryptionParams, KeyDerivationParams, AES_NONCE_SIZE, KDF_SALT_SIZE,
+};
+use starcoin_types::account_address::AccountAddress;
+use std::convert::TryInto;
+use std::path::Path;
+
+pub const KEYSTORE_VERSION: u32 = 1;
+pub const KEYSTORE_CIPHER: &str = "aes-256-gcm";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyStore {
+    pub version: u32,
+    pub address: AccountAddress,
+    pub crypto: KeyStoreCrypto,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyStoreCrypto {
+    pub cipher: String,
+    /// hex encoded ciphertext of the private key
+    pub ciphertext: String,
+    pub cipherparams: CipherParams,
+    #[serde(flatten)]
+    pub kdf: KeyStoreKdf,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CipherParams {
+    /// hex encoded nonce
+    pub nonce: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+pub enum KeyStoreKdf {
+    Pbkdf2 {
+        iterations: u32,
+        salt: String,
+    },
+    Argon2id {
+        mem_cost: u32,
+        time_cost: u32,
+        lanes: u32,
+        salt: String,
+    },
+}
+
+impl KeyStore {
+    /// Encrypt the `private_key` of the account `address` by `password`.
+    pub fn encrypt(address: AccountAddress, private_key: &[u8], password: &str) -> Self {
+        let data = EncryptedData::encrypt(password.as_bytes(), private_key);
+        let kdf = match data.key_derive_params {
+            KeyDerivationParams::Pbkdf2 { iterations, salt } => KeyStoreKdf::Pbkdf2 {
+                iterations,
+                salt: hex::encode(salt),
+            },
+            KeyDerivationParams::Argon2id {
+                mem_cost,
+                time_cost,
+                lanes,
+                salt,
+            } => KeyStoreKdf::Argon2id {
+                mem_cost,
+                time_cost,
+                lanes,
+                salt: hex::encode(salt),
+            },
+        };
+        Self {
+            version: KEYSTORE_VERSION,
+            address,
+            crypto: KeyStoreCrypto {
+                cipher: KEYSTORE_CIPHER.to_string(),
+                ciphertext: hex::encode(data.ciphertext),
+                cipherparams: CipherParams {
+                    nonce: hex::encode(data.encryption_params.nonce),
+                },
+                kdf,
+            },
+        }
+    }
+
+    /// Decrypt the private key by `password`.
+    pub fn decrypt(&self, password: &str) -> Result<Vec<u8>> {
+        ensure!(
+            self.version == KEYSTORE_VERSION,
+            "Unsupported keystore version: {}",
+            self.version
+        );
+        ensure!(
+            self.crypto.cipher == KEYSTORE_CIPHER,
+            "Unsupported keystore cipher: {}",
+            self.crypto.cipher
+        );
+        let key_derive_params = match &self.crypto.kdf {
+            KeyStoreKdf::Pbkdf2 { iterations, salt } => KeyDerivationParams::Pbkdf2 {
+                iterations: *iterations,
+                salt: decode_hex_array::<KDF_SALT_SIZE>(salt)?,
+            },
+            KeyStoreKdf::Argon2id {
+                mem_cost,
+                time_cost,
+                lanes,
+                salt,
+            } => KeyDerivationParams::Argon2id {
+                mem_cost: *mem_cost,
+                time_cost: *time_cost,
+                lanes: *lanes,
+                salt: decode_hex_array::<KDF_SALT_SIZE>(salt)?,
+            },
+        };
+        key_derive_params.check()?;
+        let data = EncryptedData {
+            key_derive_params,
+            encryption_params: EncryptionParams {
+                nonce: decode_hex_array::<AES_NONCE_SIZE>(&self.crypto.cipherparams.nonce)?,
+            },
+            ciphertext: hex::decode(&self.crypto.ciphertext)?,
+        };
+        data.decrypt(password.as_bytes())
+            .map_err(|_| format_err!("Invalid password, cannot decrypt the keystore"))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(content.as_str())?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+fn decode_hex_array<const N: usize>(data: &str) -> Result<[u8; N]> {
+    hex::decode(data)?
+        .try_into()
+        .map_err(|v: Vec<u8>| format_err!("Invalid length {}, expect {}", v.len(), N))
+}
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod error;
+pub mod hd_wallet;
+pub mod keystore;
 pub mod message;
 mod provider;
 mod rich_wallet;
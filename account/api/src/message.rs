@@ -36,6 +36,14 @@ pub enum AccountRequest {
         private_key: Vec<u8>,
         password: String,
     },
+    ImportMnemonic {
+        mnemonic: String,
+        password: String,
+    },
+    DeriveAccount {
+        index: Option<u32>,
+        password: String,
+    },
     ImportReadonlyAccount {
         address: AccountAddress,
         public_key: Vec<u8>,
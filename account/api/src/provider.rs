@@ -11,6 +11,7 @@ pub enum AccountProviderStrategy {
     RPC,
     Local,
     PrivateKey,
+    Remote,
 }
 
 pub trait AccountProvider {
@@ -48,6 +49,12 @@ pub trait AccountProvider {
         password: String,
     ) -> Result<AccountInfo>;
 
+    /// Import the BIP39 mnemonic as the hd wallet seed, and create the first derived account.
+    fn import_mnemonic(&self, mnemonic: String, password: String) -> Result<AccountInfo>;
+
+    /// Derive the account with `index` from the hd wallet seed, or with the next index if `index` is None.
+    fn derive_account(&self, index: Option<u32>, password: String) -> Result<AccountInfo>;
+
     fn import_readonly_account(
         &self,
         address: AccountAddress,
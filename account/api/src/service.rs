@@ -48,6 +48,12 @@ pub trait AccountAsyncService:
         password: String,
     ) -> Result<AccountInfo>;
 
+    /// Import the BIP39 mnemonic as the hd wallet seed, and create the first derived account.
+    async fn import_mnemonic(&self, mnemonic: String, password: String) -> Result<AccountInfo>;
+
+    /// Derive the account with `index` from the hd wallet seed, or with the next index if `index` is None.
+    async fn derive_account(&self, index: Option<u32>, password: String) -> Result<AccountInfo>;
+
     async fn import_readonly_account(
         &self,
         address: AccountAddress,
@@ -206,6 +212,28 @@ where
         }
     }
 
+    async fn import_mnemonic(&self, mnemonic: String, password: String) -> Result<AccountInfo> {
+        let response = self
+            .send(AccountRequest::ImportMnemonic { mnemonic, password })
+            .await??;
+        if let AccountResponse::AccountInfo(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn derive_account(&self, index: Option<u32>, password: String) -> Result<AccountInfo> {
+        let response = self
+            .send(AccountRequest::DeriveAccount { index, password })
+            .await??;
+        if let AccountResponse::AccountInfo(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
     async fn import_readonly_account(
         &self,
         address: AccountAddress,
@@ -1,5 +1,10 @@
 mod local_provider;
 mod private_key_provider;
 mod provider;
+mod remote_provider;
+pub mod remote_signer;
 mod rpc_provider;
+pub use local_provider::AccountLocalProvider;
+pub use private_key_provider::AccountPrivateKeyProvider;
 pub use provider::ProviderFactory;
+pub use remote_provider::AccountRemoteProvider;
@@ -95,6 +95,20 @@ impl AccountProvider for AccountLocalProvider {
             .map(|account| account.info())
     }
 
+    fn import_mnemonic(&self, mnemonic: String, password: String) -> anyhow::Result<AccountInfo> {
+        self.manager
+            .import_mnemonic(mnemonic.as_str(), password.as_str())
+            .map_err(|e| e.into())
+            .map(|account| account.info())
+    }
+
+    fn derive_account(&self, index: Option<u32>, password: String) -> anyhow::Result<AccountInfo> {
+        self.manager
+            .derive_account(index, password.as_str())
+            .map_err(|e| e.into())
+            .map(|account| account.info())
+    }
+
     fn import_readonly_account(
         &self,
         address: AccountAddress,
@@ -113,6 +113,18 @@ impl AccountProvider for AccountPrivateKeyProvider {
         bail!("Unsupported")
     }
 
+    fn import_mnemonic(&self, _mnemonic: String, _password: String) -> anyhow::Result<AccountInfo> {
+        bail!("Unsupported")
+    }
+
+    fn derive_account(
+        &self,
+        _index: Option<u32>,
+        _password: String,
+    ) -> anyhow::Result<AccountInfo> {
+        bail!("Unsupported")
+    }
+
     fn import_readonly_account(
         &self,
         _address: AccountAddress,
@@ -1,3 +1,5 @@
+use crate::remote_provider::AccountRemoteProvider;
+use crate::remote_signer::SignerAuth;
 use crate::rpc_provider::AccountRpcProvider;
 use crate::{
     local_provider::AccountLocalProvider, private_key_provider::AccountPrivateKeyProvider,
@@ -38,6 +40,19 @@ impl ProviderFactory {
                 Ok(p) => Ok(Box::new(p)),
                 Err(e) => Err(e),
             },
+            AccountProviderStrategy::Remote => {
+                let provider = AccountRemoteProvider::create(
+                    config
+                        .remote_signer
+                        .as_ref()
+                        .ok_or_else(|| anyhow!("expect endpoint for remote signer"))?
+                        .parse()?,
+                );
+                Ok(Box::new(match &config.remote_signer_secret_file {
+                    Some(path) => provider.with_auth(SignerAuth::load(path.as_path())?),
+                    None => provider,
+                }))
+            }
         }
     }
 }
@@ -0,0 +1,375 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::remote_signer::{
+    SignMessageParams, SignTxnParams, SignerAuth, SignerEndpoint, SignerRequest, SignerResponse,
+    SIGNER_METHOD_ACCOUNTS, SIGNER_METHOD_SIGN_MESSAGE, SIGNER_METHOD_SIGN_TXN,
+};
+use anyhow::{bail, format_err, Result};
+use bcs_ext::BCSCodec;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use starcoin_account_api::{AccountInfo, AccountProvider};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::token_code::TokenCode;
+use starcoin_types::sign_message::{SignedMessage, SigningMessage};
+use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+const SIGNER_IO_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Forward the signing requests to an external signer, the private keys never touch this host.
+/// The accounts are managed by the signer, so only the read and sign methods are supported.
+pub struct AccountRemoteProvider {
+    endpoint: SignerEndpoint,
+    auth: Option<SignerAuth>,
+    next_id: AtomicU64,
+}
+
+impl AccountRemoteProvider {
+    pub fn create(endpoint: SignerEndpoint) -> Self {
+        Self {
+            endpoint,
+            auth: None,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Authenticate the requests and the responses by the shared secret of the signer.
+    pub fn with_auth(mut self, auth: SignerAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    fn call<P, R>(&self, method: &str, params: P) -> Result<R>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let mut request = SignerRequest::new(id, method, serde_json::to_value(params)?);
+        if let Some(auth) = &self.auth {
+            auth.sign_request(&mut request)?;
+        }
+        let response = match &self.endpoint {
+            SignerEndpoint::Tcp(addr) => {
+                let stream = TcpStream::connect_timeout(addr, SIGNER_IO_TIMEOUT)?;
+                stream.set_read_timeout(Some(SIGNER_IO_TIMEOUT))?;
+                stream.set_write_timeout(Some(SIGNER_IO_TIMEOUT))?;
+                Self::send(stream, &request)?
+            }
+            #[cfg(unix)]
+            SignerEndpoint::Unix(path) => {
+                let stream = std::os::unix::net::UnixStream::connect(path)?;
+                stream.set_read_timeout(Some(SIGNER_IO_TIMEOUT))?;
+                stream.set_write_timeout(Some(SIGNER_IO_TIMEOUT))?;
+                Self::send(stream, &request)?
+            }
+        };
+        if response.id != id {
+            bail!(
+                "Unexpected remote signer response id {}, expect {}",
+                response.id,
+                id
+            );
+        }
+        if let (Some(auth), Some(timestamp)) = (&self.auth, request.timestamp) {
+            auth.verify_response(&response, timestamp)?;
+        }
+        Ok(serde_json::from_value(response.into_result()?)?)
+    }
+
+    fn send<S>(mut stream: S, request: &SignerRequest) -> Result<SignerResponse>
+    where
+        S: Read + Write,
+    {
+        serde_json::to_writer(&mut stream, request)?;
+        stream.write_all(b"\n")?;
+        stream.flush()?;
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line)?;
+        if line.is_empty() {
+            bail!("Remote signer closed the connection without response");
+        }
+        Ok(serde_json::from_str(line.as_str())?)
+    }
+
+    fn get_account_or_err(&self, address: AccountAddress) -> Result<AccountInfo> {
+        self.get_account(address)?.ok_or_else(|| {
+            format_err!(
+                "Account {} not exists in remote signer {}",
+                address,
+                self.endpoint
+            )
+        })
+    }
+}
+
+impl AccountProvider for AccountRemoteProvider {
+    fn create_account(&self, _password: String) -> Result<AccountInfo> {
+        bail!("Unsupported")
+    }
+
+    fn get_default_account(&self) -> Result<Option<AccountInfo>> {
+        let accounts = self.get_accounts()?;
+        Ok(accounts
+            .iter()
+            .find(|account| account.is_default)
+            .or_else(|| accounts.first())
+            .cloned())
+    }
+
+    fn set_default_account(&self, _address: AccountAddress) -> Result<AccountInfo> {
+        bail!("Unsupported")
+    }
+
+    fn get_accounts(&self) -> Result<Vec<AccountInfo>> {
+        self.call(SIGNER_METHOD_ACCOUNTS, ())
+    }
+
+    fn get_account(&self, address: AccountAddress) -> Result<Option<AccountInfo>> {
+        Ok(self
+            .get_accounts()?
+            .into_iter()
+            .find(|account| account.address == address))
+    }
+
+    fn sign_message(
+        &self,
+        address: AccountAddress,
+        message: SigningMessage,
+    ) -> Result<SignedMessage> {
+        let signed_message: String = self.call(
+            SIGNER_METHOD_SIGN_MESSAGE,
+            SignMessageParams {
+                address,
+                message: hex::encode(message.encode()?),
+            },
+        )?;
+        let signed_message = SignedMessage::decode(hex::decode(signed_message)?.as_slice())?;
+        signed_message.check_signature()?;
+        Ok(signed_message)
+    }
+
+    fn sign_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        signer_address: AccountAddress,
+    ) -> Result<SignedUserTransaction> {
+        if raw_txn.sender() != signer_address {
+            bail!(
+                "The txn sender {} is not the signer {}",
+                raw_txn.sender(),
+                signer_address
+            );
+        }
+        let signed_txn: String = self.call(
+            SIGNER_METHOD_SIGN_TXN,
+            SignTxnParams {
+                raw_txn: hex::encode(raw_txn.encode()?),
+            },
+        )?;
+        let signed_txn = SignedUserTransaction::decode(hex::decode(signed_txn)?.as_slice())?;
+        if signed_txn.raw_txn() != &raw_txn {
+            bail!("The txn signed by remote signer is not the requested txn");
+        }
+        Ok(signed_txn.check_signature()?.into_inner())
+    }
+
+    fn unlock_account(
+        &self,
+        address: AccountAddress,
+        _password: String,
+        _duration: Duration,
+    ) -> Result<AccountInfo> {
+        // the remote signer decides whether to sign, there is nothing to unlock here.
+        self.get_account_or_err(address)
+    }
+
+    fn lock_account(&self, _address: AccountAddress) -> Result<AccountInfo> {
+        bail!("Unsupported")
+    }
+
+    fn import_account(
+        &self,
+        _address: AccountAddress,
+        _private_key: Vec<u8>,
+        _password: String,
+    ) -> Result<AccountInfo> {
+        bail!("Unsupported")
+    }
+
+    fn import_mnemonic(&self, _mnemonic: String, _password: String) -> Result<AccountInfo> {
+        bail!("Unsupported")
+    }
+
+    fn derive_account(&self, _index: Option<u32>, _password: String) -> Result<AccountInfo> {
+        bail!("Unsupported")
+    }
+
+    fn import_readonly_account(
+        &self,
+        _address: AccountAddress,
+        _public_key: Vec<u8>,
+    ) -> Result<AccountInfo> {
+        bail!("Unsupported")
+    }
+
+    fn export_account(&self, _address: AccountAddress, _password: String) -> Result<Vec<u8>> {
+        bail!("Unsupported")
+    }
+
+    fn accepted_tokens(&self, _address: AccountAddress) -> Result<Vec<TokenCode>> {
+        bail!("Unsupported")
+    }
+
+    fn change_account_password(
+        &self,
+        _address: AccountAddress,
+        _new_password: String,
+    ) -> Result<AccountInfo> {
+        bail!("Unsupported")
+    }
+
+    fn remove_account(
+        &self,
+        _address: AccountAddress,
+        _password: Option<String>,
+    ) -> Result<AccountInfo> {
+        bail!("Unsupported")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local_provider::AccountLocalProvider;
+    use crate::remote_signer::RemoteSigner;
+    use serde_json::Value;
+    use starcoin_types::genesis_config::ChainId;
+    use starcoin_types::transaction::{Script, TransactionPayload};
+    use std::net::{SocketAddr, TcpListener};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_remote_provider() -> Result<()> {
+        let tempdir = tempfile::tempdir()?;
+        let local_provider = AccountLocalProvider::create(tempdir.path(), ChainId::test())?;
+        let account = local_provider.create_account("hello".to_string())?;
+        let locked_account = local_provider.create_account("hello".to_string())?;
+        local_provider.unlock_account(
+            account.address,
+            "hello".to_string(),
+            Duration::from_secs(60),
+        )?;
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let endpoint = SignerEndpoint::Tcp(listener.local_addr()?);
+        std::thread::spawn(move || RemoteSigner::new(local_provider).run_tcp(listener));
+
+        let provider = AccountRemoteProvider::create(endpoint);
+        assert_eq!(provider.get_accounts()?.len(), 2);
+        assert_eq!(
+            provider
+                .get_default_account()?
+                .map(|account| account.address),
+            Some(account.address)
+        );
+        assert!(provider.create_account("hello".to_string()).is_err());
+
+        let raw_txn = RawUserTransaction::new_with_default_gas_token(
+            account.address,
+            1,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            1000,
+            1,
+            100000,
+            ChainId::test(),
+        );
+        let signed_txn = provider.sign_txn(raw_txn.clone(), account.address)?;
+        assert_eq!(signed_txn.raw_txn(), &raw_txn);
+        // the signer address is not the txn sender.
+        assert!(provider.sign_txn(raw_txn, locked_account.address).is_err());
+
+        let signed_message =
+            provider.sign_message(account.address, SigningMessage::from_str("hello")?)?;
+        signed_message.check_account(ChainId::test(), None)?;
+        // the account is locked in the signer.
+        assert!(provider
+            .sign_message(locked_account.address, SigningMessage::from_str("hello")?)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_remote_signer_reject_non_loopback() -> Result<()> {
+        let tempdir = tempfile::tempdir()?;
+        let local_provider = AccountLocalProvider::create(tempdir.path(), ChainId::test())?;
+        let endpoint = SignerEndpoint::from_str("0.0.0.0:0")?;
+        let signer = RemoteSigner::new(local_provider);
+        let err = signer.run(&endpoint).unwrap_err();
+        assert!(err.to_string().contains("loopback"));
+        // a non-loopback address is allowed with the shared secret.
+        let signer = signer.with_auth(SignerAuth::new(b"remote signer secret".to_vec())?);
+        signer.check_listen_addr(&SocketAddr::from_str("0.0.0.0:9860")?)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_remote_provider_with_auth() -> Result<()> {
+        let tempdir = tempfile::tempdir()?;
+        let local_provider = AccountLocalProvider::create(tempdir.path(), ChainId::test())?;
+        let account = local_provider.create_account("hello".to_string())?;
+        local_provider.unlock_account(
+            account.address,
+            "hello".to_string(),
+            Duration::from_secs(60),
+        )?;
+        assert!(SignerAuth::new(b"short".to_vec()).is_err());
+        let auth = SignerAuth::new(b"remote signer secret".to_vec())?;
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let endpoint = SignerEndpoint::Tcp(addr);
+        let signer = RemoteSigner::new(local_provider).with_auth(auth.clone());
+        std::thread::spawn(move || signer.run_tcp(listener));
+
+        let provider = AccountRemoteProvider::create(endpoint.clone()).with_auth(auth.clone());
+        assert_eq!(provider.get_accounts()?.len(), 1);
+        let signed_message =
+            provider.sign_message(account.address, SigningMessage::from_str("hello")?)?;
+        signed_message.check_account(ChainId::test(), None)?;
+
+        // the requests without the secret or with a wrong secret are refused.
+        let err = AccountRemoteProvider::create(endpoint.clone())
+            .get_accounts()
+            .unwrap_err();
+        assert!(err.to_string().contains("Unauthorized"), "{}", err);
+        let err = AccountRemoteProvider::create(endpoint)
+            .with_auth(SignerAuth::new(b"wrong signer secret".to_vec())?)
+            .get_accounts()
+            .unwrap_err();
+        assert!(err.to_string().contains("Unauthorized"), "{}", err);
+
+        // a request can not be replayed or tampered.
+        let mut request = SignerRequest::new(1, SIGNER_METHOD_ACCOUNTS, Value::Null);
+        auth.sign_request(&mut request)?;
+        let send = |request: &SignerRequest| -> Result<SignerResponse> {
+            AccountRemoteProvider::send(TcpStream::connect(addr)?, request)
+        };
+        let response = send(&request)?;
+        auth.verify_response(&response, request.timestamp.unwrap())?;
+        assert!(response.into_result().is_ok());
+        let err = send(&request)?.into_result().unwrap_err();
+        assert!(err.to_string().contains("replayed"), "{}", err);
+        let mut tampered = SignerRequest::new(2, SIGNER_METHOD_ACCOUNTS, Value::Null);
+        auth.sign_request(&mut tampered)?;
+        tampered.method = SIGNER_METHOD_SIGN_TXN.to_string();
+        let err = send(&tampered)?.into_result().unwrap_err();
+        assert!(err.to_string().contains("Invalid request mac"), "{}", err);
+        Ok(())
+    }
+}
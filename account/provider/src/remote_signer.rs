@@ -0,0 +1,503 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The protocol between the remote account provider and the external signer, and a reference
+//! signer implementation.
+//!
+//! The protocol is JSON-RPC 2.0 over a TCP or Unix socket stream, one request or response per line.
+//! The requests and responses are authenticated by a shared secret if it is configured: the
+//! request carries its timestamp in milliseconds and the hex of the HMAC-SHA256 of
+//! `{id}.{timestamp}.{method}.{params}`, the response carries the hex of the HMAC-SHA256 of
+//! `{id}.{timestamp}.{result or error}`, where the json values are in their compact form.
+//! The messages are authenticated but not encrypted. Without the shared secret, the reference signer
+//! only listens on a loopback address or a Unix socket which is only accessible to its owner.
+//! The transaction and message are BCS encoded and then hex encoded, the supported methods are:
+//! - `signer_accounts`: no params, return the list of `AccountInfo` of the signer.
+//! - `signer_sign_txn`: params `{"raw_txn": <hex>}`, return the hex of the `SignedUserTransaction`.
+//! - `signer_sign_message`: params `{"address": <address>, "message": <hex>}`, return the hex of
+//!   the `SignedMessage`.
+
+use anyhow::{bail, ensure, format_err, Result};
+use bcs_ext::BCSCodec;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use starcoin_account_api::AccountProvider;
+use starcoin_logger::prelude::*;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::sign_message::SigningMessage;
+use starcoin_types::transaction::RawUserTransaction;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::path::Path;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const JSONRPC_VERSION: &str = "2.0";
+pub const SIGNER_METHOD_ACCOUNTS: &str = "signer_accounts";
+pub const SIGNER_METHOD_SIGN_TXN: &str = "signer_sign_txn";
+pub const SIGNER_METHOD_SIGN_MESSAGE: &str = "signer_sign_message";
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SIGNER_ERROR: i64 = -32000;
+const UNAUTHORIZED: i64 = -32001;
+
+/// The max difference between the timestamp of an authenticated request and the signer's clock.
+const MAX_REQUEST_TIME_SKEW_MS: u64 = 30_000;
+const MIN_SECRET_LENGTH: usize = 16;
+
+/// The address of the external signer, a TCP socket address such as `127.0.0.1:9860`,
+/// or the path of a Unix socket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignerEndpoint {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for SignerEndpoint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Ok(addr) = SocketAddr::from_str(s) {
+            return Ok(Self::Tcp(addr));
+        }
+        #[cfg(unix)]
+        {
+            Ok(Self::Unix(PathBuf::from(s)))
+        }
+        #[cfg(not(unix))]
+        {
+            bail!("Invalid signer endpoint {}, expect a socket address", s)
+        }
+    }
+}
+
+impl fmt::Display for SignerEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{}", addr),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignerRequest {
+    pub jsonrpc: String,
+    pub id: u64,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+    /// the milliseconds since the unix epoch when the request is authenticated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mac: Option<String>,
+}
+
+impl SignerRequest {
+    pub fn new(id: u64, method: &str, params: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            method: method.to_string(),
+            params,
+            timestamp: None,
+            mac: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignerResponse {
+    pub jsonrpc: String,
+    pub id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SignerError>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mac: Option<String>,
+}
+
+impl SignerResponse {
+    fn error(id: u64, code: i64, message: String) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            result: None,
+            error: Some(SignerError { code, message }),
+            mac: None,
+        }
+    }
+
+    pub fn into_result(self) -> Result<Value> {
+        match (self.result, self.error) {
+            (_, Some(error)) => bail!("Remote signer error {}: {}", error.code, error.message),
+            (Some(result), None) => Ok(result),
+            (None, None) => {
+                bail!("Invalid remote signer response, both result and error are empty")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignerError {
+    pub code: i64,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignTxnParams {
+    /// hex of the BCS encoded `RawUserTransaction`
+    pub raw_txn: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignMessageParams {
+    pub address: AccountAddress,
+    /// hex of the BCS encoded `SigningMessage`
+    pub message: String,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// The shared secret of the signer and its clients, which authenticates the requests and responses.
+#[derive(Clone)]
+pub struct SignerAuth {
+    secret: Vec<u8>,
+}
+
+impl SignerAuth {
+    pub fn new(secret: Vec<u8>) -> Result<Self> {
+        ensure!(
+            secret.len() >= MIN_SECRET_LENGTH,
+            "The remote signer secret should be at least {} bytes",
+            MIN_SECRET_LENGTH
+        );
+        Ok(Self { secret })
+    }
+
+    /// Load the secret from the file, the trailing line break is ignored.
+    pub fn load(path: &Path) -> Result<Self> {
+        let secret = std::fs::read_to_string(path)
+            .map_err(|e| format_err!("Read remote signer secret file {:?} error: {}", path, e))?;
+        Self::new(
+            secret
+                .trim_end_matches(&['\r', '\n'][..])
+                .as_bytes()
+                .to_vec(),
+        )
+    }
+
+    fn mac(&self, id: u64, timestamp: u64, parts: &[&str]) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(self.secret.as_slice()).expect("hmac accepts any key");
+        mac.update(format!("{}.{}", id, timestamp).as_bytes());
+        for part in parts {
+            mac.update(b".");
+            mac.update(part.as_bytes());
+        }
+        mac
+    }
+
+    fn request_mac(&self, request: &SignerRequest, timestamp: u64) -> Result<Hmac<Sha256>> {
+        Ok(self.mac(
+            request.id,
+            timestamp,
+            &[
+                request.method.as_str(),
+                serde_json::to_string(&request.params)?.as_str(),
+            ],
+        ))
+    }
+
+    fn response_mac(&self, response: &SignerResponse, timestamp: u64) -> Result<Hmac<Sha256>> {
+        let body = match (&response.result, &response.error) {
+            (_, Some(error)) => serde_json::to_string(&serde_json::to_value(error)?)?,
+            (Some(result), None) => serde_json::to_string(result)?,
+            (None, None) => String::new(),
+        };
+        Ok(self.mac(response.id, timestamp, &[body.as_str()]))
+    }
+
+    pub fn sign_request(&self, request: &mut SignerRequest) -> Result<()> {
+        let timestamp = now_millis();
+        let mac = self.request_mac(request, timestamp)?;
+        request.timestamp = Some(timestamp);
+        request.mac = Some(hex::encode(mac.finalize().into_bytes()));
+        Ok(())
+    }
+
+    /// Verify the mac and the timestamp of the request, return the timestamp.
+    pub fn verify_request(&self, request: &SignerRequest) -> Result<u64> {
+        let (timestamp, mac) = match (request.timestamp, request.mac.as_ref()) {
+            (Some(timestamp), Some(mac)) => (timestamp, mac),
+            _ => bail!("The request is not authenticated"),
+        };
+        self.request_mac(request, timestamp)?
+            .verify_slice(hex::decode(mac)?.as_slice())
+            .map_err(|_| format_err!("Invalid request mac"))?;
+        ensure!(
+            now_millis().abs_diff(timestamp) <= MAX_REQUEST_TIME_SKEW_MS,
+            "The request timestamp {} is out of the valid time window",
+            timestamp
+        );
+        Ok(timestamp)
+    }
+
+    /// Sign the response of the request at `timestamp`.
+    pub fn sign_response(&self, response: &mut SignerResponse, timestamp: u64) -> Result<()> {
+        let mac = self.response_mac(response, timestamp)?;
+        response.mac = Some(hex::encode(mac.finalize().into_bytes()));
+        Ok(())
+    }
+
+    pub fn verify_response(&self, response: &SignerResponse, timestamp: u64) -> Result<()> {
+        let mac = match (&response.mac, &response.error) {
+            (Some(mac), _) => mac,
+            (None, Some(error)) => bail!(
+                "The remote signer response is not authenticated, error {}: {}",
+                error.code,
+                error.message
+            ),
+            (None, None) => bail!("The remote signer response is not authenticated"),
+        };
+        self.response_mac(response, timestamp)?
+            .verify_slice(hex::decode(mac)?.as_slice())
+            .map_err(|_| format_err!("Invalid remote signer response mac"))
+    }
+}
+
+/// A reference signer, serve the signer protocol with the accounts of the `provider`.
+pub struct RemoteSigner<P> {
+    provider: P,
+    auth: Option<SignerAuth>,
+    /// the macs of the authenticated requests in the valid time window, to reject the replays.
+    seen_requests: Mutex<HashMap<String, u64>>,
+}
+
+impl<P> RemoteSigner<P>
+where
+    P: AccountProvider,
+{
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            auth: None,
+            seen_requests: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Require the requests to be authenticated by the shared secret.
+    pub fn with_auth(mut self, auth: SignerAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Authenticate the request, return the timestamp of the request if the auth is configured.
+    fn authenticate(&self, request: &SignerRequest) -> Result<Option<u64>> {
+        let auth = match &self.auth {
+            Some(auth) => auth,
+            None => return Ok(None),
+        };
+        let timestamp = auth.verify_request(request)?;
+        let now = now_millis();
+        let mut seen_requests = self
+            .seen_requests
+            .lock()
+            .map_err(|e| format_err!("{}", e))?;
+        seen_requests.retain(|_, seen| now.abs_diff(*seen) <= MAX_REQUEST_TIME_SKEW_MS);
+        let mac = request.mac.clone().unwrap_or_default();
+        ensure!(
+            !seen_requests.contains_key(&mac),
+            "The request {} is replayed",
+            request.id
+        );
+        seen_requests.insert(mac, timestamp);
+        Ok(Some(timestamp))
+    }
+
+    pub fn handle_request(&self, request: SignerRequest) -> SignerResponse {
+        let (result, error) = match self.do_handle(request.method.as_str(), request.params) {
+            Ok(result) => (Some(result), None),
+            Err((code, e)) => (
+                None,
+                Some(SignerError {
+                    code,
+                    message: e.to_string(),
+                }),
+            ),
+        };
+        SignerResponse {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id: request.id,
+            result,
+            error,
+            mac: None,
+        }
+    }
+
+    fn do_handle(&self, method: &str, params: Value) -> Result<Value, (i64, anyhow::Error)> {
+        let signer_error = |e: anyhow::Error| (SIGNER_ERROR, e);
+        let invalid_params = |e: anyhow::Error| (INVALID_PARAMS, e);
+        match method {
+            SIGNER_METHOD_ACCOUNTS => {
+                let accounts = self.provider.get_accounts().map_err(signer_error)?;
+                serde_json::to_value(accounts).map_err(|e| signer_error(e.into()))
+            }
+            SIGNER_METHOD_SIGN_TXN => {
+                let params: SignTxnParams =
+                    serde_json::from_value(params).map_err(|e| invalid_params(e.into()))?;
+                let raw_txn = hex::decode(params.raw_txn.as_str())
+                    .map_err(|e| invalid_params(e.into()))
+                    .and_then(|data| {
+                        RawUserTransaction::decode(data.as_slice()).map_err(invalid_params)
+                    })?;
+                let signer = raw_txn.sender();
+                let signed_txn = self
+                    .provider
+                    .sign_txn(raw_txn, signer)
+                    .map_err(signer_error)?;
+                let data = signed_txn.encode().map_err(signer_error)?;
+                Ok(Value::String(hex::encode(data)))
+            }
+            SIGNER_METHOD_SIGN_MESSAGE => {
+                let params: SignMessageParams =
+                    serde_json::from_value(params).map_err(|e| invalid_params(e.into()))?;
+                let message = hex::decode(params.message.as_str())
+                    .map_err(|e| invalid_params(e.into()))
+                    .and_then(|data| {
+                        SigningMessage::decode(data.as_slice()).map_err(invalid_params)
+                    })?;
+                let signed_message = self
+                    .provider
+                    .sign_message(params.address, message)
+                    .map_err(signer_error)?;
+                let data = signed_message.encode().map_err(signer_error)?;
+                Ok(Value::String(hex::encode(data)))
+            }
+            _ => Err((METHOD_NOT_FOUND, format_err!("Method {} not found", method))),
+        }
+    }
+
+    /// Serve the requests from the `stream` until it is closed.
+    pub fn serve_stream<S>(&self, stream: S) -> Result<()>
+    where
+        S: Read + Write,
+    {
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<SignerRequest>(line.as_str()) {
+                Ok(request) => match self.authenticate(&request) {
+                    Ok(timestamp) => {
+                        let mut response = self.handle_request(request);
+                        if let (Some(auth), Some(timestamp)) = (&self.auth, timestamp) {
+                            auth.sign_response(&mut response, timestamp)?;
+                        }
+                        response
+                    }
+                    Err(e) => {
+                        warn!("Refuse remote signer request {}: {}", request.id, e);
+                        SignerResponse::error(
+                            request.id,
+                            UNAUTHORIZED,
+                            format!("Unauthorized: {}", e),
+                        )
+                    }
+                },
+                Err(e) => {
+                    SignerResponse::error(0, INVALID_PARAMS, format!("Invalid request: {}", e))
+                }
+            };
+            let stream = reader.get_mut();
+            serde_json::to_writer(&mut *stream, &response)?;
+            stream.write_all(b"\n")?;
+            stream.flush()?;
+        }
+    }
+
+    /// Check the TCP address to listen, a non-loopback address requires the auth.
+    pub fn check_listen_addr(&self, addr: &SocketAddr) -> Result<()> {
+        ensure!(
+            addr.ip().is_loopback() || self.auth.is_some(),
+            "Remote signer can only listen on a loopback address without the shared secret, but got: {}",
+            addr
+        );
+        Ok(())
+    }
+
+    /// Listen on the `endpoint` and serve the connections one by one.
+    /// A TCP endpoint must be a loopback address unless the auth is configured,
+    /// a Unix socket is made accessible to its owner only.
+    pub fn run(&self, endpoint: &SignerEndpoint) -> Result<()> {
+        match endpoint {
+            SignerEndpoint::Tcp(addr) => {
+                self.check_listen_addr(addr)?;
+                self.run_tcp(TcpListener::bind(addr)?)
+            }
+            #[cfg(unix)]
+            SignerEndpoint::Unix(path) => {
+                use std::os::unix::fs::PermissionsExt;
+                let listener = std::os::unix::net::UnixListener::bind(path)?;
+                std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+                self.run_unix(listener)
+            }
+        }
+    }
+
+    /// Serve the connections of `listener`, the connections not from a loopback address are refused
+    /// unless the auth is configured.
+    pub fn run_tcp(&self, listener: TcpListener) -> Result<()> {
+        for stream in listener.incoming() {
+            if let Err(e) = stream.map_err(|e| e.into()).and_then(|s| {
+                let peer = s.peer_addr()?;
+                ensure!(
+                    peer.ip().is_loopback() || self.auth.is_some(),
+                    "Refuse remote signer connection from {}",
+                    peer
+                );
+                self.serve_stream(s)
+            }) {
+                warn!("Serve remote signer connection error: {:?}", e);
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    pub fn run_unix(&self, listener: std::os::unix::net::UnixListener) -> Result<()> {
+        for stream in listener.incoming() {
+            if let Err(e) = stream
+                .map_err(|e| e.into())
+                .and_then(|s| self.serve_stream(s))
+            {
+                warn!("Serve remote signer connection error: {:?}", e);
+            }
+        }
+        Ok(())
+    }
+}
@@ -79,6 +79,14 @@ impl AccountProvider for AccountRpcProvider {
         self.rpc.account_import(address, private_key, password)
     }
 
+    fn import_mnemonic(&self, mnemonic: String, password: String) -> anyhow::Result<AccountInfo> {
+        self.rpc.account_import_mnemonic(mnemonic, password)
+    }
+
+    fn derive_account(&self, index: Option<u32>, password: String) -> anyhow::Result<AccountInfo> {
+        self.rpc.account_derive(password, index)
+    }
+
     fn import_readonly_account(
         &self,
         address: AccountAddress,
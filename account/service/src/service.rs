@@ -163,6 +163,16 @@ impl ServiceHandler<Self, AccountRequest> for AccountService {
                         .import_account(address, private_key, password.as_str())?;
                 AccountResponse::AccountInfo(Box::new(account.info()))
             }
+            AccountRequest::ImportMnemonic { mnemonic, password } => {
+                let account = self
+                    .manager
+                    .import_mnemonic(mnemonic.as_str(), password.as_str())?;
+                AccountResponse::AccountInfo(Box::new(account.info()))
+            }
+            AccountRequest::DeriveAccount { index, password } => {
+                let account = self.manager.derive_account(index, password.as_str())?;
+                AccountResponse::AccountInfo(Box::new(account.info()))
+            }
             AccountRequest::ImportReadonlyAccount {
                 address,
                 public_key,
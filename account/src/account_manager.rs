@@ -4,9 +4,10 @@
 use crate::account::Account;
 use crate::account_storage::AccountStorage;
 use anyhow::format_err;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use rand::prelude::*;
 use starcoin_account_api::error::AccountError;
+use starcoin_account_api::hd_wallet;
 use starcoin_account_api::{AccountInfo, AccountPrivateKey, AccountPublicKey, AccountResult};
 use starcoin_crypto::ed25519::Ed25519PrivateKey;
 use starcoin_crypto::{Uniform, ValidCryptoMaterial};
@@ -20,7 +21,6 @@ use starcoin_types::{
 };
 use std::collections::HashMap;
 use std::convert::TryFrom;
-use std::ops::Add;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -29,14 +29,17 @@ pub struct AccountManager {
     store: AccountStorage,
     key_cache: RwLock<PasswordCache>,
     chain_id: ChainId,
+    /// Serialize the updates of the hd wallet, so one index is never derived twice by `next_index`.
+    hd_wallet_lock: Mutex<()>,
 }
 
 #[derive(Default, Debug, PartialEq, Eq)]
 struct PasswordCache {
-    cache: HashMap<AccountAddress, (Instant, String)>,
+    /// The password and its expiry, `None` means it never expires.
+    cache: HashMap<AccountAddress, (Option<Instant>, String)>,
 }
 impl PasswordCache {
-    pub fn cache_pass(&mut self, account: AccountAddress, pass: String, ttl: Instant) {
+    pub fn cache_pass(&mut self, account: AccountAddress, pass: String, ttl: Option<Instant>) {
         self.cache.insert(account, (ttl, pass));
     }
     pub fn remove_pass(&mut self, account: &AccountAddress) {
@@ -46,7 +49,7 @@ impl PasswordCache {
         match self.cache.remove(account) {
             None => None,
             Some((ttl, kp)) => {
-                if Instant::now() < ttl {
+                if ttl.map_or(true, |ttl| Instant::now() < ttl) {
                     self.cache.insert(*account, (ttl, kp));
                     self.cache.get(account).map(|t| t.1.to_string())
                 } else {
@@ -59,7 +62,8 @@ impl PasswordCache {
     #[allow(dead_code)]
     pub fn clean_expired(&mut self) {
         let cur_instant = Instant::now();
-        self.cache.retain(|_account, (ttl, _)| &cur_instant < ttl);
+        self.cache
+            .retain(|_account, (ttl, _)| ttl.map_or(true, |ttl| cur_instant < ttl));
     }
 }
 
@@ -69,6 +73,7 @@ impl AccountManager {
             store: storage,
             key_cache: RwLock::new(PasswordCache::default()),
             chain_id,
+            hd_wallet_lock: Mutex::new(()),
         };
         Ok(manager)
     }
@@ -84,6 +89,58 @@ impl AccountManager {
         )
     }
 
+    /// Import the BIP39 `mnemonic` as the hd wallet seed encrypted by `password`,
+    /// and create the first account derived from the seed.
+    pub fn import_mnemonic(&self, mnemonic: &str, password: &str) -> AccountResult<Account> {
+        let _guard = self.hd_wallet_lock.lock();
+        if self.store.hd_wallet()?.is_some() {
+            return Err(AccountError::HDSeedAlreadyExist);
+        }
+        let seed = hd_wallet::mnemonic_to_seed(mnemonic).map_err(AccountError::InvalidMnemonic)?;
+        self.store.init_hd_wallet(seed.as_slice(), password)?;
+        let account = self.derive_hd_account(seed.as_slice(), 0, password)?;
+        self.store.update_hd_next_index(1)?;
+        Ok(account)
+    }
+
+    /// Derive the account with `index` from the hd wallet seed, or with the next index if `index`
+    /// is None, the account is encrypted by the same `password` as the seed.
+    /// The next index is moved past `index`, an index can not be derived twice.
+    pub fn derive_account(&self, index: Option<u32>, password: &str) -> AccountResult<Account> {
+        let _guard = self.hd_wallet_lock.lock();
+        let hd_wallet = self
+            .store
+            .hd_wallet()?
+            .ok_or(AccountError::HDSeedNotExist)?;
+        let seed = self
+            .store
+            .decrypt_hd_seed(password)
+            .map_err(|e| {
+                warn!(
+                    "Try to decrypt hd wallet seed with a invalid password, err: {:?}",
+                    e
+                );
+                AccountError::InvalidHDSeedPassword
+            })?
+            .ok_or(AccountError::HDSeedNotExist)?;
+        let index = index.unwrap_or(hd_wallet.next_index);
+        let account = self.derive_hd_account(seed.as_slice(), index, password)?;
+        if index >= hd_wallet.next_index {
+            self.store.update_hd_next_index(index.saturating_add(1))?;
+        }
+        Ok(account)
+    }
+
+    fn derive_hd_account(&self, seed: &[u8], index: u32, password: &str) -> AccountResult<Account> {
+        let private_key = AccountPrivateKey::Single(hd_wallet::derive_private_key(seed, index)?);
+        let address = private_key.public_key().derived_address();
+        self.save_account(
+            address,
+            private_key.public_key(),
+            Some((private_key, password.to_string())),
+        )
+    }
+
     pub fn unlock_account(
         &self,
         address: AccountAddress,
@@ -92,7 +149,8 @@ impl AccountManager {
     ) -> AccountResult<AccountInfo> {
         let account = Account::load(address, Some(password.to_string()), self.store.clone())?
             .ok_or(AccountError::AccountNotExist(address))?;
-        let ttl = std::time::Instant::now().add(duration);
+        // a duration too long to be represented, such as `Duration::MAX`, never expires.
+        let ttl = std::time::Instant::now().checked_add(duration);
         self.key_cache
             .write()
             .cache_pass(address, password.to_string(), ttl);
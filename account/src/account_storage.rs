@@ -1,14 +1,14 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{Error, Result};
+use anyhow::{format_err, Error, Result};
 use bcs_ext::BCSCodec;
 use serde::Deserialize;
 use serde::Serialize;
 use starcoin_account_api::{AccountPrivateKey, AccountPublicKey, Setting};
 use starcoin_config::RocksdbConfig;
 use starcoin_crypto::ValidCryptoMaterial;
-use starcoin_decrypt::{decrypt, encrypt};
+use starcoin_decrypt::{decrypt, encrypt, is_outdated};
 use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::db_storage::DBStorage;
 use starcoin_storage::storage::{KeyCodec, ValueCodec};
@@ -26,6 +26,7 @@ pub const ENCRYPTED_PRIVATE_KEY_PREFIX_NAME: ColumnFamilyName = "encrypted_priva
 pub const PUBLIC_KEY_PREFIX_NAME: ColumnFamilyName = "public_key";
 pub const ACCEPTED_TOKEN_PREFIX_NAME: ColumnFamilyName = "accepted_token";
 pub const GLOBAL_PREFIX_NAME: ColumnFamilyName = "global";
+pub const HD_WALLET_PREFIX_NAME: ColumnFamilyName = "hd_wallet";
 
 define_storage!(
     AccountSettingStore,
@@ -61,6 +62,8 @@ define_storage!(
     ACCEPTED_TOKEN_PREFIX_NAME
 );
 
+define_storage!(HDWalletStore, HDWalletKey, HDWallet, HD_WALLET_PREFIX_NAME);
+
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct AcceptedTokens(pub Vec<TokenCode>);
 
@@ -106,6 +109,39 @@ impl ValueCodec for GlobalValue {
     }
 }
 
+/// There is only one hd wallet seed in the account storage.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum HDWalletKey {
+    Default,
+}
+
+impl KeyCodec for HDWalletKey {
+    fn encode_key(&self) -> Result<Vec<u8>, Error> {
+        self.encode()
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self, Error> {
+        Self::decode(data)
+    }
+}
+
+/// The encrypted seed of the hd wallet, and the index of the next account to derive.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HDWallet {
+    pub encrypted_seed: Vec<u8>,
+    pub next_index: u32,
+}
+
+impl ValueCodec for HDWallet {
+    fn encode_value(&self) -> Result<Vec<u8>, Error> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self, Error> {
+        Self::decode(data)
+    }
+}
+
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy)]
 pub struct AccountAddressWrapper(AccountAddress);
 impl From<AccountAddress> for AccountAddressWrapper {
@@ -186,6 +222,7 @@ pub struct AccountStorage {
     public_key_store: PublicKeyStore,
     global_value_store: GlobalSettingStore,
     accepted_token_store: AcceptedTokenStore,
+    hd_wallet_store: HDWalletStore,
 }
 
 impl AccountStorage {
@@ -198,6 +235,7 @@ impl AccountStorage {
                 PUBLIC_KEY_PREFIX_NAME,
                 ACCEPTED_TOKEN_PREFIX_NAME,
                 GLOBAL_PREFIX_NAME,
+                HD_WALLET_PREFIX_NAME,
             ],
             false,
             rocksdb_config,
@@ -214,7 +252,8 @@ impl AccountStorage {
             private_key_store: PrivateKeyStore::new(store.clone()),
             public_key_store: PublicKeyStore::new(store.clone()),
             accepted_token_store: AcceptedTokenStore::new(store.clone()),
-            global_value_store: GlobalSettingStore::new(store),
+            global_value_store: GlobalSettingStore::new(store.clone()),
+            hd_wallet_store: HDWalletStore::new(store),
         }
     }
 
@@ -304,6 +343,10 @@ impl AccountStorage {
             Some(encrypted_key) => {
                 let plain_key_data = decrypt(password.as_ref().as_bytes(), &encrypted_key.0)?;
                 let private_key = AccountPrivateKey::try_from(plain_key_data.as_slice())?;
+                // re-encrypt the private key protected by the legacy key derivation params.
+                if is_outdated(&encrypted_key.0) {
+                    self.update_key(address, &private_key, password)?;
+                }
                 Ok(Some(private_key))
             }
         }
@@ -381,4 +424,38 @@ impl AccountStorage {
         }
         Ok(())
     }
+
+    pub fn hd_wallet(&self) -> Result<Option<HDWallet>> {
+        self.hd_wallet_store.get(HDWalletKey::Default)
+    }
+
+    /// Save the hd wallet `seed` encrypted by `password`.
+    pub fn init_hd_wallet(&self, seed: &[u8], password: impl AsRef<str>) -> Result<()> {
+        let encrypted_seed = encrypt(password.as_ref().as_bytes(), seed);
+        self.hd_wallet_store.put(
+            HDWalletKey::Default,
+            HDWallet {
+                encrypted_seed,
+                next_index: 0,
+            },
+        )
+    }
+
+    pub fn decrypt_hd_seed(&self, password: impl AsRef<str>) -> Result<Option<Vec<u8>>> {
+        match self.hd_wallet()? {
+            None => Ok(None),
+            Some(hd_wallet) => Ok(Some(decrypt(
+                password.as_ref().as_bytes(),
+                &hd_wallet.encrypted_seed,
+            )?)),
+        }
+    }
+
+    pub fn update_hd_next_index(&self, next_index: u32) -> Result<()> {
+        let mut hd_wallet = self
+            .hd_wallet()?
+            .ok_or_else(|| format_err!("hd wallet seed not exists"))?;
+        hd_wallet.next_index = next_index;
+        self.hd_wallet_store.put(HDWalletKey::Default, hd_wallet)
+    }
 }
@@ -6,7 +6,11 @@ use crate::Account;
 use crate::AccountManager;
 use anyhow::Result;
 use starcoin_account_api::error::AccountError;
-use starcoin_account_api::AccountPublicKey;
+use starcoin_account_api::hd_wallet::{
+    derive_ed25519_key, derive_private_key, generate_mnemonic, mnemonic_to_seed,
+};
+use starcoin_account_api::keystore::KeyStore;
+use starcoin_account_api::{AccountPrivateKey, AccountPublicKey};
 use starcoin_config::RocksdbConfig;
 use starcoin_crypto::keygen::KeyGen;
 use starcoin_crypto::{SigningKey, ValidCryptoMaterial};
@@ -290,3 +294,164 @@ pub fn test_account_info_between_lock_and_unlock() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+pub fn test_unlock_account_until_locked() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage, ChainId::test())?;
+
+    let account = manager.create_account("hello")?;
+    manager.unlock_account(*account.address(), "hello", Duration::MAX)?;
+    assert!(!manager.account_info(*account.address())?.unwrap().is_locked);
+    manager.lock_account(*account.address())?;
+    assert!(manager.account_info(*account.address())?.unwrap().is_locked);
+    Ok(())
+}
+
+#[test]
+pub fn test_slip10_ed25519_derivation() -> Result<()> {
+    // SLIP-0010 test vector 1 for ed25519
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f")?;
+    assert_eq!(
+        hex::encode(derive_ed25519_key(&seed, &[])?),
+        "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+    );
+    assert_eq!(
+        hex::encode(derive_ed25519_key(&seed, &[0])?),
+        "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_hd_wallet_account() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage, ChainId::test())?;
+
+    let result = manager.derive_account(None, "hello");
+    assert!(matches!(
+        result.err().unwrap(),
+        AccountError::HDSeedNotExist
+    ));
+
+    let mnemonic = generate_mnemonic();
+    let first = manager.import_mnemonic(mnemonic.as_str(), "hello")?;
+    let second = manager.derive_account(None, "hello")?;
+    assert_ne!(first.address(), second.address());
+    assert_eq!(manager.list_account_infos()?.len(), 2);
+
+    let result = manager.import_mnemonic(mnemonic.as_str(), "hello");
+    assert!(matches!(
+        result.err().unwrap(),
+        AccountError::HDSeedAlreadyExist
+    ));
+
+    let result = manager.derive_account(None, "world");
+    assert!(matches!(
+        result.err().unwrap(),
+        AccountError::InvalidHDSeedPassword
+    ));
+
+    // the same mnemonic always derives the same accounts
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage, ChainId::test())?;
+    let account = manager.import_mnemonic(mnemonic.as_str(), "world")?;
+    assert_eq!(account.address(), first.address());
+    Ok(())
+}
+
+#[test]
+pub fn test_hd_wallet_derive_by_index() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage, ChainId::test())?;
+    let mnemonic = generate_mnemonic();
+    let seed = mnemonic_to_seed(mnemonic.as_str())?;
+    let address_of = |index: u32| -> Result<AccountAddress> {
+        Ok(
+            AccountPrivateKey::Single(derive_private_key(seed.as_slice(), index)?)
+                .public_key()
+                .derived_address(),
+        )
+    };
+    manager.import_mnemonic(mnemonic.as_str(), "hello")?;
+
+    let account = manager.derive_account(Some(5), "hello")?;
+    assert_eq!(*account.address(), address_of(5)?);
+    // the next index moves past the derived index
+    let account = manager.derive_account(None, "hello")?;
+    assert_eq!(*account.address(), address_of(6)?);
+    // the skipped indexes can still be derived, without moving the next index back
+    let account = manager.derive_account(Some(3), "hello")?;
+    assert_eq!(*account.address(), address_of(3)?);
+    let account = manager.derive_account(None, "hello")?;
+    assert_eq!(*account.address(), address_of(7)?);
+
+    let result = manager.derive_account(Some(5), "hello");
+    assert!(matches!(
+        result.err().unwrap(),
+        AccountError::AccountAlreadyExist(_)
+    ));
+    Ok(())
+}
+
+#[test]
+pub fn test_hd_wallet_derive_concurrently() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage, ChainId::test())?;
+    let mnemonic = generate_mnemonic();
+    manager.import_mnemonic(mnemonic.as_str(), "hello")?;
+
+    let mut addresses = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| scope.spawn(|| manager.derive_account(None, "hello")))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap().map(|account| *account.address()))
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+    let seed = mnemonic_to_seed(mnemonic.as_str())?;
+    let mut expected = (1..=8)
+        .map(|index| {
+            Ok(
+                AccountPrivateKey::Single(derive_private_key(seed.as_slice(), index)?)
+                    .public_key()
+                    .derived_address(),
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+    addresses.sort();
+    expected.sort();
+    assert_eq!(addresses, expected);
+    Ok(())
+}
+
+#[test]
+pub fn test_keystore() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage, ChainId::test())?;
+    let account = manager.create_account("hello")?;
+    let private_key = manager.export_account(*account.address(), "hello")?;
+
+    let keystore = KeyStore::encrypt(*account.address(), private_key.as_slice(), "hello");
+    let keystore_file = tempdir.path().join("keystore.json");
+    keystore.save(keystore_file.as_path())?;
+    let loaded = KeyStore::load(keystore_file.as_path())?;
+    assert_eq!(loaded, keystore);
+    assert!(loaded.decrypt("world").is_err());
+    assert_eq!(loaded.decrypt("hello")?, private_key);
+
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage, ChainId::test())?;
+    let imported = manager.import_account(loaded.address, loaded.decrypt("hello")?, "hello")?;
+    assert_eq!(imported.address(), account.address());
+    assert_eq!(imported.public_key(), account.public_key());
+    Ok(())
+}
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2
 
 use anyhow::Result;
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::proof::{AccumulatorConsistencyProof, AccumulatorRangeProof};
 use starcoin_crypto::HashValue;
 use starcoin_state_api::ChainStateReader;
 use starcoin_statedb::ChainStateDB;
@@ -102,6 +104,24 @@ pub trait ChainReader {
         access_path: Option<AccessPath>,
     ) -> Result<Option<TransactionInfoWithProof>>;
 
+    /// Get the range proof of the `num_leaves` leaves start from `first_leaf_index` of the accumulator.
+    /// `block_id`: use which block's accumulator info for get proof
+    fn get_accumulator_range_proof(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        first_leaf_index: u64,
+        num_leaves: u64,
+    ) -> Result<Option<AccumulatorRangeProof>>;
+
+    /// Get the consistency proof from the accumulator with `old_num_leaves` leaves to the accumulator of `block_id`.
+    fn get_accumulator_consistency_proof(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        old_num_leaves: u64,
+    ) -> Result<Option<AccumulatorConsistencyProof>>;
+
     fn current_tips_hash(&self) -> Result<Option<(HashValue, Vec<HashValue>)>>;
     fn has_dag_block(&self, header_id: HashValue) -> Result<bool>;
     fn check_chain_type(&self) -> Result<ChainType>;
@@ -6,6 +6,7 @@ use anyhow::{bail, format_err, Result};
 use serde::{Deserialize, Serialize};
 use starcoin_accumulator::proof::AccumulatorProof;
 use starcoin_state_api::StateWithProof;
+use starcoin_types::block::BlockNumber;
 use starcoin_vm_types::transaction::{RichTransactionInfo, SignedUserTransaction};
 
 mod chain;
@@ -27,6 +28,19 @@ use starcoin_crypto::HashValue;
 use starcoin_vm_types::access_path::AccessPath;
 use starcoin_vm_types::contract_event::ContractEvent;
 
+/// The gas usage and gas prices of a range of main chain blocks.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeHistory {
+    /// The number of the oldest block in the range.
+    pub oldest_block: BlockNumber,
+    /// The ratio of the block gas used to the block gas limit, for each block in the range.
+    pub gas_used_ratio: Vec<f64>,
+    /// The gas unit prices at the requested percentiles of each block in the range,
+    /// the percentiles are weighted by the gas used of the transactions.
+    /// The prices of a block without user transactions are 0.
+    pub gas_price_percentiles: Vec<Vec<u64>>,
+}
+
 #[derive(PartialEq, Eq, Debug)]
 pub enum ChainType {
     Single, // Single chain
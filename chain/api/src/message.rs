@@ -1,8 +1,10 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
-use crate::{ChainType, TransactionInfoWithProof};
+use crate::{ChainType, FeeHistory, TransactionInfoWithProof};
 use anyhow::Result;
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::proof::{AccumulatorConsistencyProof, AccumulatorRangeProof};
 use starcoin_crypto::HashValue;
 use starcoin_dag::consensusdb::consenses_state::DagStateView;
 use starcoin_dag::types::ghostdata::GhostdagData;
@@ -16,6 +18,7 @@ use starcoin_types::{
     transaction::Transaction,
 };
 use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_address::AccountAddress;
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug)]
@@ -55,12 +58,34 @@ pub enum ChainRequest {
         reverse: bool,
         max_size: u64,
     },
+    GetTransactionInfosByAddress {
+        address: AccountAddress,
+        start_index: Option<u64>,
+        reverse: bool,
+        max_size: u64,
+    },
+    GetFeeHistory {
+        block_count: u64,
+        newest_block: Option<BlockNumber>,
+        percentiles: Vec<f64>,
+    },
     GetTransactionProof {
         block_id: HashValue,
         transaction_global_index: u64,
         event_index: Option<u64>,
         access_path: Option<AccessPath>,
     },
+    GetAccumulatorRangeProof {
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        first_leaf_index: u64,
+        num_leaves: u64,
+    },
+    GetAccumulatorConsistencyProof {
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        old_num_leaves: u64,
+    },
     GetBlockInfos(Vec<HashValue>),
     GetDagBlockChildren {
         block_ids: Vec<HashValue>,
@@ -95,8 +120,11 @@ pub enum ChainResponse {
     MainEvents(Vec<ContractEventInfo>),
     HashVec(Vec<HashValue>),
     TransactionProof(Box<Option<TransactionInfoWithProof>>),
+    AccumulatorRangeProof(Box<Option<AccumulatorRangeProof>>),
+    AccumulatorConsistencyProof(Box<Option<AccumulatorConsistencyProof>>),
     BlockInfoVec(Box<Vec<Option<BlockInfo>>>),
     DagStateView(Box<DagStateView>),
     CheckChainType(ChainType),
     GhostdagDataOption(Box<Option<GhostdagData>>),
+    FeeHistory(Box<FeeHistory>),
 }
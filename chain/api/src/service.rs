@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2
 
 use crate::message::{ChainRequest, ChainResponse};
-use crate::{ChainType, TransactionInfoWithProof};
+use crate::{ChainType, FeeHistory, TransactionInfoWithProof};
 use anyhow::{bail, Result};
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::proof::{AccumulatorConsistencyProof, AccumulatorRangeProof};
 use starcoin_crypto::HashValue;
 use starcoin_dag::consensusdb::consenses_state::DagStateView;
 use starcoin_dag::types::ghostdata::GhostdagData;
@@ -17,6 +19,7 @@ use starcoin_types::{
     startup_info::StartupInfo,
 };
 use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_address::AccountAddress;
 
 /// Readable block chain service trait
 pub trait ReadableChainService {
@@ -65,6 +68,25 @@ pub trait ReadableChainService {
         max_size: u64,
     ) -> Result<Vec<RichTransactionInfo>>;
 
+    /// Get the main chain txn infos of the transactions sent by or affecting `address`,
+    /// `start_index` is the position in the transaction list of the address.
+    fn get_transaction_infos_by_address(
+        &self,
+        address: AccountAddress,
+        start_index: Option<u64>,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<RichTransactionInfo>>;
+
+    /// Get the gas used ratios and the gas price `percentiles` of `block_count` main chain blocks
+    /// ending at `newest_block`, the head block if absent.
+    fn get_fee_history(
+        &self,
+        block_count: u64,
+        newest_block: Option<BlockNumber>,
+        percentiles: Vec<f64>,
+    ) -> Result<FeeHistory>;
+
     fn get_transaction_proof(
         &self,
         block_id: HashValue,
@@ -73,6 +95,21 @@ pub trait ReadableChainService {
         access_path: Option<AccessPath>,
     ) -> Result<Option<TransactionInfoWithProof>>;
 
+    fn get_accumulator_range_proof(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        first_leaf_index: u64,
+        num_leaves: u64,
+    ) -> Result<Option<AccumulatorRangeProof>>;
+
+    fn get_accumulator_consistency_proof(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        old_num_leaves: u64,
+    ) -> Result<Option<AccumulatorConsistencyProof>>;
+
     fn get_block_infos(&self, ids: Vec<HashValue>) -> Result<Vec<Option<BlockInfo>>>;
     fn get_dag_block_children(&self, ids: Vec<HashValue>) -> Result<Vec<HashValue>>;
     fn get_dag_state(&self) -> Result<DagStateView>;
@@ -135,6 +172,20 @@ pub trait ChainAsyncService:
         reverse: bool,
         max_size: u64,
     ) -> Result<Vec<RichTransactionInfo>>;
+    async fn get_transaction_infos_by_address(
+        &self,
+        address: AccountAddress,
+        start_index: Option<u64>,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<RichTransactionInfo>>;
+
+    async fn get_fee_history(
+        &self,
+        block_count: u64,
+        newest_block: Option<BlockNumber>,
+        percentiles: Vec<f64>,
+    ) -> Result<FeeHistory>;
 
     async fn get_transaction_proof(
         &self,
@@ -144,6 +195,21 @@ pub trait ChainAsyncService:
         access_path: Option<AccessPath>,
     ) -> Result<Option<TransactionInfoWithProof>>;
 
+    async fn get_accumulator_range_proof(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        first_leaf_index: u64,
+        num_leaves: u64,
+    ) -> Result<Option<AccumulatorRangeProof>>;
+
+    async fn get_accumulator_consistency_proof(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        old_num_leaves: u64,
+    ) -> Result<Option<AccumulatorConsistencyProof>>;
+
     async fn get_block_infos(&self, hashes: Vec<HashValue>) -> Result<Vec<Option<BlockInfo>>>;
     async fn get_dag_block_children(&self, hashes: Vec<HashValue>) -> Result<Vec<HashValue>>;
     async fn get_dag_state(&self) -> Result<DagStateView>;
@@ -416,6 +482,48 @@ where
         }
     }
 
+    async fn get_transaction_infos_by_address(
+        &self,
+        address: AccountAddress,
+        start_index: Option<u64>,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<RichTransactionInfo>> {
+        let response = self
+            .send(ChainRequest::GetTransactionInfosByAddress {
+                address,
+                start_index,
+                reverse,
+                max_size,
+            })
+            .await??;
+        if let ChainResponse::TransactionInfos(tx_infos) = response {
+            Ok(tx_infos)
+        } else {
+            bail!("get txn infos by address error")
+        }
+    }
+
+    async fn get_fee_history(
+        &self,
+        block_count: u64,
+        newest_block: Option<BlockNumber>,
+        percentiles: Vec<f64>,
+    ) -> Result<FeeHistory> {
+        let response = self
+            .send(ChainRequest::GetFeeHistory {
+                block_count,
+                newest_block,
+                percentiles,
+            })
+            .await??;
+        if let ChainResponse::FeeHistory(fee_history) = response {
+            Ok(*fee_history)
+        } else {
+            bail!("get fee history error")
+        }
+    }
+
     async fn get_transaction_proof(
         &self,
         block_id: HashValue,
@@ -438,6 +546,48 @@ where
         }
     }
 
+    async fn get_accumulator_range_proof(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        first_leaf_index: u64,
+        num_leaves: u64,
+    ) -> Result<Option<AccumulatorRangeProof>> {
+        let response = self
+            .send(ChainRequest::GetAccumulatorRangeProof {
+                block_id,
+                accumulator_type,
+                first_leaf_index,
+                num_leaves,
+            })
+            .await??;
+        if let ChainResponse::AccumulatorRangeProof(proof) = response {
+            Ok(*proof)
+        } else {
+            bail!("get accumulator range proof error")
+        }
+    }
+
+    async fn get_accumulator_consistency_proof(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        old_num_leaves: u64,
+    ) -> Result<Option<AccumulatorConsistencyProof>> {
+        let response = self
+            .send(ChainRequest::GetAccumulatorConsistencyProof {
+                block_id,
+                accumulator_type,
+                old_num_leaves,
+            })
+            .await??;
+        if let ChainResponse::AccumulatorConsistencyProof(proof) = response {
+            Ok(*proof)
+        } else {
+            bail!("get accumulator consistency proof error")
+        }
+    }
+
     async fn get_block_infos(&self, hashes: Vec<HashValue>) -> Result<Vec<Option<BlockInfo>>> {
         let response = self.send(ChainRequest::GetBlockInfos(hashes)).await??;
         if let ChainResponse::BlockInfoVec(block_infos) = response {
@@ -1,11 +1,13 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{bail, format_err, Error, Result};
+use anyhow::{bail, ensure, format_err, Error, Result};
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::proof::{AccumulatorConsistencyProof, AccumulatorRangeProof};
 use starcoin_chain::BlockChain;
 use starcoin_chain_api::message::{ChainRequest, ChainResponse};
 use starcoin_chain_api::{
-    ChainReader, ChainType, ChainWriter, ReadableChainService, TransactionInfoWithProof,
+    ChainReader, ChainType, ChainWriter, FeeHistory, ReadableChainService, TransactionInfoWithProof,
 };
 use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
@@ -16,6 +18,8 @@ use starcoin_logger::prelude::*;
 use starcoin_service_registry::{
     ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler,
 };
+use starcoin_state_api::AccountStateReader;
+use starcoin_statedb::ChainStateDB;
 use starcoin_storage::{BlockStore, Storage, Store};
 use starcoin_types::block::ExecutedBlock;
 use starcoin_types::contract_event::ContractEventInfo;
@@ -30,8 +34,13 @@ use starcoin_types::{
 };
 use starcoin_vm_runtime::metrics::VMMetrics;
 use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::on_chain_resource::Epoch;
+use std::collections::HashMap;
 use std::sync::Arc;
 
+const MAX_FEE_HISTORY_PERCENTILES: usize = 100;
+
 /// A Chain reader service to provider Reader API.
 pub struct ChainReaderService {
     inner: ChainReaderServiceInner,
@@ -226,6 +235,27 @@ impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
                 self.inner
                     .get_transaction_infos(start_index, reverse, max_size)?,
             )),
+            ChainRequest::GetTransactionInfosByAddress {
+                address,
+                start_index,
+                reverse,
+                max_size,
+            } => Ok(ChainResponse::TransactionInfos(
+                self.inner.get_transaction_infos_by_address(
+                    address,
+                    start_index,
+                    reverse,
+                    max_size,
+                )?,
+            )),
+            ChainRequest::GetFeeHistory {
+                block_count,
+                newest_block,
+                percentiles,
+            } => Ok(ChainResponse::FeeHistory(Box::new(
+                self.inner
+                    .get_fee_history(block_count, newest_block, percentiles)?,
+            ))),
             ChainRequest::GetTransactionProof {
                 block_id,
                 transaction_global_index,
@@ -239,6 +269,30 @@ impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
                     access_path,
                 )?,
             ))),
+            ChainRequest::GetAccumulatorRangeProof {
+                block_id,
+                accumulator_type,
+                first_leaf_index,
+                num_leaves,
+            } => Ok(ChainResponse::AccumulatorRangeProof(Box::new(
+                self.inner.get_accumulator_range_proof(
+                    block_id,
+                    accumulator_type,
+                    first_leaf_index,
+                    num_leaves,
+                )?,
+            ))),
+            ChainRequest::GetAccumulatorConsistencyProof {
+                block_id,
+                accumulator_type,
+                old_num_leaves,
+            } => Ok(ChainResponse::AccumulatorConsistencyProof(Box::new(
+                self.inner.get_accumulator_consistency_proof(
+                    block_id,
+                    accumulator_type,
+                    old_num_leaves,
+                )?,
+            ))),
             ChainRequest::GetBlockInfos(ids) => Ok(ChainResponse::BlockInfoVec(Box::new(
                 self.inner.get_block_infos(ids)?,
             ))),
@@ -313,6 +367,20 @@ impl ChainReaderServiceInner {
         )?;
         Ok(())
     }
+
+    /// The epoch the block is executed in, read from the state of its parent block.
+    fn get_block_epoch(&self, header: &BlockHeader) -> Result<Epoch> {
+        let state_root = match self
+            .storage
+            .get_block_header_by_hash(header.parent_hash())?
+        {
+            Some(parent) => parent.state_root(),
+            // the genesis block has no parent.
+            None => header.state_root(),
+        };
+        let state = ChainStateDB::new(self.storage.clone().into_super_arc(), Some(state_root));
+        AccountStateReader::new(&state).get_epoch()
+    }
 }
 
 impl ReadableChainService for ChainReaderServiceInner {
@@ -426,6 +494,105 @@ impl ReadableChainService for ChainReaderServiceInner {
             .get_transaction_infos(start_index, reverse, max_size)
     }
 
+    fn get_transaction_infos_by_address(
+        &self,
+        address: AccountAddress,
+        start_index: Option<u64>,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<RichTransactionInfo>> {
+        if !self.config.storage.address_txn_index() {
+            bail!("The address txn index is not enabled, please start the node with --address-txn-index.");
+        }
+        let count = self.storage.get_address_txn_count(address)?;
+        if count == 0 {
+            return Ok(vec![]);
+        }
+        let start_index = match start_index {
+            Some(start_index) => start_index,
+            None if reverse => count - 1,
+            None => 0,
+        };
+        let txn_hashes = self
+            .storage
+            .get_address_txns(address, start_index, reverse, max_size)?;
+        let mut txn_infos = Vec::with_capacity(txn_hashes.len());
+        for txn_hash in txn_hashes {
+            if let Some(txn_info) = self.main.get_transaction_info(txn_hash)? {
+                txn_infos.push(txn_info);
+            }
+        }
+        Ok(txn_infos)
+    }
+
+    fn get_fee_history(
+        &self,
+        block_count: u64,
+        newest_block: Option<BlockNumber>,
+        percentiles: Vec<f64>,
+    ) -> Result<FeeHistory> {
+        ensure!(block_count > 0, "block_count should be greater than 0");
+        ensure!(
+            percentiles.len() <= MAX_FEE_HISTORY_PERCENTILES,
+            "too many percentiles, max is {}",
+            MAX_FEE_HISTORY_PERCENTILES
+        );
+        let mut prev = 0f64;
+        for percentile in &percentiles {
+            ensure!(
+                (0f64..=100f64).contains(percentile) && *percentile >= prev,
+                "percentiles should be in [0, 100] and in ascending order"
+            );
+            prev = *percentile;
+        }
+        let head_number = self.main.current_header().number();
+        let newest_block = newest_block.unwrap_or(head_number);
+        ensure!(
+            newest_block <= head_number,
+            "newest_block {} is greater than the head block number {}",
+            newest_block,
+            head_number
+        );
+        let oldest_block = newest_block.saturating_sub(block_count - 1);
+        let mut fee_history = FeeHistory {
+            oldest_block,
+            ..Default::default()
+        };
+        let mut epoch: Option<Epoch> = None;
+        for number in oldest_block..=newest_block {
+            let block = self
+                .main
+                .get_block_by_number(number)?
+                .ok_or_else(|| format_err!("Can not find block by number {}", number))?;
+            // The epoch only changes at its end block, so reuse it for the blocks it covers.
+            let block_epoch = match epoch {
+                Some(epoch)
+                    if epoch.start_block_number() <= number
+                        && number < epoch.end_block_number() =>
+                {
+                    epoch
+                }
+                _ => self.get_block_epoch(block.header())?,
+            };
+            let block_gas_limit = block_epoch.block_gas_limit();
+            epoch = Some(block_epoch);
+            let txn_infos = self.storage.get_block_transaction_infos(block.id())?;
+            fee_history.gas_used_ratio.push(if block_gas_limit == 0 {
+                0f64
+            } else {
+                block.header().gas_used() as f64 / block_gas_limit as f64
+            });
+            fee_history
+                .gas_price_percentiles
+                .push(block_gas_price_percentiles(
+                    &block,
+                    &txn_infos,
+                    &percentiles,
+                ));
+        }
+        Ok(fee_history)
+    }
+
     fn get_transaction_proof(
         &self,
         block_id: HashValue,
@@ -441,6 +608,31 @@ impl ReadableChainService for ChainReaderServiceInner {
         )
     }
 
+    fn get_accumulator_range_proof(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        first_leaf_index: u64,
+        num_leaves: u64,
+    ) -> Result<Option<AccumulatorRangeProof>> {
+        self.main.get_accumulator_range_proof(
+            block_id,
+            accumulator_type,
+            first_leaf_index,
+            num_leaves,
+        )
+    }
+
+    fn get_accumulator_consistency_proof(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        old_num_leaves: u64,
+    ) -> Result<Option<AccumulatorConsistencyProof>> {
+        self.main
+            .get_accumulator_consistency_proof(block_id, accumulator_type, old_num_leaves)
+    }
+
     fn get_block_infos(&self, ids: Vec<HashValue>) -> Result<Vec<Option<BlockInfo>>> {
         self.storage.get_block_infos(ids)
     }
@@ -462,6 +654,7 @@ impl ReadableChainService for ChainReaderServiceInner {
         Ok(DagStateView {
             dag_genesis,
             tips: state.tips,
+            pruning_point: state.pruning_point,
         })
     }
 
@@ -479,12 +672,60 @@ impl ReadableChainService for ChainReaderServiceInner {
     }
 }
 
+/// The gas unit prices at `percentiles` of the user transactions in the block, weighted by
+/// the gas used of the transactions.
+fn block_gas_price_percentiles(
+    block: &Block,
+    txn_infos: &[RichTransactionInfo],
+    percentiles: &[f64],
+) -> Vec<u64> {
+    let gas_used: HashMap<HashValue, u64> = txn_infos
+        .iter()
+        .map(|txn_info| (txn_info.transaction_hash(), txn_info.gas_used()))
+        .collect();
+    let mut prices: Vec<(u64, u64)> = block
+        .transactions()
+        .iter()
+        .map(|txn| {
+            (
+                txn.gas_unit_price(),
+                gas_used.get(&txn.id()).copied().unwrap_or_default(),
+            )
+        })
+        .collect();
+    if prices.is_empty() {
+        return vec![0; percentiles.len()];
+    }
+    prices.sort_unstable();
+    let total_gas_used: u64 = prices.iter().map(|(_, gas_used)| *gas_used).sum();
+    percentiles
+        .iter()
+        .map(|percentile| {
+            let threshold = total_gas_used as f64 * percentile / 100f64;
+            let mut sum_gas_used = 0u64;
+            for (price, gas_used) in &prices {
+                sum_gas_used = sum_gas_used.saturating_add(*gas_used);
+                if sum_gas_used as f64 >= threshold {
+                    return *price;
+                }
+            }
+            prices.last().map(|(price, _)| *price).unwrap_or_default()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use starcoin_chain_api::ChainAsyncService;
     use starcoin_config::NodeConfig;
+    use starcoin_consensus::Consensus;
     use starcoin_service_registry::{RegistryAsyncService, RegistryService};
+    use starcoin_transaction_builder::{
+        create_signed_txn_with_association_account, encode_transfer_script_function,
+        DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT,
+    };
+    use starcoin_types::transaction::TransactionPayload;
 
     #[stest::test]
     async fn test_actor_launch() -> Result<()> {
@@ -500,4 +741,101 @@ mod tests {
         assert_eq!(&chain_status, chain_info.status());
         Ok(())
     }
+
+    #[stest::test]
+    async fn test_fee_history() -> Result<()> {
+        let config = Arc::new(NodeConfig::random_for_test());
+        let (storage, _, _, dag) = test_helper::Genesis::init_storage_for_test(config.net())?;
+        let registry = RegistryService::launch();
+        registry.put_shared(dag).await?;
+        registry.put_shared(config).await?;
+        registry.put_shared(storage).await?;
+        let service_ref = registry.register::<ChainReaderService>().await?;
+        let fee_history = service_ref
+            .get_fee_history(10, None, vec![10f64, 50f64, 90f64])
+            .await?;
+        assert_eq!(fee_history.oldest_block, 0);
+        assert_eq!(fee_history.gas_used_ratio.len(), 1);
+        assert_eq!(fee_history.gas_price_percentiles, vec![vec![0, 0, 0]]);
+        assert!(service_ref
+            .get_fee_history(1, None, vec![50f64, 10f64])
+            .await
+            .is_err());
+        assert!(service_ref
+            .get_fee_history(1, Some(1), vec![])
+            .await
+            .is_err());
+        Ok(())
+    }
+    #[stest::test]
+    fn test_fee_history_of_mined_blocks() -> Result<()> {
+        let config = Arc::new(NodeConfig::random_for_test());
+        let net = config.net();
+        let (storage, chain_info, _, dag) = test_helper::Genesis::init_storage_for_test(net)?;
+        let mut chain = BlockChain::new(
+            net.time_service(),
+            chain_info.head().id(),
+            storage.clone(),
+            None,
+            dag.clone(),
+        )?;
+        let miner = AccountAddress::random();
+        let mut seq_num = 0;
+        // the gas unit prices of the transactions in each block.
+        for gas_prices in [vec![3, 1, 2], vec![], vec![5]] {
+            let txns = gas_prices
+                .into_iter()
+                .map(|gas_price| {
+                    let txn = create_signed_txn_with_association_account(
+                        TransactionPayload::ScriptFunction(encode_transfer_script_function(
+                            AccountAddress::random(),
+                            10000,
+                        )),
+                        seq_num,
+                        DEFAULT_MAX_GAS_AMOUNT,
+                        gas_price,
+                        net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+                        net,
+                    );
+                    seq_num += 1;
+                    txn
+                })
+                .collect::<Vec<_>>();
+            let (template, excluded) =
+                chain.create_block_template(miner, None, txns, vec![], None, None)?;
+            assert!(excluded.discarded_txns.is_empty());
+            let block = chain
+                .consensus()
+                .create_block(template, net.time_service().as_ref())?;
+            chain.apply(block)?;
+        }
+
+        let inner = ChainReaderServiceInner::new(
+            config.clone(),
+            StartupInfo::new(chain.current_header().id()),
+            storage,
+            dag,
+            None,
+        )?;
+        let fee_history = inner.get_fee_history(3, None, vec![10f64, 50f64, 90f64])?;
+        assert_eq!(fee_history.oldest_block, 1);
+        assert_eq!(
+            fee_history.gas_price_percentiles,
+            vec![vec![1, 2, 3], vec![0, 0, 0], vec![5, 5, 5]]
+        );
+        let block_gas_limit = chain.epoch().block_gas_limit();
+        let gas_used_ratio = (1..=3)
+            .map(|number| {
+                let header = chain.get_header_by_number(number)?.unwrap();
+                Ok(header.gas_used() as f64 / block_gas_limit as f64)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(fee_history.gas_used_ratio, gas_used_ratio);
+        assert!(fee_history.gas_used_ratio[0] > fee_history.gas_used_ratio[2]);
+
+        let fee_history = inner.get_fee_history(2, Some(2), vec![50f64])?;
+        assert_eq!(fee_history.oldest_block, 1);
+        assert_eq!(fee_history.gas_price_percentiles, vec![vec![2], vec![0]]);
+        Ok(())
+    }
 }
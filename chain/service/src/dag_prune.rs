@@ -0,0 +1,98 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{format_err, Result};
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_dag::blockdag::BlockDAG;
+use starcoin_logger::prelude::*;
+use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
+use starcoin_storage::{BlockStore, Storage, Store};
+use starcoin_types::block::{BlockInfo, BlockNumber};
+use starcoin_types::system_events::NewHeadBlock;
+use std::sync::Arc;
+
+/// DagPruneService watch `NewHeadBlock` and move the pruning point of the dag forward,
+/// the consensus data of the blocks in the past of the pruning point are deleted.
+/// The pruning is done out of the block connecting, so a pruning batch never delays
+/// the connecting of the blocks. It is only registered with `--enable-dag-pruning`, a pruned node
+/// can not serve the dag sync of the pruned blocks, and the new nodes can not sync from the
+/// pruning point snapshot yet.
+pub struct DagPruneService {
+    config: Arc<NodeConfig>,
+    storage: Arc<Storage>,
+    dag: BlockDAG,
+}
+
+impl DagPruneService {
+    pub fn new(config: Arc<NodeConfig>, storage: Arc<Storage>, dag: BlockDAG) -> Self {
+        Self {
+            config,
+            storage,
+            dag,
+        }
+    }
+
+    /// Prune the dag of the main chain whose head is `head_block_info`, return the new
+    /// pruning point, or `None` if the pruning point is not moved.
+    pub fn prune(
+        &self,
+        head_number: BlockNumber,
+        head_block_info: &BlockInfo,
+    ) -> Result<Option<HashValue>> {
+        let dag_effective_height = self.config.net().genesis_config().dag_effective_height;
+        if head_number < dag_effective_height {
+            return Ok(None);
+        }
+        let block_accumulator = MerkleAccumulator::new_with_info(
+            head_block_info.block_accumulator_info.clone(),
+            self.storage
+                .get_accumulator_store(AccumulatorStoreType::Block),
+        );
+        let dag_genesis = block_accumulator
+            .get_leaf(dag_effective_height)?
+            .ok_or_else(|| format_err!("Can not find the dag genesis"))?;
+        let origin = self
+            .storage
+            .get_block_header_by_hash(dag_genesis)?
+            .ok_or_else(|| format_err!("Can not find block header by id: {}", dag_genesis))?
+            .parent_hash();
+        self.dag.try_prune(dag_genesis, origin)
+    }
+}
+
+impl ServiceFactory<Self> for DagPruneService {
+    fn create(ctx: &mut ServiceContext<Self>) -> Result<Self> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let storage = ctx.get_shared::<Arc<Storage>>()?;
+        let dag = ctx.get_shared::<BlockDAG>()?;
+        Ok(Self::new(config, storage, dag))
+    }
+}
+
+impl ActorService for DagPruneService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.subscribe::<NewHeadBlock>();
+        Ok(())
+    }
+
+    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.unsubscribe::<NewHeadBlock>();
+        Ok(())
+    }
+}
+
+impl EventHandler<Self, NewHeadBlock> for DagPruneService {
+    fn handle_event(&mut self, msg: NewHeadBlock, _ctx: &mut ServiceContext<Self>) {
+        let head_number = msg.executed_block.header().number();
+        match self.prune(head_number, msg.executed_block.block_info()) {
+            Ok(Some(pruning_point)) => {
+                info!("Dag pruned, the pruning point moves to {}", pruning_point)
+            }
+            Ok(None) => (),
+            Err(e) => error!("Dag prune failed at head {}: {:?}", head_number, e),
+        }
+    }
+}
@@ -2,6 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod chain_service;
+mod dag_prune;
 
 pub use chain_service::ChainReaderService;
-pub use starcoin_chain_api::{ChainAsyncService, ReadableChainService, WriteableChainService};
+pub use dag_prune::DagPruneService;
+pub use starcoin_chain_api::{
+    ChainAsyncService, FeeHistory, ReadableChainService, WriteableChainService,
+};
@@ -6,6 +6,7 @@ use anyhow::{anyhow, bail, ensure, format_err, Ok, Result};
 use once_cell::sync::Lazy;
 use sp_utils::stop_watch::{watch, CHAIN_WATCH_NAME};
 use starcoin_accumulator::inmemory::InMemoryAccumulator;
+use starcoin_accumulator::proof::{AccumulatorConsistencyProof, AccumulatorRangeProof};
 use starcoin_accumulator::{
     accumulator_info::AccumulatorInfo, node::AccumulatorStoreType, Accumulator, MerkleAccumulator,
 };
@@ -993,11 +994,19 @@ impl BlockChain {
     where
         V: BlockVerifier,
     {
-        let verified_block = self.verify_with_verifier::<V>(block)?;
+        let _span = tracing::info_span!(
+            "apply_block",
+            id = %block.id(),
+            number = block.header().number()
+        )
+        .entered();
+        let verified_block = tracing::info_span!("verify_block")
+            .in_scope(|| self.verify_with_verifier::<V>(block))?;
         watch(CHAIN_WATCH_NAME, "n1");
-        let executed_block = self.execute(verified_block)?;
+        let executed_block =
+            tracing::info_span!("execute_block").in_scope(|| self.execute(verified_block))?;
         watch(CHAIN_WATCH_NAME, "n2");
-        self.connect(executed_block)
+        tracing::info_span!("connect_block").in_scope(|| self.connect(executed_block))
     }
 
     pub fn verify_without_save<V>(&mut self, block: Block) -> Result<ExecutedBlock>
@@ -1732,6 +1741,26 @@ impl BlockChain {
             Err(_) => 0,
         })
     }
+
+    /// Fork the accumulator of `accumulator_type` at the state after the block `block_id`.
+    fn fork_accumulator(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+    ) -> Result<Option<MerkleAccumulator>> {
+        let block_info = match self.get_block_info(Some(block_id))? {
+            Some(block_info) => block_info,
+            None => return Ok(None),
+        };
+        Ok(Some(match accumulator_type {
+            AccumulatorStoreType::Transaction => self
+                .txn_accumulator
+                .fork(Some(block_info.txn_accumulator_info)),
+            AccumulatorStoreType::Block => self
+                .block_accumulator
+                .fork(Some(block_info.block_accumulator_info)),
+        }))
+    }
 }
 
 impl ChainReader for BlockChain {
@@ -2118,6 +2147,31 @@ impl ChainReader for BlockChain {
         }))
     }
 
+    fn get_accumulator_range_proof(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        first_leaf_index: u64,
+        num_leaves: u64,
+    ) -> Result<Option<AccumulatorRangeProof>> {
+        match self.fork_accumulator(block_id, accumulator_type)? {
+            Some(accumulator) => accumulator.get_range_proof(first_leaf_index, num_leaves),
+            None => Ok(None),
+        }
+    }
+
+    fn get_accumulator_consistency_proof(
+        &self,
+        block_id: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        old_num_leaves: u64,
+    ) -> Result<Option<AccumulatorConsistencyProof>> {
+        match self.fork_accumulator(block_id, accumulator_type)? {
+            Some(accumulator) => accumulator.get_consistency_proof(old_num_leaves),
+            None => Ok(None),
+        }
+    }
+
     fn current_tips_hash(&self) -> Result<Option<(HashValue, Vec<HashValue>)>> {
         let (dag_genesis, dag_state) = self.get_dag_state_by_block()?;
         Ok(Some((dag_genesis, dag_state.tips)))
@@ -2309,7 +2363,8 @@ impl BlockChain {
         if self.epoch.end_block_number() == block.header().number() {
             self.epoch = get_epoch_from_statedb(&self.statedb)?;
         }
-        self.dag.save_dag_state(dag_genesis, DagState { tips })?;
+        // The pruning point is moved by the `DagPruneService` out of the connecting.
+        self.dag.save_dag_tips(dag_genesis, tips)?;
         Ok(executed_block)
     }
 }
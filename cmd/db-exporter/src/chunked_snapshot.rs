@@ -0,0 +1,784 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Chunked snapshot format.
+//!
+//! Every column of the snapshot is split into gzip compressed chunks of at most `chunk_size`
+//! records, so neither export nor apply has to hold a whole column in memory.
+//! The snapshot dir layout:
+//!
+//! ```text
+//! snapshot_manifest.json       the SnapshotManifest
+//! snapshot_manifest.json.sha3  the sha3 hash of the manifest file
+//! <column>.<chunk_index>.gz    the chunks of every column
+//! ```
+//!
+//! The manifest records the sha3 hash of every chunk and the accumulator roots and state roots
+//! of the snapshot block. Apply verifies every chunk before applying it, records the last applied
+//! chunk of every column in a progress file in the target dir, and resumes from there when it is
+//! interrupted.
+//!
+//! The `.sha3` file only proves the integrity of the manifest, anyone who can modify the snapshot
+//! can rewrite it too. To make sure the snapshot comes from a trusted exporter, get the manifest
+//! hash from the exporter through a trusted channel and pass it to apply as the expected hash.
+
+use anyhow::{ensure, format_err, Result};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
+use starcoin_accumulator::{
+    accumulator_info::AccumulatorInfo, node::AccumulatorStoreType, Accumulator, MerkleAccumulator,
+};
+use starcoin_crypto::HashValue;
+use starcoin_statedb::{ChainStateDB, ChainStateReader, ChainStateWriter};
+use starcoin_storage::{
+    block_info::BlockInfoStore, BlockStore, Storage, Store, BLOCK_ACCUMULATOR_NODE_PREFIX_NAME,
+    BLOCK_INFO_PREFIX_NAME, BLOCK_PREFIX_NAME, STATE_NODE_PREFIX_NAME, STATE_NODE_PREFIX_NAME_PREV,
+    TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME,
+};
+use starcoin_types::{
+    account_address::AccountAddress,
+    block::{Block, BlockInfo, BlockNumber},
+    state_set::{AccountStateSet, ChainStateSet},
+};
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+    thread::JoinHandle,
+};
+
+pub const SNAPSHOT_MANIFEST_NAME: &str = "snapshot_manifest.json";
+pub const SNAPSHOT_MANIFEST_HASH_NAME: &str = "snapshot_manifest.json.sha3";
+pub const SNAPSHOT_APPLY_PROGRESS_NAME: &str = "snapshot_apply_progress.json";
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+const READ_BATCH_SIZE: u64 = 1000;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ChunkInfo {
+    /// The chunk file name, relative to the snapshot dir.
+    pub file: String,
+    pub records: u64,
+    /// The sha3 hash of the compressed chunk file.
+    pub hash: HashValue,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ColumnManifest {
+    pub column: String,
+    pub records: u64,
+    /// The accumulator root for accumulator columns, the snapshot block id for block columns,
+    /// and the state root for state columns.
+    pub root: HashValue,
+    pub chunks: Vec<ChunkInfo>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub version: u32,
+    pub chain_id: u8,
+    pub block_number: BlockNumber,
+    pub block_id: HashValue,
+    pub block_accumulator_root: HashValue,
+    pub txn_accumulator_root: HashValue,
+    pub state_root: HashValue,
+    /// The state root of the parent of the snapshot block, which is required to verify the
+    /// block after the snapshot block.
+    pub prev_state_root: HashValue,
+    pub chunk_size: u64,
+    pub columns: Vec<ColumnManifest>,
+}
+
+impl SnapshotManifest {
+    pub fn column(&self, column: &str) -> Result<&ColumnManifest> {
+        self.columns
+            .iter()
+            .find(|manifest| manifest.column == column)
+            .ok_or_else(|| format_err!("Can not find column {} in snapshot manifest", column))
+    }
+
+    /// Write the manifest and its hash to `dir`, return the manifest hash.
+    pub fn save(&self, dir: &Path) -> Result<HashValue> {
+        let bytes = serde_json::to_vec_pretty(self)?;
+        let hash = HashValue::sha3_256_of(&bytes);
+        std::fs::write(dir.join(SNAPSHOT_MANIFEST_NAME), bytes)?;
+        std::fs::write(dir.join(SNAPSHOT_MANIFEST_HASH_NAME), hash.to_string())?;
+        Ok(hash)
+    }
+
+    /// Load the manifest from `dir` and check it against the hash file, and against
+    /// `expected_hash` if given. Return the manifest and its hash.
+    /// Only the check against `expected_hash` proves the manifest is the one of the exporter.
+    pub fn load(dir: &Path, expected_hash: Option<HashValue>) -> Result<(Self, HashValue)> {
+        let bytes = std::fs::read(dir.join(SNAPSHOT_MANIFEST_NAME))?;
+        let hash = HashValue::sha3_256_of(&bytes);
+        let recorded_hash = HashValue::from_hex_literal(
+            std::fs::read_to_string(dir.join(SNAPSHOT_MANIFEST_HASH_NAME))?.trim(),
+        )?;
+        ensure!(
+            hash == recorded_hash,
+            "Snapshot manifest hash mismatch, expect {}, got {}",
+            recorded_hash,
+            hash
+        );
+        if let Some(expected_hash) = expected_hash {
+            ensure!(
+                hash == expected_hash,
+                "Snapshot manifest hash mismatch, expect {}, got {}",
+                expected_hash,
+                hash
+            );
+        }
+        let manifest: Self = serde_json::from_slice(&bytes)?;
+        ensure!(
+            manifest.version == SNAPSHOT_FORMAT_VERSION,
+            "Unsupported snapshot format version {}",
+            manifest.version
+        );
+        Ok((manifest, hash))
+    }
+}
+
+/// Check whether `dir` contains a chunked snapshot.
+pub fn is_chunked_snapshot(dir: &Path) -> bool {
+    dir.join(SNAPSHOT_MANIFEST_NAME).exists()
+}
+
+struct ChunkWriter {
+    dir: PathBuf,
+    column: &'static str,
+    chunk_size: u64,
+    encoder: Option<GzEncoder<Vec<u8>>>,
+    records: u64,
+    chunks: Vec<ChunkInfo>,
+}
+
+impl ChunkWriter {
+    fn new(dir: PathBuf, column: &'static str, chunk_size: u64) -> Self {
+        Self {
+            dir,
+            column,
+            chunk_size,
+            encoder: None,
+            records: 0,
+            chunks: vec![],
+        }
+    }
+
+    fn write_record(&mut self, record: &str) -> Result<()> {
+        let encoder = self
+            .encoder
+            .get_or_insert_with(|| GzEncoder::new(Vec::new(), Compression::default()));
+        writeln!(encoder, "{}", record)?;
+        self.records += 1;
+        if self.records >= self.chunk_size {
+            self.finish_chunk()?;
+        }
+        Ok(())
+    }
+
+    fn finish_chunk(&mut self) -> Result<()> {
+        if let Some(encoder) = self.encoder.take() {
+            let bytes = encoder.finish()?;
+            let file = format!("{}.{:06}.gz", self.column, self.chunks.len());
+            std::fs::write(self.dir.join(&file), &bytes)?;
+            self.chunks.push(ChunkInfo {
+                file,
+                records: self.records,
+                hash: HashValue::sha3_256_of(&bytes),
+            });
+            self.records = 0;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self, root: HashValue) -> Result<ColumnManifest> {
+        self.finish_chunk()?;
+        Ok(ColumnManifest {
+            column: self.column.to_string(),
+            records: self.chunks.iter().map(|chunk| chunk.records).sum(),
+            root,
+            chunks: self.chunks,
+        })
+    }
+}
+
+/// Read the records of the chunk, the chunk is verified against the hash in the manifest.
+fn read_chunk(dir: &Path, chunk: &ChunkInfo) -> Result<Vec<String>> {
+    let bytes = std::fs::read(dir.join(&chunk.file))?;
+    let hash = HashValue::sha3_256_of(&bytes);
+    ensure!(
+        hash == chunk.hash,
+        "Chunk {} hash mismatch, expect {}, got {}",
+        chunk.file,
+        chunk.hash,
+        hash
+    );
+    let records = BufReader::new(GzDecoder::new(bytes.as_slice()))
+        .lines()
+        .collect::<std::io::Result<Vec<_>>>()?;
+    ensure!(
+        records.len() as u64 == chunk.records,
+        "Chunk {} records mismatch, expect {}, got {}",
+        chunk.file,
+        chunk.records,
+        records.len()
+    );
+    Ok(records)
+}
+
+fn new_bar(mbar: &MultiProgress, len: u64) -> ProgressBar {
+    let bar = mbar.add(ProgressBar::new(len));
+    bar.set_style(
+        ProgressStyle::default_bar()
+            .template("[{elapsed_precise}] {bar:100.cyan/blue} {percent}% {msg}"),
+    );
+    bar
+}
+
+/// Export the leaves of the accumulator from index 1 to `num`, and the blocks or block infos
+/// of the leaves for block columns. The genesis is skipped, it is created by the target node.
+fn export_accumulator_column(
+    storage: Arc<Storage>,
+    accumulator: MerkleAccumulator,
+    mut writer: ChunkWriter,
+    num: u64,
+    root: HashValue,
+    bar: ProgressBar,
+) -> Result<ColumnManifest> {
+    let column = writer.column;
+    let mut start_index = 1;
+    while start_index <= num {
+        let max_size = std::cmp::min(READ_BATCH_SIZE, num - start_index + 1);
+        let ids = accumulator.get_leaves(start_index, false, max_size)?;
+        match column {
+            BLOCK_ACCUMULATOR_NODE_PREFIX_NAME | TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME => {
+                for id in ids {
+                    writer.write_record(id.to_string().as_str())?;
+                }
+            }
+            BLOCK_PREFIX_NAME => {
+                for (id, block) in ids.iter().zip(storage.get_blocks(ids.clone())?) {
+                    let block =
+                        block.ok_or_else(|| format_err!("get block by hash {} error", id))?;
+                    writer.write_record(serde_json::to_string(&block)?.as_str())?;
+                }
+            }
+            BLOCK_INFO_PREFIX_NAME => {
+                for (id, block_info) in ids.iter().zip(storage.get_block_infos(ids.clone())?) {
+                    let block_info = block_info
+                        .ok_or_else(|| format_err!("get block info by hash {} error", id))?;
+                    writer.write_record(serde_json::to_string(&block_info)?.as_str())?;
+                }
+            }
+            _ => return Err(format_err!("Unsupported accumulator column {}", column)),
+        }
+        start_index += max_size;
+        bar.set_message(format!("export {} {}", column, start_index - 1));
+        bar.inc(max_size);
+    }
+    bar.finish();
+    writer.finish(root)
+}
+
+fn export_state_column(
+    storage: Arc<Storage>,
+    mut writer: ChunkWriter,
+    state_root: HashValue,
+    bar: ProgressBar,
+) -> Result<ColumnManifest> {
+    let column = writer.column;
+    let statedb = ChainStateDB::new(storage, Some(state_root));
+    let mut index: u64 = 0;
+    for (account_address, account_state_set) in statedb.dump_iter()? {
+        writer.write_record(
+            format!(
+                "{} {}",
+                serde_json::to_string(&account_address)?,
+                serde_json::to_string(&account_state_set)?
+            )
+            .as_str(),
+        )?;
+        index += 1;
+        if index % READ_BATCH_SIZE == 0 {
+            bar.set_message(format!("export {} {}", column, index));
+            bar.tick();
+        }
+    }
+    bar.finish();
+    writer.finish(state_root)
+}
+
+/// Export a chunked snapshot of the chain at `block` to `output`, `prev_block` is the parent
+/// of `block`. Return the manifest and its hash.
+pub fn export_chunked_snapshot(
+    storage: Arc<Storage>,
+    chain_id: u8,
+    block: Block,
+    block_info: BlockInfo,
+    prev_block: Block,
+    output: PathBuf,
+    chunk_size: u64,
+) -> Result<(SnapshotManifest, HashValue)> {
+    ensure!(chunk_size > 0, "chunk size should be greater than 0");
+    std::fs::create_dir_all(&output)?;
+    let block_id = block.id();
+    let block_number = block.header().number();
+    let block_accumulator_info = block_info.get_block_accumulator_info().clone();
+    let txn_accumulator_info = block_info.get_txn_accumulator_info().clone();
+    let state_root = block.header().state_root();
+    let prev_state_root = prev_block.header().state_root();
+
+    let mbar = MultiProgress::new();
+    let mut handles: Vec<JoinHandle<Result<ColumnManifest>>> = vec![];
+    for (column, num, root) in [
+        (
+            BLOCK_ACCUMULATOR_NODE_PREFIX_NAME,
+            block_number,
+            block_accumulator_info.accumulator_root,
+        ),
+        (BLOCK_PREFIX_NAME, block_number, block_id),
+        (BLOCK_INFO_PREFIX_NAME, block_number, block_id),
+        (
+            TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME,
+            txn_accumulator_info.get_num_leaves().saturating_sub(1),
+            txn_accumulator_info.accumulator_root,
+        ),
+    ] {
+        let accumulator = if column == TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME {
+            MerkleAccumulator::new_with_info(
+                txn_accumulator_info.clone(),
+                storage.get_accumulator_store(AccumulatorStoreType::Transaction),
+            )
+        } else {
+            MerkleAccumulator::new_with_info(
+                block_accumulator_info.clone(),
+                storage.get_accumulator_store(AccumulatorStoreType::Block),
+            )
+        };
+        let writer = ChunkWriter::new(output.clone(), column, chunk_size);
+        let storage = storage.clone();
+        let bar = new_bar(&mbar, num);
+        handles.push(thread::spawn(move || {
+            export_accumulator_column(storage, accumulator, writer, num, root, bar)
+        }));
+    }
+    for (column, root) in [
+        (STATE_NODE_PREFIX_NAME, state_root),
+        (STATE_NODE_PREFIX_NAME_PREV, prev_state_root),
+    ] {
+        let writer = ChunkWriter::new(output.clone(), column, chunk_size);
+        let storage = storage.clone();
+        let bar = new_bar(&mbar, 0);
+        handles.push(thread::spawn(move || {
+            export_state_column(storage, writer, root, bar)
+        }));
+    }
+    mbar.join_and_clear()?;
+    let mut columns = vec![];
+    for handle in handles {
+        columns.push(
+            handle
+                .join()
+                .map_err(|_| format_err!("export snapshot thread panicked"))??,
+        );
+    }
+
+    let manifest = SnapshotManifest {
+        version: SNAPSHOT_FORMAT_VERSION,
+        chain_id,
+        block_number,
+        block_id,
+        block_accumulator_root: block_accumulator_info.accumulator_root,
+        txn_accumulator_root: txn_accumulator_info.accumulator_root,
+        state_root,
+        prev_state_root,
+        chunk_size,
+        columns,
+    };
+    let hash = manifest.save(output.as_path())?;
+    Ok((manifest, hash))
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct ColumnProgress {
+    /// The index of the next chunk to apply.
+    next_chunk: usize,
+    /// The accumulator info after the applied chunks, for accumulator columns.
+    accumulator_info: Option<AccumulatorInfo>,
+    /// The state root after the applied chunks, for state columns.
+    state_root: Option<HashValue>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ApplyProgress {
+    manifest_hash: HashValue,
+    columns: BTreeMap<String, ColumnProgress>,
+}
+
+/// The apply progress, it is saved after every applied chunk.
+struct ProgressFile {
+    path: PathBuf,
+    progress: Mutex<ApplyProgress>,
+}
+
+impl ProgressFile {
+    fn load_or_create(path: PathBuf, manifest_hash: HashValue) -> Result<Self> {
+        let progress = if path.exists() {
+            let progress: ApplyProgress = serde_json::from_slice(&std::fs::read(&path)?)?;
+            ensure!(
+                progress.manifest_hash == manifest_hash,
+                "The apply progress {:?} belongs to snapshot {}, not {}, remove it to apply a new snapshot",
+                path,
+                progress.manifest_hash,
+                manifest_hash
+            );
+            progress
+        } else {
+            ApplyProgress {
+                manifest_hash,
+                columns: BTreeMap::new(),
+            }
+        };
+        Ok(Self {
+            path,
+            progress: Mutex::new(progress),
+        })
+    }
+
+    fn get(&self, column: &str) -> ColumnProgress {
+        self.progress
+            .lock()
+            .unwrap()
+            .columns
+            .get(column)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Update the progress of the column, the file is replaced by rename,
+    /// so it is never left half written.
+    fn update(&self, column: &str, column_progress: ColumnProgress) -> Result<()> {
+        let mut progress = self.progress.lock().unwrap();
+        progress.columns.insert(column.to_string(), column_progress);
+        let tmp_path = self.path.with_extension("new");
+        std::fs::write(&tmp_path, serde_json::to_vec(&*progress)?)?;
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Applying a chunk twice gives the same result, so a chunk applied before an interruption
+/// but not recorded in the progress is just applied again.
+fn apply_accumulator_column(
+    storage: Arc<Storage>,
+    input: PathBuf,
+    manifest: ColumnManifest,
+    base_info: AccumulatorInfo,
+    progress: Arc<ProgressFile>,
+    bar: ProgressBar,
+) -> Result<()> {
+    let column = manifest.column.as_str();
+    let store_type = if column == TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME {
+        AccumulatorStoreType::Transaction
+    } else {
+        AccumulatorStoreType::Block
+    };
+    let column_progress = progress.get(column);
+    let accumulator = MerkleAccumulator::new_with_info(
+        column_progress.accumulator_info.unwrap_or(base_info),
+        storage.get_accumulator_store(store_type),
+    );
+    bar.inc(column_progress.next_chunk as u64);
+    for (index, chunk) in manifest
+        .chunks
+        .iter()
+        .enumerate()
+        .skip(column_progress.next_chunk)
+    {
+        let records = read_chunk(input.as_path(), chunk)?;
+        let mut leaves = Vec::with_capacity(records.len());
+        for record in records {
+            leaves.push(HashValue::from_hex_literal(record.as_str())?);
+        }
+        accumulator.append(&leaves)?;
+        accumulator.flush()?;
+        progress.update(
+            column,
+            ColumnProgress {
+                next_chunk: index + 1,
+                accumulator_info: Some(accumulator.get_info()),
+                state_root: None,
+            },
+        )?;
+        bar.set_message(format!("import {} {}", column, chunk.file));
+        bar.inc(1);
+    }
+    ensure!(
+        accumulator.root_hash() == manifest.root,
+        "{} root mismatch, expect {}, got {}",
+        column,
+        manifest.root,
+        accumulator.root_hash()
+    );
+    bar.finish();
+    Ok(())
+}
+
+fn apply_block_column(
+    storage: Arc<Storage>,
+    input: PathBuf,
+    manifest: ColumnManifest,
+    progress: Arc<ProgressFile>,
+    bar: ProgressBar,
+) -> Result<()> {
+    let column = manifest.column.as_str();
+    let column_progress = progress.get(column);
+    bar.inc(column_progress.next_chunk as u64);
+    for (index, chunk) in manifest
+        .chunks
+        .iter()
+        .enumerate()
+        .skip(column_progress.next_chunk)
+    {
+        for record in read_chunk(input.as_path(), chunk)? {
+            match column {
+                BLOCK_PREFIX_NAME => {
+                    let block: Block = serde_json::from_str(record.as_str())?;
+                    storage.commit_block(block)?;
+                }
+                BLOCK_INFO_PREFIX_NAME => {
+                    let block_info: BlockInfo = serde_json::from_str(record.as_str())?;
+                    storage.save_block_info(block_info)?;
+                }
+                _ => return Err(format_err!("Unsupported block column {}", column)),
+            }
+        }
+        progress.update(
+            column,
+            ColumnProgress {
+                next_chunk: index + 1,
+                ..Default::default()
+            },
+        )?;
+        bar.set_message(format!("import {} {}", column, chunk.file));
+        bar.inc(1);
+    }
+    let exist = match column {
+        BLOCK_PREFIX_NAME => storage.get_block(manifest.root)?.is_some(),
+        _ => storage.get_block_info(manifest.root)?.is_some(),
+    };
+    ensure!(
+        exist,
+        "{} of snapshot block {} is not applied",
+        column,
+        manifest.root
+    );
+    bar.finish();
+    Ok(())
+}
+
+fn apply_state_column(
+    storage: Arc<Storage>,
+    input: PathBuf,
+    manifest: ColumnManifest,
+    base_state_root: HashValue,
+    progress: Arc<ProgressFile>,
+    bar: ProgressBar,
+) -> Result<()> {
+    let column = manifest.column.as_str();
+    let column_progress = progress.get(column);
+    let statedb = ChainStateDB::new(
+        storage,
+        Some(column_progress.state_root.unwrap_or(base_state_root)),
+    );
+    bar.inc(column_progress.next_chunk as u64);
+    for (index, chunk) in manifest
+        .chunks
+        .iter()
+        .enumerate()
+        .skip(column_progress.next_chunk)
+    {
+        let mut account_states = vec![];
+        for record in read_chunk(input.as_path(), chunk)? {
+            let (address, state_set) = record
+                .split_once(' ')
+                .ok_or_else(|| format_err!("Invalid state record in chunk {}", chunk.file))?;
+            let account_address: AccountAddress = serde_json::from_str(address)?;
+            let account_state_set: AccountStateSet = serde_json::from_str(state_set)?;
+            account_states.push((account_address, account_state_set));
+        }
+        statedb.apply(ChainStateSet::new(account_states))?;
+        progress.update(
+            column,
+            ColumnProgress {
+                next_chunk: index + 1,
+                accumulator_info: None,
+                state_root: Some(statedb.state_root()),
+            },
+        )?;
+        bar.set_message(format!("import {} {}", column, chunk.file));
+        bar.inc(1);
+    }
+    ensure!(
+        statedb.state_root() == manifest.root,
+        "{} root mismatch, expect {}, got {}",
+        column,
+        manifest.root,
+        statedb.state_root()
+    );
+    bar.finish();
+    Ok(())
+}
+
+/// Apply the chunked snapshot in `input` to the storage of `to_dir`, which only contains the
+/// genesis described by `genesis_info` and `genesis_state_root`.
+/// Every chunk is verified before it is applied, and the apply resumes from the last applied
+/// chunks if it was interrupted. Return the applied manifest.
+pub fn apply_chunked_snapshot(
+    storage: Arc<Storage>,
+    chain_id: u8,
+    genesis_info: BlockInfo,
+    genesis_state_root: HashValue,
+    input: PathBuf,
+    to_dir: PathBuf,
+    expected_manifest_hash: Option<HashValue>,
+) -> Result<SnapshotManifest> {
+    let (manifest, manifest_hash) =
+        SnapshotManifest::load(input.as_path(), expected_manifest_hash)?;
+    ensure!(
+        manifest.chain_id == chain_id,
+        "Snapshot chain id {} mismatch with the network chain id {}",
+        manifest.chain_id,
+        chain_id
+    );
+    for column_manifest in &manifest.columns {
+        for chunk in &column_manifest.chunks {
+            ensure!(
+                input.join(&chunk.file).exists(),
+                "Chunk file {} is missing",
+                chunk.file
+            );
+        }
+    }
+    let progress = Arc::new(ProgressFile::load_or_create(
+        to_dir.join(SNAPSHOT_APPLY_PROGRESS_NAME),
+        manifest_hash,
+    )?);
+
+    let mbar = MultiProgress::new();
+    let mut handles: Vec<JoinHandle<Result<()>>> = vec![];
+    for column_manifest in manifest.columns.iter().cloned() {
+        let storage = storage.clone();
+        let input = input.clone();
+        let progress = progress.clone();
+        let bar = new_bar(&mbar, column_manifest.chunks.len() as u64);
+        let handle = match column_manifest.column.as_str() {
+            BLOCK_ACCUMULATOR_NODE_PREFIX_NAME => {
+                let base_info = genesis_info.get_block_accumulator_info().clone();
+                thread::spawn(move || {
+                    apply_accumulator_column(
+                        storage,
+                        input,
+                        column_manifest,
+                        base_info,
+                        progress,
+                        bar,
+                    )
+                })
+            }
+            TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME => {
+                let base_info = genesis_info.get_txn_accumulator_info().clone();
+                thread::spawn(move || {
+                    apply_accumulator_column(
+                        storage,
+                        input,
+                        column_manifest,
+                        base_info,
+                        progress,
+                        bar,
+                    )
+                })
+            }
+            BLOCK_PREFIX_NAME | BLOCK_INFO_PREFIX_NAME => thread::spawn(move || {
+                apply_block_column(storage, input, column_manifest, progress, bar)
+            }),
+            STATE_NODE_PREFIX_NAME | STATE_NODE_PREFIX_NAME_PREV => thread::spawn(move || {
+                apply_state_column(
+                    storage,
+                    input,
+                    column_manifest,
+                    genesis_state_root,
+                    progress,
+                    bar,
+                )
+            }),
+            column => return Err(format_err!("Unsupported snapshot column {}", column)),
+        };
+        handles.push(handle);
+    }
+    mbar.join_and_clear()?;
+    for handle in handles {
+        handle
+            .join()
+            .map_err(|_| format_err!("apply snapshot thread panicked"))??;
+    }
+
+    verify_snapshot_roots(storage.as_ref(), &manifest)?;
+    std::fs::remove_file(to_dir.join(SNAPSHOT_APPLY_PROGRESS_NAME))?;
+    Ok(manifest)
+}
+
+/// Verify the applied snapshot block against the roots recorded in the manifest.
+fn verify_snapshot_roots(storage: &Storage, manifest: &SnapshotManifest) -> Result<()> {
+    ensure!(
+        manifest.column(BLOCK_ACCUMULATOR_NODE_PREFIX_NAME)?.root
+            == manifest.block_accumulator_root
+            && manifest
+                .column(TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME)?
+                .root
+                == manifest.txn_accumulator_root
+            && manifest.column(STATE_NODE_PREFIX_NAME)?.root == manifest.state_root
+            && manifest.column(STATE_NODE_PREFIX_NAME_PREV)?.root == manifest.prev_state_root,
+        "Snapshot manifest column roots mismatch with the snapshot block roots"
+    );
+    let block = storage
+        .get_block(manifest.block_id)?
+        .ok_or_else(|| format_err!("Can not find snapshot block {}", manifest.block_id))?;
+    let block_info = storage
+        .get_block_info(manifest.block_id)?
+        .ok_or_else(|| format_err!("Can not find snapshot block info {}", manifest.block_id))?;
+    ensure!(
+        block.header().number() == manifest.block_number
+            && block.header().state_root() == manifest.state_root,
+        "Snapshot block {} mismatch with the manifest",
+        manifest.block_id
+    );
+    ensure!(
+        block_info.get_block_accumulator_info().accumulator_root == manifest.block_accumulator_root
+            && block_info.get_txn_accumulator_info().accumulator_root
+                == manifest.txn_accumulator_root,
+        "Snapshot block info {} mismatch with the manifest",
+        manifest.block_id
+    );
+    let prev_block = storage
+        .get_block(block.header().parent_hash())?
+        .ok_or_else(|| {
+            format_err!(
+                "Can not find the parent of snapshot block {}",
+                manifest.block_id
+            )
+        })?;
+    ensure!(
+        prev_block.header().state_root() == manifest.prev_state_root,
+        "Snapshot parent block {} mismatch with the manifest",
+        prev_block.id()
+    );
+    Ok(())
+}
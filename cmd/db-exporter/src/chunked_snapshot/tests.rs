@@ -0,0 +1,209 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use starcoin_account_api::AccountInfo;
+use starcoin_chain::{BlockChain, ChainReader, ChainWriter};
+use starcoin_config::{temp_dir, ChainNetwork};
+use starcoin_consensus::Consensus;
+use starcoin_genesis::Genesis;
+use starcoin_types::startup_info::ChainInfo;
+
+const CHUNK_SIZE: u64 = 2;
+
+/// Export a snapshot of a chain with `count` blocks to `output`.
+fn export_test_snapshot(
+    net: &ChainNetwork,
+    count: u64,
+    output: &Path,
+) -> Result<(SnapshotManifest, HashValue)> {
+    let (storage, chain_info, _, dag) = Genesis::init_storage_for_test(net)?;
+    let mut chain = BlockChain::new(
+        net.time_service(),
+        chain_info.head().id(),
+        storage.clone(),
+        None,
+        dag,
+    )?;
+    let miner_account = AccountInfo::random();
+    for _ in 0..count {
+        let (template, _) = chain.create_block_template(
+            *miner_account.address(),
+            None,
+            vec![],
+            vec![],
+            None,
+            None,
+        )?;
+        let block = chain
+            .consensus()
+            .create_block(template, net.time_service().as_ref())?;
+        chain.apply(block)?;
+    }
+    let head = chain.current_header();
+    let block = chain
+        .get_block(head.id())?
+        .ok_or_else(|| format_err!("Can not find block {}", head.id()))?;
+    let block_info = chain
+        .get_block_info(Some(head.id()))?
+        .ok_or_else(|| format_err!("Can not find block info {}", head.id()))?;
+    let prev_block = chain
+        .get_block(head.parent_hash())?
+        .ok_or_else(|| format_err!("Can not find block {}", head.parent_hash()))?;
+    export_chunked_snapshot(
+        storage,
+        net.chain_id().id(),
+        block,
+        block_info,
+        prev_block,
+        output.to_path_buf(),
+        CHUNK_SIZE,
+    )
+}
+
+fn apply_test_snapshot(
+    net: &ChainNetwork,
+    storage: Arc<Storage>,
+    genesis: &ChainInfo,
+    input: &Path,
+    to_dir: &Path,
+    expected_manifest_hash: Option<HashValue>,
+) -> Result<SnapshotManifest> {
+    apply_chunked_snapshot(
+        storage,
+        net.chain_id().id(),
+        genesis.status().info().clone(),
+        genesis.head().state_root(),
+        input.to_path_buf(),
+        to_dir.to_path_buf(),
+        expected_manifest_hash,
+    )
+}
+
+/// Flip a byte of the chunk file, return the original content.
+fn tamper_chunk(dir: &Path, chunk: &ChunkInfo) -> Result<Vec<u8>> {
+    let path = dir.join(&chunk.file);
+    let original = std::fs::read(&path)?;
+    let mut tampered = original.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 0xff;
+    std::fs::write(&path, tampered)?;
+    Ok(original)
+}
+
+#[test]
+fn test_chunked_snapshot_round_trip() -> Result<()> {
+    let net = ChainNetwork::new_test();
+    let snapshot_dir = temp_dir();
+    let (manifest, manifest_hash) = export_test_snapshot(&net, 5, snapshot_dir.path())?;
+    assert_eq!(manifest.block_number, 5);
+    assert!(manifest
+        .column(BLOCK_PREFIX_NAME)?
+        .chunks
+        .iter()
+        .all(|chunk| chunk.records <= CHUNK_SIZE));
+    assert!(manifest.column(BLOCK_PREFIX_NAME)?.chunks.len() > 1);
+
+    let (storage, genesis, _, _) = Genesis::init_storage_for_test(&net)?;
+    let to_dir = temp_dir();
+    let applied = apply_test_snapshot(
+        &net,
+        storage.clone(),
+        &genesis,
+        snapshot_dir.path(),
+        to_dir.path(),
+        Some(manifest_hash),
+    )?;
+    assert_eq!(applied, manifest);
+    let block = storage
+        .get_block(manifest.block_id)?
+        .ok_or_else(|| format_err!("Can not find snapshot block"))?;
+    assert_eq!(block.header().state_root(), manifest.state_root);
+    assert!(!to_dir.path().join(SNAPSHOT_APPLY_PROGRESS_NAME).exists());
+    Ok(())
+}
+
+#[test]
+fn test_chunked_snapshot_reject_tampered() -> Result<()> {
+    let net = ChainNetwork::new_test();
+    let snapshot_dir = temp_dir();
+    let (manifest, manifest_hash) = export_test_snapshot(&net, 3, snapshot_dir.path())?;
+    let (storage, genesis, _, _) = Genesis::init_storage_for_test(&net)?;
+
+    // A manifest hash from somewhere else is rejected before applying anything.
+    let to_dir = temp_dir();
+    let err = apply_test_snapshot(
+        &net,
+        storage.clone(),
+        &genesis,
+        snapshot_dir.path(),
+        to_dir.path(),
+        Some(HashValue::random()),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("manifest hash mismatch"));
+    assert!(!to_dir.path().join(SNAPSHOT_APPLY_PROGRESS_NAME).exists());
+
+    let chunk = &manifest.column(BLOCK_PREFIX_NAME)?.chunks[0];
+    tamper_chunk(snapshot_dir.path(), chunk)?;
+    let err = apply_test_snapshot(
+        &net,
+        storage.clone(),
+        &genesis,
+        snapshot_dir.path(),
+        to_dir.path(),
+        Some(manifest_hash),
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(format!("Chunk {} hash mismatch", chunk.file).as_str()));
+    assert!(storage.get_block(manifest.block_id)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_chunked_snapshot_resume_apply() -> Result<()> {
+    let net = ChainNetwork::new_test();
+    let snapshot_dir = temp_dir();
+    let (manifest, manifest_hash) = export_test_snapshot(&net, 5, snapshot_dir.path())?;
+    let (storage, genesis, _, _) = Genesis::init_storage_for_test(&net)?;
+    let to_dir = temp_dir();
+    let progress_path = to_dir.path().join(SNAPSHOT_APPLY_PROGRESS_NAME);
+
+    // Interrupt the apply of the block column at its last chunk.
+    let block_column = manifest.column(BLOCK_PREFIX_NAME)?;
+    let last_chunk = block_column.chunks.last().unwrap();
+    let original = tamper_chunk(snapshot_dir.path(), last_chunk)?;
+    assert!(apply_test_snapshot(
+        &net,
+        storage.clone(),
+        &genesis,
+        snapshot_dir.path(),
+        to_dir.path(),
+        Some(manifest_hash),
+    )
+    .is_err());
+    let progress: ApplyProgress = serde_json::from_slice(&std::fs::read(&progress_path)?)?;
+    assert_eq!(progress.manifest_hash, manifest_hash);
+    assert_eq!(
+        progress.columns[BLOCK_PREFIX_NAME].next_chunk,
+        block_column.chunks.len() - 1
+    );
+    assert!(storage.get_block(manifest.block_id)?.is_none());
+
+    // The apply resumes from the progress once the chunk is repaired.
+    std::fs::write(snapshot_dir.path().join(&last_chunk.file), original)?;
+    let applied = apply_test_snapshot(
+        &net,
+        storage.clone(),
+        &genesis,
+        snapshot_dir.path(),
+        to_dir.path(),
+        Some(manifest_hash),
+    )?;
+    assert_eq!(applied, manifest);
+    assert!(storage.get_block(manifest.block_id)?.is_some());
+    assert!(!progress_path.exists());
+    Ok(())
+}
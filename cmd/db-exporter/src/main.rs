@@ -5,6 +5,9 @@ use anyhow::{bail, format_err, Result};
 use bcs_ext::{BCSCodec, Sample};
 use clap::{IntoApp, Parser};
 use csv::Writer;
+use db_exporter::chunked_snapshot::{
+    apply_chunked_snapshot, export_chunked_snapshot, is_chunked_snapshot,
+};
 use db_exporter::force_deploy_output::{force_deploy_output, ForceDeployOutput};
 use db_exporter::{
     verify_header::{verify_header_via_export_file, VerifyHeaderOptions},
@@ -386,6 +389,9 @@ pub struct ExportSnapshotOptions {
     #[clap(long, short = 'b')]
     /// special block_num for debug usage
     pub special_block_num: Option<BlockNumber>,
+    #[clap(long, short = 'c')]
+    /// export a chunked and compressed snapshot with a hashed manifest, every chunk has at most chunk_size records
+    pub chunk_size: Option<u64>,
 }
 
 #[derive(Debug, Parser)]
@@ -398,8 +404,12 @@ pub struct ApplySnapshotOptions {
     /// starcoin node db path. like ~/.starcoin/main
     pub to_path: PathBuf,
     #[clap(long, short = 'i', parse(from_os_str))]
-    /// input_path, manifest.csv in this dir
+    /// input_path, manifest.csv or snapshot_manifest.json in this dir
     pub input_path: PathBuf,
+    #[clap(long, short = 'm')]
+    /// the expected hash of snapshot_manifest.json, only for chunked snapshot,
+    /// get it from the exporter through a trusted channel, the hash file in the snapshot dir only proves integrity
+    pub manifest_hash: Option<HashValue>,
 }
 
 #[derive(Debug, Clone, Parser)]
@@ -659,11 +669,17 @@ async fn main() -> anyhow::Result<()> {
                 option.net,
                 option.increment,
                 option.special_block_num,
+                option.chunk_size,
             );
             return result;
         }
         Cmd::ApplySnapshot(option) => {
-            let result = apply_snapshot(option.to_path, option.input_path, option.net);
+            let result = apply_snapshot(
+                option.to_path,
+                option.input_path,
+                option.net,
+                option.manifest_hash,
+            );
             return result;
         }
         Cmd::ExportResource(option) => {
@@ -1485,6 +1501,7 @@ pub fn export_snapshot(
     network: BuiltinNetworkID,
     increment: Option<bool>,
     special_block_num: Option<BlockNumber>,
+    chunk_size: Option<u64>,
 ) -> anyhow::Result<()> {
     let start_time = SystemTime::now();
     let net = ChainNetwork::new_builtin(network);
@@ -1547,6 +1564,46 @@ pub fn export_snapshot(
 
     // increment export read num
     let inc_export = increment.unwrap_or(false);
+    if let Some(chunk_size) = chunk_size {
+        if inc_export {
+            bail!("increment export is not supported by chunked snapshot");
+        }
+        let block = chain
+            .get_block_by_number(cur_num)?
+            .ok_or_else(|| format_err!("get block by number {} error", cur_num))?;
+        let block_info = chain
+            .get_block_info(Some(block.id()))?
+            .ok_or_else(|| format_err!("get block info by hash {} error", block.id()))?;
+        let prev_block = chain
+            .get_block_by_number(cur_num_prev)?
+            .ok_or_else(|| format_err!("get block by number {} error", cur_num_prev))?;
+        println!(
+            "chain height {} snapshot block height {}",
+            chain_info.head().number(),
+            cur_num
+        );
+        let (manifest, manifest_hash) = export_chunked_snapshot(
+            storage,
+            net.chain_id().id(),
+            block,
+            block_info,
+            prev_block,
+            output,
+            chunk_size,
+        )?;
+        for column in manifest.columns {
+            println!(
+                "{} nums {} chunks {}",
+                column.column,
+                column.records,
+                column.chunks.len()
+            );
+        }
+        println!("snapshot manifest hash {}", manifest_hash);
+        let use_time = SystemTime::now().duration_since(start_time)?;
+        println!("export snapshot use time: {:?}", use_time.as_secs());
+        return Ok(());
+    }
     let mut old_snapshot_nums: HashMap<String, u64> = HashMap::new();
     if inc_export {
         let reader = BufReader::new(File::open(output.join("manifest.csv"))?);
@@ -1845,6 +1902,7 @@ pub fn apply_snapshot(
     to_dir: PathBuf,
     input_path: PathBuf,
     network: BuiltinNetworkID,
+    manifest_hash: Option<HashValue>,
 ) -> anyhow::Result<()> {
     let start_time = SystemTime::now();
     let net = ChainNetwork::new_builtin(network);
@@ -1872,6 +1930,25 @@ pub fn apply_snapshot(
         .expect("create block chain should success."),
     ));
 
+    if is_chunked_snapshot(input_path.as_path()) {
+        let status = chain.lock().unwrap().status();
+        let manifest = apply_chunked_snapshot(
+            storage.clone(),
+            net.chain_id().id(),
+            status.info,
+            status.head.state_root(),
+            input_path,
+            to_dir,
+            manifest_hash,
+        )?;
+        println!("snapshot block {} roots match", manifest.block_id);
+        storage.save_startup_info(StartupInfo::new(manifest.block_id))?;
+        storage.save_snapshot_range(SnapshotRange::new(1, manifest.block_number))?;
+        let use_time = SystemTime::now().duration_since(start_time)?;
+        println!("apply snapshot use time: {:?}", use_time.as_secs());
+        return Ok(());
+    }
+
     let mut block_hash = HashValue::zero();
     let mut block_num = 1;
     let mut handles = vec![];
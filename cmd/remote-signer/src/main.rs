@@ -0,0 +1,129 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, format_err, Result};
+use clap::Parser;
+use starcoin_account_api::AccountProvider;
+use starcoin_account_provider::remote_signer::{RemoteSigner, SignerAuth, SignerEndpoint};
+use starcoin_account_provider::{AccountLocalProvider, AccountPrivateKeyProvider};
+use starcoin_logger::prelude::*;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::genesis_config::ChainId;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// An unlock duration which never expires, the accounts are kept unlocked until the signer exits.
+const UNLOCK_DURATION: Duration = Duration::MAX;
+
+/// A reference external signer, serve the remote signer protocol with the local accounts,
+/// so that the node and the cli can sign txns without the private keys on their host.
+#[derive(Debug, Parser)]
+#[clap(name = "starcoin_remote_signer")]
+pub struct RemoteSignerOpt {
+    #[clap(long = "listen")]
+    /// the endpoint to listen, a TCP socket address or the path of a Unix socket.
+    /// A non-loopback TCP socket address requires the auth secret file.
+    listen: SignerEndpoint,
+
+    #[clap(long = "auth-secret-file", parse(from_os_str))]
+    /// the file of the secret shared with the clients, the requests and responses are authenticated
+    /// by it if present.
+    auth_secret_file: Option<PathBuf>,
+
+    #[clap(long = "local-account-dir", parse(from_os_str))]
+    /// load the accounts from the local account dir, all the accounts are unlocked by the password.
+    account_dir: Option<PathBuf>,
+
+    #[clap(long = "password-file", parse(from_os_str))]
+    /// read the password of the accounts in the local account dir from the file,
+    /// the password is prompted if not present.
+    password_file: Option<PathBuf>,
+
+    #[clap(
+        long = "secret-file",
+        parse(from_os_str),
+        conflicts_with("account_dir")
+    )]
+    /// file path of the private key.
+    secret_file: Option<PathBuf>,
+
+    #[clap(long = "account-address")]
+    /// the address of the private key in the secret file, default to the derived address.
+    account_address: Option<AccountAddress>,
+
+    #[clap(long = "chain-id", default_value = "1")]
+    /// the chain id used to sign the messages.
+    chain_id: u8,
+}
+
+fn run<P>(
+    provider: P,
+    password: &str,
+    endpoint: &SignerEndpoint,
+    auth: Option<SignerAuth>,
+) -> Result<()>
+where
+    P: AccountProvider,
+{
+    for account in provider.get_accounts()? {
+        if account.is_readonly {
+            continue;
+        }
+        provider.unlock_account(account.address, password.to_string(), UNLOCK_DURATION)?;
+        info!("Serve account: {}", account.address);
+    }
+    info!(
+        "Remote signer listen on: {}, authenticated: {}",
+        endpoint,
+        auth.is_some()
+    );
+    let signer = RemoteSigner::new(provider);
+    match auth {
+        Some(auth) => signer.with_auth(auth).run(endpoint),
+        None => signer.run(endpoint),
+    }
+}
+
+fn read_password(password_file: Option<&PathBuf>) -> Result<String> {
+    match password_file {
+        Some(path) => {
+            let password = std::fs::read_to_string(path)
+                .map_err(|e| format_err!("Read password file {:?} error: {}", path, e))?;
+            Ok(password.trim_end_matches(&['\r', '\n'][..]).to_string())
+        }
+        None => Ok(rpassword::prompt_password_stdout(
+            "Please input the password of the accounts: ",
+        )?),
+    }
+}
+
+fn main() -> Result<()> {
+    let _logger = starcoin_logger::init();
+    let opt: RemoteSignerOpt = RemoteSignerOpt::parse();
+    let chain_id = ChainId::new(opt.chain_id);
+    let auth = opt
+        .auth_secret_file
+        .as_ref()
+        .map(|path| SignerAuth::load(path.as_path()))
+        .transpose()?;
+    match (&opt.account_dir, &opt.secret_file) {
+        (Some(account_dir), None) => run(
+            AccountLocalProvider::create(account_dir.as_path(), chain_id)?,
+            read_password(opt.password_file.as_ref())?.as_str(),
+            &opt.listen,
+            auth,
+        ),
+        (None, Some(secret_file)) => run(
+            AccountPrivateKeyProvider::create(
+                Some(secret_file.clone()),
+                opt.account_address,
+                false,
+                chain_id,
+            )?,
+            "",
+            &opt.listen,
+            auth,
+        ),
+        _ => bail!("Please specify one and only one of [local-account-dir, secret-file]."),
+    }
+}
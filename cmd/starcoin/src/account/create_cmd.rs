@@ -6,6 +6,7 @@ use crate::StarcoinOpt;
 use anyhow::Result;
 use clap::Parser;
 use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::hd_wallet::generate_mnemonic;
 use starcoin_account_api::AccountInfo;
 
 /// Create a new account
@@ -14,6 +15,11 @@ use starcoin_account_api::AccountInfo;
 pub struct CreateOpt {
     #[clap(short = 'p')]
     password: String,
+
+    #[clap(long = "mnemonic")]
+    /// generate a new BIP39 mnemonic as the hd wallet seed, and create the first account derived from it,
+    /// the wallet can only have one hd wallet seed.
+    mnemonic: bool,
 }
 
 pub struct CreateCommand;
@@ -28,9 +34,20 @@ impl CommandAction for CreateCommand {
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<AccountInfo> {
+        let opt = ctx.opt();
         let account_client = ctx.state().account_client();
-        let account = account_client.create_account(ctx.opt().password.clone())?;
-        Ok(account)
+        if opt.mnemonic {
+            let mnemonic = generate_mnemonic();
+            let account = account_client.import_mnemonic(mnemonic.clone(), opt.password.clone())?;
+            eprintln!(
+                "Please write down the mnemonic and keep it safe, all the hd accounts can be recovered from it:\n{}",
+                mnemonic
+            );
+            Ok(account)
+        } else {
+            let account = account_client.create_account(opt.password.clone())?;
+            Ok(account)
+        }
     }
 
     fn skip_history(&self, _ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>) -> bool {
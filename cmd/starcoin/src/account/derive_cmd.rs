@@ -0,0 +1,44 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::AccountInfo;
+
+/// Derive an account from the hd wallet seed, with the next index by default.
+#[derive(Debug, Parser)]
+#[clap(name = "derive")]
+pub struct DeriveOpt {
+    #[clap(short = 'p', default_value = "")]
+    /// password of the hd wallet seed, the derived account uses the same password.
+    password: String,
+
+    #[clap(short = 'i', long = "index")]
+    /// derive the account with this index instead of the next index.
+    index: Option<u32>,
+}
+
+pub struct DeriveCommand;
+
+impl CommandAction for DeriveCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = DeriveOpt;
+    type ReturnItem = AccountInfo;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let account_client = ctx.state().account_client();
+        let opt = ctx.opt();
+        account_client.derive_account(opt.index, opt.password.clone())
+    }
+
+    fn skip_history(&self, _ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>) -> bool {
+        true
+    }
+}
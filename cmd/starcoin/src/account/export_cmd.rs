@@ -7,13 +7,14 @@ use anyhow::{bail, Result};
 use clap::Parser;
 use scmd::{CommandAction, ExecContext};
 use serde::{Deserialize, Serialize};
+use starcoin_account_api::keystore::KeyStore;
 use starcoin_crypto::ValidCryptoMaterialStringExt;
 use starcoin_types::transaction::authenticator::AccountPrivateKey;
 use starcoin_vm_types::account_address::AccountAddress;
 use std::convert::TryFrom;
 use std::path::PathBuf;
 
-/// Export account's private key.
+/// Export account's private key, or a JSON keystore file encrypted by the account password.
 #[derive(Debug, Parser)]
 #[clap(name = "export")]
 pub struct ExportOpt {
@@ -21,8 +22,11 @@ pub struct ExportOpt {
     account_address: AccountAddress,
     #[clap(short = 'p', default_value = "")]
     password: String,
-    #[clap(short = 'o', parse(from_os_str))]
+    #[clap(short = 'o', parse(from_os_str), conflicts_with("keystore"))]
     output_file: Option<PathBuf>,
+    #[clap(long = "keystore", name = "keystore", parse(from_os_str))]
+    /// export the account to the keystore file, instead of the raw private key.
+    keystore: Option<PathBuf>,
 }
 
 pub struct ExportCommand;
@@ -40,6 +44,22 @@ impl CommandAction for ExportCommand {
         let client = ctx.state().account_client();
         let opt: &ExportOpt = ctx.opt();
         let data = client.export_account(opt.account_address, opt.password.clone())?;
+        if let Some(keystore_file) = &opt.keystore {
+            if keystore_file.exists() {
+                bail!(
+                    "the keystore file {} is already exists, please change a name",
+                    keystore_file.display()
+                );
+            }
+            KeyStore::encrypt(opt.account_address, data.as_slice(), opt.password.as_str())
+                .save(keystore_file)?;
+            eprintln!("keystore saved to {}", keystore_file.as_path().display());
+            return Ok(ExportData {
+                account: opt.account_address,
+                private_key: None,
+                keystore: Some(keystore_file.clone()),
+            });
+        }
         let private_key = AccountPrivateKey::try_from(data.as_slice())?;
         let encoded = private_key.to_encoded_string()?;
         if let Some(output_file) = &opt.output_file {
@@ -54,7 +74,8 @@ impl CommandAction for ExportCommand {
         }
         Ok(ExportData {
             account: opt.account_address,
-            private_key: encoded,
+            private_key: Some(encoded),
+            keystore: None,
         })
     }
 
@@ -66,5 +87,8 @@ impl CommandAction for ExportCommand {
 #[derive(Debug, Clone, Hash, Serialize, Deserialize)]
 pub struct ExportData {
     pub account: AccountAddress,
-    pub private_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keystore: Option<PathBuf>,
 }
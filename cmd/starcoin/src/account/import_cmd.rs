@@ -6,12 +6,13 @@ use crate::StarcoinOpt;
 use anyhow::{bail, Result};
 use clap::Parser;
 use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::keystore::KeyStore;
 use starcoin_account_api::{AccountInfo, AccountPrivateKey};
 use starcoin_crypto::{ValidCryptoMaterial, ValidCryptoMaterialStringExt};
 use starcoin_vm_types::account_address::AccountAddress;
 use std::path::PathBuf;
 
-/// Import account by private key to node wallet.
+/// Import account by private key, JSON keystore file or BIP39 mnemonic to node wallet.
 #[derive(Debug, Parser)]
 #[clap(name = "import")]
 pub struct ImportOpt {
@@ -29,6 +30,18 @@ pub struct ImportOpt {
     )]
     from_file: Option<PathBuf>,
 
+    #[clap(long = "mnemonic", conflicts_with_all(&["input", "account_address"]))]
+    /// import the BIP39 mnemonic as the hd wallet seed, and create the first account derived from it.
+    mnemonic: Option<String>,
+
+    #[clap(
+        long = "keystore",
+        parse(from_os_str),
+        conflicts_with_all(&["input", "mnemonic", "account_address"])
+    )]
+    /// import the account from the keystore file, the password should be the keystore's password.
+    keystore: Option<PathBuf>,
+
     /// if account_address is absent, generate address by public_key.
     #[clap(name = "account_address")]
     account_address: Option<AccountAddress>,
@@ -48,6 +61,20 @@ impl CommandAction for ImportCommand {
     ) -> Result<Self::ReturnItem> {
         let opt: &ImportOpt = ctx.opt();
         let client = ctx.state().account_client();
+        if let Some(mnemonic) = opt.mnemonic.as_ref() {
+            if opt.from_file.is_some() {
+                bail!("mnemonic and private key file can not be specified together")
+            }
+            return client.import_mnemonic(mnemonic.clone(), opt.password.clone());
+        }
+        if let Some(keystore_file) = opt.keystore.as_ref() {
+            if opt.from_file.is_some() {
+                bail!("keystore file and private key file can not be specified together")
+            }
+            let keystore = KeyStore::load(keystore_file)?;
+            let private_key = keystore.decrypt(opt.password.as_str())?;
+            return client.import_account(keystore.address, private_key, opt.password.clone());
+        }
         let private_key = match (opt.from_input.as_ref(), opt.from_file.as_ref()) {
             (Some(p), _) => AccountPrivateKey::from_encoded_string(p)?,
             (None, Some(p)) => {
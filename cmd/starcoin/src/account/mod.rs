@@ -6,6 +6,7 @@ pub use change_password_cmd::*;
 pub use create_cmd::*;
 pub use default_cmd::*;
 pub use derive_account_address_cmd::*;
+pub use derive_cmd::*;
 pub use execute_script_cmd::*;
 pub use execute_script_function_cmd::*;
 pub use export_cmd::*;
@@ -23,6 +24,7 @@ mod change_password_cmd;
 mod create_cmd;
 mod default_cmd;
 mod derive_account_address_cmd;
+mod derive_cmd;
 mod execute_script_cmd;
 mod execute_script_function_cmd;
 mod export_cmd;
@@ -35,6 +37,7 @@ mod lock_cmd;
 pub mod nft_cmd;
 pub mod receipt_identifier_cmd;
 pub mod remove_cmd;
+pub mod replace_txn_cmd;
 pub mod rotate_auth_key_cmd;
 mod show_cmd;
 mod sign_cmd;
@@ -0,0 +1,103 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use bcs_ext::BCSCodec;
+use clap::Parser;
+
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
+use starcoin_transaction_builder::encode_cancel_script_function;
+use starcoin_txpool_api::{min_replace_gas_price, DEFAULT_REPLACE_GAS_PRICE_BUMP};
+use starcoin_vm_types::genesis_config::ChainId;
+use starcoin_vm_types::transaction::{RawUserTransaction, TransactionPayload};
+
+use crate::cli_state::CliState;
+use crate::view::ExecutionOutputView;
+use crate::StarcoinOpt;
+
+/// Replace a pending transaction in the txpool with a higher gas price,
+/// or cancel it by a zero-value transfer to the sender itself.
+/// The replacement transaction has the same sender and sequence number as the pending one.
+#[derive(Debug, Parser)]
+#[clap(name = "replace-txn")]
+pub struct ReplaceTxnOpt {
+    #[clap(name = "txn-hash")]
+    /// hash of the pending txn to replace
+    txn_hash: HashValue,
+
+    #[clap(long = "gas-price", name = "gas-price")]
+    /// gas price of the replacement txn, default to the pending txn's gas price bumped by 10 percent
+    gas_price: Option<u64>,
+
+    #[clap(long = "cancel")]
+    /// cancel the pending txn by a zero-value STC transfer to the sender itself
+    cancel: bool,
+
+    #[clap(
+        short = 'b',
+        name = "blocking-mode",
+        long = "blocking",
+        help = "blocking wait txn mined"
+    )]
+    blocking: bool,
+}
+
+pub struct ReplaceTxnCommand;
+
+impl CommandAction for ReplaceTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ReplaceTxnOpt;
+    type ReturnItem = ExecutionOutputView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let state = ctx.state();
+        let client = state.client();
+        let pending_txn = client
+            .get_pending_txn_by_hash(opt.txn_hash)?
+            .ok_or_else(|| format_err!("Can not find pending txn by hash: {}", opt.txn_hash))?
+            .raw_txn;
+        let old_gas_price = pending_txn.gas_unit_price.0;
+        let min_gas_price = min_replace_gas_price(old_gas_price, DEFAULT_REPLACE_GAS_PRICE_BUMP);
+        let gas_price = opt.gas_price.unwrap_or(min_gas_price);
+        ensure!(
+            gas_price > old_gas_price,
+            "The gas price {} should be higher than the pending txn's gas price {}",
+            gas_price,
+            old_gas_price
+        );
+        let sender = pending_txn.sender;
+        let payload = if opt.cancel {
+            TransactionPayload::ScriptFunction(encode_cancel_script_function(sender))
+        } else {
+            TransactionPayload::decode(pending_txn.payload.0.as_slice())?
+        };
+        let raw_txn = RawUserTransaction::new(
+            sender,
+            pending_txn.sequence_number.0,
+            payload,
+            pending_txn.max_gas_amount.0,
+            gas_price,
+            pending_txn.expiration_timestamp_secs.0,
+            ChainId::new(pending_txn.chain_id),
+            pending_txn.gas_token_code,
+        );
+        let signed_txn = state.account_client().sign_txn(raw_txn, sender)?;
+        let txn_hash = if opt.cancel {
+            client.cancel_transaction(opt.txn_hash, signed_txn)?
+        } else {
+            client.replace_transaction(opt.txn_hash, signed_txn)?
+        };
+        eprintln!("txn {:#x} replaced by txn {:#x}.", opt.txn_hash, txn_hash);
+        if opt.blocking {
+            state.watch_txn(txn_hash)
+        } else {
+            Ok(ExecutionOutputView::new(txn_hash))
+        }
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::types::FeeHistoryView;
+use starcoin_types::block::BlockNumber;
+
+/// Get the gas used ratios and the gas price percentiles of the recent blocks.
+#[derive(Debug, Parser)]
+#[clap(name = "fee-history", alias = "fee_history")]
+pub struct FeeHistoryOpt {
+    /// The number of blocks to query.
+    #[clap(name = "block-count", long, short = 'c', default_value = "20")]
+    block_count: u64,
+
+    /// The newest block number of the range, default is the head block.
+    #[clap(name = "newest-block", long)]
+    newest_block: Option<BlockNumber>,
+
+    /// The gas price percentiles of each block, in [0, 100] and ascending order,
+    /// default is 25, 50 and 75.
+    #[clap(name = "percentile", long, short = 'p', multiple_occurrences = true)]
+    percentiles: Vec<f64>,
+}
+
+pub struct FeeHistoryCommand;
+
+impl CommandAction for FeeHistoryCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = FeeHistoryOpt;
+    type ReturnItem = FeeHistoryView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        let percentiles = if opt.percentiles.is_empty() {
+            vec![25f64, 50f64, 75f64]
+        } else {
+            opt.percentiles.clone()
+        };
+        client.chain_fee_history(opt.block_count, opt.newest_block, Some(percentiles))
+    }
+}
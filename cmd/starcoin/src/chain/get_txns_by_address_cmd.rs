@@ -0,0 +1,54 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::types::TransactionInfoView;
+use starcoin_types::account_address::AccountAddress;
+
+/// Get the transaction infos sent by or affecting the address,
+/// require the node started with `--address-txn-index`.
+#[derive(Debug, Parser)]
+#[clap(name = "get-txns-by-address", alias = "get_txns_by_address")]
+pub struct GetTxnsByAddressOpt {
+    #[clap(name = "address")]
+    address: AccountAddress,
+
+    /// The position in the transaction list of the address for start scan,
+    /// default is the first transaction, or the latest transaction if reverse.
+    #[clap(name = "start-index", alias = "start_index", long, short = 's')]
+    start_index: Option<u64>,
+
+    #[clap(name = "reverse", long, short = 'r')]
+    reverse: Option<bool>,
+
+    #[clap(name = "count", long, short = 'c', default_value = "32")]
+    count: u64,
+}
+
+pub struct GetTxnsByAddressCommand;
+
+impl CommandAction for GetTxnsByAddressCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GetTxnsByAddressOpt;
+    type ReturnItem = Vec<TransactionInfoView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        let txn_infos = client.chain_get_transactions_by_address(
+            opt.address,
+            opt.start_index,
+            opt.reverse.unwrap_or(false),
+            opt.count,
+        )?;
+        Ok(txn_infos)
+    }
+}
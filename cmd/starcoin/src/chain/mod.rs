@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod epoch_info;
+mod fee_history_cmd;
 mod get_block_cmd;
 mod get_block_info_cmd;
 mod get_dag_state_cmd;
@@ -11,10 +12,12 @@ mod get_txn_info_cmd;
 mod get_txn_info_list_cmd;
 mod get_txn_infos_cmd;
 pub mod get_txn_proof_cmd;
+mod get_txns_by_address_cmd;
 mod info_cmd;
 mod list_block_cmd;
 
 pub use epoch_info::*;
+pub use fee_history_cmd::*;
 pub use get_block_cmd::*;
 pub use get_block_info_cmd::*;
 pub use get_dag_state_cmd::*;
@@ -23,5 +26,6 @@ pub use get_txn_cmd::*;
 pub use get_txn_info_cmd::*;
 pub use get_txn_info_list_cmd::*;
 pub use get_txn_infos_cmd::*;
+pub use get_txns_by_address_cmd::*;
 pub use info_cmd::*;
 pub use list_block_cmd::*;
@@ -7,7 +7,14 @@ use anyhow::Result;
 use clap::Parser;
 use scmd::{CommandAction, ExecContext};
 use starcoin_abi_decoder::DecodedMoveValue;
-use starcoin_rpc_api::types::{ContractCall, FunctionIdView, TransactionArgumentView, TypeTagView};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::{
+    ContractCall, ContractStateOption, FunctionIdView, StateOverrides, TransactionArgumentView,
+    TypeTagView,
+};
+use starcoin_types::block::BlockNumber;
+use std::fs::File;
+use std::path::PathBuf;
 
 /// Call Contract command
 ///  Some examples:
@@ -16,6 +23,10 @@ use starcoin_rpc_api::types::{ContractCall, FunctionIdView, TransactionArgumentV
 ///  dev call --function 0x1::Block::current_block_number
 ///  # 0x1::Account::balance<0x1::STC::STC>(0x726098b70ba8aa2cc172af19af8804)
 ///  dev call --function 0x1::Account::balance -t 0x1::STC::STC --arg 0x726098b70ba8aa2cc172af19af8804
+///  # the balance at the block 100
+///  dev call --function 0x1::Account::balance -t 0x1::STC::STC --arg 0x726098b70ba8aa2cc172af19af8804 --block-number 100
+///  # the balance with the resources and modules overridden by the JSON file
+///  dev call --function 0x1::Account::balance -t 0x1::STC::STC --arg 0x726098b70ba8aa2cc172af19af8804 --state-overrides overrides.json
 ///  ```
 #[derive(Debug, Parser)]
 #[clap(name = "call")]
@@ -37,6 +48,22 @@ pub struct CallContractOpt {
         help = "can specify multi arg"
     )]
     args: Option<Vec<TransactionArgumentView>>,
+
+    #[clap(long = "block-number", conflicts_with_all(&["block-hash", "state-root"]))]
+    /// call at the state of the main chain block with the number, default is the latest block
+    block_number: Option<BlockNumber>,
+
+    #[clap(long = "block-hash", name = "block-hash", conflicts_with("state-root"))]
+    /// call at the state of the block with the hash
+    block_hash: Option<HashValue>,
+
+    #[clap(long = "state-root", name = "state-root")]
+    /// call at the state with the state root
+    state_root: Option<HashValue>,
+
+    #[clap(long = "state-overrides", name = "state-overrides")]
+    /// the JSON file of the state overrides, such as `{"resources": {"<access_path>": {"json": {..}}}, "modules": ["<hex>"]}`
+    state_overrides: Option<PathBuf>,
 }
 
 pub struct CallContractCommand;
@@ -52,11 +79,23 @@ impl CommandAction for CallContractCommand {
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let opt = ctx.opt();
+        let state_overrides = match opt.state_overrides.as_ref() {
+            Some(path) => Some(serde_json::from_reader::<_, StateOverrides>(File::open(
+                path,
+            )?)?),
+            None => None,
+        };
 
         let call = ContractCall {
             function_id: opt.function.clone(),
             type_args: opt.type_tags.clone().unwrap_or_default(),
             args: opt.args.clone().unwrap_or_default(),
+            state_option: ContractStateOption {
+                block_number: opt.block_number,
+                block_hash: opt.block_hash,
+                state_root: opt.state_root,
+            },
+            state_overrides,
         };
 
         let result = ctx.state().client().contract_call(call)?;
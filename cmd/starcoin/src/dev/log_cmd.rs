@@ -47,7 +47,7 @@ impl CommandAction for LogLevelCommand {
 #[clap(name = "pattern")]
 pub struct LogPatternOpt {
     #[clap(name = "pattern")]
-    /// Set log pattern, support default|withline or custom pattern string.
+    /// Set log pattern, support default|withline|json or custom pattern string.
     pattern: LogPattern,
 }
 
@@ -8,6 +8,7 @@ pub use deploy_cmd::*;
 pub use get_coin_cmd::*;
 pub use package_cmd::*;
 pub use subscribe_cmd::*;
+pub use trace_txn_cmd::*;
 pub use upgrade_module_exe_cmd::*;
 pub use upgrade_module_plan_cmd::*;
 pub use upgrade_module_proposal_cmd::*;
@@ -30,6 +31,7 @@ pub(crate) mod resolve_cmd;
 pub(crate) mod sign_txn_helper;
 pub(crate) mod sleep_cmd;
 mod subscribe_cmd;
+mod trace_txn_cmd;
 mod upgrade_module_exe_cmd;
 mod upgrade_module_plan_cmd;
 mod upgrade_module_proposal_cmd;
@@ -4,7 +4,8 @@ use anyhow::Result;
 use clap::Parser;
 use futures::{TryStream, TryStreamExt};
 use scmd::{CommandAction, ExecContext};
-use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::pubsub::{EventFilter, TxnStatusFilter};
 use starcoin_rpc_api::types::TypeTagView;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::event::EventKey;
@@ -143,6 +144,40 @@ impl CommandAction for SubscribeNewTxnCommand {
     }
 }
 
+/// Subscribe transaction status, watch all transactions if no hash or sender is given.
+#[derive(Debug, Parser)]
+#[clap(name = "txn_status")]
+pub struct SubscribeTxnStatusOpt {
+    #[clap(long = "txn-hash", name = "txn-hash", multiple_occurrences = true)]
+    /// hashes of the transactions to watch
+    txn_hashes: Option<Vec<HashValue>>,
+    #[clap(long = "sender", name = "sender", multiple_occurrences = true)]
+    /// senders of the transactions to watch
+    senders: Option<Vec<AccountAddress>>,
+}
+pub struct SubscribeTxnStatusCommand;
+impl CommandAction for SubscribeTxnStatusCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SubscribeTxnStatusOpt;
+    type ReturnItem = ();
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let filter = TxnStatusFilter {
+            txn_hashes: ctx.opt().txn_hashes.clone(),
+            senders: ctx.opt().senders.clone(),
+        };
+        let event_stream = ctx.state().client().subscribe_txn_status(filter)?;
+        println!("Subscribe successful, Press `q` and Enter to quit");
+        blocking_display_notification(event_stream, |evt| {
+            serde_json::to_string(&evt).expect("should never fail")
+        });
+        Ok(())
+    }
+}
+
 fn blocking_display_notification<T, F>(
     mut event_stream: impl TryStream<Ok = T, Error = anyhow::Error> + Unpin,
     display: F,
@@ -0,0 +1,36 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::TransactionTraceView;
+
+/// Re-execute the transaction at its parent state, and show the call tree of the execution.
+#[derive(Debug, Parser)]
+#[clap(name = "trace-txn")]
+pub struct TraceTxnOpt {
+    #[clap(name = "txn-hash")]
+    /// hash of the transaction to trace
+    txn_hash: HashValue,
+}
+
+pub struct TraceTxnCommand;
+
+impl CommandAction for TraceTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = TraceTxnOpt;
+    type ReturnItem = TransactionTraceView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        ctx.state().client().debug_trace_transaction(opt.txn_hash)
+    }
+}
@@ -42,9 +42,11 @@ pub fn add_command(
                 .subcommand(account::ExecuteScriptCommand)
                 .subcommand(account::sign_multisig_txn_cmd::GenerateMultisigTxnCommand)
                 .subcommand(account::submit_txn_cmd::SubmitSignedTxnCommand)
+                .subcommand(account::replace_txn_cmd::ReplaceTxnCommand)
                 .subcommand(account::SignMessageCmd)
                 .subcommand(account::VerifySignMessageCmd)
                 .subcommand(account::DeriveAddressCommand)
+                .subcommand(account::DeriveCommand)
                 .subcommand(account::receipt_identifier_cmd::ReceiptIdentifierCommand)
                 .subcommand(account::generate_keypair::GenerateKeypairCommand)
                 .subcommand(account::rotate_auth_key_cmd::RotateAuthenticationKeyCommand)
@@ -101,6 +103,8 @@ pub fn add_command(
                 .subcommand(chain::GetEventsCommand)
                 .subcommand(chain::EpochInfoCommand)
                 .subcommand(chain::GetTransactionInfoListCommand)
+                .subcommand(chain::GetTxnsByAddressCommand)
+                .subcommand(chain::FeeHistoryCommand)
                 .subcommand(chain::get_txn_proof_cmd::GetTransactionProofCommand)
                 .subcommand(chain::GetBlockInfoCommand)
                 .subcommand(chain::GetDagStateCommand),
@@ -132,7 +136,8 @@ pub fn add_command(
                         .subcommand(dev::SubscribeNewMintBlockCommand)
                         .subcommand(dev::SubscribeBlockCommand)
                         .subcommand(dev::SubscribeEventCommand)
-                        .subcommand(dev::SubscribeNewTxnCommand),
+                        .subcommand(dev::SubscribeNewTxnCommand)
+                        .subcommand(dev::SubscribeTxnStatusCommand),
                 )
                 .subcommand(
                     CustomCommand::with_name("log")
@@ -144,7 +149,8 @@ pub fn add_command(
                 .subcommand(dev::sleep_cmd::SleepCommand)
                 .subcommand(dev::gen_block_cmd::GenBlockCommand)
                 .subcommand(dev::SetConcurrencyLevelCommand)
-                .subcommand(dev::GetConcurrencyLevelCommand),
+                .subcommand(dev::GetConcurrencyLevelCommand)
+                .subcommand(dev::TraceTxnCommand),
         )
         .command(CustomCommand::with_name("contract").subcommand(contract::GetContractDataCommand))
 }
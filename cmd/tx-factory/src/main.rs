@@ -3,7 +3,9 @@
 
 use anyhow::{bail, Result};
 use clap::Parser;
-use starcoin_account_api::AccountInfo;
+use starcoin_account_api::{AccountInfo, AccountProvider};
+use starcoin_account_provider::remote_signer::{SignerAuth, SignerEndpoint};
+use starcoin_account_provider::AccountRemoteProvider;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_rpc_api::types::FactoryAction;
@@ -14,7 +16,7 @@ use starcoin_tx_factory::txn_generator::MockTxnGenerator;
 use starcoin_types::account::DEFAULT_EXPIRATION_TIME;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::association_address;
-use starcoin_types::transaction::RawUserTransaction;
+use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -78,10 +80,39 @@ pub struct TxFactoryOpt {
         help = "create account batch size"
     )]
     pub batch_size: u32,
+    #[clap(
+        long = "remote-signer",
+        help = "sign the txns of the sending account by the external signer, a TCP socket address or the path of a Unix socket"
+    )]
+    pub remote_signer: Option<SignerEndpoint>,
+    #[clap(
+        long = "remote-signer-secret-file",
+        parse(from_os_str),
+        requires("remote_signer"),
+        help = "the file of the secret shared with the external signer to authenticate the requests"
+    )]
+    pub remote_signer_secret_file: Option<PathBuf>,
 }
 
 const INITIAL_BALANCE: u128 = 1_000_000_000;
 
+fn get_remote_account_or_default(
+    remote_signer: &AccountRemoteProvider,
+    account_address: Option<AccountAddress>,
+) -> Result<AccountInfo> {
+    let account = match account_address {
+        None => remote_signer.get_default_account()?,
+        Some(a) => remote_signer.get_account(a)?,
+    };
+    match account {
+        None => bail!("the specified account does not exists in the remote signer"),
+        Some(account) => {
+            info!("get_remote_account_or_default: {}", account.address);
+            Ok(account)
+        }
+    }
+}
+
 fn get_account_or_default(
     client: &RpcClient,
     account_address: Option<AccountAddress>,
@@ -140,7 +171,18 @@ fn main() {
     }
     let client = connected.unwrap();
 
-    let account = get_account_or_default(&client, account_address).unwrap();
+    let remote_signer = opts.remote_signer.clone().map(|endpoint| {
+        let provider = AccountRemoteProvider::create(endpoint);
+        match opts.remote_signer_secret_file.as_ref() {
+            Some(path) => provider.with_auth(SignerAuth::load(path.as_path()).unwrap()),
+            None => provider,
+        }
+    });
+    let account = match remote_signer.as_ref() {
+        Some(remote_signer) => get_remote_account_or_default(remote_signer, account_address),
+        None => get_account_or_default(&client, account_address),
+    }
+    .unwrap();
 
     let receiver_address = opts.receiver_address.unwrap_or_else(association_address);
 
@@ -153,6 +195,7 @@ fn main() {
         account_password,
         Duration::from_secs(60 * 10),
         watch_timeout,
+        remote_signer,
     );
 
     let mut tx_mocker = match tx_mocker {
@@ -215,6 +258,7 @@ struct TxnMocker {
     next_sequence_number: u64,
     account_unlock_time: Option<Instant>,
     watch_timeout: u32,
+    remote_signer: Option<AccountRemoteProvider>,
 }
 
 impl TxnMocker {
@@ -225,6 +269,7 @@ impl TxnMocker {
         account_password: String,
         unlock_duration: Duration,
         watch_timeout: u32,
+        remote_signer: Option<AccountRemoteProvider>,
     ) -> Result<Self> {
         let state_reader = client.state_reader(StateRootOption::Latest)?;
 
@@ -250,6 +295,7 @@ impl TxnMocker {
             account_unlock_time: None,
             next_sequence_number,
             watch_timeout,
+            remote_signer,
         })
     }
 }
@@ -299,7 +345,7 @@ impl TxnMocker {
 
         self.unlock_account()?;
 
-        let user_txn = match self.client.account_sign_txn(raw_txn) {
+        let user_txn = match self.sign_txn(raw_txn) {
             Err(e) => {
                 // sign txn fail, we should unlock again
                 self.account_unlock_time = None;
@@ -328,7 +374,20 @@ impl TxnMocker {
         result
     }
 
+    /// Sign the txn by the remote signer if the sender is the sending account, otherwise by the node.
+    fn sign_txn(&self, raw_txn: RawUserTransaction) -> Result<SignedUserTransaction> {
+        match self.remote_signer.as_ref() {
+            Some(remote_signer) if raw_txn.sender() == self.account_address => {
+                remote_signer.sign_txn(raw_txn, self.account_address)
+            }
+            _ => self.client.account_sign_txn(raw_txn),
+        }
+    }
+
     fn unlock_account(&mut self) -> Result<()> {
+        if self.remote_signer.is_some() {
+            return Ok(());
+        }
         let unlock_time = self.account_unlock_time;
         match unlock_time {
             Some(t) if t + self.unlock_duration > Instant::now() => {}
@@ -357,10 +416,15 @@ impl TxnMocker {
         blocking: bool,
     ) -> Result<HashValue> {
         // try unlock account
-        self.client
-            .account_unlock(sender, self.account_password.clone(), self.unlock_duration)?;
+        if self.remote_signer.is_none() || sender != self.account_address {
+            self.client.account_unlock(
+                sender,
+                self.account_password.clone(),
+                self.unlock_duration,
+            )?;
+        }
 
-        let user_txn = match self.client.account_sign_txn(raw_txn) {
+        let user_txn = match self.sign_txn(raw_txn) {
             Err(e) => {
                 return Err(e);
             }
@@ -175,6 +175,76 @@ fn test_proof() {
     proof_verify(&accumulator, root_hash1, &batch1, 0);
 }
 
+#[test]
+fn test_range_proof() {
+    let mock_store = MockAccumulatorStore::new();
+    let accumulator = MerkleAccumulator::new_empty(Arc::new(mock_store));
+    let leaves = create_leaves(0..37);
+    let root_hash = accumulator.append(&leaves).unwrap();
+    accumulator.flush().unwrap();
+    for first_leaf_index in 0..leaves.len() {
+        for num_leaves in 1..=(leaves.len() - first_leaf_index) {
+            let range = &leaves[first_leaf_index..first_leaf_index + num_leaves];
+            let proof = accumulator
+                .get_range_proof(first_leaf_index as u64, num_leaves as u64)
+                .unwrap()
+                .unwrap();
+            assert!(
+                proof
+                    .verify(root_hash, first_leaf_index as u64, range)
+                    .is_ok(),
+                "first_leaf_index: {}, num_leaves: {}, proof: {:?} verify failed",
+                first_leaf_index,
+                num_leaves,
+                proof
+            );
+        }
+    }
+    let proof = accumulator.get_range_proof(3, 4).unwrap().unwrap();
+    assert!(proof.verify(root_hash, 4, &leaves[4..8]).is_err());
+    assert!(proof.verify(root_hash, 3, &leaves[3..6]).is_err());
+    assert!(accumulator.get_range_proof(30, 8).unwrap().is_none());
+}
+
+#[test]
+fn test_consistency_proof() {
+    let mock_store = MockAccumulatorStore::new();
+    let accumulator = MerkleAccumulator::new_empty(Arc::new(mock_store));
+    let leaves = create_leaves(0..37);
+    let root_hash = accumulator.append(&leaves).unwrap();
+    accumulator.flush().unwrap();
+    let new_num_leaves = leaves.len() as u64;
+    for old_num_leaves in 0..=leaves.len() {
+        let old_root_hash = compute_root_hash_naive(&leaves[..old_num_leaves]);
+        let proof = accumulator
+            .get_consistency_proof(old_num_leaves as u64)
+            .unwrap()
+            .unwrap();
+        assert!(
+            proof
+                .verify(
+                    old_root_hash,
+                    old_num_leaves as u64,
+                    root_hash,
+                    new_num_leaves
+                )
+                .is_ok(),
+            "old_num_leaves: {}, proof: {:?} verify failed",
+            old_num_leaves,
+            proof
+        );
+    }
+    let proof = accumulator.get_consistency_proof(10).unwrap().unwrap();
+    let other_root_hash = compute_root_hash_naive(&create_leaves(100..110));
+    assert!(proof
+        .verify(other_root_hash, 10, root_hash, new_num_leaves)
+        .is_err());
+    assert!(accumulator
+        .get_consistency_proof(new_num_leaves + 1)
+        .unwrap()
+        .is_none());
+}
+
 #[test]
 fn test_multiple_leaves() {
     let mut batch1 = create_leaves(600..608);
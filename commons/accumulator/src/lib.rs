@@ -1,10 +1,10 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 use crate::accumulator_info::AccumulatorInfo;
-use crate::node_index::NodeIndex;
-use crate::proof::AccumulatorProof;
+use crate::node_index::{FrozenSubTreeIterator, FrozenSubtreeSiblingIterator, NodeIndex};
+use crate::proof::{AccumulatorConsistencyProof, AccumulatorProof, AccumulatorRangeProof};
 use crate::tree::AccumulatorTree;
-use anyhow::{format_err, Result};
+use anyhow::{ensure, format_err, Result};
 pub use node::AccumulatorNode;
 use parking_lot::Mutex;
 use starcoin_crypto::HashValue;
@@ -42,6 +42,17 @@ pub trait Accumulator {
     fn get_node_by_position(&self, position: u64) -> Result<Option<HashValue>>;
     /// Get proof by leaf index.
     fn get_proof(&self, leaf_index: u64) -> Result<Option<AccumulatorProof>>;
+    /// Get range proof of the `num_leaves` consecutive leaves start from `first_leaf_index`.
+    fn get_range_proof(
+        &self,
+        first_leaf_index: u64,
+        num_leaves: u64,
+    ) -> Result<Option<AccumulatorRangeProof>>;
+    /// Get consistency proof from the accumulator with `old_num_leaves` leaves to current accumulator.
+    fn get_consistency_proof(
+        &self,
+        old_num_leaves: u64,
+    ) -> Result<Option<AccumulatorConsistencyProof>>;
     /// Flush node to storage.
     fn flush(&self) -> Result<()>;
     /// Get current accumulator tree root hash.
@@ -185,6 +196,51 @@ impl Accumulator for MerkleAccumulator {
         Ok(Some(AccumulatorProof::new(siblings)))
     }
 
+    fn get_range_proof(
+        &self,
+        first_leaf_index: u64,
+        num_leaves: u64,
+    ) -> Result<Option<AccumulatorRangeProof>> {
+        ensure!(
+            num_leaves > 0,
+            "The num_leaves of range proof should not be 0"
+        );
+        let mut tree_guard = self.tree.lock();
+        let last_leaf_index = match first_leaf_index.checked_add(num_leaves - 1) {
+            Some(index) if index < tree_guard.num_leaves => index,
+            _ => return Ok(None),
+        };
+
+        let left_siblings = tree_guard.get_siblings(first_leaf_index, |p| p.is_left_child())?;
+        let right_siblings = tree_guard.get_siblings(last_leaf_index, |p| p.is_right_child())?;
+        Ok(Some(AccumulatorRangeProof::new(
+            left_siblings,
+            right_siblings,
+        )))
+    }
+
+    fn get_consistency_proof(
+        &self,
+        old_num_leaves: u64,
+    ) -> Result<Option<AccumulatorConsistencyProof>> {
+        let mut tree_guard = self.tree.lock();
+        if old_num_leaves > tree_guard.num_leaves {
+            return Ok(None);
+        }
+
+        // The frozen subtrees of the old accumulator are still frozen in current accumulator.
+        let old_subtrees = FrozenSubTreeIterator::new(old_num_leaves)
+            .map(|p| tree_guard.get_node_hash_always(p))
+            .collect::<Result<Vec<_>>>()?;
+        let subtrees = FrozenSubtreeSiblingIterator::new(old_num_leaves, tree_guard.num_leaves)
+            .map(|p| tree_guard.get_node_hash_always(p))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(AccumulatorConsistencyProof::new(
+            old_subtrees,
+            subtrees,
+        )))
+    }
+
     fn flush(&self) -> Result<()> {
         self.tree.lock().flush()
     }
@@ -3,6 +3,7 @@
 
 use crate::node_index::{NodeIndex, G_NODE_ERROR_INDEX};
 use anyhow::Result;
+use schemars::{self, JsonSchema};
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::{
     hash::{CryptoHash, CryptoHasher, ACCUMULATOR_PLACEHOLDER_HASH},
@@ -11,7 +12,17 @@ use starcoin_crypto::{
 
 //TODO move to a more suitable crate.
 #[derive(
-    Clone, Copy, Debug, Hash, Eq, PartialEq, Serialize, Deserialize, CryptoHasher, CryptoHash,
+    Clone,
+    Copy,
+    Debug,
+    Hash,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    CryptoHasher,
+    CryptoHash,
+    JsonSchema,
 )]
 pub enum AccumulatorStoreType {
     Transaction,
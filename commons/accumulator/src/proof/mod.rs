@@ -1,10 +1,11 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::inmemory::{InMemoryAccumulator, MerkleTreeInternalNode};
 use crate::node::InternalNode;
 use crate::node_index::NodeIndex;
 use crate::MAX_ACCUMULATOR_PROOF_DEPTH;
-use anyhow::{ensure, Result};
+use anyhow::{ensure, format_err, Result};
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
 
@@ -80,3 +81,148 @@ impl AccumulatorProof {
         Ok(())
     }
 }
+
+/// A proof that a run of consecutive leaves exists in the accumulator.
+#[derive(Default, Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AccumulatorRangeProof {
+    /// The siblings on the left of the path from the first leaf to the root, ordered from the
+    /// bottom level to the root level.
+    pub left_siblings: Vec<HashValue>,
+    /// The siblings on the right of the path from the last leaf to the root, including the
+    /// placeholders, ordered from the bottom level to the root level.
+    pub right_siblings: Vec<HashValue>,
+}
+
+impl AccumulatorRangeProof {
+    /// Constructs a new `AccumulatorRangeProof` using the left and right siblings.
+    pub fn new(left_siblings: Vec<HashValue>, right_siblings: Vec<HashValue>) -> Self {
+        Self {
+            left_siblings,
+            right_siblings,
+        }
+    }
+
+    /// Verifies the `leaf_hashes` are the consecutive leaves start from `first_leaf_index` in
+    /// the accumulator whose root hash is `expected_root_hash`.
+    pub fn verify(
+        &self,
+        expected_root_hash: HashValue,
+        first_leaf_index: u64,
+        leaf_hashes: &[HashValue],
+    ) -> Result<()> {
+        ensure!(
+            !leaf_hashes.is_empty(),
+            "The leaf hashes to verify is empty."
+        );
+        ensure!(
+            self.left_siblings.len() <= MAX_ACCUMULATOR_PROOF_DEPTH,
+            "Accumulator range proof has more than {} ({}) left siblings.",
+            MAX_ACCUMULATOR_PROOF_DEPTH,
+            self.left_siblings.len()
+        );
+        ensure!(
+            self.right_siblings.len() <= MAX_ACCUMULATOR_PROOF_DEPTH,
+            "Accumulator range proof has more than {} ({}) right siblings.",
+            MAX_ACCUMULATOR_PROOF_DEPTH,
+            self.right_siblings.len()
+        );
+
+        let mut left_sibling_iter = self.left_siblings.iter().peekable();
+        let mut right_sibling_iter = self.right_siblings.iter().peekable();
+        let mut first_index = NodeIndex::from_leaf_index(first_leaf_index);
+        let mut current_hashes = leaf_hashes.to_vec();
+        let mut parent_hashes = vec![];
+
+        // Combine the children pairs level by level, until only the root is left.
+        while current_hashes.len() > 1
+            || left_sibling_iter.peek().is_some()
+            || right_sibling_iter.peek().is_some()
+        {
+            let mut children = current_hashes.as_slice();
+            // If the first node of the current level is a right child, combine it with the left sibling.
+            if first_index.is_right_child() {
+                let left_hash = *left_sibling_iter.next().ok_or_else(|| {
+                    format_err!("The first node is a right child, but missing the left sibling.")
+                })?;
+                parent_hashes.push(MerkleTreeInternalNode::new(left_hash, children[0]).hash());
+                children = &children[1..];
+            }
+            let mut chunks = children.chunks_exact(2);
+            for chunk in chunks.by_ref() {
+                parent_hashes.push(MerkleTreeInternalNode::new(chunk[0], chunk[1]).hash());
+            }
+            // If the last node of the current level is a left child, combine it with the right sibling.
+            if let [left_hash] = chunks.remainder() {
+                let right_hash = *right_sibling_iter.next().ok_or_else(|| {
+                    format_err!("The last node is a left child, but missing the right sibling.")
+                })?;
+                parent_hashes.push(MerkleTreeInternalNode::new(*left_hash, right_hash).hash());
+            }
+            first_index = first_index.parent();
+            current_hashes.clear();
+            std::mem::swap(&mut current_hashes, &mut parent_hashes);
+        }
+
+        ensure!(
+            current_hashes[0] == expected_root_hash,
+            "Root hashes do not match. Actual root hash: {:x}. Expected root hash: {:x}.",
+            current_hashes[0],
+            expected_root_hash
+        );
+        Ok(())
+    }
+}
+
+/// A proof that the accumulator with fewer leaves is a prefix of the accumulator with more leaves.
+#[derive(Default, Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AccumulatorConsistencyProof {
+    /// The frozen subtree roots of the old accumulator, ordered from left to right.
+    pub old_subtrees: Vec<HashValue>,
+    /// The roots of the subtrees appended to the old accumulator to get the new accumulator,
+    /// ordered from left to right.
+    pub subtrees: Vec<HashValue>,
+}
+
+impl AccumulatorConsistencyProof {
+    /// Constructs a new `AccumulatorConsistencyProof` using the old frozen subtree roots and the
+    /// appended subtree roots.
+    pub fn new(old_subtrees: Vec<HashValue>, subtrees: Vec<HashValue>) -> Self {
+        Self {
+            old_subtrees,
+            subtrees,
+        }
+    }
+
+    /// Verifies the accumulator with `old_num_leaves` leaves and root hash `old_root_hash` is a
+    /// prefix of the accumulator with `new_num_leaves` leaves and root hash `new_root_hash`.
+    pub fn verify(
+        &self,
+        old_root_hash: HashValue,
+        old_num_leaves: u64,
+        new_root_hash: HashValue,
+        new_num_leaves: u64,
+    ) -> Result<()> {
+        ensure!(
+            old_num_leaves <= new_num_leaves,
+            "The old accumulator has more leaves than the new one, {} > {}.",
+            old_num_leaves,
+            new_num_leaves
+        );
+        let old_accumulator = InMemoryAccumulator::new(self.old_subtrees.clone(), old_num_leaves)?;
+        ensure!(
+            old_accumulator.root_hash() == old_root_hash,
+            "Old root hashes do not match. Actual root hash: {:x}. Expected root hash: {:x}.",
+            old_accumulator.root_hash(),
+            old_root_hash
+        );
+        let new_accumulator =
+            old_accumulator.append_subtrees(&self.subtrees, new_num_leaves - old_num_leaves)?;
+        ensure!(
+            new_accumulator.root_hash() == new_root_hash,
+            "New root hashes do not match. Actual root hash: {:x}. Expected root hash: {:x}.",
+            new_accumulator.root_hash(),
+            new_root_hash
+        );
+        Ok(())
+    }
+}
@@ -292,7 +292,7 @@ impl AccumulatorTree {
     }
 
     /// Get node hash always.
-    fn get_node_hash_always(&mut self, index: NodeIndex) -> Result<HashValue> {
+    pub(crate) fn get_node_hash_always(&mut self, index: NodeIndex) -> Result<HashValue> {
         // get hash from cache
         let mut temp_index = index;
         let mut index_key = temp_index;
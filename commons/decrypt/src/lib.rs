@@ -1,33 +1,149 @@
 use aes_gcm::aead::{generic_array::GenericArray, Aead, NewAead};
-use anyhow::{bail, format_err, Result};
+use anyhow::{bail, ensure, format_err, Result};
 use byteorder::{ReadBytesExt, WriteBytesExt};
 use rand::RngCore;
 use std::io::{Cursor, Read, Write};
 
+/// The iterations of the legacy PBKDF2 key derivation, only used to decrypt the old data.
 pub const PBKDF2_DEFAULT_ITERATIONS: usize = 1000;
 pub const PBKDF2_SALT_SIZE: usize = 32;
+pub const KDF_SALT_SIZE: usize = PBKDF2_SALT_SIZE;
 pub const AES_NONCE_SIZE: usize = 12;
 
+/// Default argon2id cost parameters of the new encrypted data, 19 MiB memory, 2 iterations.
+pub const ARGON2_DEFAULT_MEM_COST: u32 = 19 * 1024;
+pub const ARGON2_DEFAULT_TIME_COST: u32 = 2;
+pub const ARGON2_DEFAULT_LANES: u32 = 1;
+
+/// The upper bounds of the key derivation params read from the encrypted data or an imported keystore,
+/// so a crafted input can not make the key derivation exhaust the memory or the cpu.
+pub const PBKDF2_MAX_ITERATIONS: u32 = 1_000_000;
+/// 1 GiB memory.
+pub const ARGON2_MAX_MEM_COST: u32 = 1024 * 1024;
+pub const ARGON2_MAX_TIME_COST: u32 = 16;
+pub const ARGON2_MAX_LANES: u32 = 16;
+
+/// The version byte at the head of the encrypted data.
+/// The legacy PBKDF2 data has no version byte, it starts with the big endian u32 iterations,
+/// so the first byte is always 0.
+const KDF_VERSION_PBKDF2: u8 = 0;
+const KDF_VERSION_ARGON2ID: u8 = 1;
+
+const PBKDF2_META_LEN: usize = 4usize + KDF_SALT_SIZE + AES_NONCE_SIZE;
+const ARGON2_META_LEN: usize = 1usize + 4 + 4 + 4 + KDF_SALT_SIZE + AES_NONCE_SIZE;
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-struct KeyDerivationParams {
-    pbkdf2_iterations: u32,
-    pbkdf2_salt: [u8; PBKDF2_SALT_SIZE],
+pub enum KeyDerivationParams {
+    Pbkdf2 {
+        iterations: u32,
+        salt: [u8; KDF_SALT_SIZE],
+    },
+    Argon2id {
+        mem_cost: u32,
+        time_cost: u32,
+        lanes: u32,
+        salt: [u8; KDF_SALT_SIZE],
+    },
 }
 
 impl KeyDerivationParams {
+    /// Generate the default argon2id params with a random salt.
     pub fn generate() -> Self {
-        let mut salt = [0u8; PBKDF2_SALT_SIZE];
+        let mut salt = [0u8; KDF_SALT_SIZE];
         rand::thread_rng().fill_bytes(&mut salt);
-        Self {
-            pbkdf2_iterations: PBKDF2_DEFAULT_ITERATIONS as u32,
-            pbkdf2_salt: salt,
+        Self::Argon2id {
+            mem_cost: ARGON2_DEFAULT_MEM_COST,
+            time_cost: ARGON2_DEFAULT_TIME_COST,
+            lanes: ARGON2_DEFAULT_LANES,
+            salt,
+        }
+    }
+
+    /// Is the params weaker than the default, the data should be re-encrypted if so.
+    pub fn is_outdated(&self) -> bool {
+        match self {
+            Self::Pbkdf2 { .. } => true,
+            Self::Argon2id {
+                mem_cost,
+                time_cost,
+                ..
+            } => *mem_cost < ARGON2_DEFAULT_MEM_COST || *time_cost < ARGON2_DEFAULT_TIME_COST,
+        }
+    }
+
+    /// Check the params are in the supported bounds.
+    pub fn check(&self) -> Result<()> {
+        match self {
+            Self::Pbkdf2 { iterations, .. } => ensure!(
+                (1..=PBKDF2_MAX_ITERATIONS).contains(iterations),
+                "pbkdf2 iterations {} should be in [1, {}]",
+                iterations,
+                PBKDF2_MAX_ITERATIONS
+            ),
+            Self::Argon2id {
+                mem_cost,
+                time_cost,
+                lanes,
+                ..
+            } => {
+                ensure!(
+                    *mem_cost <= ARGON2_MAX_MEM_COST,
+                    "argon2 mem_cost {} should not be greater than {}",
+                    mem_cost,
+                    ARGON2_MAX_MEM_COST
+                );
+                ensure!(
+                    *time_cost <= ARGON2_MAX_TIME_COST,
+                    "argon2 time_cost {} should not be greater than {}",
+                    time_cost,
+                    ARGON2_MAX_TIME_COST
+                );
+                ensure!(
+                    *lanes <= ARGON2_MAX_LANES,
+                    "argon2 lanes {} should not be greater than {}",
+                    lanes,
+                    ARGON2_MAX_LANES
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn derive_key(&self, secret: &[u8]) -> Result<[u8; 32]> {
+        self.check()?;
+        // 256-bit derived key
+        let mut dk = [0u8; 32];
+        // use secret to derive a key to encrypt plaintext
+        match self {
+            Self::Pbkdf2 { iterations, salt } => {
+                pbkdf2::pbkdf2::<hmac::Hmac<sha2::Sha256>>(secret, salt, *iterations, &mut dk);
+            }
+            Self::Argon2id {
+                mem_cost,
+                time_cost,
+                lanes,
+                salt,
+            } => {
+                let config = argon2::Config {
+                    variant: argon2::Variant::Argon2id,
+                    version: argon2::Version::Version13,
+                    mem_cost: *mem_cost,
+                    time_cost: *time_cost,
+                    lanes: *lanes,
+                    hash_length: dk.len() as u32,
+                    ..Default::default()
+                };
+                let hash = argon2::hash_raw(secret, salt, &config)?;
+                dk.copy_from_slice(hash.as_slice());
+            }
         }
+        Ok(dk)
     }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-struct EncryptionParams {
-    nonce: [u8; AES_NONCE_SIZE],
+pub struct EncryptionParams {
+    pub nonce: [u8; AES_NONCE_SIZE],
 }
 
 impl EncryptionParams {
@@ -39,62 +155,105 @@ impl EncryptionParams {
     }
 }
 
-const META_LEN: usize = 4usize + PBKDF2_SALT_SIZE + AES_NONCE_SIZE;
+/// The decoded encrypted data, with the key derivation and encryption params.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-struct Meta {
-    key_derive_params: KeyDerivationParams,
-
-    encryption_params: EncryptionParams,
+pub struct EncryptedData {
+    pub key_derive_params: KeyDerivationParams,
+    pub encryption_params: EncryptionParams,
+    pub ciphertext: Vec<u8>,
 }
-impl Meta {
-    pub fn generate() -> Self {
+
+impl EncryptedData {
+    pub fn encrypt(secret: &[u8], plain: &[u8]) -> Self {
+        let key_derive_params = KeyDerivationParams::generate();
+        let encryption_params = EncryptionParams::generate();
+        let dk = key_derive_params
+            .derive_key(secret)
+            .expect("derive key with default params should never fail");
+        let ciphertext = aes_encrypt(&encryption_params, dk, plain);
         Self {
-            key_derive_params: KeyDerivationParams::generate(),
-            encryption_params: EncryptionParams::generate(),
+            key_derive_params,
+            encryption_params,
+            ciphertext,
         }
     }
 
+    pub fn decrypt(&self, secret: &[u8]) -> Result<Vec<u8>> {
+        let dk = self.key_derive_params.derive_key(secret)?;
+        aes_decrypt(&self.encryption_params, dk, &self.ciphertext)
+    }
+
     pub fn encode(&self) -> Vec<u8> {
-        let mut buf = std::io::Cursor::new(Vec::with_capacity(META_LEN));
-        buf.write_u32::<byteorder::BigEndian>(self.key_derive_params.pbkdf2_iterations)
-            .expect("should never fail");
-        buf.write_all(&self.key_derive_params.pbkdf2_salt)
-            .expect("should never fail");
+        let mut buf =
+            std::io::Cursor::new(Vec::with_capacity(ARGON2_META_LEN + self.ciphertext.len()));
+        match &self.key_derive_params {
+            KeyDerivationParams::Pbkdf2 { iterations, salt } => {
+                buf.write_u32::<byteorder::BigEndian>(*iterations)
+                    .expect("should never fail");
+                buf.write_all(salt).expect("should never fail");
+            }
+            KeyDerivationParams::Argon2id {
+                mem_cost,
+                time_cost,
+                lanes,
+                salt,
+            } => {
+                buf.write_u8(KDF_VERSION_ARGON2ID)
+                    .expect("should never fail");
+                buf.write_u32::<byteorder::BigEndian>(*mem_cost)
+                    .expect("should never fail");
+                buf.write_u32::<byteorder::BigEndian>(*time_cost)
+                    .expect("should never fail");
+                buf.write_u32::<byteorder::BigEndian>(*lanes)
+                    .expect("should never fail");
+                buf.write_all(salt).expect("should never fail");
+            }
+        }
         buf.write_all(&self.encryption_params.nonce)
             .expect("should never fail");
+        buf.write_all(&self.ciphertext).expect("should never fail");
         buf.into_inner()
     }
 
-    pub fn decode(buf: &[u8]) -> Result<Self> {
-        let mut buf = Cursor::new(buf);
-        let iterations = buf.read_u32::<byteorder::BigEndian>()?;
-        let mut salt = [0u8; PBKDF2_SALT_SIZE];
-        buf.read_exact(&mut salt)?;
+    pub fn decode(encrypted: &[u8]) -> Result<Self> {
+        let meta_len = match encrypted.first() {
+            Some(&KDF_VERSION_PBKDF2) => PBKDF2_META_LEN,
+            Some(&KDF_VERSION_ARGON2ID) => ARGON2_META_LEN,
+            Some(version) => bail!("unsupported encrypted data version: {}", version),
+            None => bail!("invalid encrypted data"),
+        };
+        if encrypted.len() <= meta_len {
+            bail!("invalid encrypted data");
+        }
+        let mut buf = Cursor::new(&encrypted[0..meta_len]);
+        let mut salt = [0u8; KDF_SALT_SIZE];
+        let key_derive_params = if encrypted[0] == KDF_VERSION_PBKDF2 {
+            let iterations = buf.read_u32::<byteorder::BigEndian>()?;
+            buf.read_exact(&mut salt)?;
+            KeyDerivationParams::Pbkdf2 { iterations, salt }
+        } else {
+            let _version = buf.read_u8()?;
+            let mem_cost = buf.read_u32::<byteorder::BigEndian>()?;
+            let time_cost = buf.read_u32::<byteorder::BigEndian>()?;
+            let lanes = buf.read_u32::<byteorder::BigEndian>()?;
+            buf.read_exact(&mut salt)?;
+            KeyDerivationParams::Argon2id {
+                mem_cost,
+                time_cost,
+                lanes,
+                salt,
+            }
+        };
         let mut nonce = [0u8; AES_NONCE_SIZE];
         buf.read_exact(&mut nonce)?;
         Ok(Self {
-            key_derive_params: KeyDerivationParams {
-                pbkdf2_salt: salt,
-                pbkdf2_iterations: iterations,
-            },
+            key_derive_params,
             encryption_params: EncryptionParams { nonce },
+            ciphertext: encrypted[meta_len..].to_vec(),
         })
     }
 }
 
-fn derive_key(derivation_param: &KeyDerivationParams, secret: &[u8]) -> [u8; 32] {
-    // 256-bit derived key
-    let mut dk = [0u8; 32];
-    // use secret to derive a key to encrypt plaintext
-    pbkdf2::pbkdf2::<hmac::Hmac<sha2::Sha256>>(
-        secret,
-        &derivation_param.pbkdf2_salt,
-        derivation_param.pbkdf2_iterations,
-        &mut dk,
-    );
-    dk
-}
-
 fn aes_encrypt(encryption_param: &EncryptionParams, key: [u8; 32], plain: &[u8]) -> Vec<u8> {
     let key = GenericArray::from(key);
     let nonce = GenericArray::clone_from_slice(&encryption_param.nonce);
@@ -117,25 +276,22 @@ fn aes_decrypt(
     }
 }
 
+/// Encrypt the `plain` data with a key derived from `secret` by argon2id.
 pub fn encrypt(secret: &[u8], plain: &[u8]) -> Vec<u8> {
-    let meta = Meta::generate();
-    // 256-bit derived key
-    let dk = derive_key(&meta.key_derive_params, secret);
-    let mut ciphertext = aes_encrypt(&meta.encryption_params, dk, plain);
-    let mut result = meta.encode();
-    result.append(&mut ciphertext);
-    result
+    EncryptedData::encrypt(secret, plain).encode()
 }
 
+/// Decrypt the data encrypted by `encrypt`, the legacy PBKDF2 data is also supported.
 pub fn decrypt(secret: &[u8], encrypted: &[u8]) -> Result<Vec<u8>> {
-    if encrypted.len() <= META_LEN {
-        bail!("invalid encrypted data");
-    }
-    let meta = Meta::decode(&encrypted[0..META_LEN])?;
-    let crypted = &encrypted[META_LEN..];
+    EncryptedData::decode(encrypted)?.decrypt(secret)
+}
 
-    let dk = derive_key(&meta.key_derive_params, secret);
-    aes_decrypt(&meta.encryption_params, dk, crypted)
+/// Is the `encrypted` data protected by outdated key derivation params,
+/// return false if the data is invalid.
+pub fn is_outdated(encrypted: &[u8]) -> bool {
+    EncryptedData::decode(encrypted)
+        .map(|data| data.key_derive_params.is_outdated())
+        .unwrap_or(false)
 }
 
 #[cfg(test)]
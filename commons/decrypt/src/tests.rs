@@ -1,4 +1,9 @@
-use crate::{decrypt, encrypt};
+use crate::{
+    aes_encrypt, decrypt, encrypt, is_outdated, EncryptedData, EncryptionParams,
+    KeyDerivationParams, ARGON2_DEFAULT_LANES, ARGON2_DEFAULT_MEM_COST, ARGON2_DEFAULT_TIME_COST,
+    ARGON2_MAX_MEM_COST, ARGON2_MAX_TIME_COST, KDF_SALT_SIZE, PBKDF2_DEFAULT_ITERATIONS,
+    PBKDF2_MAX_ITERATIONS,
+};
 
 #[test]
 fn test_encryption() {
@@ -6,7 +11,86 @@ fn test_encryption() {
     let plain = "world";
     let encrypted = encrypt(secret.as_bytes(), plain.as_bytes());
     assert_ne!(encrypted.as_slice(), plain.as_bytes());
+    assert!(!is_outdated(encrypted.as_slice()));
 
     let decrypted = decrypt(secret.as_bytes(), encrypted.as_slice()).unwrap();
     assert_eq!(decrypted.as_slice(), plain.as_bytes());
+    assert!(decrypt("world".as_bytes(), encrypted.as_slice()).is_err());
+}
+
+#[test]
+fn test_decrypt_legacy_pbkdf2() {
+    let secret = "hello";
+    let plain = "world";
+    let key_derive_params = KeyDerivationParams::Pbkdf2 {
+        iterations: PBKDF2_DEFAULT_ITERATIONS as u32,
+        salt: [1u8; KDF_SALT_SIZE],
+    };
+    let encryption_params = EncryptionParams::generate();
+    let dk = key_derive_params.derive_key(secret.as_bytes()).unwrap();
+    let ciphertext = aes_encrypt(&encryption_params, dk, plain.as_bytes());
+    let encrypted = EncryptedData {
+        key_derive_params: key_derive_params.clone(),
+        encryption_params,
+        ciphertext,
+    }
+    .encode();
+    // the legacy format starts with the big endian u32 iterations.
+    assert_eq!(
+        &encrypted[0..4],
+        &(PBKDF2_DEFAULT_ITERATIONS as u32).to_be_bytes()
+    );
+    assert!(is_outdated(encrypted.as_slice()));
+    assert_eq!(
+        EncryptedData::decode(encrypted.as_slice())
+            .unwrap()
+            .key_derive_params,
+        key_derive_params
+    );
+
+    let decrypted = decrypt(secret.as_bytes(), encrypted.as_slice()).unwrap();
+    assert_eq!(decrypted.as_slice(), plain.as_bytes());
+}
+
+#[test]
+fn test_reject_excessive_kdf_params() {
+    let secret = "hello";
+    let encrypted = EncryptedData::decode(encrypt(secret.as_bytes(), b"world").as_slice()).unwrap();
+    let salt = [1u8; KDF_SALT_SIZE];
+    for key_derive_params in [
+        KeyDerivationParams::Argon2id {
+            mem_cost: ARGON2_MAX_MEM_COST + 1,
+            time_cost: ARGON2_DEFAULT_TIME_COST,
+            lanes: ARGON2_DEFAULT_LANES,
+            salt,
+        },
+        KeyDerivationParams::Argon2id {
+            mem_cost: ARGON2_DEFAULT_MEM_COST,
+            time_cost: u32::MAX,
+            lanes: ARGON2_DEFAULT_LANES,
+            salt,
+        },
+        KeyDerivationParams::Argon2id {
+            mem_cost: ARGON2_DEFAULT_MEM_COST,
+            time_cost: ARGON2_MAX_TIME_COST,
+            lanes: u32::MAX,
+            salt,
+        },
+        KeyDerivationParams::Pbkdf2 {
+            iterations: PBKDF2_MAX_ITERATIONS + 1,
+            salt,
+        },
+        KeyDerivationParams::Pbkdf2 {
+            iterations: 0,
+            salt,
+        },
+    ] {
+        assert!(key_derive_params.check().is_err());
+        let tampered = EncryptedData {
+            key_derive_params,
+            ..encrypted.clone()
+        }
+        .encode();
+        assert!(decrypt(secret.as_bytes(), tampered.as_slice()).is_err());
+    }
 }
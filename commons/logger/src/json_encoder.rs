@@ -0,0 +1,101 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use chrono::{Local, SecondsFormat};
+use log::Record;
+use log4rs::encode::{Encode, Write};
+use serde::Serialize;
+use std::cell::RefCell;
+
+thread_local! {
+    static G_LOG_CONTEXT: RefCell<LogContext> = RefCell::new(LogContext::default());
+}
+
+/// The optional fields attached to the json log lines of the current thread.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogContext {
+    pub block_number: Option<u64>,
+    pub peer_id: Option<String>,
+}
+
+impl LogContext {
+    pub fn current() -> Self {
+        G_LOG_CONTEXT.with(|ctx| ctx.borrow().clone())
+    }
+
+    /// Attach the block number to the logs of current thread until the guard is dropped.
+    pub fn with_block_number(block_number: u64) -> LogContextGuard {
+        Self::update(|ctx| ctx.block_number = Some(block_number))
+    }
+
+    /// Attach the peer id to the logs of current thread until the guard is dropped.
+    pub fn with_peer_id(peer_id: impl ToString) -> LogContextGuard {
+        let peer_id = peer_id.to_string();
+        Self::update(|ctx| ctx.peer_id = Some(peer_id))
+    }
+
+    fn update<F: FnOnce(&mut LogContext)>(f: F) -> LogContextGuard {
+        G_LOG_CONTEXT.with(|ctx| {
+            let mut ctx = ctx.borrow_mut();
+            let prev = ctx.clone();
+            f(&mut ctx);
+            LogContextGuard { prev }
+        })
+    }
+}
+
+/// Restore the previous log context when dropped.
+#[must_use]
+pub struct LogContextGuard {
+    prev: LogContext,
+}
+
+impl Drop for LogContextGuard {
+    fn drop(&mut self) {
+        let prev = std::mem::take(&mut self.prev);
+        G_LOG_CONTEXT.with(|ctx| *ctx.borrow_mut() = prev);
+    }
+}
+
+#[derive(Serialize)]
+struct JsonLogLine<'a> {
+    timestamp: String,
+    level: &'a str,
+    target: &'a str,
+    thread: Option<&'a str>,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peer_id: Option<String>,
+}
+
+/// Encode every log record as one json line, the fields are fixed so the log can be parsed
+/// without depending on the message format.
+#[derive(Clone, Debug, Default)]
+pub struct JsonEncoder;
+
+impl JsonEncoder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Encode for JsonEncoder {
+    fn encode(&self, w: &mut dyn Write, record: &Record) -> anyhow::Result<()> {
+        let thread = std::thread::current();
+        let context = LogContext::current();
+        let line = JsonLogLine {
+            timestamp: Local::now().to_rfc3339_opts(SecondsFormat::Millis, false),
+            level: record.level().as_str(),
+            target: record.target(),
+            thread: thread.name(),
+            message: record.args().to_string(),
+            block_number: context.block_number,
+            peer_id: context.peer_id,
+        };
+        serde_json::to_writer(&mut *w, &line)?;
+        w.write_all(b"\n")?;
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::json_encoder::JsonEncoder;
 use crate::structured_log::disable_slog_stderr;
 use anyhow::{format_err, Result};
 use log::LevelFilter;
@@ -13,7 +14,7 @@ use log4rs::{
         rolling_file::RollingFileAppender,
     },
     config::{Appender, Config, Logger, Root},
-    encode::pattern::PatternEncoder,
+    encode::{pattern::PatternEncoder, Encode},
     Handle,
 };
 use once_cell::sync::Lazy;
@@ -25,8 +26,11 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::{Arc, Once};
 
+mod json_encoder;
 pub mod structured_log;
 
+pub use json_encoder::{LogContext, LogContextGuard};
+
 /// Logger prelude which includes all logging macros.
 pub mod prelude {
     pub use crate::stacktrace;
@@ -50,6 +54,8 @@ const LOG_PATTERN_DEFAULT: &str = "{d} {l} - {m}{n}";
 pub enum LogPattern {
     Default,
     WithLine,
+    /// One json object per line, with fixed timestamp, level, target, thread and message fields.
+    Json,
     Custom(String),
 }
 
@@ -60,6 +66,7 @@ impl FromStr for LogPattern {
         Ok(match s.to_lowercase().as_str() {
             "default" => Self::Default,
             "withline" | "with_line" => Self::WithLine,
+            "json" => Self::Json,
             _ => Self::Custom(s.to_owned()),
         })
     }
@@ -68,12 +75,23 @@ impl FromStr for LogPattern {
 impl LogPattern {
     pub fn get_pattern(&self) -> String {
         match self {
-            Self::Default => LOG_PATTERN_DEFAULT.to_owned(),
+            Self::Default | Self::Json => LOG_PATTERN_DEFAULT.to_owned(),
             Self::WithLine => LOG_PATTERN_WITH_LINE.to_owned(),
             Self::Custom(pattern) => pattern.clone(),
         }
     }
 
+    pub fn is_json(&self) -> bool {
+        matches!(self, Self::Json)
+    }
+
+    fn encoder(&self) -> Box<dyn Encode> {
+        match self {
+            Self::Json => Box::new(JsonEncoder::new()),
+            _ => Box::new(PatternEncoder::new(self.get_pattern().as_str())),
+        }
+    }
+
     pub fn by_level(level: LevelFilter) -> Self {
         match level {
             LevelFilter::Trace | LevelFilter::Debug => Self::WithLine,
@@ -87,6 +105,7 @@ impl std::fmt::Display for LogPattern {
         let log_pattern = match self {
             Self::Default => "default".to_owned(),
             Self::WithLine => "withline".to_owned(),
+            Self::Json => "json".to_owned(),
             Self::Custom(p) => format!("custom({})", p),
         };
         write!(f, "{}", log_pattern)
@@ -201,7 +220,10 @@ impl LoggerHandle {
     pub fn update_level(&self, level: LevelFilter) {
         let mut arg = self.arg.lock().clone();
         arg.level = level;
-        arg.pattern = LogPattern::by_level(level);
+        // keep the json output, the log collector depends on it.
+        if !arg.pattern.is_json() {
+            arg.pattern = LogPattern::by_level(level);
+        }
         self.update_logger(arg);
     }
 
@@ -259,9 +281,7 @@ fn build_config(arg: LoggerConfigArg) -> Result<Config> {
     let mut root_builder = Root::builder();
     if enable_stderr {
         let stderr = ConsoleAppender::builder()
-            .encoder(Box::new(PatternEncoder::new(
-                pattern.get_pattern().as_str(),
-            )))
+            .encoder(pattern.encoder())
             .target(Target::Stderr)
             .build();
         builder = builder.appender(Appender::builder().build("stderr", Box::new(stderr)));
@@ -294,9 +314,7 @@ fn rolling_file_append(
     let log_file_backup_pattern =
         format!("{}.{{}}.gz", log_path.to_str().expect("invalid log_path"));
     let file_appender = RollingFileAppender::builder()
-        .encoder(Box::new(PatternEncoder::new(
-            pattern.get_pattern().as_str(),
-        )))
+        .encoder(pattern.encoder())
         .build(
             log_path,
             Box::new(CompoundPolicy::new(
@@ -337,7 +355,7 @@ pub fn init_with_default_level(
 ) -> Arc<LoggerHandle> {
     let (global_level, module_levels) = env_log_level(default_level);
     G_LOG_INIT.call_once(|| {
-        let arg = LoggerConfigArg::new(true, global_level, module_levels, pattern);
+        let arg = LoggerConfigArg::new(true, global_level, module_levels, pattern.clone());
         let config = build_config(arg.clone()).expect("build log config fail.");
         let handle = match log4rs::init_config(config) {
             Ok(handle) => handle,
@@ -354,6 +372,10 @@ pub fn init_with_default_level(
     if logger_handle.level() != global_level {
         logger_handle.update_level(global_level);
     }
+    // the logger may have been initialized before the config is loaded.
+    if let Some(pattern) = pattern {
+        logger_handle.set_log_pattern(pattern);
+    }
     logger_handle
 }
 
@@ -50,3 +50,39 @@ fn test_log_level_spec() {
         assert_eq!(actual, expected);
     }
 }
+
+#[test]
+fn test_json_encoder() {
+    use crate::json_encoder::JsonEncoder;
+    use crate::{LogContext, LogPattern};
+    use log4rs::encode::writer::simple::SimpleWriter;
+    use log4rs::encode::Encode;
+
+    assert_eq!("json".parse::<LogPattern>().unwrap(), LogPattern::Json);
+
+    let mut writer = SimpleWriter(Vec::new());
+    {
+        let _block_number = LogContext::with_block_number(10);
+        JsonEncoder::new()
+            .encode(
+                &mut writer,
+                &log::Record::builder()
+                    .args(format_args!("block \"connected\""))
+                    .level(Level::Info)
+                    .target("sync")
+                    .build(),
+            )
+            .unwrap();
+    }
+    assert_eq!(LogContext::current(), LogContext::default());
+
+    let output = String::from_utf8(writer.0).unwrap();
+    assert!(output.ends_with('\n'));
+    let line: serde_json::Value = serde_json::from_str(output.trim_end()).unwrap();
+    assert_eq!(line["level"], "INFO");
+    assert_eq!(line["target"], "sync");
+    assert_eq!(line["message"], "block \"connected\"");
+    assert_eq!(line["block_number"], 10);
+    assert!(line["timestamp"].is_string());
+    assert!(line.get("peer_id").is_none());
+}
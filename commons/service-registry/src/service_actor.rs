@@ -15,6 +15,7 @@ use anyhow::{format_err, Result};
 use futures::{Stream, StreamExt};
 use log::{debug, error, info};
 use std::fmt::Debug;
+use tracing::{info_span, Span};
 
 const DEFAULT_MAIL_BOX_CAP: usize = 128;
 
@@ -110,11 +111,17 @@ where
 #[derive(Debug)]
 pub struct ServiceMessage<R: ServiceRequest + 'static> {
     request: R,
+    /// The caller's span, the request is handled as a child of it so trace context
+    /// follows the request across service boundaries.
+    span: Span,
 }
 
 impl<R: ServiceRequest> ServiceMessage<R> {
     pub fn new(request: R) -> Self {
-        Self { request }
+        Self {
+            request,
+            span: Span::current(),
+        }
     }
 
     pub fn into_inner(self) -> R {
@@ -127,7 +134,7 @@ where
     R: ServiceRequest,
 {
     fn from(request: R) -> Self {
-        Self { request }
+        Self::new(request)
     }
 }
 
@@ -144,6 +151,13 @@ where
 
     fn handle(&mut self, msg: ServiceMessage<R>, ctx: &mut Self::Context) -> Self::Result {
         debug!("{} handle request: {:?}", S::service_name(), &msg.request);
+        let span = info_span!(
+            parent: &msg.span,
+            "service_request",
+            service = S::service_name(),
+            request = std::any::type_name::<R>()
+        );
+        let _enter = span.enter();
         if self.proxy.status().is_stopped() {
             return MessageResult(Err(format_err!("Service {} is stopped", S::service_name())));
         }
@@ -0,0 +1,61 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use opentelemetry::sdk::trace::TracerProvider;
+use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+use starcoin_service_registry::{
+    ActorService, RegistryAsyncService, RegistryService, ServiceContext, ServiceHandler,
+    ServiceRequest,
+};
+use tracing::{info_span, Instrument, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+
+fn trace_id(span: &Span) -> String {
+    span.context().span().span_context().trace_id().to_string()
+}
+
+#[derive(Default)]
+struct TraceService;
+
+impl ActorService for TraceService {}
+
+#[derive(Debug)]
+struct TraceIdRequest;
+
+impl ServiceRequest for TraceIdRequest {
+    type Response = String;
+}
+
+impl ServiceHandler<Self, TraceIdRequest> for TraceService {
+    fn handle(&mut self, _msg: TraceIdRequest, _ctx: &mut ServiceContext<Self>) -> String {
+        trace_id(&Span::current())
+    }
+}
+
+#[stest::test]
+async fn test_trace_context_across_service_request() {
+    let provider = TracerProvider::builder().build();
+    tracing::subscriber::set_global_default(
+        tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test"))),
+    )
+    .unwrap();
+    let registry = RegistryService::launch();
+    let service_ref = registry.register::<TraceService>().await.unwrap();
+
+    let caller = info_span!("caller");
+    let caller_trace_id = trace_id(&caller);
+    let handler_trace_id = service_ref
+        .send(TraceIdRequest)
+        .instrument(caller)
+        .await
+        .unwrap();
+    // the request is handled in the caller's trace.
+    assert_eq!(handler_trace_id, caller_trace_id);
+
+    // a request out of any span starts a new trace.
+    let other_trace_id = service_ref.send(TraceIdRequest).await.unwrap();
+    assert_ne!(other_trace_id, caller_trace_id);
+    registry.shutdown_system().await.unwrap();
+}
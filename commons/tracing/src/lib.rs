@@ -0,0 +1,233 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export the `tracing` spans emitted by the node to OpenTelemetry.
+
+use anyhow::{format_err, Result};
+use futures::future::BoxFuture;
+use opentelemetry::global;
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::runtime;
+use opentelemetry::sdk::export::trace::{ExportResult, SpanData, SpanExporter};
+use opentelemetry::sdk::propagation::TraceContextPropagator;
+use opentelemetry::sdk::trace::{self, Sampler, TracerProvider};
+use opentelemetry::sdk::Resource;
+use opentelemetry::trace::{TraceError, TracerProvider as _};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{Level, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Layer;
+
+/// The W3C trace context header used to carry the remote parent span.
+pub const TRACE_PARENT_HEADER: &str = "traceparent";
+
+/// The crates used by the exporters, their spans are never exported, otherwise exporting a
+/// batch of spans produces new spans to export.
+const EXPORTER_TARGETS: &[&str] = &[
+    "h2",
+    "hyper",
+    "opentelemetry",
+    "tonic",
+    "tower",
+];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TraceExporter {
+    /// Export spans to an OTLP collector over gRPC.
+    Otlp { endpoint: String },
+    /// Append spans as JSON lines to a local file.
+    File { path: PathBuf },
+}
+
+/// Keep the handle alive while the node is running, dropping it flushes the pending spans.
+#[derive(Debug)]
+pub struct TracingHandle {
+    _private: (),
+}
+
+impl Drop for TracingHandle {
+    fn drop(&mut self) {
+        global::shutdown_tracer_provider();
+    }
+}
+
+/// Write every finished span as a JSON line to a file.
+#[derive(Debug)]
+struct FileExporter {
+    file: File,
+}
+
+fn unix_nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default()
+}
+
+impl SpanExporter for FileExporter {
+    fn export(&mut self, batch: Vec<SpanData>) -> BoxFuture<'static, ExportResult> {
+        let result = batch.iter().try_for_each(|span| {
+            let attributes: HashMap<&str, String> = span
+                .attributes
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str().into_owned()))
+                .collect();
+            let line = serde_json::json!({
+                "name": span.name,
+                "trace_id": span.span_context.trace_id().to_string(),
+                "span_id": span.span_context.span_id().to_string(),
+                "parent_span_id": span.parent_span_id.to_string(),
+                "start_time_unix_nano": unix_nanos(span.start_time),
+                "end_time_unix_nano": unix_nanos(span.end_time),
+                "attributes": attributes,
+            });
+            writeln!(self.file, "{}", line).map_err(|e| TraceError::Other(Box::new(e)))
+        });
+        Box::pin(futures::future::ready(result))
+    }
+
+    fn force_flush(&mut self) -> BoxFuture<'static, ExportResult> {
+        let result = self
+            .file
+            .flush()
+            .map_err(|e| TraceError::Other(Box::new(e)));
+        Box::pin(futures::future::ready(result))
+    }
+}
+
+/// Export the spans of the starcoin crates at info level, the spans of the other crates are
+/// dropped.
+fn export_filter() -> Targets {
+    Targets::new()
+        .with_default(LevelFilter::OFF)
+        .with_target("starcoin", Level::INFO)
+        .with_targets(
+            EXPORTER_TARGETS
+                .iter()
+                .map(|target| (*target, LevelFilter::OFF)),
+        )
+}
+
+/// Install a global tracing subscriber which exports spans by `exporter`.
+/// The sampled flag of a remote parent is followed if `honor_remote_sampled`,
+/// otherwise every trace is sampled by `sample_ratio`, the remote flag is ignored.
+/// Must be called within a tokio runtime, spans are exported in batch on it.
+pub fn init(
+    service_name: &str,
+    exporter: TraceExporter,
+    sample_ratio: f64,
+    honor_remote_sampled: bool,
+) -> Result<TracingHandle> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    let sampler = if honor_remote_sampled {
+        Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(sample_ratio)))
+    } else {
+        Sampler::TraceIdRatioBased(sample_ratio)
+    };
+    let config = trace::config()
+        .with_sampler(sampler)
+        .with_resource(Resource::new(vec![KeyValue::new(
+            "service.name",
+            service_name.to_string(),
+        )]));
+    let tracer = match exporter {
+        TraceExporter::Otlp { endpoint } => opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .tonic()
+                    .with_endpoint(endpoint),
+            )
+            .with_trace_config(config)
+            .install_batch(runtime::Tokio)?,
+        TraceExporter::File { path } => {
+            let file = File::options()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|e| format_err!("Open trace file {:?} error: {}", path, e))?;
+            let provider = TracerProvider::builder()
+                .with_config(config)
+                .with_batch_exporter(FileExporter { file }, runtime::Tokio)
+                .build();
+            let tracer = provider.tracer("starcoin");
+            global::set_tracer_provider(provider);
+            tracer
+        }
+    };
+    // Only set the tracing dispatcher, the `log` records are handled by starcoin-logger.
+    tracing::subscriber::set_global_default(
+        tracing_subscriber::registry().with(
+            tracing_opentelemetry::layer()
+                .with_tracer(tracer)
+                .with_filter(export_filter()),
+        ),
+    )
+    .map_err(|e| format_err!("Init tracing subscriber error: {}", e))?;
+    Ok(TracingHandle { _private: () })
+}
+
+/// Set the parent of `span` from a W3C `traceparent` value, such as the one carried by a rpc request.
+/// An invalid value is ignored and the span stays a root span.
+pub fn set_remote_parent(span: &Span, trace_parent: &str) {
+    let carrier = HashMap::from([(TRACE_PARENT_HEADER.to_string(), trace_parent.to_string())]);
+    let cx = global::get_text_map_propagator(|propagator| propagator.extract(&carrier));
+    span.set_parent(cx);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::info_span;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_export_to_file() {
+        // The node installs the `log` logger before the tracing.
+        starcoin_logger::init_for_test();
+        let path = std::env::temp_dir().join(format!("trace-{}.json", std::process::id()));
+        let handle = init(
+            "starcoin-test",
+            TraceExporter::File { path: path.clone() },
+            1.0,
+            true,
+        )
+        .expect("init tracing should ok");
+        {
+            let span = info_span!("test_remote_parent_span");
+            set_remote_parent(
+                &span,
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            );
+            let _enter = span.enter();
+        }
+        drop(handle);
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("test_remote_parent_span"));
+        assert!(content.contains("4bf92f3577b34da6a3ce929d0e0e4736"));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_export_filter() {
+        let filter = export_filter();
+        assert!(filter.would_enable("starcoin_chain::chain", &Level::INFO));
+        assert!(filter.would_enable("starcoin_rpc_middleware", &Level::INFO));
+        assert!(!filter.would_enable("starcoin_chain::chain", &Level::DEBUG));
+        for target in [
+            "tonic::transport",
+            "h2::proto",
+            "hyper::client",
+            "tower::buffer",
+        ] {
+            assert!(!filter.would_enable(target, &Level::ERROR));
+        }
+        assert!(!filter.would_enable("actix::actor", &Level::INFO));
+    }
+}
@@ -30,6 +30,18 @@ pub struct AccountProviderConfig {
     #[clap(long = "from-env")]
     pub from_env: bool,
 
+    /// The endpoint of the external signer, a TCP socket address or the path of a Unix socket.
+    /// The txns and messages are signed by the remote signer, the private keys are not on this host.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "remote-signer")]
+    pub remote_signer: Option<String>,
+
+    /// Path to the file of the secret shared with the remote signer, which authenticates the
+    /// requests and responses. It is required if the remote signer is not on a loopback address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "remote-signer-secret-file", parse(from_os_str))]
+    pub remote_signer_secret_file: Option<PathBuf>,
+
     #[serde(skip)]
     #[clap(skip)]
     pub account_address: Option<AccountAddress>,
@@ -44,6 +56,7 @@ impl ConfigModule for AccountProviderConfig {
         if (self.account_dir.is_some() as i32)
             + (self.secret_file.is_some() as i32)
             + (self.from_env as i32)
+            + (self.remote_signer.is_some() as i32)
             > 1
         {
             bail!("Account provider conflicts")
@@ -57,11 +70,21 @@ impl ConfigModule for AccountProviderConfig {
                 .clone_from(&opt.account_provider.secret_file);
             self.account_address = opt.account_provider.account_address;
         }
+        if opt.account_provider.remote_signer.is_some() {
+            self.remote_signer
+                .clone_from(&opt.account_provider.remote_signer);
+        }
+        if opt.account_provider.remote_signer_secret_file.is_some() {
+            self.remote_signer_secret_file
+                .clone_from(&opt.account_provider.remote_signer_secret_file);
+        }
         self.from_env = opt.account_provider.from_env;
         if self.account_dir.is_some() {
             self.provider_strategy = AccountProviderStrategy::Local
         } else if self.secret_file.is_some() || self.from_env {
             self.provider_strategy = AccountProviderStrategy::PrivateKey
+        } else if self.remote_signer.is_some() {
+            self.provider_strategy = AccountProviderStrategy::Remote
         } else {
             self.provider_strategy = AccountProviderStrategy::RPC
         }
@@ -75,11 +98,28 @@ impl AccountProviderConfig {
             account_dir: Some(account_dir),
             secret_file: None,
             from_env: false,
+            remote_signer: None,
+            remote_signer_secret_file: None,
             account_address: None,
             provider_strategy: AccountProviderStrategy::Local,
         })
     }
 
+    pub fn new_remote_provider_config(
+        remote_signer: String,
+        remote_signer_secret_file: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            account_dir: None,
+            secret_file: None,
+            from_env: false,
+            remote_signer: Some(remote_signer),
+            remote_signer_secret_file,
+            account_address: None,
+            provider_strategy: AccountProviderStrategy::Remote,
+        }
+    }
+
     pub fn new_private_key_provider_config(
         secret_file: Option<PathBuf>,
         account_address: Option<AccountAddress>,
@@ -92,6 +132,8 @@ impl AccountProviderConfig {
             account_dir: None,
             secret_file,
             from_env,
+            remote_signer: None,
+            remote_signer_secret_file: None,
             account_address,
             provider_strategy: AccountProviderStrategy::PrivateKey,
         })
@@ -102,12 +144,15 @@ impl AccountProviderConfig {
             (self.account_dir.is_some() as i32)
                 + (self.secret_file.is_some() as i32)
                 + (self.from_env as i32)
+                + (self.remote_signer.is_some() as i32)
                 <= 1
         );
         if self.account_dir.is_some() {
             AccountProviderStrategy::Local
         } else if self.secret_file.is_some() || self.from_env {
             AccountProviderStrategy::PrivateKey
+        } else if self.remote_signer.is_some() {
+            AccountProviderStrategy::Remote
         } else {
             AccountProviderStrategy::RPC
         }
@@ -121,6 +166,8 @@ impl Default for AccountProviderConfig {
             secret_file: None,
             account_address: None,
             from_env: false,
+            remote_signer: None,
+            remote_signer_secret_file: None,
             provider_strategy: AccountProviderStrategy::RPC,
         }
     }
@@ -1213,14 +1213,15 @@ mod tests {
     use starcoin_gas::StarcoinGasParameters;
     use starcoin_gas_algebra_ext::{CostTable, FromOnChainGasSchedule};
     use starcoin_vm_types::gas_schedule::{
-        latest_cost_table, G_GAS_CONSTANTS_V1, G_GAS_CONSTANTS_V2, G_LATEST_GAS_COST_TABLE,
-        G_TEST_GAS_CONSTANTS,
+        latest_cost_table, G_GAS_CONSTANTS_V1, G_GAS_CONSTANTS_V2, G_GAS_CONSTANTS_V3,
+        G_LATEST_GAS_COST_TABLE, G_TEST_GAS_CONSTANTS,
     };
     use starcoin_vm_types::on_chain_config::{
         instruction_gas_schedule_v1, instruction_gas_schedule_v2, instruction_table_v1,
-        native_gas_schedule_v1, native_gas_schedule_v2, native_gas_schedule_v4, native_table_v1,
-        native_table_v2, txn_gas_schedule_test, txn_gas_schedule_v1, txn_gas_schedule_v2,
-        txn_gas_schedule_v3, GasSchedule,
+        instruction_table_v2, native_gas_schedule_v1, native_gas_schedule_v2,
+        native_gas_schedule_v4, native_gas_schedule_v6, native_table_v1, native_table_v2,
+        txn_gas_schedule_test, txn_gas_schedule_v1, txn_gas_schedule_v2, txn_gas_schedule_v3,
+        v6_native_table, GasSchedule,
     };
 
     fn config_entries(
@@ -1288,10 +1289,45 @@ mod tests {
         let gas_schedule = GasSchedule::from(&G_LATEST_GAS_COST_TABLE.clone());
         assert_eq!(entries, gas_schedule.entries);
         let gas_params =
-            StarcoinGasParameters::from_on_chain_gas_schedule(&gas_schedule.to_btree_map());
+            StarcoinGasParameters::from_on_chain_gas_schedule(&gas_schedule.to_btree_map())
+                .unwrap();
+        assert_eq!(gas_params.natives.nursery.debug.print.base_cost, 1.into());
+        // the genesis cost table is fixed, the BLS12381 natives are charged after their upgrade.
         assert_eq!(
-            gas_params.unwrap().natives.nursery.debug.print.base_cost,
-            1.into()
+            gas_params
+                .natives
+                .starcoin_natives
+                .bls12381
+                .verify_signature
+                .base,
+            0.into()
+        );
+    }
+
+    #[test]
+    fn test_bls12381_upgrade_config() {
+        let entries = config_entries(
+            instruction_gas_schedule_v2(),
+            native_gas_schedule_v6(),
+            txn_gas_schedule_v3(),
+        );
+        let gas_schedule = GasSchedule::from(&CostTable {
+            instruction_table: instruction_table_v2(),
+            native_table: v6_native_table(),
+            gas_constants: G_GAS_CONSTANTS_V3.clone(),
+        });
+        assert_eq!(entries, gas_schedule.entries);
+        let gas_params =
+            StarcoinGasParameters::from_on_chain_gas_schedule(&gas_schedule.to_btree_map())
+                .unwrap();
+        assert_eq!(
+            gas_params
+                .natives
+                .starcoin_natives
+                .bls12381
+                .verify_signature
+                .base,
+            20001.into()
         );
     }
 
@@ -1348,10 +1384,18 @@ mod tests {
         });
         assert_eq!(entries, gas_schedule.entries);
         let gas_params =
-            StarcoinGasParameters::from_on_chain_gas_schedule(&gas_schedule.to_btree_map());
+            StarcoinGasParameters::from_on_chain_gas_schedule(&gas_schedule.to_btree_map())
+                .unwrap();
+        assert_eq!(gas_params.natives.nursery.debug.print.base_cost, 1.into());
+        // the natives added later are not charged by the gas schedule before them.
         assert_eq!(
-            gas_params.unwrap().natives.nursery.debug.print.base_cost,
-            1.into()
+            gas_params
+                .natives
+                .starcoin_natives
+                .bls12381
+                .verify_signature
+                .base,
+            0.into()
         );
     }
 }
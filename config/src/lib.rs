@@ -41,6 +41,7 @@ mod stratum_config;
 mod sync_config;
 #[cfg(test)]
 mod tests;
+mod tracing_config;
 mod txpool_config;
 
 use thiserror::Error;
@@ -60,15 +61,17 @@ pub use genesis_config::{
 };
 pub use logger_config::LoggerConfig;
 pub use metrics_config::MetricsConfig;
-pub use miner_config::{MinerClientConfig, MinerConfig};
+pub use miner_config::{MinerClientConfig, MinerConfig, TxnSelectionStrategy};
 pub use network_config::{NetworkConfig, NetworkRpcQuotaConfiguration};
 pub use rpc_config::{
-    ApiQuotaConfiguration, HttpConfiguration, IpcConfiguration, RpcConfig, TcpConfiguration,
-    WsConfiguration,
+    ApiQuotaConfiguration, HttpConfiguration, IpcConfiguration, RpcAuthConfiguration, RpcConfig,
+    RpcCredentialConfig, TcpConfiguration, WsConfiguration,
 };
 pub use starcoin_crypto::ed25519::genesis_key_pair;
 pub use starcoin_time_service::{MockTimeService, RealTimeService, TimeService};
 pub use storage_config::{RocksdbConfig, StorageConfig, DEFAULT_CACHE_SIZE};
+pub use sync_config::{SyncMode, DEFAULT_FAST_SYNC_PIVOT_DISTANCE};
+pub use tracing_config::{TraceExporterType, TracingConfig};
 pub use txpool_config::TxPoolConfig;
 
 pub static G_CRATE_VERSION: &str = clap::crate_version!();
@@ -224,6 +227,9 @@ pub struct StarcoinOpt {
     pub stratum: StratumConfig,
     #[clap(flatten)]
     pub account_provider: AccountProviderConfig,
+    #[serde(default)]
+    #[clap(flatten)]
+    pub tracing: TracingConfig,
 }
 
 impl std::fmt::Display for StarcoinOpt {
@@ -450,6 +456,8 @@ pub struct NodeConfig {
     pub stratum: StratumConfig,
     #[serde(default)]
     pub account_provider: AccountProviderConfig,
+    #[serde(default)]
+    pub tracing: TracingConfig,
 }
 
 impl std::fmt::Display for NodeConfig {
@@ -547,7 +555,8 @@ impl NodeConfig {
         self.metrics.merge_with_opt(opt, base.clone())?;
         self.logger.merge_with_opt(opt, base.clone())?;
         self.stratum.merge_with_opt(opt, base.clone())?;
-        self.account_provider.merge_with_opt(opt, base)?;
+        self.account_provider.merge_with_opt(opt, base.clone())?;
+        self.tracing.merge_with_opt(opt, base)?;
         Ok(())
     }
 }
@@ -4,7 +4,8 @@
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
 use anyhow::Result;
 use clap::Parser;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use starcoin_logger::LogPattern;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -33,11 +34,43 @@ pub struct LoggerConfig {
     #[clap(name = "logger-max-backup", long)]
     pub max_backup: Option<u32>,
 
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_pattern",
+        deserialize_with = "deserialize_pattern"
+    )]
+    #[clap(name = "logger-pattern", long)]
+    /// Log pattern, support default|withline|json or custom pattern string, json writes one json object per line.
+    pub pattern: Option<LogPattern>,
+
     #[clap(skip)]
     #[serde(skip)]
     base: Option<Arc<BaseConfig>>,
 }
 
+/// Write the pattern as the string accepted by the command line, such as `json` or the custom
+/// pattern itself.
+fn serialize_pattern<S>(pattern: &Option<LogPattern>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match pattern {
+        Some(LogPattern::Custom(pattern)) => serializer.serialize_str(pattern),
+        Some(pattern) => serializer.serialize_str(pattern.to_string().as_str()),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_pattern<'de, D>(deserializer: D) -> Result<Option<LogPattern>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|pattern| pattern.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
 impl LoggerConfig {
     fn base(&self) -> &BaseConfig {
         self.base.as_ref().expect("Config should init.")
@@ -75,6 +108,10 @@ impl LoggerConfig {
         })
     }
 
+    pub fn pattern(&self) -> Option<LogPattern> {
+        self.pattern.clone()
+    }
+
     pub fn max_backup(&self) -> u32 {
         self.max_backup.unwrap_or_else(|| {
             let base = self.base();
@@ -104,6 +141,9 @@ impl ConfigModule for LoggerConfig {
         if opt.logger.max_backup.is_some() {
             self.max_backup = opt.logger.max_backup;
         }
+        if opt.logger.pattern.is_some() {
+            self.pattern = opt.logger.pattern.clone();
+        }
         Ok(())
     }
 }
@@ -2,11 +2,48 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use clap::Parser;
 use serde::{Deserialize, Serialize};
+use starcoin_types::account_address::AccountAddress;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use std::sync::Arc;
 
+pub const DEFAULT_RESERVED_TXNS_PERCENT: u8 = 10;
+
+/// The order to select the transactions from the pool for the block template.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize, Default)]
+pub enum TxnSelectionStrategy {
+    /// Keep the order of the pending transactions in the pool.
+    #[default]
+    Pool,
+    /// Select the transaction with the highest gas price first, the transactions of the same
+    /// sender are still selected by sequence number.
+    GasPrice,
+}
+
+impl Display for TxnSelectionStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pool => write!(f, "pool"),
+            Self::GasPrice => write!(f, "gas-price"),
+        }
+    }
+}
+
+impl FromStr for TxnSelectionStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pool" => Ok(Self::Pool),
+            "gas-price" => Ok(Self::GasPrice),
+            other => Err(format!("Unknown txn selection strategy: {}", other)),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, Parser)]
 #[serde(deny_unknown_fields)]
 pub struct MinerConfig {
@@ -31,6 +68,31 @@ pub struct MinerConfig {
     /// Miner client thread number, not work for dev network, default is 1
     pub miner_thread: Option<u16>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "miner-txn-selection")]
+    /// The order to select the transactions of the block template, pool or gas-price, default is pool.
+    pub txn_selection: Option<TxnSelectionStrategy>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "miner-min-gas-price")]
+    /// The min gas unit price of the transactions packed into the block template, default is 0.
+    pub min_gas_price: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "miner-max-txns-per-sender")]
+    /// The max number of transactions of one sender in a block template, default is unlimited.
+    pub max_txns_per_sender: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "miner-reserved-senders", use_value_delimiter = true)]
+    /// The transactions of these senders are packed first, in the reserved space of the block template.
+    pub reserved_senders: Option<Vec<AccountAddress>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "miner-reserved-txns-percent")]
+    /// The percent of the block template reserved for the reserved senders, default is 10.
+    pub reserved_txns_percent: Option<u8>,
+
     #[serde(skip)]
     #[clap(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -49,6 +111,19 @@ impl MinerConfig {
         self.disable_mint_empty_block
             .unwrap_or_else(|| self.base().net().is_dev())
     }
+    pub fn txn_selection(&self) -> TxnSelectionStrategy {
+        self.txn_selection.unwrap_or_default()
+    }
+    pub fn min_gas_price(&self) -> u64 {
+        self.min_gas_price.unwrap_or(0)
+    }
+    pub fn reserved_senders(&self) -> Vec<AccountAddress> {
+        self.reserved_senders.clone().unwrap_or_default()
+    }
+    pub fn reserved_txns_percent(&self) -> u8 {
+        self.reserved_txns_percent
+            .unwrap_or(DEFAULT_RESERVED_TXNS_PERCENT)
+    }
     pub fn miner_client_config(&self) -> Option<MinerClientConfig> {
         if self.disable_miner_client() {
             return None;
@@ -102,6 +177,25 @@ impl ConfigModule for MinerConfig {
         if opt.miner.block_gas_limit.is_some() {
             self.block_gas_limit = opt.miner.block_gas_limit;
         }
+        if opt.miner.txn_selection.is_some() {
+            self.txn_selection = opt.miner.txn_selection;
+        }
+        if opt.miner.min_gas_price.is_some() {
+            self.min_gas_price = opt.miner.min_gas_price;
+        }
+        if opt.miner.max_txns_per_sender.is_some() {
+            self.max_txns_per_sender = opt.miner.max_txns_per_sender;
+        }
+        if opt.miner.reserved_senders.is_some() {
+            self.reserved_senders = opt.miner.reserved_senders.clone();
+        }
+        if opt.miner.reserved_txns_percent.is_some() {
+            self.reserved_txns_percent = opt.miner.reserved_txns_percent;
+        }
+        ensure!(
+            self.reserved_txns_percent() <= 100,
+            "reserved_txns_percent should not greater than 100"
+        );
 
         Ok(())
     }
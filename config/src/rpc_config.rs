@@ -5,7 +5,7 @@ use crate::{
     get_available_port_from, get_random_available_ports, parse_key_val, ApiQuotaConfig, ApiSet,
     BaseConfig, ConfigModule, QuotaDuration, StarcoinOpt,
 };
-use anyhow::Result;
+use anyhow::{bail, ensure, Result};
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 use starcoin_logger::prelude::*;
@@ -335,6 +335,70 @@ impl ApiQuotaConfiguration {
     }
 }
 
+/// A credential to access the http and websocket rpc, the caller pass it by the
+/// `Authorization: Bearer <token>` header, or a `bearer.<token>` websocket sub-protocol.
+/// The token is the `api_key`, or a HS256 JWT signed by the `jwt_secret`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcCredentialConfig {
+    /// Name of the credential, identify the caller in logs and quotas.
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwt_secret: Option<String>,
+    /// The apis allowed for the credential, in addition to the apis of the rpc endpoint.
+    pub apis: ApiSet,
+    /// The quota of every api for the credential, checked in addition to the global and user api quota.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quota: Option<ApiQuotaConfig>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RpcAuthConfiguration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Credentials of the http and websocket rpc, only can be set by config file.
+    pub credentials: Option<Vec<RpcCredentialConfig>>,
+}
+
+impl RpcAuthConfiguration {
+    pub fn credentials(&self) -> &[RpcCredentialConfig] {
+        self.credentials.as_deref().unwrap_or_default()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.credentials().is_empty()
+    }
+
+    pub fn check(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        for credential in self.credentials() {
+            ensure!(
+                names.insert(credential.name.as_str()),
+                "Duplicate rpc credential name: {}",
+                credential.name
+            );
+            match (&credential.api_key, &credential.jwt_secret) {
+                (Some(key), None) => ensure!(
+                    !key.is_empty(),
+                    "The api_key of rpc credential {} is empty",
+                    credential.name
+                ),
+                (None, Some(secret)) => ensure!(
+                    !secret.is_empty(),
+                    "The jwt_secret of rpc credential {} is empty",
+                    credential.name
+                ),
+                _ => bail!(
+                    "The rpc credential {} should have one of api_key and jwt_secret",
+                    credential.name
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Deserialize, Serialize, Parser)]
 #[serde(deny_unknown_fields)]
 pub struct RpcConfig {
@@ -358,6 +422,10 @@ pub struct RpcConfig {
     #[clap(flatten)]
     pub api_quotas: ApiQuotaConfiguration,
 
+    #[serde(default)]
+    #[clap(skip)]
+    pub auth: RpcAuthConfiguration,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[clap(long = "rpc-address")]
     /// Rpc address, default is 0.0.0.0
@@ -536,6 +604,7 @@ impl ConfigModule for RpcConfig {
         self.ws.merge(&opt.rpc.ws)?;
         self.ipc.merge(&opt.rpc.ipc)?;
         self.api_quotas.merge(&opt.rpc.api_quotas)?;
+        self.auth.check()?;
 
         self.generate_address();
 
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use clap::Parser;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
@@ -78,6 +78,8 @@ static G_DEFAULT_DB_DIR: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("starcoindb/
 static G_DEFAULT_DAG_DB_DIR: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("dag/db"));
 static G_DEFAULT_SYNC_DB_DIR: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("sync/db"));
 pub const DEFAULT_CACHE_SIZE: usize = 20000;
+/// Keep enough state for chain reorganization and recent state queries.
+pub const MIN_STATE_PRUNE_RETAIN_BLOCKS: u64 = 1000;
 
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, Parser)]
 #[serde(deny_unknown_fields)]
@@ -121,6 +123,30 @@ pub struct StorageConfig {
         help = "rocksdb background threads"
     )]
     pub parallelism: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "state-prune-retain-blocks",
+        long,
+        help = "keep the state of the latest N blocks and prune the older state, keep all state if not set"
+    )]
+    pub state_prune_retain_blocks: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "address-txn-index",
+        long,
+        help = "index the transactions by the sender and the event addresses, default to false"
+    )]
+    pub address_txn_index: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "enable-dag-pruning",
+        long,
+        help = "delete the dag consensus data below the pruning point, a pruned node can not serve the dag sync of the pruned blocks, default to false"
+    )]
+    pub enable_dag_pruning: Option<bool>,
 }
 
 impl StorageConfig {
@@ -154,6 +180,19 @@ impl StorageConfig {
     pub fn cache_size(&self) -> usize {
         self.cache_size.unwrap_or(DEFAULT_CACHE_SIZE)
     }
+
+    /// The state retention window in blocks, `None` means the node is an archive node.
+    pub fn state_prune_retain_blocks(&self) -> Option<u64> {
+        self.state_prune_retain_blocks
+    }
+
+    pub fn address_txn_index(&self) -> bool {
+        self.address_txn_index.unwrap_or(false)
+    }
+
+    pub fn enable_dag_pruning(&self) -> bool {
+        self.enable_dag_pruning.unwrap_or(false)
+    }
 }
 
 impl ConfigModule for StorageConfig {
@@ -174,6 +213,22 @@ impl ConfigModule for StorageConfig {
         if opt.storage.wal_bytes_per_sync.is_some() {
             self.wal_bytes_per_sync = opt.storage.wal_bytes_per_sync;
         }
+        if opt.storage.state_prune_retain_blocks.is_some() {
+            self.state_prune_retain_blocks = opt.storage.state_prune_retain_blocks;
+        }
+        if opt.storage.address_txn_index.is_some() {
+            self.address_txn_index = opt.storage.address_txn_index;
+        }
+        if opt.storage.enable_dag_pruning.is_some() {
+            self.enable_dag_pruning = opt.storage.enable_dag_pruning;
+        }
+        if let Some(retain_blocks) = self.state_prune_retain_blocks {
+            ensure!(
+                retain_blocks >= MIN_STATE_PRUNE_RETAIN_BLOCKS,
+                "state-prune-retain-blocks should be at least {}",
+                MIN_STATE_PRUNE_RETAIN_BLOCKS
+            );
+        }
         Ok(())
     }
 }
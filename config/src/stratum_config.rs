@@ -2,7 +2,7 @@ use crate::{
     get_available_port_from, get_random_available_port, BaseConfig, ConfigModule, Parser,
     StarcoinOpt,
 };
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use serde::{Deserialize, Serialize};
 use starcoin_logger::prelude::*;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -28,6 +28,26 @@ pub struct StratumConfig {
     /// Stratum address, default is 0.0.0.0
     pub address: Option<IpAddr>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "stratum-admin-address")]
+    /// Stratum admin rpc address, such as 127.0.0.1:9881, the admin rpc is disabled if not set.
+    pub admin_address: Option<SocketAddr>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "stratum-vardiff-share-period")]
+    /// The expected seconds between two shares of a worker, default is 2.
+    pub vardiff_share_period: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "stratum-vardiff-retarget-period")]
+    /// The min seconds between two difficulty adjustments of a worker, default is 20.
+    pub vardiff_retarget_period: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "stratum-vardiff-init-hash-rate")]
+    /// The hash rate to compute the first difficulty of a new worker, default is 10000.
+    pub vardiff_init_hash_rate: Option<u64>,
+
     #[clap(skip)]
     #[serde(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -54,6 +74,13 @@ impl StratumConfig {
         });
         format!("{}:{}", address, port).parse::<SocketAddr>().ok()
     }
+
+    pub fn get_admin_address(&self) -> Option<SocketAddr> {
+        if self.disable {
+            return None;
+        }
+        self.admin_address
+    }
 }
 
 impl ConfigModule for StratumConfig {
@@ -68,6 +95,26 @@ impl ConfigModule for StratumConfig {
         if opt.stratum.port.is_some() {
             self.port = opt.stratum.port;
         }
+        if opt.stratum.admin_address.is_some() {
+            self.admin_address = opt.stratum.admin_address;
+        }
+        if opt.stratum.vardiff_share_period.is_some() {
+            self.vardiff_share_period = opt.stratum.vardiff_share_period;
+        }
+        if opt.stratum.vardiff_retarget_period.is_some() {
+            self.vardiff_retarget_period = opt.stratum.vardiff_retarget_period;
+        }
+        if opt.stratum.vardiff_init_hash_rate.is_some() {
+            self.vardiff_init_hash_rate = opt.stratum.vardiff_init_hash_rate;
+        }
+        ensure!(
+            self.vardiff_share_period != Some(0),
+            "stratum vardiff share period should be greater than 0"
+        );
+        ensure!(
+            self.vardiff_retarget_period != Some(0),
+            "stratum vardiff retarget period should be greater than 0"
+        );
         info!(
             "Stratum listen address: {:?}, port:{:?}",
             self.address, self.port
@@ -2,12 +2,61 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use clap::Parser;
 use network_api::PeerStrategy;
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use std::sync::Arc;
 
+pub const DEFAULT_FAST_SYNC_PIVOT_DISTANCE: u64 = 1000;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize, Default)]
+pub enum SyncMode {
+    /// Download and execute every block from genesis.
+    #[default]
+    Full,
+    /// Download the state of a pivot block from peers, then execute the blocks after the pivot.
+    Fast,
+    /// Only download and verify the block headers and block infos, the state is read from
+    /// full peers and verified by the proofs against the state root of the headers.
+    Light,
+}
+
+impl SyncMode {
+    pub fn is_fast(&self) -> bool {
+        matches!(self, Self::Fast)
+    }
+
+    pub fn is_light(&self) -> bool {
+        matches!(self, Self::Light)
+    }
+}
+
+impl Display for SyncMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full => write!(f, "full"),
+            Self::Fast => write!(f, "fast"),
+            Self::Light => write!(f, "light"),
+        }
+    }
+}
+
+impl FromStr for SyncMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(Self::Full),
+            "fast" => Ok(Self::Fast),
+            "light" => Ok(Self::Light),
+            other => Err(format!("Unknown sync mode: {}", other)),
+        }
+    }
+}
+
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Eq, Serialize, Parser)]
 #[serde(deny_unknown_fields)]
 pub struct SyncConfig {
@@ -28,6 +77,24 @@ pub struct SyncConfig {
         help = "max retry times once sync block failed, default 15."
     )]
     max_retry_times: Option<u64>,
+
+    /// sync mode, full, fast or light
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "sync-mode",
+        long,
+        help = "sync mode, full, fast or light, default full. Fast mode only takes effect when the node starts from genesis."
+    )]
+    sync_mode: Option<SyncMode>,
+
+    /// distance between the fast sync pivot block and the best block of peers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "fast-sync-pivot-distance",
+        long,
+        help = "the distance between the fast sync pivot block and the sync target, default 1000."
+    )]
+    fast_sync_pivot_distance: Option<u64>,
 }
 
 impl SyncConfig {
@@ -38,10 +105,19 @@ impl SyncConfig {
     pub fn max_retry_times(&self) -> u64 {
         self.max_retry_times.unwrap_or(15)
     }
+
+    pub fn sync_mode(&self) -> SyncMode {
+        self.sync_mode.unwrap_or_default()
+    }
+
+    pub fn fast_sync_pivot_distance(&self) -> u64 {
+        self.fast_sync_pivot_distance
+            .unwrap_or(DEFAULT_FAST_SYNC_PIVOT_DISTANCE)
+    }
 }
 
 impl ConfigModule for SyncConfig {
-    fn merge_with_opt(&mut self, opt: &StarcoinOpt, _base: Arc<BaseConfig>) -> Result<()> {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
         if opt.sync.peer_select_strategy.is_some() {
             self.peer_select_strategy = opt.sync.peer_select_strategy;
         }
@@ -50,6 +126,25 @@ impl ConfigModule for SyncConfig {
             self.max_retry_times = opt.sync.max_retry_times;
         }
 
+        if opt.sync.sync_mode.is_some() {
+            self.sync_mode = opt.sync.sync_mode;
+        }
+
+        if opt.sync.fast_sync_pivot_distance.is_some() {
+            self.fast_sync_pivot_distance = opt.sync.fast_sync_pivot_distance;
+        }
+        ensure!(
+            self.fast_sync_pivot_distance() > 0,
+            "fast-sync-pivot-distance should be greater than 0"
+        );
+        // the light sync only verifies the single chain blocks, it can not follow the dag blocks.
+        ensure!(
+            !self.sync_mode().is_light()
+                || base.net().genesis_config().dag_effective_height == u64::MAX,
+            "light sync mode is not supported by the dag network {}",
+            base.net()
+        );
+
         Ok(())
     }
 }
@@ -3,6 +3,7 @@
 
 use super::*;
 use crate::helper::to_toml;
+use starcoin_logger::LogPattern;
 
 #[test]
 fn test_generate_and_load() -> Result<()> {
@@ -189,3 +190,47 @@ fn test_check_method_in_api_sets() {
     assert!(!ApiSet::UnsafeContext.check_rpc_method("unknown"));
     assert!(!ApiSet::UnsafeContext.check_rpc_method(""));
 }
+
+#[test]
+fn test_light_sync_mode_on_dag_network() -> Result<()> {
+    let data_dir = temp_dir();
+    let load_light_config = |net: &str| -> Result<NodeConfig> {
+        let opt = StarcoinOpt::try_parse_from(vec![
+            "starcoin",
+            "-n",
+            net,
+            "-d",
+            data_dir.path().to_str().unwrap(),
+            "--sync-mode",
+            "light",
+        ])?;
+        NodeConfig::load_with_opt(&opt)
+    };
+    let config = load_light_config("test")?;
+    assert!(config.sync.sync_mode().is_light());
+    assert!(load_light_config("halley").is_err());
+    assert!(load_light_config("dev").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_logger_pattern_toml() -> Result<()> {
+    for (pattern, expect) in [
+        ("json", LogPattern::Json),
+        ("default", LogPattern::Default),
+        ("withline", LogPattern::WithLine),
+        (
+            "{d} {l} {m}{n}",
+            LogPattern::Custom("{d} {l} {m}{n}".to_string()),
+        ),
+    ] {
+        let config: LoggerConfig = toml::from_str(format!("pattern = \"{}\"", pattern).as_str())?;
+        assert_eq!(config.pattern, Some(expect));
+        let config2: LoggerConfig = toml::from_str(to_toml(&config)?.as_str())?;
+        assert_eq!(config, config2);
+    }
+    let config: LoggerConfig = toml::from_str("")?;
+    assert_eq!(config.pattern, None);
+    assert!(!to_toml(&config)?.contains("pattern"));
+    Ok(())
+}
@@ -0,0 +1,152 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseConfig, ConfigModule, StarcoinOpt};
+use anyhow::{ensure, Result};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+static G_TRACE_FILE_NAME: &str = "trace.json";
+pub static G_DEFAULT_OTLP_ENDPOINT: &str = "http://127.0.0.1:4317";
+const DEFAULT_SAMPLE_RATIO: f64 = 1.0;
+
+/// Where the spans are exported to.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceExporterType {
+    /// Export to an OTLP collector.
+    #[default]
+    Otlp,
+    /// Write to a local JSON file.
+    File,
+}
+
+impl Display for TraceExporterType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Otlp => write!(f, "otlp"),
+            Self::File => write!(f, "file"),
+        }
+    }
+}
+
+impl FromStr for TraceExporterType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "otlp" => Ok(Self::Otlp),
+            "file" => Ok(Self::File),
+            other => Err(format!("Unknown trace exporter: {}", other)),
+        }
+    }
+}
+
+#[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, Parser)]
+#[serde(deny_unknown_fields)]
+pub struct TracingConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "enable-tracing", long)]
+    /// Enable the OpenTelemetry tracing of rpc, service requests and block execution, default is false.
+    pub enable_tracing: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "tracing-exporter", long)]
+    /// Where to export the spans, otlp or file, default is otlp.
+    pub exporter: Option<TraceExporterType>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "tracing-otlp-endpoint", long)]
+    /// The OTLP collector endpoint, default is http://127.0.0.1:4317
+    pub otlp_endpoint: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "tracing-file", long)]
+    /// The file the spans are written to when the exporter is file, default is trace.json in data dir.
+    pub file: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "tracing-sample-ratio", long)]
+    /// The ratio of the traces to sample, between 0.0 and 1.0, default is 1.0
+    pub sample_ratio: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "tracing-honor-remote-sampled", long)]
+    /// Follow the sampled flag of the remote `traceparent`, otherwise the remote traces are sampled by the sample ratio too, default is true.
+    pub honor_remote_sampled: Option<bool>,
+
+    #[serde(skip)]
+    #[clap(skip)]
+    base: Option<Arc<BaseConfig>>,
+}
+
+impl TracingConfig {
+    fn base(&self) -> &BaseConfig {
+        self.base.as_ref().expect("Config should init.")
+    }
+
+    pub fn enable_tracing(&self) -> bool {
+        self.enable_tracing.unwrap_or(false)
+    }
+
+    pub fn exporter(&self) -> TraceExporterType {
+        self.exporter.unwrap_or_default()
+    }
+
+    pub fn otlp_endpoint(&self) -> String {
+        self.otlp_endpoint
+            .clone()
+            .unwrap_or_else(|| G_DEFAULT_OTLP_ENDPOINT.to_string())
+    }
+
+    pub fn file(&self) -> PathBuf {
+        match &self.file {
+            Some(file) if file.is_absolute() => file.clone(),
+            Some(file) => self.base().data_dir().join(file),
+            None => self.base().data_dir().join(G_TRACE_FILE_NAME),
+        }
+    }
+
+    pub fn sample_ratio(&self) -> f64 {
+        self.sample_ratio.unwrap_or(DEFAULT_SAMPLE_RATIO)
+    }
+
+    pub fn honor_remote_sampled(&self) -> bool {
+        self.honor_remote_sampled.unwrap_or(true)
+    }
+}
+
+impl ConfigModule for TracingConfig {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
+        self.base = Some(base);
+        if opt.tracing.enable_tracing.is_some() {
+            self.enable_tracing = opt.tracing.enable_tracing;
+        }
+        if opt.tracing.exporter.is_some() {
+            self.exporter = opt.tracing.exporter;
+        }
+        if opt.tracing.otlp_endpoint.is_some() {
+            self.otlp_endpoint = opt.tracing.otlp_endpoint.clone();
+        }
+        if opt.tracing.file.is_some() {
+            self.file = opt.tracing.file.clone();
+        }
+        if opt.tracing.sample_ratio.is_some() {
+            self.sample_ratio = opt.tracing.sample_ratio;
+        }
+        if opt.tracing.honor_remote_sampled.is_some() {
+            self.honor_remote_sampled = opt.tracing.honor_remote_sampled;
+        }
+        let sample_ratio = self.sample_ratio();
+        ensure!(
+            (0.0..=1.0).contains(&sample_ratio),
+            "tracing sample ratio should be between 0.0 and 1.0, but got {}",
+            sample_ratio
+        );
+        Ok(())
+    }
+}
@@ -2,15 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use clap::Parser;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use starcoin_system::get_free_mem_size;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 pub const DEFAULT_MEM_SIZE: u64 = 128 * 1024 * 1024; // 128M
+static G_DEFAULT_JOURNAL_PATH: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("txpool/journal"));
 
-#[derive(Default, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, Parser)]
+#[derive(Default, Clone, Debug, PartialEq, Deserialize, Serialize, Parser)]
 #[serde(deny_unknown_fields)]
 pub struct TxPoolConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -37,6 +40,25 @@ pub struct TxPoolConfig {
     #[clap(name = "txpool-min-gas-price", long)]
     /// reject transaction whose gas_price is less than the min_gas_price. default to 1.
     min_gas_price: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "txpool-disable-journal", long)]
+    /// disable persisting the pending transactions to the journal in data dir. default to false.
+    disable_journal: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "txpool-journal-interval", long)]
+    /// interval(s) of rewriting the journal with the transactions in the pool. default to 60.
+    journal_interval: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "txpool-replace-gas-price-bump", long)]
+    /// the percent a replacement transaction's gas price must be higher than the pending transaction with the same sender and sequence number. default to 10.
+    replace_gas_price_bump: Option<u64>,
+
+    #[serde(skip)]
+    #[clap(skip)]
+    base: Option<Arc<BaseConfig>>,
 }
 
 impl TxPoolConfig {
@@ -68,10 +90,36 @@ impl TxPoolConfig {
     pub fn min_gas_price(&self) -> u64 {
         self.min_gas_price.unwrap_or(1)
     }
+    pub fn disable_journal(&self) -> bool {
+        self.disable_journal.unwrap_or(false)
+    }
+    pub fn journal_interval(&self) -> u64 {
+        self.journal_interval.unwrap_or(60)
+    }
+    pub fn replace_gas_price_bump(&self) -> u64 {
+        self.replace_gas_price_bump.unwrap_or(10)
+    }
+    /// The journal file path, return None if the journal is disabled.
+    pub fn journal_path(&self) -> Option<PathBuf> {
+        if self.disable_journal() {
+            None
+        } else {
+            Some(
+                self.base()
+                    .data_dir()
+                    .join(G_DEFAULT_JOURNAL_PATH.as_path()),
+            )
+        }
+    }
+
+    fn base(&self) -> &BaseConfig {
+        self.base.as_ref().expect("Config should init.")
+    }
 }
 
 impl ConfigModule for TxPoolConfig {
-    fn merge_with_opt(&mut self, opt: &StarcoinOpt, _base: Arc<BaseConfig>) -> Result<()> {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
+        self.base = Some(base);
         let txpool_opt = &opt.txpool;
         if let Some(m) = txpool_opt.max_mem_usage.as_ref() {
             self.max_mem_usage = Some(*m);
@@ -88,6 +136,16 @@ impl ConfigModule for TxPoolConfig {
         if let Some(m) = txpool_opt.min_gas_price.as_ref() {
             self.min_gas_price = Some(*m);
         }
+        if let Some(m) = txpool_opt.disable_journal.as_ref() {
+            self.disable_journal = Some(*m);
+        }
+        if let Some(m) = txpool_opt.journal_interval.as_ref() {
+            ensure!(*m > 0, "txpool journal interval should be greater than 0");
+            self.journal_interval = Some(*m);
+        }
+        if let Some(m) = txpool_opt.replace_gas_price_bump.as_ref() {
+            self.replace_gas_price_bump = Some(*m);
+        }
         Ok(())
     }
 }
@@ -4,7 +4,7 @@
 #![allow(clippy::arithmetic_side_effects)]
 
 use crate::consensus::Consensus;
-use crate::difficulty::{get_next_target_helper, BlockDiffInfo};
+use crate::difficulty::{get_next_target_helper, get_next_work_required_by_headers, BlockDiffInfo};
 use crate::{difficult_to_target, target_to_difficulty, G_ARGON, G_CRYPTONIGHT};
 use starcoin_crypto::hash::PlainCryptoHash;
 use starcoin_crypto::HashValue;
@@ -14,8 +14,11 @@ use starcoin_types::block::{
     Block, BlockHeader, BlockHeaderBuilder, BlockHeaderExtra, RawBlockHeader,
 };
 use starcoin_types::U256;
-use starcoin_vm_types::genesis_config::ChainId;
+use starcoin_vm_types::event::EventHandle;
+use starcoin_vm_types::genesis_config::{ChainId, ConsensusStrategy};
+use starcoin_vm_types::on_chain_resource::Epoch;
 use std::collections::VecDeque;
+use std::convert::TryFrom;
 
 #[stest::test]
 fn raw_hash_test() {
@@ -307,3 +310,52 @@ fn test_next_target_increment_difficulty_compare() {
     assert!(next_target_1 < target0);
     assert!(next_target_2 > target0);
 }
+
+#[stest::test]
+fn test_next_work_required_by_headers() {
+    let window = 3u64;
+    let epoch = Epoch::new(
+        0,
+        0,
+        0,
+        100,
+        10_000,
+        0,
+        0,
+        window,
+        2,
+        50_000_000,
+        ConsensusStrategy::CryptoNight.value(),
+        EventHandle::random_handle(0),
+    );
+    let mut now = duration_since_epoch().as_millis() as u64;
+    let headers: Vec<BlockHeader> = (0..5u64)
+        .map(|number| {
+            now += 9_000 + number * 1_000;
+            BlockHeaderBuilder::random()
+                .with_number(number)
+                .with_timestamp(now)
+                .with_difficulty((1000 + number).into())
+                .build()
+        })
+        .rev()
+        .collect();
+
+    // only the headers in the difficulty window are used.
+    let blocks = headers
+        .iter()
+        .take(window as usize)
+        .map(|header| BlockDiffInfo::try_from(header.clone()).unwrap())
+        .collect();
+    assert_eq!(
+        get_next_work_required_by_headers(&epoch, headers.clone()).unwrap(),
+        get_next_target_helper(blocks, epoch.block_time_target()).unwrap()
+    );
+    // the target of the first block is the target of the genesis.
+    let genesis = headers.last().cloned().unwrap();
+    assert_eq!(
+        get_next_work_required_by_headers(&epoch, vec![genesis.clone()]).unwrap(),
+        difficult_to_target(genesis.difficulty()).unwrap()
+    );
+    assert!(get_next_work_required_by_headers(&epoch, vec![]).is_err());
+}
@@ -6,6 +6,7 @@ use starcoin_chain_api::ChainReader;
 use starcoin_logger::prelude::*;
 use starcoin_types::block::BlockHeader;
 use starcoin_types::{U256, U512};
+use starcoin_vm_types::on_chain_resource::Epoch;
 use std::cmp::Ordering;
 use std::convert::TryFrom;
 
@@ -25,7 +26,7 @@ pub fn get_next_work_required(chain: &dyn ChainReader) -> Result<U256> {
             .checked_add(1)
             .ok_or_else(|| format_err!("block number overflow"))?
     };
-    let blocks = (start_window_num
+    let headers = (start_window_num
         ..current_header
             .number()
             .checked_add(1)
@@ -35,15 +36,36 @@ pub fn get_next_work_required(chain: &dyn ChainReader) -> Result<U256> {
             chain
                 .get_header_by_number(n)?
                 .ok_or_else(|| format_err!("Can not find header by number {}", n))
-                .and_then(|header| header.try_into())
         })
-        .collect::<Result<Vec<BlockDiffInfo>>>()?;
-    if start_window_num != 0 {
+        .collect::<Result<Vec<BlockHeader>>>()?;
+    get_next_work_required_by_headers(epoch, headers)
+}
+
+/// Get the target of next pow work by the `headers` of the difficulty window, the headers are
+/// ordered from the current header backwards, and the window is truncated by the `epoch`.
+/// It does not require a `ChainReader`, so the light client can verify the headers too.
+pub fn get_next_work_required_by_headers(
+    epoch: &Epoch,
+    mut headers: Vec<BlockHeader>,
+) -> Result<U256> {
+    let current_header = headers
+        .first()
+        .cloned()
+        .ok_or_else(|| format_err!("block headers of difficulty window is empty"))?;
+    if current_header.number() <= 1 {
+        return difficult_to_target(current_header.difficulty());
+    }
+    headers.truncate(epoch.block_difficulty_window() as usize);
+    if current_header.number() >= epoch.block_difficulty_window() {
         debug_assert!(
-            blocks.len() == epoch.block_difficulty_window() as usize,
+            headers.len() == epoch.block_difficulty_window() as usize,
             "block difficulty count should eq block_difficulty_window"
         );
     }
+    let blocks = headers
+        .into_iter()
+        .map(|header| header.try_into())
+        .collect::<Result<Vec<BlockDiffInfo>>>()?;
     let target = get_next_target_helper(blocks, epoch.block_time_target())?;
     debug!(
         "get_next_work_required current_number: {}, epoch: {:?}, target: {}",
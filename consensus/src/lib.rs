@@ -16,6 +16,7 @@ use starcoin_time_service::TimeService;
 use starcoin_types::block::{BlockHeader, BlockHeaderExtra};
 use starcoin_types::U256;
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
+use starcoin_vm_types::on_chain_resource::Epoch;
 use std::io::Write;
 
 pub mod argon;
@@ -109,6 +110,25 @@ impl Consensus for ConsensusStrategy {
     }
 }
 
+/// Verify the difficulty and pow of the `header` by the `epoch` and the difficulty window `headers`
+/// of its parent, the headers are ordered from the parent backwards.
+/// It is used by the light client, which only has the headers instead of a `ChainReader`.
+pub fn verify_header_by_headers(
+    epoch: &Epoch,
+    headers: Vec<BlockHeader>,
+    header: &BlockHeader,
+) -> Result<()> {
+    let strategy = epoch.strategy();
+    // The same as DummyConsensus::verify, the dummy consensus is not verified.
+    if matches!(strategy, ConsensusStrategy::Dummy) {
+        return Ok(());
+    }
+    let difficulty = target_to_difficulty(difficulty::get_next_work_required_by_headers(
+        epoch, headers,
+    )?)?;
+    strategy.verify_header_difficulty(difficulty, header)
+}
+
 pub fn generate_nonce() -> u32 {
     let mut rng = rand::thread_rng();
     rng.gen::<u32>();
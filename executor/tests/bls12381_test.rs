@@ -0,0 +1,246 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use blst::min_pk::{AggregateSignature, SecretKey};
+use starcoin_statedb::{ChainStateDB, ChainStateWriter};
+use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_config::genesis_address;
+use starcoin_vm_types::file_format::CompiledModule;
+use starcoin_vm_types::gas_schedule::G_LATEST_GAS_CONSTANTS;
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::ModuleId;
+use starcoin_vm_types::on_chain_config::{v6_native_table, G_LATEST_INSTRUCTION_TABLE};
+use starcoin_vm_types::state_store::state_key::StateKey;
+use starcoin_vm_types::transaction::Module;
+use starcoin_vm_types::write_set::{WriteOp, WriteSetMut};
+use test_helper::executor::{compile_modules_with_address, prepare_genesis};
+
+const DST_SIGNATURE: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const DST_PROOF_OF_POSSESSION: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+fn secret_key(seed: u8) -> SecretKey {
+    SecretKey::key_gen(&[seed; 32], &[]).unwrap()
+}
+
+/// The source of a 0x1::VMConfig which prices the BLS12381 natives, the same as the release of
+/// the BLS12381 upgrade. Only the functions read by the VM are kept.
+fn vm_config_source() -> String {
+    let push_costs = |costs: Vec<u64>| {
+        costs
+            .into_iter()
+            .map(|cost| {
+                format!(
+                    "Vector::push_back(&mut table, GasCost {{ instruction_gas: {}, memory_gas: 0 }});",
+                    cost
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let constants = &*G_LATEST_GAS_CONSTANTS;
+    format!(
+        r#"
+        module StarcoinFramework::VMConfig {{
+            use StarcoinFramework::Vector;
+
+            struct GasCost has copy, drop, store {{
+                instruction_gas: u64,
+                memory_gas: u64,
+            }}
+
+            struct GasConstants has copy, drop, store {{
+                global_memory_per_byte_cost: u64,
+                global_memory_per_byte_write_cost: u64,
+                min_transaction_gas_units: u64,
+                large_transaction_cutoff: u64,
+                intrinsic_gas_per_byte: u64,
+                maximum_number_of_gas_units: u64,
+                min_price_per_gas_unit: u64,
+                max_price_per_gas_unit: u64,
+                max_transaction_size_in_bytes: u64,
+                gas_unit_scaling_factor: u64,
+                default_account_size: u64,
+            }}
+
+            public fun instruction_schedule(): vector<GasCost> {{
+                let table = Vector::empty<GasCost>();
+                {}
+                table
+            }}
+
+            public fun native_schedule(): vector<GasCost> {{
+                let table = Vector::empty<GasCost>();
+                {}
+                table
+            }}
+
+            public fun gas_constants(): GasConstants {{
+                GasConstants {{
+                    global_memory_per_byte_cost: {},
+                    global_memory_per_byte_write_cost: {},
+                    min_transaction_gas_units: {},
+                    large_transaction_cutoff: {},
+                    intrinsic_gas_per_byte: {},
+                    maximum_number_of_gas_units: {},
+                    min_price_per_gas_unit: {},
+                    max_price_per_gas_unit: {},
+                    max_transaction_size_in_bytes: {},
+                    gas_unit_scaling_factor: {},
+                    default_account_size: {},
+                }}
+            }}
+        }}
+        "#,
+        push_costs(
+            G_LATEST_INSTRUCTION_TABLE
+                .iter()
+                .map(|cost| cost.total())
+                .collect()
+        ),
+        push_costs(v6_native_table().iter().map(|cost| cost.total()).collect()),
+        constants.global_memory_per_byte_cost,
+        constants.global_memory_per_byte_write_cost,
+        constants.min_transaction_gas_units,
+        constants.large_transaction_cutoff,
+        constants.intrinsic_gas_per_byte,
+        constants.maximum_number_of_gas_units,
+        constants.min_price_per_gas_unit,
+        constants.max_price_per_gas_unit,
+        constants.max_transaction_size_in_bytes,
+        constants.gas_unit_scaling_factor,
+        constants.default_account_size,
+    )
+}
+
+fn write_modules(chain_state: &ChainStateDB, modules: Vec<Module>) -> Result<()> {
+    let mut writes = WriteSetMut::default();
+    for module in modules {
+        let module_id = CompiledModule::deserialize(module.code())?.self_id();
+        writes.push((
+            StateKey::AccessPath(AccessPath::from(&module_id)),
+            WriteOp::Value(module.code().to_vec()),
+        ));
+    }
+    chain_state.apply_write_set(writes.freeze()?)?;
+    chain_state.commit()?;
+    Ok(())
+}
+
+fn call_bls12381(chain_state: &ChainStateDB, function: &str, args: Vec<Vec<u8>>) -> Result<bool> {
+    let mut vm = StarcoinVM::new(None);
+    let result = vm
+        .execute_readonly_function(
+            chain_state,
+            &ModuleId::new(genesis_address(), Identifier::new("BLS12381")?),
+            Identifier::new(function)?.as_ident_str(),
+            vec![],
+            args,
+        )
+        .map_err(|status| anyhow::anyhow!("call {} failed: {:?}", function, status))?;
+    Ok(bcs_ext::from_bytes::<bool>(&result[0])?)
+}
+
+#[stest::test]
+fn test_bls12381_natives_from_move() -> Result<()> {
+    // the test network runs the latest stdlib version, which is after the BLS12381 upgrade.
+    let (chain_state, _net) = prepare_genesis();
+    let bls12381 = compile_modules_with_address(
+        genesis_address(),
+        include_str!("../../vm/stdlib/sources/BLS12381.move"),
+    );
+    write_modules(&chain_state, bls12381)?;
+
+    let message = b"starcoin".to_vec();
+    let sk = secret_key(1);
+    let pk = sk.sk_to_pk().to_bytes().to_vec();
+    let sig = sk.sign(&message, DST_SIGNATURE, &[]).to_bytes().to_vec();
+    let verify_args = vec![
+        bcs_ext::to_bytes(&sig)?,
+        bcs_ext::to_bytes(&pk)?,
+        bcs_ext::to_bytes(&message)?,
+    ];
+
+    // the gas schedule of the genesis does not price the BLS12381 natives, they are not registered.
+    assert!(call_bls12381(
+        &chain_state,
+        "bls12381_verify_signature",
+        verify_args.clone()
+    )
+    .is_err());
+
+    write_modules(
+        &chain_state,
+        compile_modules_with_address(genesis_address(), vm_config_source().as_str()),
+    )?;
+
+    assert!(call_bls12381(
+        &chain_state,
+        "bls12381_verify_signature",
+        verify_args
+    )?);
+    assert!(!call_bls12381(
+        &chain_state,
+        "bls12381_verify_signature",
+        vec![
+            bcs_ext::to_bytes(&sig)?,
+            bcs_ext::to_bytes(&pk)?,
+            bcs_ext::to_bytes(&b"other".to_vec())?,
+        ]
+    )?);
+
+    let pop = sk
+        .sign(&pk, DST_PROOF_OF_POSSESSION, &[])
+        .to_bytes()
+        .to_vec();
+    assert!(call_bls12381(
+        &chain_state,
+        "bls12381_verify_proof_of_possession",
+        vec![bcs_ext::to_bytes(&pk)?, bcs_ext::to_bytes(&pop)?]
+    )?);
+    // a signature of the key is not a proof of its possession.
+    assert!(!call_bls12381(
+        &chain_state,
+        "bls12381_verify_proof_of_possession",
+        vec![bcs_ext::to_bytes(&pk)?, bcs_ext::to_bytes(&sig)?]
+    )?);
+
+    let keys = vec![secret_key(2), secret_key(3)];
+    let public_keys = keys
+        .iter()
+        .map(|sk| sk.sk_to_pk().to_bytes().to_vec())
+        .collect::<Vec<_>>();
+    let messages = vec![b"message 2".to_vec(), b"message 3".to_vec()];
+    let signatures = keys
+        .iter()
+        .zip(messages.iter())
+        .map(|(sk, message)| sk.sign(message, DST_SIGNATURE, &[]))
+        .collect::<Vec<_>>();
+    let aggregated = AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>(), true)
+        .unwrap()
+        .to_signature()
+        .to_bytes()
+        .to_vec();
+    assert!(call_bls12381(
+        &chain_state,
+        "bls12381_aggregate_verify",
+        vec![
+            bcs_ext::to_bytes(&aggregated)?,
+            bcs_ext::to_bytes(&public_keys)?,
+            bcs_ext::to_bytes(&messages)?,
+        ]
+    )?);
+    let mut swapped = messages;
+    swapped.reverse();
+    assert!(!call_bls12381(
+        &chain_state,
+        "bls12381_aggregate_verify",
+        vec![
+            bcs_ext::to_bytes(&aggregated)?,
+            bcs_ext::to_bytes(&public_keys)?,
+            bcs_ext::to_bytes(&swapped)?,
+        ]
+    )?);
+    Ok(())
+}
@@ -0,0 +1,163 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_types::account::Account;
+use starcoin_types::transaction::Transaction;
+use starcoin_vm_runtime::data_cache::{AsMoveResolver, StateViewCache};
+use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::{ModuleId, StructTag, TypeTag};
+use starcoin_vm_types::transaction::{
+    DryRunTransaction, Package, ScriptFunction, TransactionPayload,
+};
+use starcoin_vm_types::vm_status::{KeptVMStatus, VMStatus};
+use test_helper::executor::{compile_modules_with_address, execute_and_apply, prepare_genesis};
+use test_helper::txn::create_account_txn_sent_as_association;
+
+const MAX_GAS_AMOUNT: u64 = 1_000_000;
+
+fn hit_payload(module_id: &ModuleId, value: u64) -> TransactionPayload {
+    TransactionPayload::ScriptFunction(ScriptFunction::new(
+        module_id.clone(),
+        Identifier::new("hit").unwrap(),
+        vec![],
+        vec![bcs_ext::to_bytes(&value).unwrap()],
+    ))
+}
+
+#[stest::test]
+fn test_trace_transaction() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let account = Account::new();
+    let output = execute_and_apply(
+        &chain_state,
+        Transaction::UserTransaction(create_account_txn_sent_as_association(
+            &account, 0, 50_000_000, 1, &net,
+        )),
+    );
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    let module_source = r#"
+        module {{sender}}::Tracer {
+            use StarcoinFramework::Event;
+
+            struct Hit has drop, store {
+                value: u64,
+            }
+
+            public fun check(value: u64): u64 {
+                assert!(value < 100, 42);
+                value
+            }
+
+            public(script) fun hit(account: signer, value: u64) {
+                let checked = check(value);
+                let handle = Event::new_event_handle<Hit>(&account);
+                Event::emit_event(&mut handle, Hit { value: checked });
+                Event::destroy_handle(handle);
+            }
+        }
+        "#;
+    let module = compile_modules_with_address(*account.address(), module_source)
+        .pop()
+        .unwrap();
+    let output = execute_and_apply(
+        &chain_state,
+        Transaction::UserTransaction(account.create_signed_txn_impl(
+            *account.address(),
+            TransactionPayload::Package(Package::new_with_module(module)?),
+            0,
+            MAX_GAS_AMOUNT,
+            1,
+            1,
+            net.chain_id(),
+        )),
+    );
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    let module_id = ModuleId::new(*account.address(), Identifier::new("Tracer")?);
+    let hit_type = TypeTag::Struct(Box::new(StructTag {
+        address: *account.address(),
+        module: Identifier::new("Tracer")?,
+        name: Identifier::new("Hit")?,
+        type_params: vec![],
+    }));
+
+    let state_view = StateViewCache::new(&chain_state);
+    let resolver = state_view.as_move_resolver();
+
+    let txn = account.create_signed_txn_impl(
+        *account.address(),
+        hit_payload(&module_id, 7),
+        1,
+        MAX_GAS_AMOUNT,
+        1,
+        1,
+        net.chain_id(),
+    );
+    let (status, output, trace) = StarcoinVM::new(None).trace_transaction(&resolver, txn)?;
+    assert_eq!(status, VMStatus::Executed);
+    assert_eq!(trace.len(), 1);
+    let root = &trace[0];
+    assert_eq!(root.module.as_ref(), Some(&module_id));
+    assert_eq!(root.function, "hit");
+    assert_eq!(
+        root.args,
+        vec![format!("0x{}", hex::encode(bcs_ext::to_bytes(&7u64)?))]
+    );
+    assert!(root.error.is_none());
+    assert!(root.gas_used > 0 && root.gas_used <= output.gas_used());
+
+    let check = &root.calls[0];
+    assert_eq!(check.module.as_ref(), Some(&module_id));
+    assert_eq!(check.function, "check");
+    assert_eq!(check.args, vec!["7".to_string()]);
+    assert_eq!(check.return_values, vec!["7".to_string()]);
+    assert!(!check.is_native);
+
+    // the event is recorded on the frame which writes it to the event store.
+    let emit = root
+        .calls
+        .iter()
+        .find(|call| call.function == "emit_event")
+        .expect("emit_event should be traced");
+    assert_eq!(emit.events.len(), 1);
+    assert_eq!(emit.events[0].type_tag, hit_type);
+    assert_eq!(emit.events[0].data, "{7}");
+    assert!(output
+        .events()
+        .iter()
+        .any(|event| event.type_tag() == &hit_type));
+
+    // the abort is recorded on the frame which aborts, the later calls are not executed.
+    let txn = DryRunTransaction {
+        raw_txn: Account::create_raw_txn_impl(
+            *account.address(),
+            hit_payload(&module_id, 200),
+            1,
+            MAX_GAS_AMOUNT,
+            1,
+            1,
+            net.chain_id(),
+        ),
+        public_key: account.public_key(),
+    };
+    let (status, _output, trace) =
+        StarcoinVM::new(None).dry_run_transaction_with_trace(&resolver, txn)?;
+    assert!(matches!(status, VMStatus::MoveAbort(_, 42)));
+    assert_eq!(trace.len(), 1);
+    let root = &trace[0];
+    assert!(root.error.is_none());
+    assert_eq!(root.calls.len(), 1);
+    assert!(root.events.is_empty());
+    let check = &root.calls[0];
+    assert_eq!(check.function, "check");
+    assert_eq!(check.args, vec!["200".to_string()]);
+    assert!(check.return_values.is_empty());
+    assert!(check
+        .error
+        .as_deref()
+        .unwrap()
+        .starts_with("ABORTED with code 42"));
+    Ok(())
+}
@@ -0,0 +1,289 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_config::ChainNetwork;
+use starcoin_dev::playground::{call_contract, dry_run};
+use starcoin_dev::state_override::StateOverrideView;
+use starcoin_rpc_api::types::{ResourceOverrideView, StateOverrides, StrView};
+use starcoin_statedb::ChainStateDB;
+use starcoin_types::account::Account;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::transaction::{Transaction, TransactionArgument};
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::{ModuleId, StructTag, TypeTag};
+use starcoin_vm_types::state_store::state_key::StateKey;
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::transaction::{
+    DryRunTransaction, Module, Package, ScriptFunction, TransactionPayload,
+};
+use starcoin_vm_types::vm_status::{KeptVMStatus, VMStatus};
+use test_helper::executor::{compile_modules_with_address, execute_and_apply, prepare_genesis};
+use test_helper::txn::create_account_txn_sent_as_association;
+
+const MAX_GAS_AMOUNT: u64 = 1_000_000;
+
+const COUNTER_SOURCE: &str = r#"
+    module {{sender}}::Counter {
+        use StarcoinFramework::Signer;
+
+        struct Counter has key, store {
+            value: u64,
+        }
+
+        public fun value(addr: address): u64 acquires Counter {
+            borrow_global<Counter>(addr).value
+        }
+
+        public(script) fun init(account: signer, value: u64) {
+            move_to(&account, Counter { value });
+        }
+
+        public(script) fun check(account: signer, expected: u64) acquires Counter {
+            assert!(value(Signer::address_of(&account)) == expected, 42);
+        }
+    }
+    "#;
+
+/// The upgraded Counter, it adds 100 to the value and defines a new resource.
+const COUNTER_V2_SOURCE: &str = r#"
+    module {{sender}}::Counter {
+        use StarcoinFramework::Signer;
+
+        struct Counter has key, store {
+            value: u64,
+        }
+
+        struct Flag has key, store {
+            enabled: bool,
+        }
+
+        public fun value(addr: address): u64 acquires Counter {
+            borrow_global<Counter>(addr).value + 100
+        }
+
+        public(script) fun init(account: signer, value: u64) {
+            move_to(&account, Counter { value });
+        }
+
+        public(script) fun check(account: signer, expected: u64) acquires Counter {
+            assert!(value(Signer::address_of(&account)) == expected, 42);
+        }
+    }
+    "#;
+
+fn compile_counter(address: AccountAddress, source: &str) -> Module {
+    compile_modules_with_address(address, source).pop().unwrap()
+}
+
+fn counter_module_id(address: AccountAddress) -> ModuleId {
+    ModuleId::new(address, Identifier::new("Counter").unwrap())
+}
+
+fn struct_access_path(address: AccountAddress, name: &str) -> AccessPath {
+    AccessPath::resource_access_path(
+        address,
+        StructTag {
+            address,
+            module: Identifier::new("Counter").unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        },
+    )
+}
+
+fn counter_payload(address: AccountAddress, function: &str, value: u64) -> TransactionPayload {
+    TransactionPayload::ScriptFunction(ScriptFunction::new(
+        counter_module_id(address),
+        Identifier::new(function).unwrap(),
+        vec![],
+        vec![bcs_ext::to_bytes(&value).unwrap()],
+    ))
+}
+
+/// Create an account, publish the Counter module under it and init its counter with 1.
+fn prepare_counter() -> Result<(ChainStateDB, ChainNetwork, Account)> {
+    let (chain_state, net) = prepare_genesis();
+    let account = Account::new();
+    let output = execute_and_apply(
+        &chain_state,
+        Transaction::UserTransaction(create_account_txn_sent_as_association(
+            &account, 0, 50_000_000, 1, &net,
+        )),
+    );
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    let address = *account.address();
+    for (seq_num, payload) in [
+        TransactionPayload::Package(Package::new_with_module(compile_counter(
+            address,
+            COUNTER_SOURCE,
+        ))?),
+        counter_payload(address, "init", 1),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let output = execute_and_apply(
+            &chain_state,
+            Transaction::UserTransaction(account.create_signed_txn_impl(
+                address,
+                payload,
+                seq_num as u64,
+                MAX_GAS_AMOUNT,
+                1,
+                1,
+                net.chain_id(),
+            )),
+        );
+        assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    }
+    Ok((chain_state, net, account))
+}
+
+fn counter_value<S: StateView>(state_view: &S, address: AccountAddress) -> Result<u64> {
+    let result = call_contract(
+        state_view,
+        counter_module_id(address),
+        "value",
+        vec![],
+        vec![TransactionArgument::Address(address)],
+        None,
+    )?;
+    assert_eq!(result[0].0, TypeTag::U64);
+    Ok(bcs_ext::from_bytes(result[0].1.as_slice())?)
+}
+
+#[stest::test]
+fn test_state_override_resources() -> Result<()> {
+    let (chain_state, _net, account) = prepare_counter()?;
+    let address = *account.address();
+    let counter_key = StateKey::AccessPath(struct_access_path(address, "Counter"));
+    let origin = chain_state.get_state_value(&counter_key)?;
+    assert_eq!(origin, Some(bcs_ext::to_bytes(&1u64)?));
+
+    let mut overrides = StateOverrides::default();
+    overrides.resources.insert(
+        struct_access_path(address, "Counter"),
+        ResourceOverrideView::Json(serde_json::json!({ "value": 5 })),
+    );
+    let view = StateOverrideView::new(chain_state.fork(), overrides)?;
+    assert_eq!(
+        view.get_state_value(&counter_key)?,
+        Some(bcs_ext::to_bytes(&5u64)?)
+    );
+    // the keys which are not overridden are read from the inner state.
+    let module_key = StateKey::AccessPath(AccessPath::from(&counter_module_id(address)));
+    assert_eq!(
+        view.get_state_value(&module_key)?,
+        chain_state.get_state_value(&module_key)?
+    );
+    // the overrides are never written to the inner state.
+    assert_eq!(chain_state.get_state_value(&counter_key)?, origin);
+
+    let mut overrides = StateOverrides::default();
+    overrides.resources.insert(
+        struct_access_path(address, "Counter"),
+        ResourceOverrideView::Bcs(StrView(bcs_ext::to_bytes(&7u64)?)),
+    );
+    let view = StateOverrideView::new(chain_state.fork(), overrides)?;
+    assert_eq!(
+        view.get_state_value(&counter_key)?,
+        Some(bcs_ext::to_bytes(&7u64)?)
+    );
+
+    // the JSON value should match the struct layout.
+    let mut overrides = StateOverrides::default();
+    overrides.resources.insert(
+        struct_access_path(address, "Counter"),
+        ResourceOverrideView::Json(serde_json::json!({ "count": 5 })),
+    );
+    assert!(StateOverrideView::new(chain_state.fork(), overrides).is_err());
+    // the struct of a JSON value should be defined on chain or in the overridden modules.
+    let mut overrides = StateOverrides::default();
+    overrides.resources.insert(
+        struct_access_path(address, "Flag"),
+        ResourceOverrideView::Json(serde_json::json!({ "enabled": true })),
+    );
+    assert!(StateOverrideView::new(chain_state.fork(), overrides).is_err());
+    Ok(())
+}
+
+#[stest::test]
+fn test_state_override_modules() -> Result<()> {
+    let (chain_state, _net, account) = prepare_counter()?;
+    let address = *account.address();
+    let module_v2 = compile_counter(address, COUNTER_V2_SOURCE);
+
+    let mut overrides = StateOverrides::default();
+    overrides.modules.push(StrView(module_v2.code().to_vec()));
+    // the resource defined in the overridden module can be set by JSON.
+    overrides.resources.insert(
+        struct_access_path(address, "Flag"),
+        ResourceOverrideView::Json(serde_json::json!({ "enabled": true })),
+    );
+    let view = StateOverrideView::new(chain_state.fork(), overrides)?;
+    let module_key = StateKey::AccessPath(AccessPath::from(&counter_module_id(address)));
+    assert_eq!(
+        view.get_state_value(&module_key)?,
+        Some(module_v2.code().to_vec())
+    );
+    assert_eq!(
+        view.get_state_value(&StateKey::AccessPath(struct_access_path(address, "Flag")))?,
+        Some(bcs_ext::to_bytes(&true)?)
+    );
+    assert_ne!(
+        chain_state.get_state_value(&module_key)?,
+        Some(module_v2.code().to_vec())
+    );
+
+    let mut overrides = StateOverrides::default();
+    overrides.modules.push(StrView(vec![0, 1, 2]));
+    assert!(StateOverrideView::new(chain_state.fork(), overrides).is_err());
+    Ok(())
+}
+
+#[stest::test]
+fn test_call_and_dry_run_with_state_override() -> Result<()> {
+    let (chain_state, net, account) = prepare_counter()?;
+    let address = *account.address();
+
+    let mut resource_overrides = StateOverrides::default();
+    resource_overrides.resources.insert(
+        struct_access_path(address, "Counter"),
+        ResourceOverrideView::Json(serde_json::json!({ "value": 5 })),
+    );
+    let resource_view = StateOverrideView::new(chain_state.fork(), resource_overrides)?;
+    let mut module_overrides = StateOverrides::default();
+    module_overrides.modules.push(StrView(
+        compile_counter(address, COUNTER_V2_SOURCE).code().to_vec(),
+    ));
+    let module_view = StateOverrideView::new(chain_state.fork(), module_overrides)?;
+
+    assert_eq!(counter_value(&chain_state, address)?, 1);
+    assert_eq!(counter_value(&resource_view, address)?, 5);
+    assert_eq!(counter_value(&module_view, address)?, 101);
+
+    let check_txn = |expected: u64| DryRunTransaction {
+        raw_txn: Account::create_raw_txn_impl(
+            address,
+            counter_payload(address, "check", expected),
+            2,
+            MAX_GAS_AMOUNT,
+            1,
+            1,
+            net.chain_id(),
+        ),
+        public_key: account.public_key(),
+    };
+    let (status, _) = dry_run(&chain_state, check_txn(5), None)?;
+    assert!(matches!(status, VMStatus::MoveAbort(_, 42)));
+    let (status, output) = dry_run(&resource_view, check_txn(5), None)?;
+    assert_eq!(status, VMStatus::Executed);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    let (status, _) = dry_run(&module_view, check_txn(1), None)?;
+    assert!(matches!(status, VMStatus::MoveAbort(_, 42)));
+    let (status, _) = dry_run(&module_view, check_txn(101), None)?;
+    assert_eq!(status, VMStatus::Executed);
+    Ok(())
+}
@@ -8,24 +8,33 @@ use crate::consensusdb::{
     schemadb::{
         DbGhostdagStore, DbHeadersStore, DbReachabilityStore, DbRelationsStore, GhostdagStore,
         HeaderStore, ReachabilityStoreReader, RelationsStore, RelationsStoreReader,
+        StagingReachabilityStore,
     },
 };
 use crate::ghostdag::protocol::GhostdagManager;
 use crate::{process_key_already_error, reachability};
-use anyhow::{bail, Ok};
+use anyhow::{bail, ensure, Ok};
+use rocksdb::WriteBatch;
+use serde::{Deserialize, Serialize};
 use starcoin_config::{temp_dir, RocksdbConfig};
 use starcoin_crypto::{HashValue as Hash, HashValue};
 use starcoin_logger::prelude::{debug, info};
 use starcoin_types::block::BlockHeader;
 use starcoin_types::{
-    blockhash::{BlockHashes, KType},
+    blockhash::{BlockHashSet, BlockHashes, KType},
     consensus_header::ConsensusHeader,
 };
+use std::collections::VecDeque;
 use std::ops::DerefMut;
 use std::path::Path;
 use std::sync::Arc;
 
 pub const DEFAULT_GHOSTDAG_K: KType = 8u16;
+/// The blue score distance between the selected tip and the pruning point.
+pub const DEFAULT_PRUNING_DEPTH: u64 = 17280;
+/// The pruning point only moves when it can advance at least this blue score,
+/// so the pruning is done in batches.
+pub const DEFAULT_PRUNING_FINALITY: u64 = 8640;
 
 pub type DbGhostdagManager = GhostdagManager<
     DbGhostdagStore,
@@ -38,6 +47,19 @@ pub type DbGhostdagManager = GhostdagManager<
 pub struct BlockDAG {
     pub storage: FlexiDagStorage,
     ghostdag_manager: DbGhostdagManager,
+    pruning_depth: u64,
+    pruning_finality: u64,
+}
+
+/// The consensus data of the pruning point, a new node can import it to start the dag from the
+/// pruning point instead of the dag genesis. Syncing from a pruning point is not supported by the
+/// sync yet, the snapshot is only exported and imported by `BlockDAG`, and nodes still sync the
+/// dag from the dag genesis.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PruningPointSnapshot {
+    pub dag_genesis: Hash,
+    pub pruning_point: BlockHeader,
+    pub ghostdata: GhostdagData,
 }
 
 impl BlockDAG {
@@ -58,8 +80,16 @@ impl BlockDAG {
         Self {
             ghostdag_manager,
             storage: db,
+            pruning_depth: DEFAULT_PRUNING_DEPTH,
+            pruning_finality: DEFAULT_PRUNING_FINALITY,
         }
     }
+
+    pub fn with_pruning_config(mut self, pruning_depth: u64, pruning_finality: u64) -> Self {
+        self.pruning_depth = pruning_depth;
+        self.pruning_finality = pruning_finality;
+        self
+    }
     pub fn create_for_testing() -> anyhow::Result<Self> {
         let dag_storage =
             FlexiDagStorage::create_from_path(temp_dir(), FlexiDagStorageConfig::default())?;
@@ -98,6 +128,7 @@ impl BlockDAG {
             genesis_id,
             DagState {
                 tips: vec![genesis_id],
+                pruning_point: None,
             },
         )?;
         Ok(origin)
@@ -271,4 +302,234 @@ impl BlockDAG {
         self.storage.state_store.write().insert(hash, state)?;
         Ok(())
     }
+
+    /// Calculate the pruning point of the `tips`, it is the block on the selected chain whose
+    /// blue score is `pruning_depth` behind the selected tip. The pruning point only moves
+    /// forward from `previous_pruning_point`, and by at least `pruning_finality`.
+    pub fn calc_pruning_point(
+        &self,
+        previous_pruning_point: Hash,
+        tips: &[Hash],
+    ) -> anyhow::Result<Hash> {
+        let selected_tip = self
+            .ghostdag_manager
+            .find_selected_parent(tips.iter().copied())?;
+        let tip_blue_score = self.storage.ghost_dag_store.get_blue_score(selected_tip)?;
+        let previous_blue_score = self
+            .storage
+            .ghost_dag_store
+            .get_blue_score(previous_pruning_point)?;
+        let target_blue_score = match tip_blue_score.checked_sub(self.pruning_depth) {
+            Some(target_blue_score) => target_blue_score,
+            None => return Ok(previous_pruning_point),
+        };
+        if target_blue_score < previous_blue_score.saturating_add(self.pruning_finality) {
+            return Ok(previous_pruning_point);
+        }
+
+        let mut candidate = selected_tip;
+        loop {
+            let data = self.storage.ghost_dag_store.get_compact_data(candidate)?;
+            if data.blue_score <= target_blue_score {
+                break;
+            }
+            candidate = data.selected_parent;
+        }
+        if candidate == previous_pruning_point
+            || !self.check_ancestor_of(previous_pruning_point, vec![candidate])?
+        {
+            return Ok(previous_pruning_point);
+        }
+        Ok(candidate)
+    }
+
+    /// Save the `tips` of the dag state, the pruning point of the state is kept, it is only
+    /// moved by `prune`.
+    pub fn save_dag_tips(&self, dag_genesis: Hash, tips: Vec<Hash>) -> anyhow::Result<()> {
+        let state_store = self.storage.state_store.write();
+        let pruning_point = match state_store.get_state(dag_genesis) {
+            Result::Ok(state) => state.pruning_point,
+            Err(StoreError::KeyNotFound(_)) => None,
+            Err(e) => return Err(e.into()),
+        };
+        state_store.insert(
+            dag_genesis,
+            DagState {
+                tips,
+                pruning_point,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Move the pruning point forward if the tips of the dag state are far enough from it, and
+    /// prune the blocks in its past. Return the new pruning point, or `None` if it is not moved.
+    pub fn try_prune(&self, dag_genesis: Hash, origin: Hash) -> anyhow::Result<Option<Hash>> {
+        let state = self.get_dag_state(dag_genesis)?;
+        let previous_pruning_point = state.pruning_point.unwrap_or(dag_genesis);
+        let pruning_point =
+            self.calc_pruning_point(previous_pruning_point, state.tips.as_slice())?;
+        if pruning_point == previous_pruning_point {
+            return Ok(None);
+        }
+        self.prune(dag_genesis, pruning_point, origin)?;
+        Ok(Some(pruning_point))
+    }
+
+    /// Delete the relations, ghostdag and reachability data of the blocks in the past of
+    /// `pruning_point`, and save it as the pruning point of the dag state. The deletions and the
+    /// state are written to the database in one batch. The headers are kept, so the pruned blocks
+    /// are still known by the dag. The pruned blocks are collected without the state store lock,
+    /// it is only held for writing the batch, so the readers of the dag state never see a pruning
+    /// point whose data is deleted, and the block connecting is not stalled by the collecting.
+    pub fn prune(
+        &self,
+        dag_genesis: Hash,
+        pruning_point: Hash,
+        origin: Hash,
+    ) -> anyhow::Result<()> {
+        let pruned = self.collect_pruned_blocks(pruning_point, origin)?;
+        info!(
+            "prune {} dag blocks below the pruning point: {:?}",
+            pruned.len(),
+            pruning_point
+        );
+        let pruned_set: BlockHashSet = pruned.iter().copied().collect();
+        let mut batch = WriteBatch::default();
+        let mut staging =
+            StagingReachabilityStore::new(self.storage.reachability_store.upgradable_read());
+        for block in pruned.iter().copied() {
+            if !staging.has(block)? {
+                continue;
+            }
+            let ghostdata = self.storage.ghost_dag_store.get_data(block)?;
+            let mut merge_set = ghostdata
+                .unordered_mergeset_without_selected_parent()
+                .filter(|hash| staging.has(*hash).unwrap_or(false))
+                .collect::<Vec<_>>()
+                .into_iter();
+            inquirer::delete_block(&mut staging, block, &mut merge_set)?;
+        }
+        for block in pruned.iter().copied() {
+            self.storage
+                .ghost_dag_store
+                .delete_batch(&mut batch, block)?;
+        }
+        self.storage
+            .relations_store
+            .read()
+            .delete_batch(&mut batch, &pruned_set)?;
+
+        let state_store = self.storage.state_store.write();
+        let mut state = state_store.get_state(dag_genesis)?;
+        state.pruning_point = Some(pruning_point);
+        state_store.insert_batch(&mut batch, dag_genesis, state)?;
+        let _reachability_store = staging.commit(&mut batch)?;
+        self.storage.write_batch(batch)?;
+        Ok(())
+    }
+
+    /// The blocks in the past of `pruning_point` which are not pruned yet, in topological order.
+    fn collect_pruned_blocks(
+        &self,
+        pruning_point: Hash,
+        origin: Hash,
+    ) -> anyhow::Result<Vec<Hash>> {
+        let relations_store = self.storage.relations_store.read();
+        let mut visited = BlockHashSet::new();
+        let mut queue = VecDeque::from([pruning_point]);
+        while let Some(current) = queue.pop_front() {
+            if !relations_store.has(current)? {
+                continue;
+            }
+            for parent in relations_store.get_parents(current)?.iter().copied() {
+                if parent == origin
+                    || !self.storage.ghost_dag_store.has(parent)?
+                    || !visited.insert(parent)
+                {
+                    continue;
+                }
+                queue.push_back(parent);
+            }
+        }
+        self.ghostdag_manager.sort_blocks(visited)
+    }
+
+    /// Export the consensus data of the current pruning point, or of the dag genesis if the dag
+    /// is not pruned yet.
+    pub fn export_pruning_point_snapshot(
+        &self,
+        dag_genesis: Hash,
+    ) -> anyhow::Result<PruningPointSnapshot> {
+        // Hold the state store lock, so the pruning point is not pruned while exporting.
+        let state_store = self.storage.state_store.read();
+        let pruning_point = state_store
+            .get_state(dag_genesis)?
+            .pruning_point
+            .unwrap_or(dag_genesis);
+        let header = self.storage.header_store.get_header(pruning_point)?;
+        let ghostdata = self.storage.ghost_dag_store.get_data(pruning_point)?;
+        Ok(PruningPointSnapshot {
+            dag_genesis,
+            pruning_point: header,
+            ghostdata: (*ghostdata).clone(),
+        })
+    }
+
+    /// Initialize an empty dag from a pruning point snapshot, the pruning point is attached to
+    /// the origin and becomes the only tip, the blocks after it are synced as usual.
+    pub fn init_with_pruning_point_snapshot(
+        &mut self,
+        snapshot: PruningPointSnapshot,
+        origin: Hash,
+    ) -> anyhow::Result<()> {
+        let pruning_point = snapshot.pruning_point.id();
+        ensure!(
+            !self.storage.ghost_dag_store.has(pruning_point)?,
+            "The pruning point {:?} already exists in the dag",
+            pruning_point
+        );
+        ensure!(
+            pruning_point == snapshot.dag_genesis
+                || snapshot
+                    .pruning_point
+                    .parents()
+                    .contains(&snapshot.ghostdata.selected_parent),
+            "The selected parent of the pruning point {:?} is not one of its parents",
+            pruning_point
+        );
+
+        inquirer::init(self.storage.reachability_store.write().deref_mut(), origin)?;
+        process_key_already_error(
+            self.storage
+                .relations_store
+                .write()
+                .insert(origin, BlockHashes::new(vec![])),
+        )?;
+
+        self.storage
+            .ghost_dag_store
+            .insert(pruning_point, Arc::new(snapshot.ghostdata))?;
+        inquirer::add_block(
+            self.storage.reachability_store.write().deref_mut(),
+            pruning_point,
+            origin,
+            &mut std::iter::empty(),
+        )?;
+        self.storage
+            .relations_store
+            .write()
+            .insert(pruning_point, BlockHashes::new(vec![origin]))?;
+        self.storage
+            .header_store
+            .insert(pruning_point, Arc::new(snapshot.pruning_point), 1)?;
+
+        self.save_dag_state(
+            snapshot.dag_genesis,
+            DagState {
+                tips: vec![pruning_point],
+                pruning_point: Some(pruning_point),
+            },
+        )
+    }
 }
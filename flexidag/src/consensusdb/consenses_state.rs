@@ -1,6 +1,12 @@
 use super::schema::{KeyCodec, ValueCodec};
-use super::{db::DBStorage, error::StoreError, prelude::CachedDbAccess, writer::DirectDbWriter};
+use super::{
+    db::DBStorage,
+    error::StoreError,
+    prelude::CachedDbAccess,
+    writer::{BatchDbWriter, DirectDbWriter},
+};
 use crate::define_schema;
+use rocksdb::WriteBatch;
 use schemars::{self, JsonSchema};
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue as Hash;
@@ -9,6 +15,15 @@ use std::sync::Arc;
 #[derive(Eq, PartialEq, Hash, Deserialize, Serialize, Clone, Debug, Default)]
 pub struct DagState {
     pub tips: Vec<Hash>,
+    /// The blocks in the past of the pruning point are pruned from the consensus database,
+    /// it is `None` before the first pruning.
+    pub pruning_point: Option<Hash>,
+}
+
+/// The `DagState` stored before the pruning point was introduced.
+#[derive(Deserialize)]
+struct LegacyDagState {
+    tips: Vec<Hash>,
 }
 
 pub(crate) const DAG_STATE_STORE_CF: &str = "dag-state-store";
@@ -29,7 +44,14 @@ impl ValueCodec<DagStateData> for DagState {
     }
 
     fn decode_value(data: &[u8]) -> Result<Self, StoreError> {
-        bcs_ext::from_bytes(data).map_err(|e| StoreError::DecodeError(e.to_string()))
+        bcs_ext::from_bytes(data)
+            .or_else(|_| {
+                bcs_ext::from_bytes::<LegacyDagState>(data).map(|legacy| DagState {
+                    tips: legacy.tips,
+                    pruning_point: None,
+                })
+            })
+            .map_err(|e| StoreError::DecodeError(e.to_string()))
     }
 }
 
@@ -56,6 +78,17 @@ impl DbDagStateStore {
             dag_state_access: CachedDbAccess::new(db.clone(), cache_size),
         }
     }
+
+    pub fn insert_batch(
+        &self,
+        batch: &mut WriteBatch,
+        dag_genesis: Hash,
+        state: DagState,
+    ) -> Result<(), StoreError> {
+        self.dag_state_access
+            .write(BatchDbWriter::new(batch), dag_genesis, state)?;
+        Ok(())
+    }
 }
 
 impl DagStateReader for DbDagStateStore {
@@ -77,10 +110,14 @@ impl DagStateStore for DbDagStateStore {
 pub struct DagStateView {
     pub dag_genesis: Hash,
     pub tips: Vec<Hash>,
+    pub pruning_point: Option<Hash>,
 }
 
 impl DagStateView {
     pub fn into_state(self) -> DagState {
-        DagState { tips: self.tips }
+        DagState {
+            tips: self.tips,
+            pruning_point: self.pruning_point,
+        }
     }
 }
@@ -45,6 +45,9 @@ pub trait GhostdagStore: GhostdagStoreReader {
     /// Additionally, this means writes are semantically "append-only", which is why
     /// we can keep the `insert` method non-mutable on self. See "Parallel Processing.md" for an overview.
     fn insert(&self, hash: Hash, data: Arc<GhostdagData>) -> Result<(), StoreError>;
+
+    /// Delete the GHOSTDAG data of a pruned block.
+    fn delete(&self, hash: Hash) -> Result<(), StoreError>;
 }
 
 pub struct GhostDagDataWrapper(GhostdagData);
@@ -243,6 +246,13 @@ impl DbGhostdagStore {
         )?;
         Ok(())
     }
+
+    pub fn delete_batch(&self, batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
+        self.access.delete(BatchDbWriter::new(batch), hash)?;
+        self.compact_access
+            .delete(BatchDbWriter::new(batch), hash)?;
+        Ok(())
+    }
 }
 
 impl GhostdagStoreReader for DbGhostdagStore {
@@ -308,6 +318,13 @@ impl GhostdagStore for DbGhostdagStore {
         )?;
         Ok(())
     }
+
+    fn delete(&self, hash: Hash) -> Result<(), StoreError> {
+        self.access.delete(DirectDbWriter::new(&self.db), hash)?;
+        self.compact_access
+            .delete(DirectDbWriter::new(&self.db), hash)?;
+        Ok(())
+    }
 }
 
 /// An in-memory implementation of `GhostdagStore` trait to be used for tests.
@@ -364,6 +381,16 @@ impl GhostdagStore for MemoryGhostdagStore {
             .insert(hash, data.blues_anticone_sizes.clone());
         Ok(())
     }
+
+    fn delete(&self, hash: Hash) -> Result<(), StoreError> {
+        self.blue_score_map.borrow_mut().remove(&hash);
+        self.blue_work_map.borrow_mut().remove(&hash);
+        self.selected_parent_map.borrow_mut().remove(&hash);
+        self.mergeset_blues_map.borrow_mut().remove(&hash);
+        self.mergeset_reds_map.borrow_mut().remove(&hash);
+        self.blues_anticone_sizes_map.borrow_mut().remove(&hash);
+        Ok(())
+    }
 }
 
 impl GhostdagStoreReader for MemoryGhostdagStore {
@@ -10,7 +10,7 @@ use crate::{
     define_schema,
     types::{interval::Interval, reachability::ReachabilityData},
 };
-use starcoin_types::blockhash::{self, BlockHashMap, BlockHashes};
+use starcoin_types::blockhash::{self, BlockHashMap, BlockHashSet, BlockHashes};
 
 use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
 use rocksdb::WriteBatch;
@@ -47,6 +47,37 @@ pub trait ReachabilityStore: ReachabilityStoreReader {
     fn get_height(&self, hash: Hash) -> Result<u64, StoreError>;
     fn set_reindex_root(&mut self, root: Hash) -> Result<(), StoreError>;
     fn get_reindex_root(&self) -> Result<Hash, StoreError>;
+    /// The functions below are used by pruning only.
+    fn set_parent(&mut self, hash: Hash, new_parent: Hash) -> Result<(), StoreError>;
+    /// Replace `replaced_child` in the children of `hash` by `replace_with`, in place.
+    fn replace_child(
+        &mut self,
+        hash: Hash,
+        replaced_child: Hash,
+        replace_with: &[Hash],
+    ) -> Result<(), StoreError>;
+    /// Replace `replaced_item` in the future covering set of `hash` by `replace_with`, in place.
+    fn replace_future_covering_item(
+        &mut self,
+        hash: Hash,
+        replaced_item: Hash,
+        replace_with: &[Hash],
+    ) -> Result<(), StoreError>;
+    fn delete(&mut self, hash: Hash) -> Result<(), StoreError>;
+}
+
+fn replace_in_place(
+    hashes: &mut BlockHashes,
+    hash: Hash,
+    replaced: Hash,
+    replace_with: &[Hash],
+) -> Result<(), StoreError> {
+    let index = hashes
+        .iter()
+        .position(|h| *h == replaced)
+        .ok_or_else(|| StoreError::KeyNotFound(format!("{} in {}", replaced, hash)))?;
+    Arc::make_mut(hashes).splice(index..=index, replace_with.iter().copied());
+    Ok(())
 }
 
 pub const REINDEX_ROOT_KEY: &str = "reachability-reindex-root";
@@ -212,6 +243,51 @@ impl ReachabilityStore for DbReachabilityStore {
     fn get_reindex_root(&self) -> Result<Hash, StoreError> {
         self.reindex_root.read()
     }
+
+    fn set_parent(&mut self, hash: Hash, new_parent: Hash) -> Result<(), StoreError> {
+        let mut data = self.access.read(hash)?;
+        Arc::make_mut(&mut data).parent = new_parent;
+        self.access
+            .write(DirectDbWriter::new(&self.db), hash, data)?;
+        Ok(())
+    }
+
+    fn replace_child(
+        &mut self,
+        hash: Hash,
+        replaced_child: Hash,
+        replace_with: &[Hash],
+    ) -> Result<(), StoreError> {
+        let mut data = self.access.read(hash)?;
+        let mut_data = Arc::make_mut(&mut data);
+        replace_in_place(&mut mut_data.children, hash, replaced_child, replace_with)?;
+        self.access
+            .write(DirectDbWriter::new(&self.db), hash, data)?;
+        Ok(())
+    }
+
+    fn replace_future_covering_item(
+        &mut self,
+        hash: Hash,
+        replaced_item: Hash,
+        replace_with: &[Hash],
+    ) -> Result<(), StoreError> {
+        let mut data = self.access.read(hash)?;
+        let mut_data = Arc::make_mut(&mut data);
+        replace_in_place(
+            &mut mut_data.future_covering_set,
+            hash,
+            replaced_item,
+            replace_with,
+        )?;
+        self.access
+            .write(DirectDbWriter::new(&self.db), hash, data)?;
+        Ok(())
+    }
+
+    fn delete(&mut self, hash: Hash) -> Result<(), StoreError> {
+        self.access.delete(DirectDbWriter::new(&self.db), hash)
+    }
 }
 
 impl ReachabilityStoreReader for DbReachabilityStore {
@@ -239,6 +315,7 @@ impl ReachabilityStoreReader for DbReachabilityStore {
 pub struct StagingReachabilityStore<'a> {
     store_read: RwLockUpgradableReadGuard<'a, DbReachabilityStore>,
     staging_writes: BlockHashMap<ReachabilityData>,
+    staging_deletions: BlockHashSet,
     staging_reindex_root: Option<Hash>,
 }
 
@@ -247,10 +324,30 @@ impl<'a> StagingReachabilityStore<'a> {
         Self {
             store_read,
             staging_writes: BlockHashMap::new(),
+            staging_deletions: BlockHashSet::new(),
             staging_reindex_root: None,
         }
     }
 
+    /// Read the data of `hash` from the store, the staging deletions are respected.
+    fn store_data(&self, hash: Hash) -> Result<Arc<ReachabilityData>, StoreError> {
+        if self.staging_deletions.contains(&hash) {
+            return Err(StoreError::KeyNotFound(hash.to_string()));
+        }
+        self.store_read.access.read(hash)
+    }
+
+    fn staging_data_mut(&mut self, hash: Hash) -> Result<&mut ReachabilityData, StoreError> {
+        if !self.staging_writes.contains_key(&hash) {
+            let data = (*self.store_data(hash)?).clone();
+            self.staging_writes.insert(hash, data);
+        }
+        Ok(self
+            .staging_writes
+            .get_mut(&hash)
+            .expect("staging data must exist"))
+    }
+
     pub fn commit(
         self,
         batch: &mut WriteBatch,
@@ -262,6 +359,9 @@ impl<'a> StagingReachabilityStore<'a> {
                 .access
                 .write(BatchDbWriter::new(batch), k, data)?
         }
+        for k in self.staging_deletions {
+            store_write.access.delete(BatchDbWriter::new(batch), k)?;
+        }
         if let Some(root) = self.staging_reindex_root {
             store_write
                 .reindex_root
@@ -285,9 +385,10 @@ impl ReachabilityStore for StagingReachabilityStore<'_> {
         interval: Interval,
         height: u64,
     ) -> Result<(), StoreError> {
-        if self.store_read.has(hash)? {
+        if self.has(hash)? {
             return Err(StoreError::KeyAlreadyExists(hash.to_string()));
         }
+        self.staging_deletions.remove(&hash);
         if let Vacant(e) = self.staging_writes.entry(hash) {
             e.insert(ReachabilityData::new(parent, interval, height));
             Ok(())
@@ -302,7 +403,7 @@ impl ReachabilityStore for StagingReachabilityStore<'_> {
             return Ok(());
         }
 
-        let mut data = (*self.store_read.access.read(hash)?).clone();
+        let mut data = (*self.store_data(hash)?).clone();
         data.interval = interval;
         self.staging_writes.insert(hash, data);
 
@@ -318,7 +419,7 @@ impl ReachabilityStore for StagingReachabilityStore<'_> {
             return Ok(data.height);
         }
 
-        let mut data = (*self.store_read.access.read(hash)?).clone();
+        let mut data = (*self.store_data(hash)?).clone();
         if data.children.contains(&child) {
             return Ok(data.height);
         }
@@ -340,7 +441,7 @@ impl ReachabilityStore for StagingReachabilityStore<'_> {
             return Ok(());
         }
 
-        let mut data = (*self.store_read.access.read(hash)?).clone();
+        let mut data = (*self.store_data(hash)?).clone();
         Arc::make_mut(&mut data.future_covering_set).insert(insertion_index, fci);
         self.staging_writes.insert(hash, data);
 
@@ -351,7 +452,7 @@ impl ReachabilityStore for StagingReachabilityStore<'_> {
         if let Some(data) = self.staging_writes.get(&hash) {
             Ok(data.height)
         } else {
-            Ok(self.store_read.access.read(hash)?.height)
+            Ok(self.store_data(hash)?.height)
         }
     }
 
@@ -367,10 +468,49 @@ impl ReachabilityStore for StagingReachabilityStore<'_> {
             Ok(self.store_read.get_reindex_root()?)
         }
     }
+
+    fn set_parent(&mut self, hash: Hash, new_parent: Hash) -> Result<(), StoreError> {
+        self.staging_data_mut(hash)?.parent = new_parent;
+        Ok(())
+    }
+
+    fn replace_child(
+        &mut self,
+        hash: Hash,
+        replaced_child: Hash,
+        replace_with: &[Hash],
+    ) -> Result<(), StoreError> {
+        let data = self.staging_data_mut(hash)?;
+        replace_in_place(&mut data.children, hash, replaced_child, replace_with)
+    }
+
+    fn replace_future_covering_item(
+        &mut self,
+        hash: Hash,
+        replaced_item: Hash,
+        replace_with: &[Hash],
+    ) -> Result<(), StoreError> {
+        let data = self.staging_data_mut(hash)?;
+        replace_in_place(
+            &mut data.future_covering_set,
+            hash,
+            replaced_item,
+            replace_with,
+        )
+    }
+
+    fn delete(&mut self, hash: Hash) -> Result<(), StoreError> {
+        self.staging_writes.remove(&hash);
+        self.staging_deletions.insert(hash);
+        Ok(())
+    }
 }
 
 impl ReachabilityStoreReader for StagingReachabilityStore<'_> {
     fn has(&self, hash: Hash) -> Result<bool, StoreError> {
+        if self.staging_deletions.contains(&hash) {
+            return Ok(false);
+        }
         Ok(self.staging_writes.contains_key(&hash) || self.store_read.access.has(hash)?)
     }
 
@@ -378,7 +518,7 @@ impl ReachabilityStoreReader for StagingReachabilityStore<'_> {
         if let Some(data) = self.staging_writes.get(&hash) {
             Ok(data.interval)
         } else {
-            Ok(self.store_read.access.read(hash)?.interval)
+            Ok(self.store_data(hash)?.interval)
         }
     }
 
@@ -386,7 +526,7 @@ impl ReachabilityStoreReader for StagingReachabilityStore<'_> {
         if let Some(data) = self.staging_writes.get(&hash) {
             Ok(data.parent)
         } else {
-            Ok(self.store_read.access.read(hash)?.parent)
+            Ok(self.store_data(hash)?.parent)
         }
     }
 
@@ -394,9 +534,7 @@ impl ReachabilityStoreReader for StagingReachabilityStore<'_> {
         if let Some(data) = self.staging_writes.get(&hash) {
             Ok(BlockHashes::clone(&data.children))
         } else {
-            Ok(BlockHashes::clone(
-                &self.store_read.access.read(hash)?.children,
-            ))
+            Ok(BlockHashes::clone(&self.store_data(hash)?.children))
         }
     }
 
@@ -405,7 +543,7 @@ impl ReachabilityStoreReader for StagingReachabilityStore<'_> {
             Ok(BlockHashes::clone(&data.future_covering_set))
         } else {
             Ok(BlockHashes::clone(
-                &self.store_read.access.read(hash)?.future_covering_set,
+                &self.store_data(hash)?.future_covering_set,
             ))
         }
     }
@@ -505,6 +643,43 @@ impl ReachabilityStore for MemoryReachabilityStore {
             None => Err(StoreError::KeyNotFound(REINDEX_ROOT_KEY.to_string())),
         }
     }
+
+    fn set_parent(&mut self, hash: Hash, new_parent: Hash) -> Result<(), StoreError> {
+        self.get_data_mut(hash)?.parent = new_parent;
+        Ok(())
+    }
+
+    fn replace_child(
+        &mut self,
+        hash: Hash,
+        replaced_child: Hash,
+        replace_with: &[Hash],
+    ) -> Result<(), StoreError> {
+        let data = self.get_data_mut(hash)?;
+        replace_in_place(&mut data.children, hash, replaced_child, replace_with)
+    }
+
+    fn replace_future_covering_item(
+        &mut self,
+        hash: Hash,
+        replaced_item: Hash,
+        replace_with: &[Hash],
+    ) -> Result<(), StoreError> {
+        let data = self.get_data_mut(hash)?;
+        replace_in_place(
+            &mut data.future_covering_set,
+            hash,
+            replaced_item,
+            replace_with,
+        )
+    }
+
+    fn delete(&mut self, hash: Hash) -> Result<(), StoreError> {
+        match self.map.remove(&hash) {
+            Some(_) => Ok(()),
+            None => Err(StoreError::KeyNotFound(hash.to_string())),
+        }
+    }
 }
 
 impl ReachabilityStoreReader for MemoryReachabilityStore {
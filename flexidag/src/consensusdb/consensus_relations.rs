@@ -6,7 +6,8 @@ use super::{
 use crate::define_schema;
 use rocksdb::WriteBatch;
 use starcoin_crypto::HashValue as Hash;
-use starcoin_types::blockhash::{BlockHashes, BlockLevel};
+use starcoin_types::blockhash::{BlockHashMap, BlockHashSet, BlockHashes, BlockLevel};
+use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::sync::Arc;
 
 /// Reader API for `RelationsStore`.
@@ -22,6 +23,11 @@ pub trait RelationsStoreReader {
 pub trait RelationsStore: RelationsStoreReader {
     /// Inserts `parents` into a new store entry for `hash`, and for each `parent ∈ parents` adds `hash` to `parent.children`
     fn insert(&self, hash: Hash, parents: BlockHashes) -> Result<(), StoreError>;
+    /// Replaces the parents of `hash` by `parents`, the `children` of the old and new parents are updated.
+    /// It is used by pruning to detach the pruned parents of a block.
+    fn replace_parents(&self, hash: Hash, parents: BlockHashes) -> Result<(), StoreError>;
+    /// Deletes the store entry of `hash` and removes `hash` from the `children` of its existing parents.
+    fn delete(&self, hash: Hash) -> Result<(), StoreError>;
 }
 
 pub(crate) const PARENTS_CF: &str = "block-parents";
@@ -125,6 +131,60 @@ impl DbRelationsStore {
 
         Ok(())
     }
+
+    /// Deletes the store entries of the `pruned` blocks into `batch`, and removes them from the
+    /// `children` of their surviving parents. The `parents` of the surviving blocks are kept as
+    /// they are in the headers. The updates are staged in memory, since the store does not see
+    /// the writes of `batch` before it is written.
+    pub fn delete_batch(
+        &self,
+        batch: &mut WriteBatch,
+        pruned: &BlockHashSet,
+    ) -> Result<(), StoreError> {
+        let mut staging_children = BlockHashMap::<Vec<Hash>>::new();
+        for block in pruned.iter().copied() {
+            if !self.has(block)? {
+                continue;
+            }
+            for parent in self.get_parents(block)?.iter().copied() {
+                if pruned.contains(&parent) || !self.children_access.has(parent)? {
+                    continue;
+                }
+                let children = match staging_children.entry(parent) {
+                    Occupied(e) => e.into_mut(),
+                    Vacant(e) => e.insert((*self.get_children(parent)?).clone()),
+                };
+                children.retain(|c| *c != block);
+            }
+            self.parents_access
+                .delete(BatchDbWriter::new(batch), block)?;
+            self.children_access
+                .delete(BatchDbWriter::new(batch), block)?;
+        }
+
+        for (hash, children) in staging_children {
+            self.children_access.write(
+                BatchDbWriter::new(batch),
+                hash,
+                BlockHashes::new(children),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Removes `child` from the `children` of `parent`, a deleted `parent` is ignored.
+    fn remove_child(&self, parent: Hash, child: Hash) -> Result<(), StoreError> {
+        if !self.children_access.has(parent)? {
+            return Ok(());
+        }
+        let mut children = (*self.get_children(parent)?).clone();
+        children.retain(|c| *c != child);
+        self.children_access.write(
+            DirectDbWriter::new(&self.db),
+            parent,
+            BlockHashes::new(children),
+        )
+    }
 }
 
 impl RelationsStoreReader for DbRelationsStore {
@@ -178,6 +238,40 @@ impl RelationsStore for DbRelationsStore {
 
         Ok(())
     }
+
+    fn replace_parents(&self, hash: Hash, parents: BlockHashes) -> Result<(), StoreError> {
+        let old_parents = self.get_parents(hash)?;
+        for parent in old_parents.iter().copied() {
+            if !parents.contains(&parent) {
+                self.remove_child(parent, hash)?;
+            }
+        }
+        for parent in parents.iter().copied() {
+            if !old_parents.contains(&parent) {
+                let mut children = (*self.get_children(parent)?).clone();
+                children.push(hash);
+                self.children_access.write(
+                    DirectDbWriter::new(&self.db),
+                    parent,
+                    BlockHashes::new(children),
+                )?;
+            }
+        }
+        self.parents_access
+            .write(DirectDbWriter::new(&self.db), hash, parents)?;
+        Ok(())
+    }
+
+    fn delete(&self, hash: Hash) -> Result<(), StoreError> {
+        for parent in self.get_parents(hash)?.iter().copied() {
+            self.remove_child(parent, hash)?;
+        }
+        self.parents_access
+            .delete(DirectDbWriter::new(&self.db), hash)?;
+        self.children_access
+            .delete(DirectDbWriter::new(&self.db), hash)?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -238,5 +332,26 @@ mod tests {
                 .copied()
                 .eq(vec.iter().copied().map(Hash::from)));
         }
+
+        // Prune 2 and 3, and attach 4 to 1
+        store
+            .replace_parents(4.into(), BlockHashes::new(vec![1.into()]))
+            .unwrap();
+        store.delete(2.into()).unwrap();
+        store.delete(3.into()).unwrap();
+        assert!(!store.has(2.into()).unwrap());
+        assert!(!store.has(3.into()).unwrap());
+        assert!(store
+            .get_children(1.into())
+            .unwrap()
+            .iter()
+            .copied()
+            .eq([5, 4].into_iter().map(Hash::from)));
+        assert!(store
+            .get_parents(4.into())
+            .unwrap()
+            .iter()
+            .copied()
+            .eq([1].into_iter().map(Hash::from)));
     }
 }
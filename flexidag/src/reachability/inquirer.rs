@@ -120,6 +120,78 @@ fn insert_to_future_covering_set(
     }
 }
 
+/// Delete a pruned block from the reachability data structures, the tree children of the block
+/// are moved to its tree parent and take over its interval, and the block is replaced by its
+/// children in the future covering sets of the blocks in `mergeset_iterator`.
+/// The blocks are expected to be deleted in the topological order.
+pub fn delete_block(
+    store: &mut (impl ReachabilityStore + ?Sized),
+    block: Hash,
+    mergeset_iterator: HashIterator,
+) -> Result<()> {
+    let interval = store.get_interval(block)?;
+    let parent = store.get_parent(block)?;
+    let children = store.get_children(block)?;
+
+    let siblings = store.get_children(parent)?;
+    let block_index = siblings
+        .iter()
+        .position(|sibling| *sibling == block)
+        .ok_or(ReachabilityError::DataInconsistency)?;
+    store.replace_child(parent, block, children.as_slice())?;
+    for child in children.iter().copied() {
+        store.set_parent(child, parent)?;
+    }
+
+    for merged_block in mergeset_iterator {
+        if store
+            .get_future_covering_set(merged_block)?
+            .contains(&block)
+        {
+            store.replace_future_covering_item(merged_block, block, children.as_slice())?;
+        }
+    }
+
+    // Give the interval of the block to its children, or to its siblings if it has no children,
+    // so the intervals of the siblings are still consecutive.
+    match children.len() {
+        0 => {
+            if block_index > 0 {
+                let sibling = siblings[block_index.checked_sub(1).unwrap()];
+                let sibling_interval = store.get_interval(sibling)?;
+                store.set_interval(sibling, Interval::new(sibling_interval.start, interval.end))?;
+            } else if let Some(sibling) = siblings.get(block_index.checked_add(1).unwrap()) {
+                let sibling_interval = store.get_interval(*sibling)?;
+                store.set_interval(
+                    *sibling,
+                    Interval::new(interval.start, sibling_interval.end),
+                )?;
+            }
+        }
+        1 => {
+            store.set_interval(children[0], interval)?;
+        }
+        _ => {
+            let first_child = children[0];
+            let first_interval = store.get_interval(first_child)?;
+            store.set_interval(
+                first_child,
+                Interval::new(interval.start, first_interval.end),
+            )?;
+
+            let last_child = *children.last().expect("children is not empty");
+            let last_interval = store.get_interval(last_child)?;
+            store.set_interval(last_child, Interval::new(last_interval.start, interval.end))?;
+        }
+    }
+
+    if store.get_reindex_root()? == block {
+        store.set_reindex_root(parent)?;
+    }
+    store.delete(block)?;
+    Ok(())
+}
+
 /// Hint to the reachability algorithm that `hint` is a candidate to become
 /// the `virtual selected parent` (`VSP`). This might affect internal reachability heuristics such
 /// as moving the reindex point. The consensus runtime is expected to call this function
@@ -331,6 +403,37 @@ mod tests {
         store.validate_intervals(root).unwrap();
     }
 
+    #[test]
+    fn test_delete_tree_blocks() {
+        // Arrange
+        let mut store = MemoryReachabilityStore::new();
+        let root: Hash = 1.into();
+        TreeBuilder::new(&mut store)
+            .init_with_params(root, Interval::new(1, 100))
+            .add_block(2.into(), root)
+            .add_block(3.into(), 2.into())
+            .add_block(4.into(), 2.into())
+            .add_block(5.into(), 3.into())
+            .add_block(6.into(), 5.into())
+            .add_block(7.into(), 1.into());
+
+        // Act
+        for block in [2u64, 3, 7] {
+            delete_block(&mut store, block.into(), &mut std::iter::empty()).unwrap();
+        }
+
+        // Assert
+        store.validate_intervals(root).unwrap();
+        assert!(!store.has(2.into()).unwrap());
+        assert_eq!(store.get_parent(5.into()).unwrap(), root);
+        assert_eq!(
+            store.get_children(root).unwrap().as_slice(),
+            &[5.into(), 4.into()]
+        );
+        assert!(is_chain_ancestor_of(&store, 5.into(), 6.into()).unwrap());
+        assert!(!is_chain_ancestor_of(&store, 4.into(), 6.into()).unwrap());
+    }
+
     #[test]
     fn test_add_dag_blocks() {
         // Arrange
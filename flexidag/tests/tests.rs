@@ -315,6 +315,7 @@ fn test_dag_tips_store() {
 
     let state1 = DagState {
         tips: vec![Hash::random()],
+        pruning_point: None,
     };
     let dag_genesis1 = Hash::random();
     dag.storage
@@ -325,6 +326,7 @@ fn test_dag_tips_store() {
 
     let state2 = DagState {
         tips: vec![Hash::random()],
+        pruning_point: None,
     };
     let dag_genesis2 = Hash::random();
     dag.storage
@@ -793,3 +795,97 @@ fn test_dag_mergeset() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_dag_pruning() -> anyhow::Result<()> {
+    let mut dag = BlockDAG::create_for_testing()?.with_pruning_config(4, 2);
+    let genesis = BlockHeader::dag_genesis_random(0)
+        .as_builder()
+        .with_difficulty(0.into())
+        .build();
+    let genesis_id = genesis.id();
+    let origin = dag.init_with_genesis(genesis)?;
+
+    // genesis <- 1 <- 2 <- 3 <- 4 <- ... <- 10, and 2 <- fork <- 4
+    let mut headers = vec![];
+    let mut parent = genesis_id;
+    let mut fork = None;
+    for i in 1..=10 {
+        let mut parents_hash = vec![parent];
+        if i == 4 {
+            parents_hash.extend(fork);
+        }
+        let header = BlockHeaderBuilder::random()
+            .with_difficulty(1.into())
+            .with_parents_hash(Some(parents_hash))
+            .build();
+        dag.commit(header.clone(), origin)?;
+        if i == 2 {
+            let fork_header = BlockHeaderBuilder::random()
+                .with_difficulty(1.into())
+                .with_parents_hash(Some(vec![header.id()]))
+                .build();
+            dag.commit(fork_header.clone(), origin)?;
+            fork = Some(fork_header.id());
+        }
+        parent = header.id();
+        headers.push(header);
+    }
+    let fork = fork.unwrap();
+    let tip = headers[9].id();
+
+    // the blue score of the tip is 11, so the pruning point is the chain block with blue score 7.
+    let pruning_point = dag.calc_pruning_point(genesis_id, &[tip])?;
+    assert_eq!(pruning_point, headers[5].id());
+    dag.save_dag_tips(genesis_id, vec![tip])?;
+    assert_eq!(dag.try_prune(genesis_id, origin)?, Some(pruning_point));
+    assert_eq!(dag.get_dag_state(genesis_id)?.tips, vec![tip]);
+    assert_eq!(
+        dag.get_dag_state(genesis_id)?.pruning_point,
+        Some(pruning_point)
+    );
+    // the pruning point can not move forward until the tip is far enough.
+    assert_eq!(
+        dag.calc_pruning_point(pruning_point, &[tip])?,
+        pruning_point
+    );
+    assert_eq!(dag.try_prune(genesis_id, origin)?, None);
+
+    for pruned in [genesis_id, fork, headers[2].id(), headers[4].id()] {
+        assert!(dag.ghostdata_by_hash(pruned)?.is_none());
+        assert!(!dag.storage.reachability_store.read().has(pruned)?);
+        assert!(!dag.storage.relations_store.read().has(pruned)?);
+        // the headers are kept
+        assert!(dag.has_dag_block(pruned)?);
+    }
+    // the parents of the surviving blocks are kept as they are in the headers.
+    assert_eq!(dag.get_parents(pruning_point)?, vec![headers[4].id()]);
+    assert!(!dag.get_children(origin)?.contains(&genesis_id));
+    assert!(dag.check_ancestor_of(pruning_point, vec![tip])?);
+
+    // the dag still accepts new blocks after pruning.
+    let header = BlockHeaderBuilder::random()
+        .with_difficulty(1.into())
+        .with_parents_hash(Some(vec![tip]))
+        .build();
+    dag.commit(header.clone(), origin)?;
+    assert_eq!(
+        dag.ghostdata_by_hash(header.id())?.unwrap().blue_score,
+        dag.ghostdata_by_hash(tip)?.unwrap().blue_score + 1
+    );
+
+    // a new dag starts from the pruning point snapshot.
+    let snapshot = dag.export_pruning_point_snapshot(genesis_id)?;
+    assert_eq!(snapshot.pruning_point.id(), pruning_point);
+    let mut new_dag = BlockDAG::create_for_testing()?;
+    new_dag.init_with_pruning_point_snapshot(snapshot, origin)?;
+    assert_eq!(new_dag.get_dag_state(genesis_id)?.tips, vec![pruning_point]);
+    for header in headers.iter().skip(6) {
+        new_dag.commit(header.clone(), origin)?;
+        assert_eq!(
+            new_dag.ghostdata_by_hash(header.id())?.unwrap().blue_score,
+            dag.ghostdata_by_hash(header.id())?.unwrap().blue_score
+        );
+    }
+    Ok(())
+}
@@ -27,8 +27,20 @@ use std::cmp::min;
 use std::sync::Arc;
 
 mod metrics;
-//#[cfg(test)]
-//mod test_create_block_template;
+#[cfg(test)]
+mod test_create_block_template;
+#[cfg(test)]
+mod test_txn_selection;
+mod txn_selection;
+
+pub use txn_selection::{
+    GasPriceGreedyPolicy, MinGasPricePolicy, PoolOrderPolicy, ReservedSendersPolicy,
+    SenderFairnessPolicy, TxnSelectionPolicies, TxnSelectionPolicy,
+};
+
+/// The pending txns fetched from the pool are this multiple of the block's capacity, so the
+/// txns dropped by the selection policies are replaced by the later ones.
+const CANDIDATE_TXNS_MULTIPLE: u64 = 2;
 
 #[derive(Debug)]
 pub struct BlockTemplateRequest;
@@ -78,7 +90,8 @@ impl ServiceFactory<Self> for BlockBuilderService {
             miner_account,
             metrics,
             vm_metrics,
-        )?;
+        )?
+        .with_txn_selection_policy(Arc::new(TxnSelectionPolicies::from_config(&config.miner)));
         Ok(Self { inner })
     }
 }
@@ -141,6 +154,7 @@ pub struct Inner<P, T: TxPoolSyncService + 'static> {
     storage: Arc<dyn Store>,
     block_connector_service: ServiceRef<BlockConnectorService<T>>,
     tx_provider: P,
+    txn_selection_policy: Arc<dyn TxnSelectionPolicy>,
     local_block_gas_limit: Option<u64>,
     miner_account: AccountInfo,
     #[allow(unused)]
@@ -166,6 +180,7 @@ where
             storage,
             block_connector_service,
             tx_provider,
+            txn_selection_policy: Arc::new(TxnSelectionPolicies::default()),
             local_block_gas_limit,
             miner_account,
             metrics,
@@ -173,6 +188,11 @@ where
         })
     }
 
+    pub fn with_txn_selection_policy(mut self, policy: Arc<dyn TxnSelectionPolicy>) -> Self {
+        self.txn_selection_policy = policy;
+        self
+    }
+
     pub fn create_block_template(&self) -> Result<BlockTemplateResponse> {
         let MinerResponse {
             previous_header,
@@ -193,7 +213,10 @@ where
         // block_gas_limit / min_gas_per_txn
         let max_txns = (block_gas_limit / 200) * 2;
 
-        let txns = self.tx_provider.get_txns(max_txns);
+        let candidates = self
+            .tx_provider
+            .get_txns(max_txns.saturating_mul(CANDIDATE_TXNS_MULTIPLE));
+        let txns = self.txn_selection_policy.select(candidates, max_txns);
         let author = *self.miner_account.address();
         let current_number = previous_header.number().saturating_add(1);
 
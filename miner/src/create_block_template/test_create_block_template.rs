@@ -2,519 +2,560 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::create_block_template::{
-    BlockBuilderService, BlockTemplateRequest, EmptyProvider, Inner,
+    BlockBuilderService, BlockTemplateRequest, EmptyProvider, Inner, TemplateTxProvider,
+    TxnSelectionPolicies, TxnSelectionPolicy, CANDIDATE_TXNS_MULTIPLE,
 };
-use anyhow::Result;
+use parking_lot::Mutex;
 use starcoin_account_api::AccountInfo;
 use starcoin_account_service::AccountService;
 use starcoin_chain::BlockChain;
-use starcoin_chain::{ChainReader, ChainWriter};
-use starcoin_config::ChainNetworkID;
-use starcoin_config::{temp_dir, NodeConfig, StarcoinOpt};
+use starcoin_config::{
+    temp_dir, ChainNetwork, ChainNetworkID, MinerConfig, NodeConfig, StarcoinOpt,
+    TxnSelectionStrategy,
+};
 use starcoin_consensus::Consensus;
+use starcoin_crypto::HashValue;
+use starcoin_dag::blockdag::BlockDAG;
 use starcoin_genesis::Genesis as StarcoinGenesis;
-use starcoin_logger::prelude::*;
-use starcoin_service_registry::{RegistryAsyncService, RegistryService};
-use starcoin_storage::BlockStore;
+use starcoin_service_registry::{RegistryAsyncService, RegistryService, ServiceRef};
+use starcoin_storage::{BlockStore, Storage};
+use starcoin_sync::block_connector::BlockConnectorService;
 use starcoin_time_service::MockTimeService;
 use starcoin_txpool::TxPoolService;
+use starcoin_types::account::DEFAULT_MAX_GAS_AMOUNT;
+use starcoin_types::block::{Block, BlockTemplate};
+use starcoin_types::system_events::MinedBlock;
+use starcoin_vm_types::account_config::{association_address, core_code_address, stc_type_tag};
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::ModuleId;
+use starcoin_vm_types::transaction::{ScriptFunction, SignedUserTransaction, TransactionPayload};
 use std::sync::Arc;
-
-#[stest::test]
-fn test_create_block_template() {
-    test_create_block_template_by_net(ChainNetworkID::TEST);
-    test_create_block_template_by_net(ChainNetworkID::DEV);
-    test_create_block_template_by_net(ChainNetworkID::HALLEY);
-    //test_create_block_template_by_net(ChainNetwork::PROXIMA);
+use test_helper::txn::create_account_txn_sent_as_association;
+use test_helper::Account;
+
+/// Provide the fixed txns, and record the number of txns requested.
+#[derive(Default)]
+struct MockTxProvider {
+    txns: Vec<SignedUserTransaction>,
+    requests: Mutex<Vec<u64>>,
 }
 
-fn test_create_block_template_by_net(net: ChainNetworkID) {
-    debug!("test_create_block_template_by_net {:?}", net);
-    let mut opt = StarcoinOpt::default();
-    let temp_path = temp_dir();
-    opt.net = Some(net);
-    opt.base_data_dir = Some(temp_path.path().to_path_buf());
-
-    let node_config = Arc::new(NodeConfig::load_with_opt(&opt).unwrap());
-    let (storage, chain_info, genesis, dag) =
-        StarcoinGenesis::init_storage_for_test(node_config.net())
-            .expect("init storage by genesis fail.");
-    let genesis_id = genesis.block().id();
-    let miner_account = AccountInfo::random();
-    let inner = Inner::new(
-        node_config.net(),
-        storage,
-        genesis_id,
-        EmptyProvider,
-        None,
-        miner_account,
-        None,
-        None,
-        dag,
-    )
-    .unwrap();
+impl TemplateTxProvider for Arc<MockTxProvider> {
+    fn get_txns(&self, max: u64) -> Vec<SignedUserTransaction> {
+        self.requests.lock().push(max);
+        self.txns.clone()
+    }
 
-    let block_template = inner.create_block_template().unwrap().template;
-    assert_eq!(block_template.parent_hash, genesis_id);
-    assert_eq!(block_template.parent_hash, chain_info.head().id());
-    assert_eq!(block_template.number, 1);
+    fn remove_invalid_txn(&self, _txn_hash: HashValue) {}
 }
 
-#[stest::test(timeout = 120)]
-fn test_switch_main() {
-    let node_config = Arc::new(NodeConfig::random_for_test());
-    let (storage, _, genesis, dag) = StarcoinGenesis::init_storage_for_test(node_config.net())
-        .expect("init storage by genesis fail.");
-    let genesis_id = genesis.block().id();
-    let times = 10;
-
-    let miner_account = AccountInfo::random();
-    // main
-    let mut head_id = genesis_id;
-    let mut main_inner = None;
+/// Keep the candidates, and record the number of candidates and the block's capacity.
+#[derive(Default)]
+struct RecordingPolicy {
+    selections: Mutex<Vec<(usize, u64)>>,
+}
 
-    let chain_header = storage
-        .get_block_header_by_hash(genesis_id)
-        .unwrap()
-        .unwrap();
-    let txpool = TxPoolService::new(node_config.clone(), storage.clone(), chain_header, None);
+impl TxnSelectionPolicy for RecordingPolicy {
+    fn select(
+        &self,
+        candidates: Vec<SignedUserTransaction>,
+        max: u64,
+    ) -> Vec<SignedUserTransaction> {
+        self.selections.lock().push((candidates.len(), max));
+        candidates
+    }
+}
 
-    let net = node_config.net();
-    for i in 0..times {
-        let mut main = BlockChain::new(
-            net.time_service(),
-            head_id,
-            storage.clone(),
-            None,
-            dag.clone(),
-        )
-        .unwrap();
+struct TestChain {
+    registry: ServiceRef<RegistryService>,
+    storage: Arc<Storage>,
+    dag: BlockDAG,
+    genesis_id: HashValue,
+    block_connector: ServiceRef<BlockConnectorService<TxPoolService>>,
+}
 
-        let mut tmp_inner = Inner::new(
-            net,
-            storage.clone(),
-            head_id,
-            txpool.clone(),
+impl TestChain {
+    fn inner<P>(&self, tx_provider: P) -> Inner<P, TxPoolService>
+    where
+        P: TemplateTxProvider,
+    {
+        Inner::new(
+            self.block_connector.clone(),
+            self.storage.clone(),
+            tx_provider,
             None,
-            miner_account.clone(),
+            AccountInfo::random(),
             None,
             None,
-            dag.clone(),
         )
-        .unwrap();
-
-        let block_template = tmp_inner.create_block_template().unwrap().template;
+        .unwrap()
+    }
 
-        let block = main
+    /// Seal the template and connect the block by the block connector, which handles the block
+    /// before the later template requests.
+    fn mine_block(&self, net: &ChainNetwork, template: BlockTemplate) -> Block {
+        let block = net
+            .genesis_config()
             .consensus()
-            .create_block(block_template, node_config.net().time_service().as_ref())
+            .create_block(template, net.time_service().as_ref())
             .unwrap();
-
-        let block_header = block.header().clone();
-        let executed_block = main.apply(block.clone()).unwrap();
-        tmp_inner.update_chain(executed_block).unwrap();
-        main_inner = Some(tmp_inner);
-
-        if i != (times - 1) {
-            head_id = block_header.id();
-        } else {
-            main_inner
-                .as_mut()
-                .unwrap()
-                .insert_uncle(block_header.clone());
-        }
+        self.block_connector
+            .notify(MinedBlock(Arc::new(block.clone())))
+            .unwrap();
+        block
     }
 
-    for i in 0..3 {
-        let mut new_main = BlockChain::new(
+    /// Create a block on the `parent_id` apart from the block connector, and connect it.
+    fn mine_branch_block(
+        &self,
+        net: &ChainNetwork,
+        parent_id: HashValue,
+        tips: Option<Vec<HashValue>>,
+    ) -> Block {
+        let branch = BlockChain::new(
             net.time_service(),
-            head_id,
-            storage.clone(),
+            parent_id,
+            self.storage.clone(),
             None,
-            dag.clone(),
+            self.dag.clone(),
         )
         .unwrap();
-
-        let block_template = if i == 0 {
-            let tmp = Inner::new(
-                net,
-                storage.clone(),
-                head_id,
-                txpool.clone(),
-                None,
-                miner_account.clone(),
+        let (template, _) = branch
+            .create_block_template(
+                *AccountInfo::random().address(),
                 None,
+                vec![],
+                vec![],
                 None,
-                dag.clone(),
+                tips,
             )
             .unwrap();
-
-            tmp.create_block_template().unwrap().template
-        } else {
-            main_inner
-                .as_ref()
-                .unwrap()
-                .create_block_template()
-                .unwrap()
-                .template
-        };
-
-        let block = new_main
+        let block = branch
             .consensus()
-            .create_block(block_template, node_config.net().time_service().as_ref())
+            .create_block(template, net.time_service().as_ref())
             .unwrap();
-
-        let executed_block = new_main.apply(block.clone()).unwrap();
-
-        head_id = block.id();
-        if i == 0 {
-            let block_header = block.header().clone();
-            assert_eq!(main_inner.as_ref().unwrap().uncles.len(), 1);
-            main_inner
-                .as_mut()
-                .unwrap()
-                .update_chain(executed_block)
-                .unwrap();
-            main_inner.as_mut().unwrap().insert_uncle(block_header);
-        } else if i == 1 {
-            assert_eq!(main_inner.as_ref().unwrap().uncles.len(), 2);
-            assert!(block.body.uncles.is_some());
-            assert_eq!(block.body.uncles.as_ref().unwrap().len(), 1);
-            main_inner
-                .as_mut()
-                .unwrap()
-                .update_chain(executed_block)
-                .unwrap();
-        } else if i == 2 {
-            assert_eq!(main_inner.as_ref().unwrap().uncles.len(), 2);
-            assert!(block.body.uncles.is_none());
-        }
+        self.block_connector
+            .notify(MinedBlock(Arc::new(block.clone())))
+            .unwrap();
+        block
     }
 }
 
-#[stest::test]
-fn test_do_uncles() {
-    let node_config = Arc::new(NodeConfig::random_for_test());
+/// Create the template out of the test's runtime, it blocks on the block connector.
+fn create_block_template<P>(
+    inner: Inner<P, TxPoolService>,
+) -> (Inner<P, TxPoolService>, BlockTemplate)
+where
+    P: TemplateTxProvider + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let template = inner.create_block_template().unwrap().template;
+        (inner, template)
+    })
+    .join()
+    .unwrap()
+}
+
+async fn launch_block_connector(node_config: Arc<NodeConfig>) -> TestChain {
+    let registry = RegistryService::launch();
+    registry.put_shared(node_config.clone()).await.unwrap();
     let (storage, _, genesis, dag) = StarcoinGenesis::init_storage_for_test(node_config.net())
         .expect("init storage by genesis fail.");
     let genesis_id = genesis.block().id();
-    let times = 2;
-
-    let miner_account = AccountInfo::random();
-    // main
-    let mut head_id = genesis_id;
-    let mut main_inner = None;
-
     let chain_header = storage
         .get_block_header_by_hash(genesis_id)
         .unwrap()
         .unwrap();
     let txpool = TxPoolService::new(node_config.clone(), storage.clone(), chain_header, None);
-
-    let net = node_config.net();
-    for _i in 0..times {
-        let mut main = BlockChain::new(
-            net.time_service(),
-            head_id,
-            storage.clone(),
-            None,
-            dag.clone(),
-        )
+    registry.put_shared(txpool).await.unwrap();
+    registry.put_shared(dag.clone()).await.unwrap();
+    registry.put_shared(storage.clone()).await.unwrap();
+    registry
+        .register_mocker(AccountService::mock().unwrap())
+        .await
         .unwrap();
-
-        let mut tmp_inner = Inner::new(
-            net,
-            storage.clone(),
-            head_id,
-            txpool.clone(),
-            None,
-            miner_account.clone(),
-            None,
-            None,
-            dag.clone(),
-        )
+    let block_connector = registry
+        .register::<BlockConnectorService<TxPoolService>>()
+        .await
         .unwrap();
-
-        let block_template = tmp_inner.create_block_template().unwrap().template;
-
-        let block = main
-            .consensus()
-            .create_block(block_template, node_config.net().time_service().as_ref())
-            .unwrap();
-        head_id = block.id();
-        let executed_block = main.apply(block.clone()).unwrap();
-        tmp_inner.update_chain(executed_block).unwrap();
-        main_inner = Some(tmp_inner);
+    TestChain {
+        registry,
+        storage,
+        dag,
+        genesis_id,
+        block_connector,
     }
+}
 
-    // branch
-    for _i in 0..times {
-        let mut branch = BlockChain::new(
-            net.time_service(),
-            genesis_id,
-            storage.clone(),
-            None,
-            dag.clone(),
-        )
-        .unwrap();
-        let inner = Inner::new(
-            net,
-            storage.clone(),
-            genesis_id,
-            txpool.clone(),
-            None,
-            miner_account.clone(),
-            None,
-            None,
-            dag.clone(),
-        )
-        .unwrap();
-
-        let block_template = inner.create_block_template().unwrap().template;
-        let uncle_block = branch
-            .consensus()
-            .create_block(block_template, node_config.net().time_service().as_ref())
-            .unwrap();
-        let uncle_block_header = uncle_block.header().clone();
-        branch.apply(uncle_block).unwrap();
+#[stest::test]
+async fn test_create_block_template() {
+    for net in [
+        ChainNetworkID::TEST,
+        ChainNetworkID::DEV,
+        ChainNetworkID::HALLEY,
+    ] {
+        let temp_path = temp_dir();
+        let opt = StarcoinOpt {
+            net: Some(net),
+            base_data_dir: Some(temp_path.path().to_path_buf()),
+            ..StarcoinOpt::default()
+        };
+        let node_config = Arc::new(NodeConfig::load_with_opt(&opt).unwrap());
+        let chain = launch_block_connector(node_config).await;
 
-        main_inner
-            .as_mut()
-            .unwrap()
-            .insert_uncle(uncle_block_header);
+        let (_, template) = create_block_template(chain.inner(EmptyProvider));
+        assert_eq!(template.parent_hash, chain.genesis_id);
+        assert_eq!(template.number, 1);
+        chain.registry.shutdown_system().await.unwrap();
     }
+}
 
-    // uncles
-    for i in 0..times {
-        let mut main = BlockChain::new(
-            net.time_service(),
-            head_id,
-            storage.clone(),
-            None,
-            dag.clone(),
-        )
-        .unwrap();
+#[stest::test(timeout = 120)]
+async fn test_new_head() {
+    let node_config = Arc::new(NodeConfig::random_for_test());
+    let chain = launch_block_connector(node_config.clone()).await;
+    let net = node_config.net();
 
-        let block_template = main_inner
-            .as_ref()
-            .unwrap()
-            .create_block_template()
-            .unwrap()
-            .template;
-        let block = main
-            .consensus()
-            .create_block(block_template, node_config.net().time_service().as_ref())
-            .unwrap();
-        if i == 0 {
-            assert_eq!(block.uncles().unwrap().len(), times);
-        } else {
-            assert!(block.uncles().is_none());
-        }
-        head_id = block.id();
-        let executed_block = main.apply(block.clone()).unwrap();
-        main_inner
-            .as_mut()
-            .unwrap()
-            .update_chain(executed_block)
-            .unwrap();
+    let mut inner = chain.inner(EmptyProvider);
+    let mut head_id = chain.genesis_id;
+    for i in 0..10 {
+        let (next_inner, template) = create_block_template(inner);
+        inner = next_inner;
+        // the template follows the head connected by the block connector.
+        assert_eq!(template.parent_hash, head_id);
+        assert_eq!(template.number, i + 1);
+        head_id = chain.mine_block(net, template).id();
     }
+    chain.registry.shutdown_system().await.unwrap();
 }
 
 #[stest::test(timeout = 120)]
-fn test_new_head() {
+async fn test_new_branch() {
     let node_config = Arc::new(NodeConfig::random_for_test());
-    let (storage, _, genesis, dag) = StarcoinGenesis::init_storage_for_test(node_config.net())
-        .expect("init storage by genesis fail.");
-    let genesis_id = genesis.block().id();
-    let times = 10;
-
-    let miner_account = AccountInfo::random();
-    let chain_header = storage
-        .get_block_header_by_hash(genesis_id)
-        .unwrap()
-        .unwrap();
-
-    let txpool = TxPoolService::new(node_config.clone(), storage.clone(), chain_header, None);
+    let chain = launch_block_connector(node_config.clone()).await;
+    let net = node_config.net();
+    let times = 5;
 
-    let mut main_inner = Inner::new(
-        node_config.net(),
-        storage,
-        genesis_id,
-        txpool,
-        None,
-        miner_account,
-        None,
-        None,
-        dag,
-    )
-    .unwrap();
+    let mut inner = chain.inner(EmptyProvider);
+    let mut main_head_id = chain.genesis_id;
+    for _i in 0..times {
+        let (next_inner, template) = create_block_template(inner);
+        inner = next_inner;
+        main_head_id = chain.mine_block(net, template).id();
+    }
 
-    for i in 0..times {
-        let block_template = main_inner.create_block_template().unwrap().template;
-        let block = main_inner
-            .chain
-            .consensus()
-            .create_block(block_template, node_config.net().time_service().as_ref())
-            .unwrap();
-        let executed_block = main_inner.chain.apply(block.clone()).unwrap();
-        if i % 2 == 0 {
-            main_inner.update_chain(executed_block).unwrap();
+    // the branch from the genesis becomes the main chain once it is longer.
+    let mut branch_head_id = chain.genesis_id;
+    for i in 0..(times * 2) {
+        branch_head_id = chain.mine_branch_block(net, branch_head_id, None).id();
+        let (next_inner, template) = create_block_template(inner);
+        inner = next_inner;
+        let branch_number = i + 1;
+        if branch_number > times {
+            assert_eq!(template.parent_hash, branch_head_id);
+            assert_eq!(template.number, branch_number + 1);
+        } else if branch_number < times {
+            // the head is kept until the branch is longer, a tie is not checked.
+            assert_eq!(template.parent_hash, main_head_id);
+            assert_eq!(template.number, times + 1);
         }
-        assert_eq!(main_inner.chain.current_header().number(), i + 1);
     }
+    chain.registry.shutdown_system().await.unwrap();
 }
 
 #[stest::test(timeout = 120)]
-fn test_new_branch() {
+async fn test_switch_main() {
     let node_config = Arc::new(NodeConfig::random_for_test());
-    let (storage, _, genesis, dag) = StarcoinGenesis::init_storage_for_test(node_config.net())
-        .expect("init storage by genesis fail.");
-    let genesis_id = genesis.block().id();
-    let times = 5;
+    let chain = launch_block_connector(node_config.clone()).await;
+    let net = node_config.net();
+    let times = 10;
 
-    let chain_header = storage
-        .get_block_header_by_hash(genesis_id)
-        .unwrap()
-        .unwrap();
+    let mut inner = chain.inner(EmptyProvider);
+    let mut main_blocks = vec![];
+    for _i in 0..times {
+        let (next_inner, template) = create_block_template(inner);
+        inner = next_inner;
+        main_blocks.push(chain.mine_block(net, template));
+    }
 
-    let txpool = TxPoolService::new(node_config.clone(), storage.clone(), chain_header, None);
+    // fork from the middle of the main chain, and switch to the longer branch.
+    let fork_number = times / 2;
+    let mut branch_head_id = main_blocks[fork_number as usize - 1].id();
+    for _i in 0..(times - fork_number + 1) {
+        branch_head_id = chain.mine_branch_block(net, branch_head_id, None).id();
+    }
+    let (next_inner, template) = create_block_template(inner);
+    inner = next_inner;
+    assert_eq!(template.parent_hash, branch_head_id);
+    assert_eq!(template.number, times + 2);
+
+    // keep mining on the new main chain.
+    let head_id = chain.mine_block(net, template).id();
+    let (_, template) = create_block_template(inner);
+    assert_eq!(template.parent_hash, head_id);
+    assert_eq!(template.number, times + 3);
+    chain.registry.shutdown_system().await.unwrap();
+}
 
-    let miner_account = AccountInfo::random();
-    // main
+#[stest::test(timeout = 120)]
+async fn test_do_uncles() {
+    let node_config = Arc::new(NodeConfig::random_for_dag_test());
+    let chain = launch_block_connector(node_config.clone()).await;
+    let net = node_config.net();
+    let times = 2;
 
-    let mut main_inner = Inner::new(
-        node_config.net(),
-        storage.clone(),
-        genesis_id,
-        txpool.clone(),
-        None,
-        miner_account.clone(),
-        None,
-        None,
-        dag.clone(),
-    )
-    .unwrap();
-    for _i in 0..times {
-        let block_template = main_inner.create_block_template().unwrap().template;
-        let block = main_inner
-            .chain
-            .consensus()
-            .create_block(block_template, node_config.net().time_service().as_ref())
-            .unwrap();
-        main_inner.chain.apply(block.clone()).unwrap();
+    let (inner, template) = create_block_template(chain.inner(EmptyProvider));
+    let main_block = chain.mine_block(net, template);
+    // the blocks on the same parent as the main block are merged as the uncles.
+    let uncles = (0..times)
+        .map(|_| {
+            chain
+                .mine_branch_block(net, chain.genesis_id, Some(vec![chain.genesis_id]))
+                .id()
+        })
+        .collect::<Vec<_>>();
+
+    let (inner, template) = create_block_template(inner);
+    assert_eq!(template.number, 2);
+    let mut merged = template
+        .body
+        .uncles
+        .clone()
+        .unwrap_or_default()
+        .iter()
+        .map(|uncle| uncle.id())
+        .collect::<Vec<_>>();
+    assert_eq!(merged.len(), times);
+    merged.push(template.parent_hash);
+    for id in uncles.iter().chain(std::iter::once(&main_block.id())) {
+        assert!(merged.contains(id));
     }
+    chain.mine_block(net, template);
+
+    // the uncles are merged, the next template has none.
+    let (_, template) = create_block_template(inner);
+    assert_eq!(template.number, 3);
+    assert!(template
+        .body
+        .uncles
+        .map(|uncles| uncles.is_empty())
+        .unwrap_or(true));
+    chain.registry.shutdown_system().await.unwrap();
+}
 
-    // branch
-    let mut new_head_id = genesis_id;
+#[stest::test(timeout = 120)]
+async fn test_create_block_template_by_adjust_time() {
+    let node_config = Arc::new(NodeConfig::random_for_test());
+    let chain = launch_block_connector(node_config.clone()).await;
     let net = node_config.net();
-    for i in 0..(times * 2) {
-        let mut branch = BlockChain::new(
-            net.time_service(),
-            new_head_id,
-            storage.clone(),
-            None,
-            dag.clone(),
-        )
-        .unwrap();
-        let inner = Inner::new(
-            net,
-            storage.clone(),
-            new_head_id,
-            txpool.clone(),
-            None,
-            miner_account.clone(),
-            None,
-            None,
-            dag.clone(),
-        )
-        .unwrap();
-        let block_template = inner.create_block_template().unwrap().template;
-        let new_block = branch
-            .consensus()
-            .create_block(block_template, node_config.net().time_service().as_ref())
-            .unwrap();
-        new_head_id = new_block.id();
-        let executed_block = branch.apply(new_block.clone()).unwrap();
 
-        if i > times {
-            main_inner.update_chain(executed_block).unwrap();
-            assert_eq!(main_inner.chain.current_header().number(), i + 1);
-        }
-    }
+    let (inner, template) = create_block_template(chain.inner(EmptyProvider));
+    let previous_block_time = template.timestamp;
+    chain.mine_block(net, template);
+
+    // adjust time to previous than parent.
+    let time_service = net.time_service();
+    let mock_time_service = time_service
+        .as_any()
+        .downcast_ref::<MockTimeService>()
+        .unwrap();
+    mock_time_service.set(previous_block_time - 1);
+    // then create block template, create_block_template() should adjust new block's timestamp.
+    let (_, template) = create_block_template(inner);
+    assert!(template.timestamp > previous_block_time);
+    let block = chain.mine_block(net, template);
+    assert!(block.header().timestamp() > previous_block_time);
+    chain.registry.shutdown_system().await.unwrap();
 }
 
 #[stest::test(timeout = 480)]
 async fn test_create_block_template_actor() {
     let node_config = Arc::new(NodeConfig::random_for_test());
-    let registry = RegistryService::launch();
-    registry.put_shared(node_config.clone()).await.unwrap();
+    let chain = launch_block_connector(node_config).await;
 
-    let (storage, _, genesis, dag) = StarcoinGenesis::init_storage_for_test(node_config.net())
-        .expect("init storage by genesis fail.");
-    let genesis_id = genesis.block().id();
-    let chain_header = storage
-        .get_block_header_by_hash(genesis_id)
-        .unwrap()
-        .unwrap();
-
-    //TODO mock txpool.
-    let txpool = TxPoolService::new(node_config.clone(), storage.clone(), chain_header, None);
-    registry.put_shared(txpool).await.unwrap();
-    registry.put_shared(dag).await.unwrap();
-    registry.put_shared(storage).await.unwrap();
-    registry
-        .register_mocker(AccountService::mock().unwrap())
+    let create_block_template_service = chain
+        .registry
+        .register::<BlockBuilderService>()
         .await
         .unwrap();
-
-    let create_block_template_service = registry.register::<BlockBuilderService>().await.unwrap();
     let response = create_block_template_service
         .send(BlockTemplateRequest)
         .await
         .unwrap()
         .unwrap();
     assert_eq!(response.template.number, 1);
+    chain.registry.shutdown_system().await.unwrap();
 }
 
-#[stest::test]
-fn test_create_block_template_by_adjust_time() -> Result<()> {
+#[stest::test(timeout = 480)]
+async fn test_create_block_template_with_txn_selection() {
     let node_config = Arc::new(NodeConfig::random_for_test());
+    let chain = launch_block_connector(node_config.clone()).await;
 
-    let (storage, _, genesis, dag) = StarcoinGenesis::init_storage_for_test(node_config.net())?;
-    let mut inner = Inner::new(
-        node_config.net(),
-        storage,
-        genesis.block().id(),
-        EmptyProvider,
-        None,
+    let net = node_config.net();
+    let expiration_timestamp_secs = net.time_service().now_secs() + 60 * 60;
+    let txns = (0..3)
+        .map(|seq_num| {
+            create_account_txn_sent_as_association(
+                &Account::new(),
+                seq_num,
+                1000,
+                expiration_timestamp_secs,
+                net,
+            )
+        })
+        .collect::<Vec<_>>();
+    let provider = Arc::new(MockTxProvider {
+        txns: txns.clone(),
+        ..Default::default()
+    });
+    let policy = Arc::new(RecordingPolicy::default());
+    let block_gas_limit = 100_000_000;
+    let inner = Inner::new(
+        chain.block_connector.clone(),
+        chain.storage.clone(),
+        provider.clone(),
+        Some(block_gas_limit),
         AccountInfo::random(),
         None,
         None,
-        dag,
-    )?;
-    let template = inner.create_block_template()?.template;
-    let previous_block_time = template.timestamp;
-    let block = node_config
-        .net()
-        .genesis_config()
-        .consensus()
-        .create_block(template, node_config.net().time_service().as_ref())?;
-    inner.chain.apply(block)?;
-    // adjust time to previous than parent.
-    let time_service = node_config.net().time_service();
-    let mock_time_service = time_service
-        .as_any()
-        .downcast_ref::<MockTimeService>()
-        .unwrap();
-    mock_time_service.set(previous_block_time - 1);
-    // then create block template, create_block_template() should adjust new block's timestamp.
-    let template = inner.create_block_template()?.template;
-    let block = node_config
-        .net()
-        .genesis_config()
-        .consensus()
-        .create_block(template, node_config.net().time_service().as_ref())?;
-    assert!(block.header().timestamp() > previous_block_time);
-    inner.chain.apply(block)?;
-    Ok(())
+    )
+    .unwrap()
+    .with_txn_selection_policy(policy.clone());
+
+    let (_, template) = create_block_template(inner);
+    assert_eq!(template.number, 1);
+    assert_eq!(template.body.transactions, txns);
+
+    // more candidates than the block's capacity are fetched for the selection.
+    let max_txns = (block_gas_limit / 200) * 2;
+    assert_eq!(
+        *provider.requests.lock(),
+        vec![max_txns * CANDIDATE_TXNS_MULTIPLE]
+    );
+    assert_eq!(*policy.selections.lock(), vec![(txns.len(), max_txns)]);
+    chain.registry.shutdown_system().await.unwrap();
+}
+
+fn transfer_txn(
+    sender: &Account,
+    seq_num: u64,
+    gas_price: u64,
+    expiration_timestamp_secs: u64,
+    net: &ChainNetwork,
+) -> SignedUserTransaction {
+    sender.create_signed_txn_with_args(
+        TransactionPayload::ScriptFunction(ScriptFunction::new(
+            ModuleId::new(
+                core_code_address(),
+                Identifier::new("TransferScripts").unwrap(),
+            ),
+            Identifier::new("peer_to_peer_v2").unwrap(),
+            vec![stc_type_tag()],
+            vec![
+                bcs_ext::to_bytes(&association_address()).unwrap(),
+                bcs_ext::to_bytes(&1u128).unwrap(),
+            ],
+        )),
+        seq_num,
+        DEFAULT_MAX_GAS_AMOUNT,
+        gas_price,
+        expiration_timestamp_secs,
+        net.chain_id(),
+    )
+}
+
+#[stest::test(timeout = 480)]
+async fn test_create_block_template_with_configured_policies() {
+    let node_config = Arc::new(NodeConfig::random_for_test());
+    let chain = launch_block_connector(node_config.clone()).await;
+    let net = node_config.net();
+    let expiration_timestamp_secs = net.time_service().now_secs() + 60 * 60;
+
+    let alice = Account::new();
+    let bob = Account::new();
+    let funding_txns = [&alice, &bob]
+        .iter()
+        .enumerate()
+        .map(|(seq_num, account)| {
+            create_account_txn_sent_as_association(
+                account,
+                seq_num as u64,
+                1_000_000_000_000,
+                expiration_timestamp_secs,
+                net,
+            )
+        })
+        .collect::<Vec<_>>();
+    let (_, template) = create_block_template(chain.inner(Arc::new(MockTxProvider {
+        txns: funding_txns.clone(),
+        ..Default::default()
+    })));
+    assert_eq!(template.body.transactions, funding_txns);
+    chain.mine_block(net, template);
+
+    // the candidates are in pool order, alice's txns are cheaper than bob's.
+    let a = (0..3)
+        .map(|seq_num| transfer_txn(&alice, seq_num, 1, expiration_timestamp_secs, net))
+        .collect::<Vec<_>>();
+    let b = (0..2)
+        .map(|seq_num| transfer_txn(&bob, seq_num, 10, expiration_timestamp_secs, net))
+        .collect::<Vec<_>>();
+    let provider = Arc::new(MockTxProvider {
+        txns: a.iter().chain(b.iter()).cloned().collect(),
+        ..Default::default()
+    });
+
+    let cases = vec![
+        (
+            MinerConfig {
+                txn_selection: Some(TxnSelectionStrategy::Pool),
+                ..MinerConfig::default()
+            },
+            vec![&a[0], &a[1], &a[2], &b[0], &b[1]],
+        ),
+        (
+            MinerConfig {
+                txn_selection: Some(TxnSelectionStrategy::GasPrice),
+                ..MinerConfig::default()
+            },
+            vec![&b[0], &b[1], &a[0], &a[1], &a[2]],
+        ),
+        (
+            MinerConfig {
+                min_gas_price: Some(5),
+                ..MinerConfig::default()
+            },
+            vec![&b[0], &b[1]],
+        ),
+        (
+            MinerConfig {
+                max_txns_per_sender: Some(1),
+                ..MinerConfig::default()
+            },
+            vec![&a[0], &b[0]],
+        ),
+        (
+            MinerConfig {
+                reserved_senders: Some(vec![*bob.address()]),
+                reserved_txns_percent: Some(100),
+                ..MinerConfig::default()
+            },
+            vec![&b[0], &b[1], &a[0], &a[1], &a[2]],
+        ),
+    ];
+    for (config, expected) in cases {
+        let inner = chain
+            .inner(provider.clone())
+            .with_txn_selection_policy(Arc::new(TxnSelectionPolicies::from_config(&config)));
+        let (_, template) = create_block_template(inner);
+        assert_eq!(template.number, 2);
+        assert_eq!(
+            template.body.transactions,
+            expected.into_iter().cloned().collect::<Vec<_>>(),
+            "unexpected txns selected by {:?}",
+            config
+        );
+    }
+    chain.registry.shutdown_system().await.unwrap();
 }
@@ -0,0 +1,162 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::create_block_template::{
+    GasPriceGreedyPolicy, MinGasPricePolicy, PoolOrderPolicy, ReservedSendersPolicy,
+    SenderFairnessPolicy, TxnSelectionPolicies, TxnSelectionPolicy,
+};
+use starcoin_config::{genesis_key_pair, MinerConfig, TxnSelectionStrategy};
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::genesis_config::ChainId;
+use starcoin_vm_types::transaction::{
+    RawUserTransaction, Script, SignedUserTransaction, TransactionPayload,
+};
+
+fn txn(sender: AccountAddress, sequence_number: u64, gas_price: u64) -> SignedUserTransaction {
+    let (private_key, public_key) = genesis_key_pair();
+    RawUserTransaction::new_with_default_gas_token(
+        sender,
+        sequence_number,
+        TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+        10000,
+        gas_price,
+        u64::MAX,
+        ChainId::test(),
+    )
+    .sign(&private_key, public_key)
+    .unwrap()
+    .into_inner()
+}
+
+fn summary(txns: &[SignedUserTransaction]) -> Vec<(AccountAddress, u64)> {
+    txns.iter()
+        .map(|txn| (txn.sender(), txn.sequence_number()))
+        .collect()
+}
+
+#[stest::test]
+fn test_pool_order_policy() {
+    let a = AccountAddress::random();
+    let txns = vec![txn(a, 0, 1), txn(a, 1, 2), txn(a, 2, 3)];
+    let selected = PoolOrderPolicy.select(txns.clone(), 2);
+    assert_eq!(summary(&selected), vec![(a, 0), (a, 1), (a, 2)]);
+    let selected = TxnSelectionPolicies::default().select(txns, 2);
+    assert_eq!(summary(&selected), vec![(a, 0), (a, 1)]);
+}
+
+#[stest::test]
+fn test_gas_price_greedy_policy() {
+    let a = AccountAddress::random();
+    let b = AccountAddress::random();
+    let c = AccountAddress::random();
+    let txns = vec![
+        txn(a, 0, 1),
+        txn(a, 1, 10),
+        txn(b, 0, 5),
+        txn(c, 0, 5),
+        txn(b, 1, 2),
+    ];
+    let selected = GasPriceGreedyPolicy.select(txns.clone(), 10);
+    // the sequence number order of a sender is kept, b wins c by the pool order.
+    assert_eq!(
+        summary(&selected),
+        vec![(b, 0), (c, 0), (b, 1), (a, 0), (a, 1)]
+    );
+    let selected = TxnSelectionPolicies::new(vec![Box::new(GasPriceGreedyPolicy)]).select(txns, 2);
+    assert_eq!(summary(&selected), vec![(b, 0), (c, 0)]);
+}
+
+#[stest::test]
+fn test_min_gas_price_policy() {
+    let a = AccountAddress::random();
+    let b = AccountAddress::random();
+    let txns = vec![txn(a, 0, 1), txn(a, 1, 10), txn(b, 0, 5), txn(b, 1, 3)];
+    let selected = MinGasPricePolicy::new(5).select(txns, 10);
+    // a's second txn can not be executed without the first one.
+    assert_eq!(summary(&selected), vec![(b, 0)]);
+}
+
+#[stest::test]
+fn test_sender_fairness_policy() {
+    let a = AccountAddress::random();
+    let b = AccountAddress::random();
+    let txns = vec![
+        txn(a, 0, 1),
+        txn(a, 1, 1),
+        txn(a, 2, 1),
+        txn(b, 0, 1),
+        txn(b, 1, 1),
+    ];
+    let selected = SenderFairnessPolicy::new(2).select(txns, 10);
+    assert_eq!(summary(&selected), vec![(a, 0), (a, 1), (b, 0), (b, 1)]);
+}
+
+#[stest::test]
+fn test_reserved_senders_policy() {
+    let a = AccountAddress::random();
+    let b = AccountAddress::random();
+    let txns = vec![
+        txn(a, 0, 1),
+        txn(a, 1, 1),
+        txn(a, 2, 1),
+        txn(b, 0, 1),
+        txn(b, 1, 1),
+    ];
+    // 50% of 4 txns are reserved for b.
+    let selected = ReservedSendersPolicy::new(vec![b], 50).select(txns, 4);
+    assert_eq!(
+        summary(&selected),
+        vec![(b, 0), (b, 1), (a, 0), (a, 1), (a, 2)]
+    );
+}
+
+#[stest::test]
+fn test_txn_selection_policies_from_config() {
+    let a = AccountAddress::random();
+    let b = AccountAddress::random();
+    let c = AccountAddress::random();
+    let txns = vec![
+        txn(a, 0, 10),
+        txn(a, 1, 10),
+        txn(a, 2, 10),
+        txn(b, 0, 1),
+        txn(c, 0, 5),
+        txn(c, 1, 20),
+    ];
+    let mut config = MinerConfig::default();
+    let selected = TxnSelectionPolicies::from_config(&config).select(txns.clone(), 10);
+    assert_eq!(summary(&selected), summary(&txns));
+
+    config.txn_selection = Some(TxnSelectionStrategy::GasPrice);
+    config.min_gas_price = Some(2);
+    config.max_txns_per_sender = Some(2);
+    config.reserved_senders = Some(vec![c]);
+    config.reserved_txns_percent = Some(25);
+    let selected = TxnSelectionPolicies::from_config(&config).select(txns, 4);
+    assert_eq!(summary(&selected), vec![(c, 0), (a, 0), (a, 1), (c, 1)]);
+}
+
+#[stest::test]
+fn test_txn_selection_policies_truncate_once() {
+    let a = AccountAddress::random();
+    let b = AccountAddress::random();
+    let txns = vec![
+        txn(a, 0, 1),
+        txn(a, 1, 1),
+        txn(a, 2, 1),
+        txn(b, 0, 1),
+        txn(b, 1, 1),
+    ];
+    let mut config = MinerConfig::default();
+    config.reserved_senders = Some(vec![b]);
+    config.reserved_txns_percent = Some(50);
+    // b's txns are after the first 2 candidates, but still get the reserved slot.
+    let selected = TxnSelectionPolicies::from_config(&config).select(txns.clone(), 2);
+    assert_eq!(summary(&selected), vec![(b, 0), (a, 0)]);
+
+    // the filtered out txns are replaced by the later candidates.
+    config.reserved_senders = None;
+    config.max_txns_per_sender = Some(1);
+    let selected = TxnSelectionPolicies::from_config(&config).select(txns, 2);
+    assert_eq!(summary(&selected), vec![(a, 0), (b, 0)]);
+}
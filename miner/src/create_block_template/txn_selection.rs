@@ -0,0 +1,252 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The policies to select the transactions of the block template from the pending transactions
+//! of the pool.
+//!
+//! Every policy must keep the sequence number order of the transactions of the same sender,
+//! and only drop the tail transactions of a sender, otherwise the remaining transactions
+//! can not be executed.
+//!
+//! The policies only filter and order the candidates, the selected transactions are truncated
+//! to the block's capacity once after all the policies are applied, so a filter never leaves
+//! the block short when there are enough candidates.
+
+use starcoin_config::{MinerConfig, TxnSelectionStrategy};
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::transaction::SignedUserTransaction;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+pub trait TxnSelectionPolicy: Send + Sync {
+    /// Filter and order the `candidates`, which are in pool order. `max` is the number of
+    /// transactions the block can hold, the caller truncates the result to it.
+    fn select(
+        &self,
+        candidates: Vec<SignedUserTransaction>,
+        max: u64,
+    ) -> Vec<SignedUserTransaction>;
+}
+
+/// Keep the order of the pool.
+#[derive(Clone, Debug, Default)]
+pub struct PoolOrderPolicy;
+
+impl TxnSelectionPolicy for PoolOrderPolicy {
+    fn select(
+        &self,
+        candidates: Vec<SignedUserTransaction>,
+        _max: u64,
+    ) -> Vec<SignedUserTransaction> {
+        candidates
+    }
+}
+
+/// Select the transaction with the highest gas price from the first pending transaction of every
+/// sender, the earlier one in the pool wins if the gas price is the same.
+#[derive(Clone, Debug, Default)]
+pub struct GasPriceGreedyPolicy;
+
+impl TxnSelectionPolicy for GasPriceGreedyPolicy {
+    fn select(
+        &self,
+        candidates: Vec<SignedUserTransaction>,
+        _max: u64,
+    ) -> Vec<SignedUserTransaction> {
+        let mut senders: HashMap<AccountAddress, VecDeque<(usize, SignedUserTransaction)>> =
+            HashMap::new();
+        for (index, txn) in candidates.into_iter().enumerate() {
+            senders
+                .entry(txn.sender())
+                .or_default()
+                .push_back((index, txn));
+        }
+        let mut heap = BinaryHeap::new();
+        for (sender, txns) in &senders {
+            if let Some((index, txn)) = txns.front() {
+                heap.push((txn.gas_unit_price(), Reverse(*index), *sender));
+            }
+        }
+        let mut selected = vec![];
+        while let Some((_, _, sender)) = heap.pop() {
+            let txns = senders.get_mut(&sender).expect("sender must exist");
+            if let Some((_, txn)) = txns.pop_front() {
+                selected.push(txn);
+            }
+            if let Some((index, txn)) = txns.front() {
+                heap.push((txn.gas_unit_price(), Reverse(*index), sender));
+            }
+        }
+        selected
+    }
+}
+
+/// Drop the transactions whose gas price is lower than `min_gas_price`, and the later
+/// transactions of the same sender.
+#[derive(Clone, Debug)]
+pub struct MinGasPricePolicy {
+    min_gas_price: u64,
+}
+
+impl MinGasPricePolicy {
+    pub fn new(min_gas_price: u64) -> Self {
+        Self { min_gas_price }
+    }
+}
+
+impl TxnSelectionPolicy for MinGasPricePolicy {
+    fn select(
+        &self,
+        candidates: Vec<SignedUserTransaction>,
+        _max: u64,
+    ) -> Vec<SignedUserTransaction> {
+        let mut dropped_senders = HashSet::new();
+        candidates
+            .into_iter()
+            .filter(|txn| {
+                if dropped_senders.contains(&txn.sender()) {
+                    return false;
+                }
+                if txn.gas_unit_price() < self.min_gas_price {
+                    dropped_senders.insert(txn.sender());
+                    return false;
+                }
+                true
+            })
+            .collect()
+    }
+}
+
+/// Select at most `max_txns_per_sender` transactions of every sender, so one sender can not
+/// fill the whole block.
+#[derive(Clone, Debug)]
+pub struct SenderFairnessPolicy {
+    max_txns_per_sender: u64,
+}
+
+impl SenderFairnessPolicy {
+    pub fn new(max_txns_per_sender: u64) -> Self {
+        Self {
+            max_txns_per_sender,
+        }
+    }
+}
+
+impl TxnSelectionPolicy for SenderFairnessPolicy {
+    fn select(
+        &self,
+        candidates: Vec<SignedUserTransaction>,
+        _max: u64,
+    ) -> Vec<SignedUserTransaction> {
+        let mut counts: HashMap<AccountAddress, u64> = HashMap::new();
+        candidates
+            .into_iter()
+            .filter(|txn| {
+                let count = counts.entry(txn.sender()).or_default();
+                if *count >= self.max_txns_per_sender {
+                    return false;
+                }
+                *count = count.saturating_add(1);
+                true
+            })
+            .collect()
+    }
+}
+
+/// Reserve `reserved_txns_percent` of the block for the transactions of the `senders`, these
+/// transactions are moved to the front, the others keep their order.
+#[derive(Clone, Debug)]
+pub struct ReservedSendersPolicy {
+    senders: HashSet<AccountAddress>,
+    reserved_txns_percent: u8,
+}
+
+impl ReservedSendersPolicy {
+    pub fn new(senders: Vec<AccountAddress>, reserved_txns_percent: u8) -> Self {
+        Self {
+            senders: senders.into_iter().collect(),
+            reserved_txns_percent,
+        }
+    }
+
+    fn reserved_txns(&self, max: u64) -> u64 {
+        (u128::from(max) * u128::from(self.reserved_txns_percent.min(100)) / 100) as u64
+    }
+}
+
+impl TxnSelectionPolicy for ReservedSendersPolicy {
+    fn select(
+        &self,
+        candidates: Vec<SignedUserTransaction>,
+        max: u64,
+    ) -> Vec<SignedUserTransaction> {
+        let reserved_txns = self.reserved_txns(max);
+        let mut reserved = vec![];
+        let mut others = vec![];
+        for txn in candidates {
+            if (reserved.len() as u64) < reserved_txns && self.senders.contains(&txn.sender()) {
+                reserved.push(txn);
+            } else {
+                others.push(txn);
+            }
+        }
+        reserved.extend(others);
+        reserved
+    }
+}
+
+/// Apply the policies one by one, then truncate the result to `max`.
+pub struct TxnSelectionPolicies {
+    policies: Vec<Box<dyn TxnSelectionPolicy>>,
+}
+
+impl TxnSelectionPolicies {
+    pub fn new(policies: Vec<Box<dyn TxnSelectionPolicy>>) -> Self {
+        Self { policies }
+    }
+
+    /// The filters are applied before the order, and the reserved senders are moved to the front
+    /// at last, so their transactions are still packed when the block is full.
+    pub fn from_config(config: &MinerConfig) -> Self {
+        let mut policies: Vec<Box<dyn TxnSelectionPolicy>> = vec![];
+        if config.min_gas_price() > 0 {
+            policies.push(Box::new(MinGasPricePolicy::new(config.min_gas_price())));
+        }
+        if let Some(max_txns_per_sender) = config.max_txns_per_sender {
+            policies.push(Box::new(SenderFairnessPolicy::new(max_txns_per_sender)));
+        }
+        match config.txn_selection() {
+            TxnSelectionStrategy::Pool => policies.push(Box::new(PoolOrderPolicy)),
+            TxnSelectionStrategy::GasPrice => policies.push(Box::new(GasPriceGreedyPolicy)),
+        }
+        let reserved_senders = config.reserved_senders();
+        if !reserved_senders.is_empty() {
+            policies.push(Box::new(ReservedSendersPolicy::new(
+                reserved_senders,
+                config.reserved_txns_percent(),
+            )));
+        }
+        Self::new(policies)
+    }
+}
+
+impl Default for TxnSelectionPolicies {
+    fn default() -> Self {
+        Self::new(vec![Box::new(PoolOrderPolicy)])
+    }
+}
+
+impl TxnSelectionPolicy for TxnSelectionPolicies {
+    fn select(
+        &self,
+        candidates: Vec<SignedUserTransaction>,
+        max: u64,
+    ) -> Vec<SignedUserTransaction> {
+        let mut selected = self
+            .policies
+            .iter()
+            .fold(candidates, |txns, policy| policy.select(txns, max));
+        selected.truncate(usize::try_from(max).unwrap_or(usize::MAX));
+        selected
+    }
+}
@@ -21,7 +21,11 @@ pub mod generate_block_event_pacemaker;
 mod metrics;
 pub mod task;
 
-pub use create_block_template::{BlockBuilderService, BlockTemplateRequest};
+pub use create_block_template::{
+    BlockBuilderService, BlockTemplateRequest, GasPriceGreedyPolicy, MinGasPricePolicy,
+    PoolOrderPolicy, ReservedSendersPolicy, SenderFairnessPolicy, TxnSelectionPolicies,
+    TxnSelectionPolicy,
+};
 use starcoin_crypto::HashValue;
 pub use starcoin_types::block::BlockHeaderExtra;
 pub use starcoin_types::system_events::{GenerateBlockEvent, MinedBlock, MintBlockEvent};
@@ -271,6 +271,6 @@ pub fn run_node_by_opt(
 /// Run node in a new Thread, and return a NodeHandle.
 pub fn run_node(config: Arc<NodeConfig>) -> Result<NodeHandle, NodeStartError> {
     crash_handler::setup_panic_handler();
-    let logger_handle = starcoin_logger::init();
+    let logger_handle = starcoin_logger::init_with_default_level("info", config.logger.pattern());
     NodeService::launch(config, logger_handle)
 }
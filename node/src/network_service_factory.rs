@@ -20,10 +20,12 @@ impl ServiceFactory<NetworkActorService> for NetworkServiceFactory {
         let config = ctx.get_shared::<Arc<NodeConfig>>()?;
         let storage = ctx.get_shared::<Arc<Storage>>()?;
         let rpc_info = starcoin_network_rpc_api::G_RPC_INFO.clone();
-        let txpool_service = ctx.service_ref::<TxPoolActorService>()?.clone();
-        let block_relayer = ctx.service_ref::<BlockRelayer>()?.clone();
-        let network_rpc_service = ctx.service_ref::<NetworkRpcService>()?.clone();
-        let announcement_service = ctx.service_ref::<AnnouncementService>()?.clone();
+        // the services are not registered by the light node, it neither handles the
+        // transactions and blocks of peers nor serves the network rpc.
+        let txpool_service = ctx.service_ref_opt::<TxPoolActorService>()?.cloned();
+        let block_relayer = ctx.service_ref_opt::<BlockRelayer>()?.cloned();
+        let network_rpc_service = ctx.service_ref_opt::<NetworkRpcService>()?.cloned();
+        let announcement_service = ctx.service_ref_opt::<AnnouncementService>()?.cloned();
         let peer_message_handle =
             NodePeerMessageHandler::new(txpool_service, block_relayer, announcement_service);
 
@@ -33,7 +35,7 @@ impl ServiceFactory<NetworkActorService> for NetworkServiceFactory {
         let actor_service = NetworkActorService::new(
             config,
             chain_info,
-            Some((rpc_info, network_rpc_service)),
+            network_rpc_service.map(|service| (rpc_info, service)),
             peer_message_handle,
         )?;
         let network_service = actor_service.network_service();
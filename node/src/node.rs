@@ -3,7 +3,6 @@
 
 use crate::metrics::{MetricsPushActorService, MetricsServerActorService};
 use crate::network_service_factory::NetworkServiceFactory;
-use crate::peer_message_handler::NodePeerMessageHandler;
 use crate::rpc_service_factory::RpcServiceFactory;
 use crate::NodeHandle;
 use actix::prelude::*;
@@ -15,9 +14,9 @@ use network_api::{PeerProvider, PeerSelector, PeerStrategy};
 use starcoin_account_service::{AccountEventService, AccountService, AccountStorage};
 use starcoin_block_relayer::BlockRelayer;
 use starcoin_chain_notify::ChainNotifyHandlerService;
-use starcoin_chain_service::ChainReaderService;
+use starcoin_chain_service::{ChainReaderService, DagPruneService};
 use starcoin_config::genesis_config::G_BASE_MAX_UNCLES_PER_BLOCK;
-use starcoin_config::NodeConfig;
+use starcoin_config::{NodeConfig, TraceExporterType};
 use starcoin_genesis::{Genesis, GenesisError};
 use starcoin_logger::prelude::*;
 use starcoin_logger::structured_log::init_slog_logger;
@@ -37,7 +36,7 @@ use starcoin_service_registry::{
     ActorService, EventHandler, RegistryAsyncService, RegistryService, ServiceContext,
     ServiceFactory, ServiceHandler, ServiceRef,
 };
-use starcoin_state_service::ChainStateService;
+use starcoin_state_service::{ChainStateService, StatePruneService};
 use starcoin_storage::block_info::BlockInfoStore;
 use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::db_storage::DBStorage;
@@ -52,6 +51,7 @@ use starcoin_sync::block_connector::{BlockConnectorService, ExecuteRequest, Rese
 use starcoin_sync::sync::SyncService;
 use starcoin_sync::txn_sync::TxnSyncService;
 use starcoin_sync::verified_rpc_client::VerifiedRpcClient;
+use starcoin_tracing::TraceExporter;
 use starcoin_txpool::{TxPoolActorService, TxPoolService};
 use starcoin_types::blockhash::KType;
 use starcoin_types::system_events::{SystemShutdown, SystemStarted};
@@ -281,6 +281,26 @@ impl NodeService {
 
         registry.put_shared(config.clone()).await?;
         registry.put_shared(logger_handle).await?;
+        if config.tracing.enable_tracing() {
+            let exporter = match config.tracing.exporter() {
+                TraceExporterType::Otlp => TraceExporter::Otlp {
+                    endpoint: config.tracing.otlp_endpoint(),
+                },
+                TraceExporterType::File => TraceExporter::File {
+                    path: config.tracing.file(),
+                },
+            };
+            info!("Enable tracing, export spans to {:?}", exporter);
+            match starcoin_tracing::init(
+                "starcoin",
+                exporter,
+                config.tracing.sample_ratio(),
+                config.tracing.honor_remote_sampled(),
+            ) {
+                Ok(tracing_handle) => registry.put_shared(Arc::new(tracing_handle)).await?,
+                Err(e) => warn!("Init tracing error: {}", e),
+            }
+        }
         let vm_metrics = config
             .metrics
             .registry()
@@ -312,7 +332,13 @@ impl NodeService {
         // disable dragon hard fork
         //storage_instance.dragon_hard_fork(config.clone())?;
         let upgrade_time = SystemTime::now().duration_since(start_time)?;
-        let storage = Arc::new(Storage::new(storage_instance)?);
+        let mut storage = Storage::new(storage_instance)?;
+        if config.storage.state_prune_retain_blocks().is_some() && !storage.is_state_prune_enabled()
+        {
+            info!("Enable state prune, the state written before is kept.");
+            storage.enable_state_prune()?;
+        }
+        let storage = Arc::new(storage);
         registry.put_shared(storage.clone()).await?;
         let dag_storage = starcoin_dag::consensusdb::prelude::FlexiDagStorage::create_from_path(
             config.storage.dag_dir(),
@@ -337,6 +363,9 @@ impl NodeService {
         let node_service = registry.register::<Self>().await?;
 
         registry.register::<ChainStateService>().await?;
+        if config.storage.state_prune_retain_blocks().is_some() {
+            registry.register::<StatePruneService>().await?;
+        }
 
         let vault_config = &config.vault;
         let account_storage =
@@ -348,27 +377,35 @@ impl NodeService {
         registry.register::<AccountService>().await?;
         registry.register::<AccountEventService>().await?;
 
-        let txpool_service = registry.register::<TxPoolActorService>().await?;
-
-        //wait TxPoolService put shared..
-        Delay::new(Duration::from_millis(200)).await;
-        // TxPoolActorService auto put shared TxPoolService,
-
-        registry.register::<ChainReaderService>().await?;
+        // The light node does not keep the chain and the state, so it runs neither the txpool,
+        // the chain reader, the block relayer nor the miner, it only syncs the light headers.
+        let light_mode = config.sync.sync_mode().is_light();
+        if !light_mode {
+            registry.register::<TxPoolActorService>().await?;
+            //wait TxPoolService put shared..
+            Delay::new(Duration::from_millis(200)).await;
+            // TxPoolActorService auto put shared TxPoolService,
+
+            registry.register::<ChainReaderService>().await?;
+            if config.storage.enable_dag_pruning() {
+                registry.register::<DagPruneService>().await?;
+            }
+        }
 
         registry.register::<ChainNotifyHandlerService>().await?;
 
-        registry
-            .register::<BlockConnectorService<TxPoolService>>()
-            .await?;
+        if !light_mode {
+            registry
+                .register::<BlockConnectorService<TxPoolService>>()
+                .await?;
+        }
         registry.register::<SyncService>().await?;
 
-        let block_relayer = registry.register::<BlockRelayer>().await?;
-
-        registry.register::<NetworkRpcService>().await?;
-        let announcement_service = registry.register::<AnnouncementService>().await?;
-
-        NodePeerMessageHandler::new(txpool_service, block_relayer, announcement_service);
+        if !light_mode {
+            registry.register::<BlockRelayer>().await?;
+            registry.register::<NetworkRpcService>().await?;
+            registry.register::<AnnouncementService>().await?;
+        }
 
         registry
             .register_by_factory::<NetworkActorService, NetworkServiceFactory>()
@@ -376,48 +413,55 @@ impl NodeService {
         //wait Network service init
         Delay::new(Duration::from_millis(200)).await;
 
-        registry.register::<TxnSyncService>().await?;
-
         let peer_id = config.network.self_peer_id();
 
         info!("Self peer_id is: {}", peer_id.to_base58());
         info!("Self address is: {}", config.network.self_address());
 
-        registry.register::<BlockBuilderService>().await?;
-        let miner_service = registry.register::<MinerService>().await?;
+        if light_mode {
+            info!("Light sync mode, the txpool, block builder and miner services are not started.");
+        } else {
+            registry.register::<TxnSyncService>().await?;
+            registry.register::<BlockBuilderService>().await?;
+            let miner_service = registry.register::<MinerService>().await?;
+
+            if let Some(miner_client_config) = config.miner.miner_client_config() {
+                registry.put_shared(miner_client_config).await?;
+                let job_client =
+                    JobBusClient::new(miner_service, bus.clone(), config.net().time_service());
+                registry.put_shared(job_client).await?;
+                registry
+                    .register::<MinerClientService<JobBusClient>>()
+                    .await?;
+            } else {
+                info!("Config.miner.enable_miner_client is false, No in process MinerClient.");
+            }
 
-        if let Some(miner_client_config) = config.miner.miner_client_config() {
-            registry.put_shared(miner_client_config).await?;
-            let job_client =
-                JobBusClient::new(miner_service, bus.clone(), config.net().time_service());
-            registry.put_shared(job_client).await?;
             registry
-                .register::<MinerClientService<JobBusClient>>()
+                .register_by_factory::<Stratum, StratumFactory>()
                 .await?;
-        } else {
-            info!("Config.miner.enable_miner_client is false, No in process MinerClient.");
-        }
 
-        registry
-            .register_by_factory::<Stratum, StratumFactory>()
-            .await?;
-
-        registry.register::<GenerateBlockEventPacemaker>().await?;
+            registry.register::<GenerateBlockEventPacemaker>().await?;
+        }
 
         // wait for service init.
         Delay::new(Duration::from_millis(1000)).await;
 
         bus.broadcast(SystemStarted)?;
 
-        registry
-            .register_by_factory::<PubSubService, PubSubServiceFactory>()
-            .await?;
+        if !light_mode {
+            registry
+                .register_by_factory::<PubSubService, PubSubServiceFactory>()
+                .await?;
+        }
         registry
             .register_by_factory::<RpcService, RpcServiceFactory>()
             .await?;
-        registry
-            .register_by_factory::<StratumService, StratumServiceFactory>()
-            .await?;
+        if !light_mode {
+            registry
+                .register_by_factory::<StratumService, StratumServiceFactory>()
+                .await?;
+        }
 
         // start metrics server
         if !config.metrics.disable_metrics() {
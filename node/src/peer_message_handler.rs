@@ -13,17 +13,18 @@ use starcoin_sync::announcement::AnnouncementService;
 use starcoin_txpool::TxPoolActorService;
 use std::sync::mpsc::TrySendError;
 
+/// The services are none on the light node, the messages of peers to them are dropped.
 pub struct NodePeerMessageHandler {
-    txpool_service: ServiceRef<TxPoolActorService>,
-    block_relayer: ServiceRef<BlockRelayer>,
-    announcement_service: ServiceRef<AnnouncementService>,
+    txpool_service: Option<ServiceRef<TxPoolActorService>>,
+    block_relayer: Option<ServiceRef<BlockRelayer>>,
+    announcement_service: Option<ServiceRef<AnnouncementService>>,
 }
 
 impl NodePeerMessageHandler {
     pub fn new(
-        txpool_service: ServiceRef<TxPoolActorService>,
-        block_relayer: ServiceRef<BlockRelayer>,
-        announcement_service: ServiceRef<AnnouncementService>,
+        txpool_service: Option<ServiceRef<TxPoolActorService>>,
+        block_relayer: Option<ServiceRef<BlockRelayer>>,
+        announcement_service: Option<ServiceRef<AnnouncementService>>,
     ) -> Self {
         Self {
             txpool_service,
@@ -37,8 +38,14 @@ impl PeerMessageHandler for NodePeerMessageHandler {
     fn handle_message(&self, peer_message: PeerMessage) {
         match peer_message.notification {
             NotificationMessage::Transactions(message) => {
-                if let Err(e) = self
-                    .txpool_service
+                let txpool_service = match self.txpool_service.as_ref() {
+                    Some(service) => service,
+                    None => {
+                        debug!("Drop PeerTransaction, the service is not started.");
+                        return;
+                    }
+                };
+                if let Err(e) = txpool_service
                     .notify(PeerTransactionsMessage::new(peer_message.peer_id, message))
                 {
                     match e {
@@ -52,8 +59,14 @@ impl PeerMessageHandler for NodePeerMessageHandler {
                 }
             }
             NotificationMessage::CompactBlock(message) => {
-                if let Err(e) = self
-                    .block_relayer
+                let block_relayer = match self.block_relayer.as_ref() {
+                    Some(service) => service,
+                    None => {
+                        debug!("Drop PeerCmpctBlock, the service is not started.");
+                        return;
+                    }
+                };
+                if let Err(e) = block_relayer
                     .notify(PeerCompactBlockMessage::new(peer_message.peer_id, *message))
                 {
                     match e {
@@ -67,8 +80,14 @@ impl PeerMessageHandler for NodePeerMessageHandler {
                 }
             }
             NotificationMessage::Announcement(message) => {
-                if let Err(e) = self
-                    .announcement_service
+                let announcement_service = match self.announcement_service.as_ref() {
+                    Some(service) => service,
+                    None => {
+                        debug!("Drop PeerAnnouncementMessage, the service is not started.");
+                        return;
+                    }
+                };
+                if let Err(e) = announcement_service
                     .notify(PeerAnnouncementMessage::new(peer_message.peer_id, message))
                 {
                     match e {
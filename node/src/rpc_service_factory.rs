@@ -13,14 +13,15 @@ use starcoin_logger::LoggerHandle;
 use starcoin_miner::MinerService;
 use starcoin_network::NetworkServiceRef;
 use starcoin_rpc_server::module::{
-    AccountRpcImpl, ChainRpcImpl, ContractRpcImpl, DebugRpcImpl, MinerRpcImpl,
-    NetworkManagerRpcImpl, NodeManagerRpcImpl, NodeRpcImpl, PubSubImpl, PubSubService,
-    StateRpcImpl, SyncManagerRpcImpl, TxPoolRpcImpl,
+    AccountRpcImpl, ChainRpcImpl, ContractRpcImpl, DebugRpcImpl, FeeHistoryGasPriceOracle,
+    MinerRpcImpl, NetworkManagerRpcImpl, NodeManagerRpcImpl, NodeRpcImpl, PubSubImpl,
+    PubSubService, StateRpcImpl, SyncManagerRpcImpl, TxPoolRpcImpl,
 };
 use starcoin_rpc_server::service::RpcService;
 use starcoin_service_registry::{ServiceContext, ServiceFactory};
 use starcoin_state_service::ChainStateService;
 use starcoin_storage::Storage;
+use starcoin_sync::light_state_service::LightChainStateService;
 use starcoin_sync::sync::SyncService;
 use starcoin_txpool::TxPoolService;
 use std::sync::Arc;
@@ -43,8 +44,12 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
             .service_ref_opt::<SyncService>()?
             .map(|service_ref| SyncManagerRpcImpl::new(service_ref.clone()));
         let network_manager_api = NetworkManagerRpcImpl::new(network_service);
+        // The light node has no local chain and state, the apis depending on them are not
+        // registered, and the state api reads the state from full peers with proofs.
+        let light_mode = config.sync.sync_mode().is_light();
         let chain_api = ctx
             .service_ref_opt::<ChainReaderService>()?
+            .filter(|_| !light_mode)
             .map(|service_ref| {
                 ChainRpcImpl::new(
                     config.clone(),
@@ -53,46 +58,91 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
                     service_ref.clone(),
                 )
             });
-        let txpool_service = ctx.get_shared::<TxPoolService>()?;
-        let txpool_api = Some(TxPoolRpcImpl::new(txpool_service.clone()));
+        // the txpool is not started in the light sync mode.
+        let txpool_service = ctx.get_shared_opt::<TxPoolService>()?;
+        let chain_service = ctx.service_ref_opt::<ChainReaderService>()?.cloned();
+        let txpool_api = txpool_service.clone().map(|txpool_service| {
+            let txpool_rpc = TxPoolRpcImpl::new(txpool_service);
+            match chain_service.clone() {
+                Some(service_ref) => txpool_rpc.with_gas_price_oracle(Arc::new(
+                    FeeHistoryGasPriceOracle::new(service_ref, config.tx_pool.min_gas_price()),
+                )),
+                None => txpool_rpc,
+            }
+        });
 
         let state_api = ctx
             .service_ref_opt::<ChainStateService>()?
             .map(|service_ref| StateRpcImpl::new(service_ref.clone(), storage.clone()));
         let chain_state_service = ctx.service_ref::<ChainStateService>()?.clone();
         let account_service = ctx.service_ref_opt::<AccountService>()?.cloned();
-        let account_api = account_service.clone().map(|service_ref| {
-            AccountRpcImpl::new(
-                config.clone(),
-                service_ref,
-                txpool_service.clone(),
-                chain_state_service.clone(),
-            )
-        });
-        let pubsub_service = ctx.service_ref::<PubSubService>()?.clone();
-        let pubsub_api = Some(PubSubImpl::new(pubsub_service));
-        let debug_api = Some(DebugRpcImpl::new(
-            config.clone(),
-            log_handler,
-            ctx.bus_ref().clone(),
-        ));
+        let account_api = account_service.clone().zip(txpool_service.clone()).map(
+            |(service_ref, txpool_service)| {
+                AccountRpcImpl::new(
+                    config.clone(),
+                    service_ref,
+                    txpool_service,
+                    chain_state_service.clone(),
+                )
+            },
+        );
+        let pubsub_api = ctx
+            .service_ref_opt::<PubSubService>()?
+            .map(|service_ref| PubSubImpl::new(service_ref.clone()));
+        let vm_metrics = ctx.get_shared_opt::<VMMetrics>()?;
+        let mut debug_rpc = DebugRpcImpl::new(config.clone(), log_handler, ctx.bus_ref().clone());
+        if let Some(service_ref) = ctx
+            .service_ref_opt::<ChainReaderService>()?
+            .filter(|_| !light_mode)
+        {
+            debug_rpc = debug_rpc.with_transaction_tracer(
+                service_ref.clone(),
+                PlaygroudService::new(storage.clone(), vm_metrics.clone()),
+            );
+        }
+        let debug_api = Some(debug_rpc);
         let miner_api = ctx
             .service_ref_opt::<MinerService>()?
             .map(|service_ref| MinerRpcImpl::new(service_ref.clone()));
 
-        let contract_api = {
-            let vm_metrics = ctx.get_shared_opt::<VMMetrics>()?;
-            let dev_playground = PlaygroudService::new(storage.clone(), vm_metrics);
-            ContractRpcImpl::new(
-                config.clone(),
-                account_service,
-                txpool_service,
-                chain_state_service,
-                dev_playground,
-                storage,
-            )
-        };
+        let contract_api = txpool_service
+            .filter(|_| !light_mode)
+            .map(|txpool_service| {
+                let dev_playground = PlaygroudService::new(storage.clone(), vm_metrics);
+                let contract_rpc = ContractRpcImpl::new(
+                    config.clone(),
+                    account_service,
+                    txpool_service,
+                    chain_state_service,
+                    dev_playground,
+                    storage.clone(),
+                );
+                match chain_service {
+                    Some(service_ref) => contract_rpc.with_chain(service_ref),
+                    None => contract_rpc,
+                }
+            });
 
+        if light_mode {
+            let network_service = ctx.get_shared::<NetworkServiceRef>()?;
+            let state_service =
+                LightChainStateService::new(config.clone(), storage.clone(), network_service);
+            return Ok(RpcService::new_with_api(
+                config,
+                node_api,
+                node_manager_api,
+                sync_manager_api,
+                Some(network_manager_api),
+                chain_api,
+                txpool_api,
+                account_api,
+                Some(StateRpcImpl::new(state_service, storage)),
+                pubsub_api,
+                debug_api,
+                miner_api,
+                contract_api,
+            ));
+        }
         Ok(RpcService::new_with_api(
             config,
             node_api,
@@ -106,7 +156,7 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
             pubsub_api,
             debug_api,
             miner_api,
-            Some(contract_api),
+            contract_api,
         ))
     }
 }
@@ -63,6 +63,15 @@ pub trait AccountApi {
         password: String,
     ) -> FutureResult<AccountInfo>;
 
+    /// Import the BIP39 mnemonic as the hd wallet seed, and create the first account derived from it.
+    #[rpc(name = "account.import_mnemonic")]
+    fn import_mnemonic(&self, mnemonic: String, password: String) -> FutureResult<AccountInfo>;
+
+    /// Derive the account with `index` from the hd wallet seed, or with the next index if `index` is None,
+    /// `password` is the password of the hd wallet seed.
+    #[rpc(name = "account.derive")]
+    fn derive(&self, password: String, index: Option<u32>) -> FutureResult<AccountInfo>;
+
     /// Import a readonly account with public key.
     #[rpc(name = "account.import_readonly")]
     fn import_readonly(
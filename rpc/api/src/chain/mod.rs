@@ -4,19 +4,22 @@
 pub use self::gen_client::Client as ChainClient;
 use crate::types::pubsub::EventFilter;
 use crate::types::{
-    BlockHeaderView, BlockInfoView, BlockView, ChainId, ChainInfoView, StrView,
-    TransactionEventResponse, TransactionInfoView, TransactionInfoWithProofView, TransactionView,
+    AccumulatorConsistencyProofView, AccumulatorRangeProofView, BlockHeaderView, BlockInfoView,
+    BlockView, ChainId, ChainInfoView, FeeHistoryView, StrView, TransactionEventResponse,
+    TransactionInfoView, TransactionInfoWithProofView, TransactionView,
 };
 use crate::FutureResult;
 use jsonrpc_core::Result;
 use openrpc_derive::openrpc;
 use schemars::{self, JsonSchema};
 use serde::{Deserialize, Serialize};
+use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_crypto::HashValue;
 use starcoin_dag::consensusdb::consenses_state::DagStateView;
 use starcoin_dag::types::ghostdata::GhostdagData;
 use starcoin_types::block::BlockNumber;
 use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_address::AccountAddress;
 
 #[openrpc]
 pub trait ChainApi {
@@ -103,6 +106,28 @@ pub trait ChainApi {
         max_size: u64,
     ) -> FutureResult<Vec<TransactionInfoView>>;
 
+    /// Get the transaction infos of the transactions sent by or affecting `address` in the main chain.
+    /// `start_index` is the position in the transaction list of the address, if absent, start from the latest when `reverse` is true, otherwise from the first.
+    /// Require the node to enable the address txn index.
+    #[rpc(name = "chain.get_transactions_by_address")]
+    fn get_transactions_by_address(
+        &self,
+        address: AccountAddress,
+        start_index: Option<u64>,
+        reverse: bool,
+        max_size: u64,
+    ) -> FutureResult<Vec<TransactionInfoView>>;
+
+    /// Get the fee history of `block_count` main chain blocks ending at `newest_block`, default to the head block.
+    /// Return the gas used ratio of each block, and the gas prices at `percentiles` (in [0, 100], ascending) of each block.
+    #[rpc(name = "chain.fee_history")]
+    fn fee_history(
+        &self,
+        block_count: u64,
+        newest_block: Option<BlockNumber>,
+        percentiles: Option<Vec<f64>>,
+    ) -> FutureResult<FeeHistoryView>;
+
     /// Get TransactionInfoWithProof, if the block with `block_hash` or transaction with `transaction_global_index` do not exists, return None.
     /// if `event_index` is some, also return the EventWithProof in current transaction event_root
     /// if `access_path` is some, also return the StateWithProof in current transaction state_root
@@ -125,6 +150,29 @@ pub trait ChainApi {
         access_path: Option<StrView<AccessPath>>,
     ) -> FutureResult<Option<StrView<Vec<u8>>>>;
 
+    /// Get the range proof of `num_leaves` consecutive leaves start from `first_leaf_index` in the `accumulator_type` accumulator,
+    /// use the accumulator after the block with `block_hash`.
+    /// Return None if the block does not exist or the leaves are out of range.
+    #[rpc(name = "chain.get_accumulator_range_proof")]
+    fn get_accumulator_range_proof(
+        &self,
+        block_hash: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        first_leaf_index: u64,
+        num_leaves: u64,
+    ) -> FutureResult<Option<AccumulatorRangeProofView>>;
+
+    /// Get the consistency proof that the `accumulator_type` accumulator with `old_num_leaves` leaves is a prefix of
+    /// the accumulator after the block with `block_hash`.
+    /// Return None if the block does not exist or the accumulator has less than `old_num_leaves` leaves.
+    #[rpc(name = "chain.get_accumulator_consistency_proof")]
+    fn get_accumulator_consistency_proof(
+        &self,
+        block_hash: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        old_num_leaves: u64,
+    ) -> FutureResult<Option<AccumulatorConsistencyProofView>>;
+
     /// Get the state of a dag.
     #[rpc(name = "chain.get_dag_state")]
     fn get_dag_state(&self) -> FutureResult<DagStateView>;
@@ -1,7 +1,8 @@
 pub use self::gen_client::Client as ContractClient;
 use crate::types::{
-    AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, DryRunOutputView,
-    DryRunTransactionRequest, FunctionIdView, ModuleIdView, StrView, StructTagView,
+    AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, ContractStateOption,
+    DryRunOutputView, DryRunTransactionRequest, FunctionIdView, ModuleIdView, StrView,
+    StructTagView,
 };
 use crate::FutureResult;
 use openrpc_derive::openrpc;
@@ -35,12 +36,14 @@ pub trait ContractApi {
     #[rpc(name = "contract.dry_run")]
     fn dry_run(&self, txn: DryRunTransactionRequest) -> FutureResult<DryRunOutputView>;
 
-    /// Dry run RawUserTransaction, the raw_txn parameter is RawUserTransaction's hex
+    /// Dry run RawUserTransaction, the raw_txn parameter is RawUserTransaction's hex,
+    /// the `option` selects the historical state to run on, default is the latest block state.
     #[rpc(name = "contract.dry_run_raw")]
     fn dry_run_raw(
         &self,
         raw_txn: String,
         sender_public_key: StrView<AccountPublicKey>,
+        option: Option<ContractStateOption>,
     ) -> FutureResult<DryRunOutputView>;
     #[rpc(name = "contract.resolve_function")]
     fn resolve_function(&self, function_id: FunctionIdView) -> FutureResult<FunctionABI>;
@@ -4,6 +4,27 @@
 use jsonrpc_pubsub::{PubSubMetadata, Session};
 use std::sync::Arc;
 
+/// Authentication state of the rpc caller.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Auth {
+    /// No credential is given.
+    #[default]
+    Anonymous,
+    /// Authenticated by the credential `name`, expires at `expiration` in seconds if set.
+    Authenticated {
+        name: String,
+        expiration: Option<u64>,
+    },
+    /// The given credential is invalid, with the reason.
+    Failed(String),
+}
+
+impl Auth {
+    pub fn is_authenticated(&self) -> bool {
+        matches!(self, Self::Authenticated { .. })
+    }
+}
+
 /// RPC methods metadata.
 #[derive(Clone, Default, Debug)]
 pub struct Metadata {
@@ -12,6 +33,9 @@ pub struct Metadata {
     /// Request PubSub Session
     pub session: Option<Arc<Session>>,
     pub user: Option<String>,
+    pub auth: Auth,
+    /// The W3C `traceparent` of the caller, the rpc span continues the caller's trace if set.
+    pub trace_parent: Option<String>,
 }
 
 impl Metadata {
@@ -19,6 +43,8 @@ impl Metadata {
         Self {
             session: Some(session),
             user: None,
+            auth: Auth::Anonymous,
+            trace_parent: None,
         }
     }
 }
@@ -15,6 +15,7 @@ use crate::types::pubsub;
 /// $ netcat localhost 3030
 /// {"id":1,"jsonrpc":"2.0","method":"starcoin_subscribe","params":["newPendingTransactions"]}
 /// {"id":1,"jsonrpc":"2.0","method":"starcoin_subscribe","params":["events", {}]}
+/// {"id":1,"jsonrpc":"2.0","method":"starcoin_subscribe","params":["transactionStatus", {"senders": ["0x1"]}]}
 #[allow(clippy::needless_return)]
 #[rpc(server)]
 pub trait StarcoinPubSub {
@@ -32,7 +33,7 @@ pub trait StarcoinPubSub {
         meta: Self::Metadata,
         subscriber: typed::Subscriber<pubsub::Result>,
         kind: pubsub::Kind,
-        params: Option<pubsub::Params>,
+        params: Option<serde_json::Value>,
     );
 
     /// Unsubscribe from existing Starcoin subscription.
@@ -1,4 +1,7 @@
-use crate::types::{ContractCall, TransactionArgumentView, TypeTagView};
+use crate::types::{ContractCall, ResourceOverrideView, TransactionArgumentView, TypeTagView};
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_config::genesis_address;
+use starcoin_vm_types::parser::parse_struct_tag;
 use starcoin_vm_types::token::stc::stc_type_tag;
 use starcoin_vm_types::transaction_argument::TransactionArgument;
 use std::path::PathBuf;
@@ -34,6 +37,49 @@ fn test_deserialize() {
     println!("{:?}", v);
 }
 
+#[test]
+fn test_deserialize_contract_call_with_state_option() {
+    let s = r#"
+{
+  "function_id": "0x1::Account::balance",
+  "type_args": ["0x1::STC::STC"],
+  "args": ["0xD6F8FAF8FA976104B8BA8C6F85DCF9E4"],
+  "block_number": 100
+}
+        "#;
+    let v = serde_json::from_str::<ContractCall>(s).unwrap();
+    assert_eq!(v.state_option.block_number, Some(100));
+    assert!(v.state_option.block_hash.is_none());
+    assert!(v.state_option.state_root.is_none());
+}
+
+#[test]
+fn test_deserialize_contract_call_with_state_overrides() {
+    let s = r#"
+{
+  "function_id": "0x1::Account::balance",
+  "type_args": ["0x1::STC::STC"],
+  "args": ["0x1"],
+  "state_overrides": {
+    "resources": {
+      "0x1/1/0x1::Account::Balance<0x1::STC::STC>": {"json": {"token": {"value": 100}}}
+    }
+  }
+}
+        "#;
+    let v = serde_json::from_str::<ContractCall>(s).unwrap();
+    let overrides = v.state_overrides.unwrap();
+    assert!(overrides.modules.is_empty());
+    let access_path = AccessPath::resource_access_path(
+        genesis_address(),
+        parse_struct_tag("0x1::Account::Balance<0x1::STC::STC>").unwrap(),
+    );
+    assert!(matches!(
+        overrides.resources.get(&access_path),
+        Some(ResourceOverrideView::Json(_))
+    ));
+}
+
 fn assert_that_version_control_has_no_unstaged_changes() {
     let output = Command::new("git")
         .arg("status")
@@ -19,7 +19,25 @@ pub trait TxPoolApi {
     #[rpc(name = "txpool.submit_hex_transaction")]
     fn submit_hex_transaction(&self, tx: String) -> FutureResult<HashValue>;
 
-    /// return current gas price
+    /// replace the pending txn `txn_hash` with `tx`, which has the same sender and sequence number,
+    /// and a gas price at least `txpool-replace-gas-price-bump` percent higher.
+    #[rpc(name = "txpool.replace_transaction")]
+    fn replace_transaction(
+        &self,
+        txn_hash: HashValue,
+        tx: SignedUserTransaction,
+    ) -> FutureResult<HashValue>;
+
+    /// cancel the pending txn `txn_hash` by replacing it with `tx`,
+    /// `tx` should be a zero-value STC transfer from the sender to itself.
+    #[rpc(name = "txpool.cancel_transaction")]
+    fn cancel_transaction(
+        &self,
+        txn_hash: HashValue,
+        tx: SignedUserTransaction,
+    ) -> FutureResult<HashValue>;
+
+    /// return the suggested gas price, based on the gas prices of the recent blocks
     #[rpc(name = "txpool.gas_price")]
     fn gas_price(&self) -> FutureResult<StrView<u64>>;
     /// get all pending txns in txpool of given sender.
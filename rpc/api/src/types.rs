@@ -18,8 +18,11 @@ use starcoin_abi_decoder::{
     DecodedPackage, DecodedScript, DecodedScriptFunction, DecodedTransactionPayload,
 };
 use starcoin_abi_types::ModuleABI;
-use starcoin_accumulator::proof::AccumulatorProof;
+use starcoin_accumulator::proof::{
+    AccumulatorConsistencyProof, AccumulatorProof, AccumulatorRangeProof,
+};
 use starcoin_crypto::{CryptoMaterialError, HashValue, ValidCryptoMaterialStringExt};
+use starcoin_gas::{CallFrame, TracedEvent};
 use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue};
 use starcoin_service_registry::ServiceRequest;
 use starcoin_state_api::{StateProof, StateWithProof, StateWithTableItemProof};
@@ -242,6 +245,15 @@ pub struct DryRunTransactionRequest {
     pub transaction: TransactionRequest,
     /// Sender's public key
     pub sender_public_key: StrView<AccountPublicKey>,
+    /// Trace the call tree of the execution.
+    #[serde(default)]
+    pub trace: bool,
+    /// The state to dry run on, default is the latest block state.
+    #[serde(flatten)]
+    pub state_option: ContractStateOption,
+    /// Overlay the state with the overrides before the dry run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverrides>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, JsonSchema)]
@@ -957,6 +969,31 @@ impl From<RichTransactionInfo> for TransactionInfoView {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FeeHistoryView {
+    /// The number of the oldest block in the range.
+    pub oldest_block: StrView<u64>,
+    /// The ratio of the block gas used to the block gas limit, for each block in the range.
+    pub gas_used_ratio: Vec<f64>,
+    /// The gas unit prices at the requested percentiles of each block in the range,
+    /// weighted by the gas used of the transactions. The prices of a block without user transactions are 0.
+    pub gas_price: Vec<Vec<StrView<u64>>>,
+}
+
+impl From<FeeHistory> for FeeHistoryView {
+    fn from(fee_history: FeeHistory) -> Self {
+        Self {
+            oldest_block: fee_history.oldest_block.into(),
+            gas_used_ratio: fee_history.gas_used_ratio,
+            gas_price: fee_history
+                .gas_price_percentiles
+                .into_iter()
+                .map(|prices| prices.into_iter().map(Into::into).collect())
+                .collect(),
+        }
+    }
+}
+
 impl TryFrom<TransactionInfoView> for RichTransactionInfo {
     type Error = anyhow::Error;
 
@@ -1183,7 +1220,7 @@ use crate::types::TransactionStatusView::Retry;
 use schemars::gen::SchemaGenerator;
 use schemars::schema::{InstanceType, Schema, SchemaObject};
 use starcoin_accumulator::accumulator_info::AccumulatorInfo;
-use starcoin_chain_api::{EventWithProof, TransactionInfoWithProof};
+use starcoin_chain_api::{EventWithProof, FeeHistory, TransactionInfoWithProof};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_vm_types::move_resource::MoveResource;
 use starcoin_vm_types::state_store::state_key::{StateKey, TableItem};
@@ -1195,6 +1232,73 @@ pub struct DryRunOutputView {
     pub explained_status: VmStatusExplainView,
     #[serde(flatten)]
     pub txn_output: TransactionOutputView,
+    /// The call tree of the execution, only available when the trace is required.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Vec<CallFrameView>>,
+}
+
+/// A Move function call in the execution trace.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CallFrameView {
+    /// The module of the function, empty for a script.
+    pub module: Option<ModuleIdView>,
+    pub function: String,
+    pub ty_args: Vec<TypeTagView>,
+    /// The arguments, the arguments of the transaction entry are the hex of the BCS encoded bytes.
+    pub args: Vec<String>,
+    /// The return values, only available for native functions.
+    pub return_values: Vec<String>,
+    pub is_native: bool,
+    /// The gas used by the call, including the gas used by the inner calls.
+    pub gas_used: StrView<u64>,
+    pub events: Vec<TracedEventView>,
+    /// The error which stops the call, such as an abort.
+    pub error: Option<String>,
+    pub calls: Vec<CallFrameView>,
+}
+
+impl From<CallFrame> for CallFrameView {
+    fn from(frame: CallFrame) -> Self {
+        Self {
+            module: frame.module.map(StrView),
+            function: frame.function,
+            ty_args: frame.ty_args.into_iter().map(StrView).collect(),
+            args: frame.args,
+            return_values: frame.return_values,
+            is_native: frame.is_native,
+            gas_used: frame.gas_used.into(),
+            events: frame.events.into_iter().map(Into::into).collect(),
+            error: frame.error,
+            calls: frame.calls.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TracedEventView {
+    pub type_tag: TypeTagView,
+    pub data: String,
+}
+
+impl From<TracedEvent> for TracedEventView {
+    fn from(event: TracedEvent) -> Self {
+        Self {
+            type_tag: event.type_tag.into(),
+            data: event.data,
+        }
+    }
+}
+
+/// The execution trace of a transaction, re-executed at the parent state.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionTraceView {
+    pub transaction_hash: HashValue,
+    pub block_hash: HashValue,
+    pub block_number: StrView<u64>,
+    pub explained_status: VmStatusExplainView,
+    #[serde(flatten)]
+    pub txn_output: TransactionOutputView,
+    pub calls: Vec<CallFrameView>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
@@ -1489,6 +1593,52 @@ impl From<AccumulatorProofView> for AccumulatorProof {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AccumulatorRangeProofView {
+    /// The left siblings of the first leaf, from the bottom level to the root level.
+    pub left_siblings: Vec<HashValue>,
+    /// The right siblings of the last leaf, from the bottom level to the root level.
+    pub right_siblings: Vec<HashValue>,
+}
+
+impl From<AccumulatorRangeProof> for AccumulatorRangeProofView {
+    fn from(origin: AccumulatorRangeProof) -> Self {
+        Self {
+            left_siblings: origin.left_siblings,
+            right_siblings: origin.right_siblings,
+        }
+    }
+}
+
+impl From<AccumulatorRangeProofView> for AccumulatorRangeProof {
+    fn from(view: AccumulatorRangeProofView) -> Self {
+        Self::new(view.left_siblings, view.right_siblings)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AccumulatorConsistencyProofView {
+    /// The frozen subtree roots of the old accumulator.
+    pub old_subtrees: Vec<HashValue>,
+    /// The roots of the subtrees appended to the old accumulator.
+    pub subtrees: Vec<HashValue>,
+}
+
+impl From<AccumulatorConsistencyProof> for AccumulatorConsistencyProofView {
+    fn from(origin: AccumulatorConsistencyProof) -> Self {
+        Self {
+            old_subtrees: origin.old_subtrees,
+            subtrees: origin.subtrees,
+        }
+    }
+}
+
+impl From<AccumulatorConsistencyProofView> for AccumulatorConsistencyProof {
+    fn from(view: AccumulatorConsistencyProofView) -> Self {
+        Self::new(view.old_subtrees, view.subtrees)
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EventWithProofView {
     /// event is serialized bytes in bcs format.
@@ -1829,6 +1979,47 @@ pub struct ContractCall {
     pub function_id: FunctionIdView,
     pub type_args: Vec<TypeTagView>,
     pub args: Vec<TransactionArgumentView>,
+    /// The state to call on, default is the latest block state.
+    #[serde(flatten)]
+    pub state_option: ContractStateOption,
+    /// Overlay the state with the overrides before the call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverrides>,
+}
+
+/// The historical state to execute a contract call or dry run on, at most one of the fields can be set.
+#[derive(Default, Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, JsonSchema)]
+#[serde(default)]
+pub struct ContractStateOption {
+    /// The state after the main chain block with the number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<BlockNumber>,
+    /// The state after the block with the hash.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<HashValue>,
+    /// The state tree root.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_root: Option<HashValue>,
+}
+
+/// The modules and resources to overlay on the state of a contract call or dry run,
+/// the overrides only take effect in the execution and are never persisted.
+#[derive(Default, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, JsonSchema)]
+#[serde(default)]
+pub struct StateOverrides {
+    /// The resources to override, keyed by the access path.
+    pub resources: BTreeMap<AccessPath, ResourceOverrideView>,
+    /// The bytecode of the modules to overlay, replace the on chain module with the same id.
+    pub modules: Vec<StrView<Vec<u8>>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceOverrideView {
+    /// The hex of the BCS encoded resource.
+    Bcs(StrView<Vec<u8>>),
+    /// The JSON value of the resource, in the format of the decoded resource `json`.
+    Json(serde_json::Value),
 }
 
 #[derive(Debug, Clone)]
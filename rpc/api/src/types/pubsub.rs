@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors;
-use crate::types::{BlockView, TransactionEventResponse, TypeTagView};
+use crate::types::{BlockView, StrView, TransactionEventResponse, TypeTagView};
 use jsonrpc_core::error::Error as JsonRpcError;
 use schemars::{self, JsonSchema};
 use serde::de::Error;
@@ -13,6 +13,7 @@ use starcoin_types::account_address::AccountAddress;
 use starcoin_types::event::EventKey;
 use starcoin_types::filter::Filter;
 use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::transaction::TxStatus;
 use starcoin_types::U256;
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
 use std::convert::TryInto;
@@ -30,6 +31,8 @@ pub enum Kind {
     NewPendingTransactions,
     /// New block for minting
     NewMintBlock,
+    /// Transaction status subscription.
+    TransactionStatus,
 }
 
 /// Subscription result.
@@ -41,6 +44,7 @@ pub enum Result {
     TransactionHash(Vec<HashValue>),
     Event(Box<TransactionEventResponse>),
     MintBlock(Box<MintBlockEvent>),
+    TransactionStatus(Box<TransactionStatusView>),
 }
 
 impl Serialize for Result {
@@ -53,6 +57,7 @@ impl Serialize for Result {
             Self::Event(ref evt) => evt.serialize(serializer),
             Self::TransactionHash(ref hash) => hash.serialize(serializer),
             Self::MintBlock(ref block) => block.serialize(serializer), // Result::SyncState(ref sync) => sync.serialize(serializer),
+            Self::TransactionStatus(ref status) => status.serialize(serializer),
         }
     }
 }
@@ -65,6 +70,8 @@ pub enum Params {
     None,
     /// Log parameters.
     Events(EventParams),
+    /// Transaction status parameters.
+    TransactionStatus(TxnStatusFilter),
 }
 
 impl<'a> Deserialize<'a> for Params {
@@ -84,6 +91,31 @@ impl<'a> Deserialize<'a> for Params {
     }
 }
 
+impl Params {
+    /// Parse the raw subscription parameters by the subscription `kind`,
+    /// the parameters of different kinds can not be told apart by their fields.
+    pub fn parse(
+        kind: &Kind,
+        params: Option<Value>,
+    ) -> ::std::result::Result<Option<Self>, JsonRpcError> {
+        let params = match params {
+            Some(params) if !params.is_null() => params,
+            _ => return Ok(None),
+        };
+        match kind {
+            Kind::Events => from_value(params)
+                .map(|params| Some(Self::Events(params)))
+                .map_err(|e| errors::invalid_params("events", e.to_string())),
+            Kind::TransactionStatus => from_value(params)
+                .map(|params| Some(Self::TransactionStatus(params)))
+                .map_err(|e| errors::invalid_params("transactionStatus", e.to_string())),
+            Kind::NewHeads | Kind::NewPendingTransactions | Kind::NewMintBlock => {
+                Ok(Some(Self::None))
+            }
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Hash)]
 pub struct EventParams {
     #[serde(flatten)]
@@ -151,6 +183,79 @@ impl TryInto<Filter> for EventFilter {
     }
 }
 
+/// Transaction status filter, match if the transaction hash is in `txn_hashes`
+/// or the transaction sender is in `senders`.
+/// if both `txn_hashes` and `senders` are empty, transaction status always match.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Eq, Hash, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TxnStatusFilter {
+    /// Transaction hashes to watch.
+    #[serde(default)]
+    pub txn_hashes: Option<Vec<HashValue>>,
+    /// Senders of the transactions to watch.
+    #[serde(default)]
+    pub senders: Option<Vec<AccountAddress>>,
+}
+
+impl TxnStatusFilter {
+    pub fn matching(&self, status: &TransactionStatusView) -> bool {
+        let txn_hashes = self.txn_hashes.as_deref().unwrap_or_default();
+        let senders = self.senders.as_deref().unwrap_or_default();
+        if txn_hashes.is_empty() && senders.is_empty() {
+            return true;
+        }
+        txn_hashes.contains(&status.txn_hash)
+            || status
+                .sender
+                .map(|sender| senders.contains(&sender))
+                .unwrap_or(false)
+    }
+}
+
+/// The status a transaction transit to.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TxnStatus {
+    /// Added to the txpool.
+    Added,
+    /// Rejected by the txpool.
+    Rejected,
+    /// Dropped from the txpool, replaced by another transaction or the txpool is full.
+    Dropped,
+    /// Removed from the txpool as invalid.
+    Invalid,
+    /// Canceled from the txpool.
+    Canceled,
+    /// Culled from the txpool, the transaction is stale.
+    Culled,
+    /// Included in a block of the main chain.
+    Included,
+}
+
+impl From<TxStatus> for TxnStatus {
+    fn from(status: TxStatus) -> Self {
+        match status {
+            TxStatus::Added => Self::Added,
+            TxStatus::Rejected => Self::Rejected,
+            TxStatus::Dropped => Self::Dropped,
+            TxStatus::Invalid => Self::Invalid,
+            TxStatus::Canceled => Self::Canceled,
+            TxStatus::Culled => Self::Culled,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionStatusView {
+    pub txn_hash: HashValue,
+    /// The sender of the transaction, None if the transaction is unknown to the node.
+    pub sender: Option<AccountAddress>,
+    pub status: TxnStatus,
+    /// The block which include the transaction, only for `included` status.
+    pub block_hash: Option<HashValue>,
+    pub block_number: Option<StrView<u64>>,
+}
+
 /// Block for minting
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
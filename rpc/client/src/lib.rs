@@ -20,6 +20,7 @@ use parking_lot::Mutex;
 use serde_json::Value;
 use starcoin_abi_types::{FunctionABI, ModuleABI, StructInstantiation};
 use starcoin_account_api::AccountInfo;
+use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_crypto::HashValue;
 use starcoin_dag::consensusdb::consenses_state::DagStateView;
 use starcoin_logger::{prelude::*, LogPattern};
@@ -31,15 +32,16 @@ use starcoin_rpc_api::service::RpcAsyncService;
 use starcoin_rpc_api::state::{
     GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption,
 };
-use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_rpc_api::types::pubsub::{EventFilter, TransactionStatusView, TxnStatusFilter};
 use starcoin_rpc_api::types::{
-    AccountStateSetView, AnnotatedMoveStructView, BlockHeaderView, BlockInfoView, BlockView,
-    ChainId, ChainInfoView, CodeView, ContractCall, DecodedMoveValue, DryRunOutputView,
-    DryRunTransactionRequest, FactoryAction, FunctionIdView, ListCodeView, ListResourceView,
-    MintedBlockView, ModuleIdView, PeerInfoView, ResourceView, SignedMessageView,
-    SignedUserTransactionView, StateWithProofView, StateWithTableItemProofView, StrView,
-    StructTagView, TableInfoView, TransactionEventResponse, TransactionInfoView,
-    TransactionInfoWithProofView, TransactionRequest, TransactionView,
+    AccountStateSetView, AccumulatorConsistencyProofView, AccumulatorRangeProofView,
+    AnnotatedMoveStructView, BlockHeaderView, BlockInfoView, BlockView, ChainId, ChainInfoView,
+    CodeView, ContractCall, DecodedMoveValue, DryRunOutputView, DryRunTransactionRequest,
+    FactoryAction, FeeHistoryView, FunctionIdView, ListCodeView, ListResourceView, MintedBlockView,
+    ModuleIdView, PeerInfoView, ResourceView, SignedMessageView, SignedUserTransactionView,
+    StateWithProofView, StateWithTableItemProofView, StrView, StructTagView, TableInfoView,
+    TransactionEventResponse, TransactionInfoView, TransactionInfoWithProofView,
+    TransactionRequest, TransactionTraceView, TransactionView,
 };
 use starcoin_rpc_api::{
     account::AccountClient, chain::ChainClient, contract_api::ContractClient, debug::DebugClient,
@@ -307,6 +309,24 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn replace_transaction(
+        &self,
+        txn_hash: HashValue,
+        txn: SignedUserTransaction,
+    ) -> anyhow::Result<HashValue> {
+        self.call_rpc_blocking(|inner| inner.txpool_client.replace_transaction(txn_hash, txn))
+            .map_err(map_err)
+    }
+
+    pub fn cancel_transaction(
+        &self,
+        txn_hash: HashValue,
+        txn: SignedUserTransaction,
+    ) -> anyhow::Result<HashValue> {
+        self.call_rpc_blocking(|inner| inner.txpool_client.cancel_transaction(txn_hash, txn))
+            .map_err(map_err)
+    }
+
     pub fn get_pending_txn_by_hash(
         &self,
         txn_hash: HashValue,
@@ -444,6 +464,24 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    pub fn account_import_mnemonic(
+        &self,
+        mnemonic: String,
+        password: String,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_blocking(|inner| inner.account_client.import_mnemonic(mnemonic, password))
+            .map_err(map_err)
+    }
+
+    pub fn account_derive(
+        &self,
+        password: String,
+        index: Option<u32>,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_blocking(|inner| inner.account_client.derive(password, index))
+            .map_err(map_err)
+    }
+
     pub fn account_import_readonly(
         &self,
         address: AccountAddress,
@@ -742,6 +780,14 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn debug_trace_transaction(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<TransactionTraceView> {
+        self.call_rpc_blocking(|inner| inner.debug_client.trace_transaction(txn_hash))
+            .map_err(map_err)
+    }
+
     pub fn chain_id(&self) -> anyhow::Result<ChainId> {
         self.call_rpc_blocking(|inner| inner.chain_client.id())
             .map_err(map_err)
@@ -866,6 +912,35 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    pub fn chain_get_transactions_by_address(
+        &self,
+        address: AccountAddress,
+        start_index: Option<u64>,
+        reverse: bool,
+        max_size: u64,
+    ) -> anyhow::Result<Vec<TransactionInfoView>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .chain_client
+                .get_transactions_by_address(address, start_index, reverse, max_size)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn chain_fee_history(
+        &self,
+        block_count: u64,
+        newest_block: Option<BlockNumber>,
+        percentiles: Option<Vec<f64>>,
+    ) -> anyhow::Result<FeeHistoryView> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .chain_client
+                .fee_history(block_count, newest_block, percentiles)
+        })
+        .map_err(map_err)
+    }
+
     pub fn chain_get_transaction_proof(
         &self,
         block_hash: HashValue,
@@ -902,6 +977,40 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    pub fn chain_get_accumulator_range_proof(
+        &self,
+        block_hash: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        first_leaf_index: u64,
+        num_leaves: u64,
+    ) -> anyhow::Result<Option<AccumulatorRangeProofView>> {
+        self.call_rpc_blocking(|inner| {
+            inner.chain_client.get_accumulator_range_proof(
+                block_hash,
+                accumulator_type,
+                first_leaf_index,
+                num_leaves,
+            )
+        })
+        .map_err(map_err)
+    }
+
+    pub fn chain_get_accumulator_consistency_proof(
+        &self,
+        block_hash: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        old_num_leaves: u64,
+    ) -> anyhow::Result<Option<AccumulatorConsistencyProofView>> {
+        self.call_rpc_blocking(|inner| {
+            inner.chain_client.get_accumulator_consistency_proof(
+                block_hash,
+                accumulator_type,
+                old_num_leaves,
+            )
+        })
+        .map_err(map_err)
+    }
+
     pub fn dry_run(&self, txn: DryRunTransactionRequest) -> anyhow::Result<DryRunOutputView> {
         self.call_rpc_blocking(|inner| inner.contract_client.dry_run(txn))
             .map_err(map_err)
@@ -915,7 +1024,7 @@ impl RpcClient {
         self.call_rpc_blocking(|inner| {
             inner
                 .contract_client
-                .dry_run_raw(raw_txn, StrView(public_key))
+                .dry_run_raw(raw_txn, StrView(public_key), None)
         })
         .map_err(map_err)
     }
@@ -974,6 +1083,17 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    pub fn subscribe_txn_status(
+        &self,
+        filter: TxnStatusFilter,
+    ) -> anyhow::Result<impl TryStream<Ok = TransactionStatusView, Error = anyhow::Error>> {
+        self.call_rpc_blocking(|inner| async move {
+            let res = inner.pubsub_client.subscribe_txn_status(filter).await;
+            res.map(|s| s.map_err(map_err))
+        })
+        .map_err(map_err)
+    }
+
     pub fn subscribe_new_mint_blocks(
         &self,
     ) -> anyhow::Result<impl TryStream<Ok = MintBlockEvent, Error = anyhow::Error>> {
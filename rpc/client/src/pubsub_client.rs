@@ -3,7 +3,7 @@
 
 use jsonrpc_core_client::*;
 use starcoin_crypto::HashValue;
-use starcoin_rpc_api::types::pubsub::EventParams;
+use starcoin_rpc_api::types::pubsub::{EventParams, TransactionStatusView, TxnStatusFilter};
 use starcoin_rpc_api::types::{pubsub::EventFilter, pubsub::Kind, BlockView, TransactionEventView};
 use starcoin_types::system_events::MintBlockEvent;
 
@@ -76,4 +76,16 @@ impl PubSubClient {
             "MintBlockEvent",
         )
     }
+    pub async fn subscribe_txn_status(
+        &self,
+        filter: TxnStatusFilter,
+    ) -> Result<TypedSubscriptionStream<TransactionStatusView>, RpcError> {
+        self.client.subscribe(
+            STARCOIN_SUBSCRIBE,
+            (Kind::TransactionStatus, filter),
+            STARCOIN_SUBSCRIPTION,
+            STARCOIN_UNSUBSCRIBE,
+            "TransactionStatusView",
+        )
+    }
 }
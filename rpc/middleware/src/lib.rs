@@ -5,8 +5,10 @@ use futures::{future::Either, Future, FutureExt};
 use jsonrpc_core::{Call, FutureResponse, Id, Middleware, Output, Params, Request, Response};
 use starcoin_logger::prelude::*;
 use starcoin_rpc_api::metadata::Metadata;
+use starcoin_tracing::set_remote_parent;
 use std::fmt;
 use std::time::Instant;
+use tracing::{info_span, Instrument};
 
 mod metrics;
 
@@ -153,7 +155,16 @@ impl Middleware<Metadata> for MetricMiddleware {
         let record: RpcCallRecord = (&call).into();
         let metrics = self.metrics.clone();
         let user_addr = meta.user.clone();
-        let fut = next(call, meta).map(move |output| {
+        let span = info_span!(
+            "rpc_call",
+            method = record.method.as_str(),
+            id = record.id.as_str()
+        );
+        if let Some(trace_parent) = meta.trace_parent.as_deref() {
+            set_remote_parent(&span, trace_parent);
+        }
+        let fut = span.in_scope(|| next(call, meta)).instrument(span);
+        let fut = fut.map(move |output| {
             record.end(output_to_code(output.as_ref()), user_addr, metrics);
             output
         });
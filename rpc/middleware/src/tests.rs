@@ -1,9 +1,16 @@
 use super::*;
 use futures::executor::block_on;
 use jsonrpc_core::{MetaIoHandler, Params, Value};
+use opentelemetry::global;
+use opentelemetry::sdk::propagation::TraceContextPropagator;
+use opentelemetry::sdk::trace::TracerProvider;
+use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
 use rand::Rng;
 use starcoin_metrics::{get_all_metrics, Registry};
 use std::time::Duration;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
 
 #[stest::test]
 fn test_middleware() {
@@ -31,3 +38,50 @@ fn test_middleware() {
     }
     info!("metrics: {:?}", get_all_metrics(&registry));
 }
+
+#[stest::test]
+fn test_middleware_honor_trace_parent() {
+    // the propagator is installed by starcoin_tracing::init in the node.
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    let provider = TracerProvider::builder().build();
+    let dispatch = tracing::Dispatch::new(
+        tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test"))),
+    );
+
+    let mut io_handler = MetaIoHandler::with_middleware(MetricMiddleware::new(None));
+    io_handler.add_method("trace_id", |_params: Params| async {
+        Ok(Value::String(
+            Span::current()
+                .context()
+                .span()
+                .span_context()
+                .trace_id()
+                .to_string(),
+        ))
+    });
+    let request = r#"{"jsonrpc":"2.0","method":"trace_id","params":[],"id":1}"#;
+    let call = |trace_parent: Option<&str>| -> String {
+        let meta = Metadata {
+            trace_parent: trace_parent.map(|v| v.to_string()),
+            ..Metadata::default()
+        };
+        let response = tracing::dispatcher::with_default(&dispatch, || {
+            block_on(io_handler.handle_request(request, meta)).unwrap()
+        });
+        let response: serde_json::Value = serde_json::from_str(response.as_str()).unwrap();
+        response["result"].as_str().unwrap().to_string()
+    };
+
+    let trace_id = "4bf92f3577b34da6a3ce929d0e0e4736";
+    assert_eq!(
+        call(Some(
+            format!("00-{}-00f067aa0ba902b7-01", trace_id).as_str()
+        )),
+        trace_id
+    );
+    // the rpc call starts a new trace without the caller's trace parent.
+    assert_ne!(call(None), trace_id);
+    // an invalid trace parent is ignored.
+    assert_ne!(call(Some("invalid")), trace_id);
+}
@@ -1,14 +1,20 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
+use crate::auth::{RpcAuth, RpcAuthMiddleware};
 use crate::rate_limit_middleware::JsonApiRateLimitMiddleware;
 use jsonrpc_core::{MetaIoHandler, RemoteProcedure};
 use starcoin_config::{Api, ApiQuotaConfiguration};
 use starcoin_rpc_api::metadata::Metadata;
 use starcoin_rpc_middleware::{MetricMiddleware, RpcMetrics};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
-type Middlewares = (MetricMiddleware, JsonApiRateLimitMiddleware);
+type Middlewares = (
+    MetricMiddleware,
+    RpcAuthMiddleware,
+    JsonApiRateLimitMiddleware,
+);
 
 pub struct ApiRegistry {
     apis: HashMap<Api, MetaIoHandler<Metadata, Middlewares>>,
@@ -34,6 +40,7 @@ impl ApiRegistry {
         let io_handler = self.apis.entry(api_type).or_insert_with(|| {
             MetaIoHandler::<Metadata, Middlewares>::with_middleware((
                 MetricMiddleware::new(metrics),
+                RpcAuthMiddleware::default(),
                 rate_limit_middleware,
             ))
         });
@@ -43,23 +50,42 @@ impl ApiRegistry {
     pub fn get_apis(
         &self,
         api_types: impl IntoIterator<Item = Api>,
+    ) -> MetaIoHandler<Metadata, Middlewares> {
+        self.get_apis_with_auth(api_types.into_iter().collect(), None)
+    }
+
+    /// Get the handler of `public_apis`, if `auth` is set, the apis of the credentials are
+    /// served too, but only for the authenticated callers.
+    pub fn get_apis_with_auth(
+        &self,
+        public_apis: HashSet<Api>,
+        auth: Option<Arc<RpcAuth>>,
     ) -> MetaIoHandler<Metadata, Middlewares> {
         let rate_limit_middleware = JsonApiRateLimitMiddleware::from_config(self.quotas.clone());
         let metrics = self.metrics.clone();
-        api_types
+        let mut api_types = public_apis.clone();
+        if let Some(auth) = &auth {
+            api_types.extend(auth.apis());
+        }
+        let apis: Vec<_> = api_types
             .into_iter()
-            .map(|api_type| self.apis.get(&api_type))
-            .fold(
-                MetaIoHandler::<Metadata, Middlewares>::with_middleware((
-                    MetricMiddleware::new(metrics),
-                    rate_limit_middleware,
-                )),
-                |mut init, apis| {
-                    if let Some(apis) = apis {
-                        init.extend_with(apis.iter().map(|(k, v)| (k.clone(), v.clone())));
-                    }
-                    init
-                },
-            )
+            .filter_map(|api_type| self.apis.get(&api_type).map(|apis| (api_type, apis)))
+            .collect();
+        let method_apis = apis
+            .iter()
+            .flat_map(|(api_type, apis)| apis.iter().map(|(method, _)| (method.clone(), *api_type)))
+            .collect();
+        let auth_middleware = RpcAuthMiddleware::new(auth, public_apis, method_apis);
+        apis.into_iter().fold(
+            MetaIoHandler::<Metadata, Middlewares>::with_middleware((
+                MetricMiddleware::new(metrics),
+                auth_middleware,
+                rate_limit_middleware,
+            )),
+            |mut init, (_, apis)| {
+                init.extend_with(apis.iter().map(|(k, v)| (k.clone(), v.clone())));
+                init
+            },
+        )
     }
 }
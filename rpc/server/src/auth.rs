@@ -0,0 +1,446 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Authentication of the http and websocket rpc.
+//! A caller is authenticated by an api key or a HS256 JWT, and is allowed to call the apis of
+//! its credential in addition to the apis of the rpc endpoint.
+
+use crate::rate_limit_middleware::QuotaWrapper;
+use anyhow::{ensure, format_err, Result};
+use api_limiter::ApiLimiters;
+use hmac::{Hmac, Mac};
+use jsonrpc_core::futures::future::Either;
+use jsonrpc_core::futures::Future;
+use jsonrpc_core::middleware::NoopCallFuture;
+use jsonrpc_core::{Call, Error, ErrorCode, Failure, FutureResponse, Id, Middleware, Output};
+use serde::Deserialize;
+use sha2::Sha256;
+use starcoin_config::{Api, RpcAuthConfiguration};
+use starcoin_rpc_api::metadata::{Auth, Metadata};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const BEARER_PREFIX: &str = "bearer ";
+/// The websocket sub-protocol prefix to carry the token, the websocket session metadata
+/// is extracted from the sub-protocols of the handshake, not the headers.
+pub const WS_PROTOCOL_PREFIX: &str = "bearer.";
+
+enum Secret {
+    ApiKey(Vec<u8>),
+    Jwt(Vec<u8>),
+}
+
+struct Credential {
+    secret: Secret,
+    apis: HashSet<Api>,
+    limiters: Option<ApiLimiters<String, String>>,
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+}
+
+#[derive(Deserialize)]
+struct JwtClaims {
+    #[serde(default)]
+    exp: Option<u64>,
+    #[serde(default)]
+    nbf: Option<u64>,
+}
+
+pub struct RpcAuth {
+    credentials: HashMap<String, Credential>,
+}
+
+impl RpcAuth {
+    /// Create the auth from config, return None if no credential is configured.
+    pub fn new(config: &RpcAuthConfiguration) -> Option<Self> {
+        if !config.is_enabled() {
+            return None;
+        }
+        let credentials = config
+            .credentials()
+            .iter()
+            .filter_map(|c| {
+                let secret = match (&c.api_key, &c.jwt_secret) {
+                    (Some(key), _) => Secret::ApiKey(key.as_bytes().to_vec()),
+                    (None, Some(secret)) => Secret::Jwt(secret.as_bytes().to_vec()),
+                    (None, None) => return None,
+                };
+                let limiters = c.quota.clone().map(|quota| {
+                    let quota = QuotaWrapper::from(quota).0;
+                    ApiLimiters::new(quota, HashMap::new(), quota, HashMap::new())
+                });
+                Some((
+                    c.name.clone(),
+                    Credential {
+                        secret,
+                        apis: c.apis.list_apis(),
+                        limiters,
+                    },
+                ))
+            })
+            .collect();
+        Some(Self { credentials })
+    }
+
+    /// All the apis allowed by the credentials.
+    pub fn apis(&self) -> HashSet<Api> {
+        self.credentials
+            .values()
+            .flat_map(|c| c.apis.iter().cloned())
+            .collect()
+    }
+
+    /// Authenticate the value of a `Authorization` header.
+    pub fn authenticate_header(&self, header: Option<&str>) -> Auth {
+        match header {
+            None => Auth::Anonymous,
+            Some(value) => {
+                if value.len() > BEARER_PREFIX.len()
+                    && value[..BEARER_PREFIX.len()].eq_ignore_ascii_case(BEARER_PREFIX)
+                {
+                    self.authenticate(value[BEARER_PREFIX.len()..].trim())
+                } else {
+                    Auth::Failed("Invalid authorization header, expect a bearer token".to_string())
+                }
+            }
+        }
+    }
+
+    /// Authenticate the token, which is an api key or a JWT.
+    pub fn authenticate(&self, token: &str) -> Auth {
+        for (name, credential) in self.credentials.iter() {
+            if let Secret::ApiKey(key) = &credential.secret {
+                if constant_time_eq(key.as_slice(), token.as_bytes()) {
+                    return Auth::Authenticated {
+                        name: name.clone(),
+                        expiration: None,
+                    };
+                }
+            }
+        }
+        let mut jwt_error = None;
+        for (name, credential) in self.credentials.iter() {
+            if let Secret::Jwt(secret) = &credential.secret {
+                match verify_jwt(secret.as_slice(), token) {
+                    Ok(expiration) => {
+                        return Auth::Authenticated {
+                            name: name.clone(),
+                            expiration,
+                        }
+                    }
+                    Err(e) => jwt_error = Some(e),
+                }
+            }
+        }
+        Auth::Failed(
+            jwt_error
+                .map(|e| format!("Invalid token: {}", e))
+                .unwrap_or_else(|| "Invalid token".to_string()),
+        )
+    }
+
+    /// Check whether the authenticated caller can call the `method` of `api`.
+    fn check_call(
+        &self,
+        name: &str,
+        expiration: Option<u64>,
+        api: Api,
+        method: &str,
+        public: bool,
+    ) -> Result<()> {
+        if let Some(expiration) = expiration {
+            ensure!(now_secs() < expiration, "The token is expired");
+        }
+        let credential = self
+            .credentials
+            .get(name)
+            .ok_or_else(|| format_err!("Unknown credential: {}", name))?;
+        ensure!(
+            public || credential.apis.contains(&api),
+            "The api {} is not allowed for credential {}",
+            api,
+            name
+        );
+        if let Some(limiters) = &credential.limiters {
+            limiters.check(&method.to_string(), None)?;
+        }
+        Ok(())
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Verify the HS256 JWT, return the expiration of the token.
+fn verify_jwt(secret: &[u8], token: &str) -> Result<Option<u64>> {
+    let parts: Vec<&str> = token.split('.').collect();
+    ensure!(parts.len() == 3, "Invalid jwt format");
+    let header: JwtHeader =
+        serde_json::from_slice(&base64::decode_config(parts[0], base64::URL_SAFE_NO_PAD)?)?;
+    ensure!(header.alg == "HS256", "Unsupported jwt alg: {}", header.alg);
+    let signature = base64::decode_config(parts[2], base64::URL_SAFE_NO_PAD)?;
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).map_err(|e| format_err!("{}", e))?;
+    mac.update(parts[0].as_bytes());
+    mac.update(b".");
+    mac.update(parts[1].as_bytes());
+    mac.verify_slice(signature.as_slice())
+        .map_err(|_| format_err!("Invalid jwt signature"))?;
+    let claims: JwtClaims =
+        serde_json::from_slice(&base64::decode_config(parts[1], base64::URL_SAFE_NO_PAD)?)?;
+    let now = now_secs();
+    if let Some(nbf) = claims.nbf {
+        ensure!(now >= nbf, "The token is not valid yet");
+    }
+    if let Some(exp) = claims.exp {
+        ensure!(now < exp, "The token is expired");
+    }
+    Ok(claims.exp)
+}
+
+/// Check the caller's authentication before calling the method, the apis of the rpc endpoint
+/// are open to the anonymous caller, other apis require a credential which allows them.
+/// The api of a method is looked up in the methods registered by every api,
+/// the methods not registered are rejected.
+#[derive(Clone, Default)]
+pub struct RpcAuthMiddleware {
+    auth: Option<Arc<RpcAuth>>,
+    public_apis: HashSet<Api>,
+    method_apis: Arc<HashMap<String, Api>>,
+}
+
+impl RpcAuthMiddleware {
+    pub fn new(
+        auth: Option<Arc<RpcAuth>>,
+        public_apis: HashSet<Api>,
+        method_apis: HashMap<String, Api>,
+    ) -> Self {
+        Self {
+            auth,
+            public_apis,
+            method_apis: Arc::new(method_apis),
+        }
+    }
+
+    fn check(&self, auth: &RpcAuth, method: &str, meta: &Metadata) -> Result<()> {
+        let api = *self
+            .method_apis
+            .get(method)
+            .ok_or_else(|| format_err!("Unknown method: {}", method))?;
+        let public = self.public_apis.contains(&api);
+        match &meta.auth {
+            Auth::Anonymous => {
+                ensure!(public, "The api {} requires authentication", api);
+                Ok(())
+            }
+            Auth::Authenticated { name, expiration } => {
+                auth.check_call(name, *expiration, api, method, public)
+            }
+            Auth::Failed(reason) => Err(format_err!("{}", reason)),
+        }
+    }
+}
+
+impl Middleware<Metadata> for RpcAuthMiddleware {
+    type Future = FutureResponse;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: Metadata, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, Metadata) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let auth = match &self.auth {
+            Some(auth) => auth,
+            None => return Either::Right(next(call, meta)),
+        };
+        let method = match &call {
+            Call::MethodCall(m) => Some((m.method.clone(), m.jsonrpc, m.id.clone())),
+            Call::Notification(n) => Some((n.method.clone(), n.jsonrpc, Id::Null)),
+            Call::Invalid { .. } => None,
+        };
+        if let Some((m, json_version, id)) = method {
+            match self.check(auth.as_ref(), m.as_str(), &meta) {
+                Ok(()) => Either::Right(next(call, meta)),
+                Err(e) => {
+                    let output = Output::Failure(Failure {
+                        jsonrpc: json_version,
+                        error: Error {
+                            code: ErrorCode::ServerError(-10001),
+                            message: format!("Unauthorized: {}", e),
+                            data: None,
+                        },
+                        id,
+                    });
+                    Either::Left(Box::pin(futures::future::ready(Some(output))))
+                }
+            }
+        } else {
+            Either::Right(next(call, meta))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_registry::ApiRegistry;
+    use jsonrpc_core::{MetaIoHandler, Value};
+    use starcoin_config::{ApiQuotaConfiguration, ApiSet, RpcCredentialConfig};
+
+    fn sign_jwt(secret: &[u8], claims: &str) -> String {
+        let header =
+            base64::encode_config(r#"{"alg":"HS256","typ":"JWT"}"#, base64::URL_SAFE_NO_PAD);
+        let payload = base64::encode_config(claims, base64::URL_SAFE_NO_PAD);
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+        mac.update(format!("{}.{}", header, payload).as_bytes());
+        let signature = base64::encode_config(mac.finalize().into_bytes(), base64::URL_SAFE_NO_PAD);
+        format!("{}.{}.{}", header, payload, signature)
+    }
+
+    fn new_auth() -> RpcAuth {
+        RpcAuth::new(&RpcAuthConfiguration {
+            credentials: Some(vec![
+                RpcCredentialConfig {
+                    name: "tool".to_string(),
+                    api_key: Some("test_key".to_string()),
+                    jwt_secret: None,
+                    apis: "account".parse().unwrap(),
+                    quota: None,
+                },
+                RpcCredentialConfig {
+                    name: "admin".to_string(),
+                    api_key: None,
+                    jwt_secret: Some("test_secret".to_string()),
+                    apis: ApiSet::All,
+                    quota: None,
+                },
+            ]),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_authenticate() {
+        let auth = new_auth();
+        assert_eq!(auth.authenticate_header(None), Auth::Anonymous);
+        assert_eq!(
+            auth.authenticate_header(Some("Bearer test_key")),
+            Auth::Authenticated {
+                name: "tool".to_string(),
+                expiration: None
+            }
+        );
+        assert!(matches!(
+            auth.authenticate_header(Some("Basic test_key")),
+            Auth::Failed(_)
+        ));
+        assert!(matches!(auth.authenticate("bad_key"), Auth::Failed(_)));
+
+        let exp = now_secs() + 60;
+        let token = sign_jwt(b"test_secret", &format!(r#"{{"exp":{}}}"#, exp));
+        assert_eq!(
+            auth.authenticate(token.as_str()),
+            Auth::Authenticated {
+                name: "admin".to_string(),
+                expiration: Some(exp)
+            }
+        );
+        let token = sign_jwt(b"bad_secret", "{}");
+        assert!(matches!(auth.authenticate(token.as_str()), Auth::Failed(_)));
+        let token = sign_jwt(b"test_secret", &format!(r#"{{"exp":{}}}"#, now_secs() - 1));
+        assert!(matches!(auth.authenticate(token.as_str()), Auth::Failed(_)));
+    }
+
+    #[test]
+    fn test_check_call() {
+        let auth = new_auth();
+        let method_apis = HashMap::from([
+            ("chain.info".to_string(), Api::Chain),
+            ("account.list".to_string(), Api::Account),
+            ("node_manager.shutdown".to_string(), Api::NodeManager),
+        ]);
+        let middleware =
+            RpcAuthMiddleware::new(None, ApiSet::UnsafeContext.list_apis(), method_apis);
+        let anonymous = Metadata::default();
+        assert!(middleware.check(&auth, "chain.info", &anonymous).is_ok());
+        assert!(middleware.check(&auth, "account.list", &anonymous).is_err());
+
+        let tool = Metadata {
+            auth: auth.authenticate("test_key"),
+            ..Default::default()
+        };
+        assert!(middleware.check(&auth, "chain.info", &tool).is_ok());
+        assert!(middleware.check(&auth, "account.list", &tool).is_ok());
+        assert!(middleware
+            .check(&auth, "node_manager.shutdown", &tool)
+            .is_err());
+
+        let failed = Metadata {
+            auth: auth.authenticate("bad_key"),
+            ..Default::default()
+        };
+        assert!(middleware.check(&auth, "chain.info", &failed).is_err());
+    }
+
+    #[test]
+    fn test_sync_api_requires_credential() {
+        let mut sync_api = MetaIoHandler::<Metadata>::default();
+        sync_api.add_sync_method("sync.status", |_| Ok(Value::Null));
+        let mut registry = ApiRegistry::new(ApiQuotaConfiguration::default(), None);
+        registry.register(
+            Api::SyncManager,
+            sync_api.iter().map(|(k, v)| (k.clone(), v.clone())),
+        );
+        let io_handler = registry.get_apis_with_auth(
+            ApiSet::UnsafeContext.list_apis(),
+            Some(Arc::new(new_auth())),
+        );
+        let request = r#"{"jsonrpc":"2.0","method":"sync.status","params":[],"id":1}"#;
+
+        let response = io_handler
+            .handle_request_sync(request, Metadata::default())
+            .unwrap();
+        assert!(response.contains("Unauthorized: The api sync_manager requires authentication"));
+
+        let tool = Metadata {
+            auth: Auth::Authenticated {
+                name: "tool".to_string(),
+                expiration: None,
+            },
+            ..Default::default()
+        };
+        let response = io_handler.handle_request_sync(request, tool).unwrap();
+        assert!(response.contains("Unauthorized"));
+
+        let admin = Metadata {
+            auth: Auth::Authenticated {
+                name: "admin".to_string(),
+                expiration: None,
+            },
+            ..Default::default()
+        };
+        let response = io_handler.handle_request_sync(request, admin).unwrap();
+        assert_eq!(response, r#"{"jsonrpc":"2.0","result":null,"id":1}"#);
+
+        let request = r#"{"jsonrpc":"2.0","method":"sync_status","params":[],"id":1}"#;
+        let response = io_handler
+            .handle_request_sync(request, Metadata::default())
+            .unwrap();
+        assert!(response.contains("Unauthorized: Unknown method: sync_status"));
+    }
+}
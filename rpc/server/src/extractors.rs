@@ -1,9 +1,11 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::auth::{RpcAuth, WS_PROTOCOL_PREFIX};
 use jsonrpc_http_server::hyper;
 use jsonrpc_pubsub::Session;
-use starcoin_rpc_api::metadata::Metadata;
+use starcoin_rpc_api::metadata::{Auth, Metadata};
+use starcoin_tracing::TRACE_PARENT_HEADER;
 use std::net::IpAddr;
 use std::sync::Arc;
 
@@ -11,6 +13,7 @@ use std::sync::Arc;
 #[derive(Default)]
 pub struct RpcExtractor {
     pub http_ip_headers: Vec<String>,
+    pub auth: Option<Arc<RpcAuth>>,
 }
 
 impl jsonrpc_http_server::MetaExtractor<Metadata> for RpcExtractor {
@@ -27,9 +30,24 @@ impl jsonrpc_http_server::MetaExtractor<Metadata> for RpcExtractor {
             }
         }
 
+        let auth = match &self.auth {
+            Some(auth) => auth.authenticate_header(
+                _req.headers()
+                    .get(hyper::header::AUTHORIZATION)
+                    .map(|v| v.to_str().unwrap_or_default()),
+            ),
+            None => Auth::Anonymous,
+        };
+        let trace_parent = _req
+            .headers()
+            .get(TRACE_PARENT_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
         Metadata {
             session: None,
             user: client_ip.map(|ip| ip.to_string()),
+            auth,
+            trace_parent,
         }
     }
 }
@@ -38,6 +56,8 @@ impl jsonrpc_ipc_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(req.sender.clone()))),
             user: None,
+            auth: Auth::Anonymous,
+            trace_parent: None,
         }
     }
 }
@@ -46,17 +66,34 @@ impl jsonrpc_tcp_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(context.sender.clone()))),
             user: Some(context.peer_addr.ip().to_string()),
+            auth: Auth::Anonymous,
+            trace_parent: None,
         }
     }
 }
 
-pub struct WsExtractor;
+/// Websocket metadata extractor, the token is carried by a `bearer.<token>` sub-protocol.
+#[derive(Default)]
+pub struct WsExtractor {
+    pub auth: Option<Arc<RpcAuth>>,
+}
 impl jsonrpc_ws_server::MetaExtractor<Metadata> for WsExtractor {
     fn extract(&self, req: &jsonrpc_ws_server::RequestContext) -> Metadata {
         let session = Some(Arc::new(Session::new(req.sender())));
+        let auth = match &self.auth {
+            Some(auth) => req
+                .protocols
+                .iter()
+                .find_map(|protocol| protocol.strip_prefix(WS_PROTOCOL_PREFIX))
+                .map(|token| auth.authenticate(token))
+                .unwrap_or_default(),
+            None => Auth::Anonymous,
+        };
         Metadata {
             session,
             user: None,
+            auth,
+            trace_parent: None,
         }
     }
 }
@@ -205,6 +205,26 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn import_mnemonic(&self, mnemonic: String, password: String) -> FutureResult<AccountInfo> {
+        let service = self.account.clone();
+        let fut = async move {
+            let result = service.import_mnemonic(mnemonic, password).await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn derive(&self, password: String, index: Option<u32>) -> FutureResult<AccountInfo> {
+        let service = self.account.clone();
+        let fut = async move {
+            let result = service.derive_account(index, password).await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     fn import_readonly(
         &self,
         address: AccountAddress,
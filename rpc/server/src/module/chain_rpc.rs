@@ -4,6 +4,7 @@
 use crate::module::map_err;
 use futures::future::{FutureExt, TryFutureExt};
 use starcoin_abi_decoder::decode_txn_payload;
+use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
@@ -16,7 +17,8 @@ use starcoin_rpc_api::chain::{
 };
 use starcoin_rpc_api::types::pubsub::EventFilter;
 use starcoin_rpc_api::types::{
-    BlockHeaderView, BlockInfoView, BlockTransactionsView, BlockView, ChainId, ChainInfoView,
+    AccumulatorConsistencyProofView, AccumulatorRangeProofView, BlockHeaderView, BlockInfoView,
+    BlockTransactionsView, BlockView, ChainId, ChainInfoView, FeeHistoryView,
     SignedUserTransactionView, StrView, TransactionEventResponse, TransactionInfoView,
     TransactionInfoWithProofView, TransactionView,
 };
@@ -25,6 +27,7 @@ use starcoin_state_api::StateView;
 use starcoin_statedb::ChainStateDB;
 use starcoin_storage::Storage;
 use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::block::BlockNumber;
 use starcoin_types::filter::Filter;
 use starcoin_types::startup_info::ChainInfo;
@@ -418,6 +421,49 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn get_transactions_by_address(
+        &self,
+        address: AccountAddress,
+        start_index: Option<u64>,
+        reverse: bool,
+        max_size: u64,
+    ) -> FutureResult<Vec<TransactionInfoView>> {
+        let service = self.service.clone();
+        let config = self.config.clone();
+        let fut = async move {
+            let max_return_num = max_size.min(config.rpc.txn_info_query_max_range());
+            Ok(service
+                .get_transaction_infos_by_address(address, start_index, reverse, max_return_num)
+                .await?
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<_>>())
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn fee_history(
+        &self,
+        block_count: u64,
+        newest_block: Option<BlockNumber>,
+        percentiles: Option<Vec<f64>>,
+    ) -> FutureResult<FeeHistoryView> {
+        let service = self.service.clone();
+        let config = self.config.clone();
+        let fut = async move {
+            let block_count = block_count.min(config.rpc.block_query_max_range());
+            Ok(service
+                .get_fee_history(block_count, newest_block, percentiles.unwrap_or_default())
+                .await?
+                .into())
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
     fn get_transaction_proof(
         &self,
         block_hash: HashValue,
@@ -472,6 +518,48 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn get_accumulator_range_proof(
+        &self,
+        block_hash: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        first_leaf_index: u64,
+        num_leaves: u64,
+    ) -> FutureResult<Option<AccumulatorRangeProofView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            Ok(service
+                .get_accumulator_range_proof(
+                    block_hash,
+                    accumulator_type,
+                    first_leaf_index,
+                    num_leaves,
+                )
+                .await?
+                .map(Into::into))
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_accumulator_consistency_proof(
+        &self,
+        block_hash: HashValue,
+        accumulator_type: AccumulatorStoreType,
+        old_num_leaves: u64,
+    ) -> FutureResult<Option<AccumulatorConsistencyProofView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            Ok(service
+                .get_accumulator_consistency_proof(block_hash, accumulator_type, old_num_leaves)
+                .await?
+                .map(Into::into))
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
     #[doc = r" Get the state of a dag."]
     fn get_dag_state(&self) -> FutureResult<DagStateView> {
         let service = self.service.clone();
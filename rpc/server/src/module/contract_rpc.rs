@@ -3,28 +3,32 @@
 
 use crate::module::helpers::TransactionRequestFiller;
 use crate::module::map_err;
-use anyhow::format_err;
+use anyhow::{ensure, format_err};
 use futures::future::TryFutureExt;
 use futures::FutureExt;
 use starcoin_abi_decoder::{decode_move_value, DecodedMoveValue};
 use starcoin_abi_resolver::ABIResolver;
 use starcoin_abi_types::{FunctionABI, ModuleABI, StructInstantiation, TypeInstantiation};
 use starcoin_account_api::AccountAsyncService;
+use starcoin_chain_service::{ChainAsyncService, ChainReaderService};
 use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
 use starcoin_dev::playground::{call_contract, PlaygroudService};
+use starcoin_dev::state_override::StateOverrideView;
 use starcoin_executor::VMMetrics;
 use starcoin_resource_viewer::module_cache::ModuleCache;
 use starcoin_resource_viewer::MoveValueAnnotator;
 use starcoin_rpc_api::contract_api::ContractApi;
 use starcoin_rpc_api::types::{
-    AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, DryRunOutputView,
-    DryRunTransactionRequest, FunctionIdView, ModuleIdView, StrView, StructTagView,
-    TransactionOutputView, WriteOpValueView,
+    AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, ContractStateOption,
+    DryRunOutputView, DryRunTransactionRequest, FunctionIdView, ModuleIdView, StrView,
+    StructTagView, TransactionOutputView, WriteOpValueView,
 };
 use starcoin_rpc_api::FutureResult;
-use starcoin_state_api::ChainStateAsyncService;
+use starcoin_service_registry::ServiceRef;
+use starcoin_state_api::{ChainStateAsyncService, StateNodeStore};
 use starcoin_statedb::ChainStateDB;
-use starcoin_storage::Storage;
+use starcoin_storage::{BlockStore, Storage};
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::language_storage::{ModuleId, StructTag};
@@ -43,6 +47,7 @@ pub struct ContractRpcImpl<Account, Pool, State> {
     pub(crate) node_config: Arc<NodeConfig>,
     playground: PlaygroudService,
     storage: Arc<Storage>,
+    chain: Option<ServiceRef<ChainReaderService>>,
 }
 
 impl<Account, Pool, State> ContractRpcImpl<Account, Pool, State>
@@ -66,8 +71,24 @@ where
             node_config,
             playground,
             storage,
+            chain: None,
         }
     }
+
+    /// Enable the contract calls and dry runs at the state of a main chain block number.
+    pub fn with_chain(mut self, chain: ServiceRef<ChainReaderService>) -> Self {
+        self.chain = Some(chain);
+        self
+    }
+
+    fn state_root_resolver(&self) -> StateRootResolver<State> {
+        StateRootResolver {
+            chain_state: self.chain_state.clone(),
+            chain: self.chain.clone(),
+            storage: self.storage.clone(),
+        }
+    }
+
     fn txn_request_filler(&self) -> TransactionRequestFiller<Account, Pool, State> {
         TransactionRequestFiller {
             account: self.account.clone(),
@@ -120,40 +141,57 @@ where
         Box::pin(f.map_err(map_err).boxed())
     }
     fn call(&self, call: ContractCall) -> FutureResult<Vec<AnnotatedMoveValueView>> {
-        let service = self.chain_state.clone();
-        let playground = self.playground.clone();
+        let resolver = self.state_root_resolver();
+        let storage = self.storage.clone();
         let ContractCall {
             function_id,
             type_args,
             args,
+            state_option,
+            state_overrides,
         } = call;
+        let metrics = self.playground.metrics.clone();
         let f = async move {
-            let state_root = service.state_root().await?;
-            let output = playground.call_contract(
-                state_root,
+            let state_root = resolver.resolve(state_option).await?;
+            let state = StateOverrideView::new(
+                ChainStateDB::new(storage, Some(state_root)),
+                state_overrides.unwrap_or_default(),
+            )?;
+            let output = call_contract(
+                &state,
                 function_id.0.module,
-                function_id.0.function,
+                function_id.0.function.as_str(),
                 type_args.into_iter().map(|v| v.0).collect(),
                 args.into_iter().map(|v| v.0).collect(),
+                metrics,
             )?;
-            Ok(output.into_iter().map(Into::into).collect())
+            let annotator = MoveValueAnnotator::new(&state);
+            output
+                .into_iter()
+                .map(|(ty, v)| annotator.view_value(&ty, &v).map(Into::into))
+                .collect::<anyhow::Result<Vec<_>>>()
         }
         .map_err(map_err);
         Box::pin(f.boxed())
     }
 
     fn call_v2(&self, call: ContractCall) -> FutureResult<Vec<DecodedMoveValue>> {
-        let service = self.chain_state.clone();
+        let resolver = self.state_root_resolver();
         let storage = self.storage.clone();
         let ContractCall {
             function_id,
             type_args,
             args,
+            state_option,
+            state_overrides,
         } = call;
         let metrics = self.playground.metrics.clone();
         let f = async move {
-            let state_root = service.state_root().await?;
-            let state = ChainStateDB::new(storage, Some(state_root));
+            let state_root = resolver.resolve(state_option).await?;
+            let state = StateOverrideView::new(
+                ChainStateDB::new(storage, Some(state_root)),
+                state_overrides.unwrap_or_default(),
+            )?;
             let output = call_contract(
                 &state,
                 function_id.0.module,
@@ -173,19 +211,25 @@ where
     }
 
     fn dry_run(&self, txn: DryRunTransactionRequest) -> FutureResult<DryRunOutputView> {
-        let service = self.chain_state.clone();
+        let resolver = self.state_root_resolver();
         let storage = self.storage.clone();
         let txn_builder = self.txn_request_filler();
         let metrics = self.playground.metrics.clone();
         let f = async move {
-            let state_root = service.state_root().await?;
             let DryRunTransactionRequest {
                 transaction,
                 sender_public_key,
+                trace,
+                state_option,
+                state_overrides,
             } = txn;
+            let state_root = resolver.resolve(state_option).await?;
 
             let txn = txn_builder.fill_transaction(transaction).await?;
-            let state_view = ChainStateDB::new(storage, Some(state_root));
+            let state_view = StateOverrideView::new(
+                ChainStateDB::new(storage, Some(state_root)),
+                state_overrides.unwrap_or_default(),
+            )?;
             dry_run(
                 &state_view,
                 DryRunTransaction {
@@ -193,6 +237,7 @@ where
                     public_key: sender_public_key.0,
                 },
                 metrics,
+                trace,
             )
         }
         .map_err(map_err);
@@ -203,12 +248,13 @@ where
         &self,
         raw_txn: String,
         sender_public_key: StrView<AccountPublicKey>,
+        option: Option<ContractStateOption>,
     ) -> FutureResult<DryRunOutputView> {
-        let service = self.chain_state.clone();
+        let resolver = self.state_root_resolver();
         let storage = self.storage.clone();
         let metrics = self.playground.metrics.clone();
         let f = async move {
-            let state_root = service.state_root().await?;
+            let state_root = resolver.resolve(option.unwrap_or_default()).await?;
             let raw_txn = RawUserTransaction::from_str(raw_txn.as_str())?;
             let state_view = ChainStateDB::new(storage, Some(state_root));
             dry_run(
@@ -218,6 +264,7 @@ where
                     public_key: sender_public_key.0,
                 },
                 metrics,
+                false,
             )
         }
         .map_err(map_err);
@@ -274,12 +321,76 @@ where
     }
 }
 
+/// Resolve the state root of the `ContractStateOption`.
+struct StateRootResolver<State> {
+    chain_state: State,
+    chain: Option<ServiceRef<ChainReaderService>>,
+    storage: Arc<Storage>,
+}
+
+impl<State> StateRootResolver<State>
+where
+    State: ChainStateAsyncService,
+{
+    /// Return the state root selected by the `option`, default is the latest block state root.
+    async fn resolve(self, option: ContractStateOption) -> anyhow::Result<HashValue> {
+        let ContractStateOption {
+            block_number,
+            block_hash,
+            state_root,
+        } = option;
+        ensure!(
+            [
+                block_number.is_some(),
+                block_hash.is_some(),
+                state_root.is_some()
+            ]
+            .iter()
+            .filter(|is_set| **is_set)
+            .count()
+                <= 1,
+            "Only one of block_number, block_hash and state_root can be set"
+        );
+        let header = match (block_number, block_hash, state_root) {
+            (_, _, Some(state_root)) => {
+                ensure!(
+                    StateNodeStore::get(self.storage.as_ref(), &state_root)?.is_some(),
+                    "Can not find state root {}",
+                    state_root
+                );
+                return Ok(state_root);
+            }
+            (_, Some(block_hash), _) => self
+                .storage
+                .get_block_header_by_hash(block_hash)?
+                .ok_or_else(|| format_err!("Can not find block by hash {}", block_hash))?,
+            (Some(block_number), _, _) => self
+                .chain
+                .ok_or_else(|| format_err!("Query state by block number is not supported"))?
+                .main_block_header_by_number(block_number)
+                .await?
+                .ok_or_else(|| format_err!("Can not find block by number {}", block_number))?,
+            (None, None, None) => return self.chain_state.state_root().await,
+        };
+        Ok(header.state_root())
+    }
+}
+
 pub fn dry_run<S: StateView>(
     state_view: &S,
     txn: DryRunTransaction,
     metrics: Option<VMMetrics>,
+    trace: bool,
 ) -> anyhow::Result<DryRunOutputView> {
-    let (vm_status, output) = starcoin_dev::playground::dry_run(state_view, txn.clone(), metrics)?;
+    let (vm_status, output, trace) = if trace {
+        let (vm_status, output, calls) =
+            starcoin_dev::playground::dry_run_with_trace(state_view, txn.clone(), metrics)?;
+        (vm_status, output, Some(calls))
+    } else {
+        let (vm_status, output) =
+            starcoin_dev::playground::dry_run(state_view, txn.clone(), metrics)?;
+        (vm_status, output, None)
+    };
     let vm_status_explain = vm_status_translator::explain_vm_status(state_view, vm_status)?;
     let mut txn_output: TransactionOutputView = output.into();
 
@@ -321,5 +432,123 @@ pub fn dry_run<S: StateView>(
     Ok(DryRunOutputView {
         explained_status: vm_status_explain,
         txn_output,
+        trace: trace.map(|calls| calls.into_iter().map(Into::into).collect()),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_chain::{BlockChain, ChainReader, ChainWriter};
+    use starcoin_consensus::Consensus;
+    use starcoin_service_registry::{RegistryAsyncService, RegistryService};
+    use starcoin_state_service::ChainStateService;
+    use starcoin_types::block::BlockNumber;
+    use starcoin_types::startup_info::StartupInfo;
+
+    fn state_option(
+        block_number: Option<BlockNumber>,
+        block_hash: Option<HashValue>,
+        state_root: Option<HashValue>,
+    ) -> ContractStateOption {
+        ContractStateOption {
+            block_number,
+            block_hash,
+            state_root,
+        }
+    }
+
+    #[stest::test]
+    async fn test_resolve_state_root() -> anyhow::Result<()> {
+        let config = Arc::new(NodeConfig::random_for_test());
+        let net = config.net();
+        let (storage, chain_info, _, dag) = test_helper::Genesis::init_storage_for_test(net)?;
+        let mut chain = BlockChain::new(
+            net.time_service(),
+            chain_info.head().id(),
+            storage.clone(),
+            None,
+            dag.clone(),
+        )?;
+        for _ in 0..2 {
+            let (template, _) = chain.create_block_template(
+                AccountAddress::random(),
+                None,
+                vec![],
+                vec![],
+                None,
+                None,
+            )?;
+            let block = chain
+                .consensus()
+                .create_block(template, net.time_service().as_ref())?;
+            chain.apply(block)?;
+        }
+        storage.save_startup_info(StartupInfo::new(chain.current_header().id()))?;
+
+        let registry = RegistryService::launch();
+        registry.put_shared(dag).await?;
+        registry.put_shared(config.clone()).await?;
+        registry.put_shared(storage.clone()).await?;
+        let chain_service = registry.register::<ChainReaderService>().await?;
+        let chain_state = registry.register::<ChainStateService>().await?;
+        let resolver = |chain: Option<ServiceRef<ChainReaderService>>| StateRootResolver {
+            chain_state: chain_state.clone(),
+            chain,
+            storage: storage.clone(),
+        };
+
+        let head = chain.current_header();
+        let block = chain.get_header_by_number(1)?.unwrap();
+        assert_ne!(block.state_root(), head.state_root());
+        assert_eq!(
+            resolver(Some(chain_service.clone()))
+                .resolve(ContractStateOption::default())
+                .await?,
+            head.state_root()
+        );
+        assert_eq!(
+            resolver(Some(chain_service.clone()))
+                .resolve(state_option(Some(1), None, None))
+                .await?,
+            block.state_root()
+        );
+        assert_eq!(
+            resolver(None)
+                .resolve(state_option(None, Some(block.id()), None))
+                .await?,
+            block.state_root()
+        );
+        assert_eq!(
+            resolver(None)
+                .resolve(state_option(None, None, Some(block.state_root())))
+                .await?,
+            block.state_root()
+        );
+
+        // the missing targets.
+        assert!(resolver(Some(chain_service.clone()))
+            .resolve(state_option(Some(head.number() + 1), None, None))
+            .await
+            .is_err());
+        assert!(resolver(None)
+            .resolve(state_option(None, Some(HashValue::random()), None))
+            .await
+            .is_err());
+        assert!(resolver(None)
+            .resolve(state_option(None, None, Some(HashValue::random())))
+            .await
+            .is_err());
+        // the block number can not be resolved without the chain service.
+        assert!(resolver(None)
+            .resolve(state_option(Some(1), None, None))
+            .await
+            .is_err());
+        // only one of the options can be set.
+        assert!(resolver(Some(chain_service))
+            .resolve(state_option(Some(1), Some(block.id()), None))
+            .await
+            .is_err());
+        Ok(())
+    }
+}
@@ -1,18 +1,27 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::to_invalid_param_err;
 use crate::module::txfactory_rpc::TxFactoryStatusHandle;
+use crate::module::{map_err, to_invalid_param_err};
+use anyhow::{bail, format_err};
+use futures::future::TryFutureExt;
+use futures::FutureExt;
 use jsonrpc_core::Result;
+use starcoin_chain_service::{ChainAsyncService, ChainReaderService};
 use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_dev::playground::PlaygroudService;
 use starcoin_logger::prelude::LevelFilter;
 use starcoin_logger::structured_log::set_slog_level;
 use starcoin_logger::{LogPattern, LoggerHandle};
 use starcoin_rpc_api::debug::DebugApi;
-use starcoin_rpc_api::types::FactoryAction;
+use starcoin_rpc_api::types::{FactoryAction, TransactionTraceView};
+use starcoin_rpc_api::FutureResult;
 use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::ServiceRef;
+use starcoin_statedb::ChainStateDB;
 use starcoin_types::system_events::GenerateBlockEvent;
+use starcoin_types::transaction::Transaction;
 use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -21,6 +30,7 @@ pub struct DebugRpcImpl {
     config: Arc<NodeConfig>,
     log_handle: Arc<LoggerHandle>,
     bus: ServiceRef<BusService>,
+    tracer: Option<(ServiceRef<ChainReaderService>, PlaygroudService)>,
 }
 
 impl DebugRpcImpl {
@@ -33,8 +43,19 @@ impl DebugRpcImpl {
             config,
             log_handle,
             bus,
+            tracer: None,
         }
     }
+
+    /// Enable `debug.trace_transaction`, the transactions are re-executed by the `playground`.
+    pub fn with_transaction_tracer(
+        mut self,
+        chain: ServiceRef<ChainReaderService>,
+        playground: PlaygroudService,
+    ) -> Self {
+        self.tracer = Some((chain, playground));
+        self
+    }
 }
 
 impl DebugApi for DebugRpcImpl {
@@ -97,4 +118,50 @@ impl DebugApi for DebugRpcImpl {
     fn get_concurrency_level(&self) -> Result<usize> {
         Ok(StarcoinVM::get_concurrency_level())
     }
+
+    fn trace_transaction(&self, txn_hash: HashValue) -> FutureResult<TransactionTraceView> {
+        let tracer = self.tracer.clone();
+        let fut = async move {
+            let (chain, playground) =
+                tracer.ok_or_else(|| format_err!("Transaction tracing is not enabled"))?;
+            let txn = match chain.get_transaction(txn_hash).await? {
+                Some(Transaction::UserTransaction(txn)) => txn,
+                Some(Transaction::BlockMetadata(_)) => {
+                    bail!("Only user transaction can be traced, {} is not", txn_hash)
+                }
+                None => bail!("Can not find transaction by hash {}", txn_hash),
+            };
+            let txn_info = chain
+                .get_transaction_info(txn_hash)
+                .await?
+                .ok_or_else(|| format_err!("Can not find transaction info by hash {}", txn_hash))?;
+            // The first transaction of a block is the block metadata,
+            // so a user transaction always has a previous transaction in the same block.
+            let parent_txn_info = match txn_info.transaction_index.checked_sub(1) {
+                Some(index) => {
+                    chain
+                        .get_txn_info_by_block_and_index(txn_info.block_id, index as u64)
+                        .await?
+                }
+                None => None,
+            }
+            .ok_or_else(|| {
+                format_err!("Can not find the parent transaction info of {}", txn_hash)
+            })?;
+            let state_root = parent_txn_info.state_root_hash();
+            let (vm_status, output, calls) = playground.trace_transaction(state_root, txn)?;
+            let state_view = ChainStateDB::new(playground.state_store(), Some(state_root));
+            let explained_status = vm_status_translator::explain_vm_status(&state_view, vm_status)?;
+            Ok(TransactionTraceView {
+                transaction_hash: txn_hash,
+                block_hash: txn_info.block_id,
+                block_number: txn_info.block_number.into(),
+                explained_status,
+                txn_output: output.into(),
+                calls: calls.into_iter().map(Into::into).collect(),
+            })
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
 }
@@ -0,0 +1,123 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use parking_lot::Mutex;
+use starcoin_chain_service::{ChainAsyncService, FeeHistory};
+use starcoin_crypto::HashValue;
+use std::sync::Arc;
+
+/// The number of recent blocks to sample.
+const ORACLE_BLOCKS: u64 = 20;
+/// The percentile of the gas prices sampled from every block, and of the sampled prices.
+const ORACLE_PERCENTILE: f64 = 60f64;
+/// If the average gas used ratio of the recent blocks is below this, the chain is not congested,
+/// and the min gas price is enough for a transaction to be included.
+const CONGESTED_GAS_USED_RATIO: f64 = 0.5;
+
+/// Suggest the gas unit price for a new transaction.
+pub trait GasPriceOracle: Send + Sync {
+    fn suggest_gas_price(&self) -> BoxFuture<'static, Result<u64>>;
+}
+
+/// A gas price oracle based on the fee history of the recent main chain blocks,
+/// the suggestion is cached until the head block changes.
+pub struct FeeHistoryGasPriceOracle<S>
+where
+    S: ChainAsyncService + 'static,
+{
+    service: S,
+    min_gas_price: u64,
+    cache: Arc<Mutex<Option<(HashValue, u64)>>>,
+}
+
+impl<S> FeeHistoryGasPriceOracle<S>
+where
+    S: ChainAsyncService,
+{
+    pub fn new(service: S, min_gas_price: u64) -> Self {
+        Self {
+            service,
+            min_gas_price,
+            cache: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl<S> GasPriceOracle for FeeHistoryGasPriceOracle<S>
+where
+    S: ChainAsyncService,
+{
+    fn suggest_gas_price(&self) -> BoxFuture<'static, Result<u64>> {
+        let service = self.service.clone();
+        let min_gas_price = self.min_gas_price;
+        let cache = self.cache.clone();
+        async move {
+            let head = service.main_head_header().await?;
+            if let Some((head_id, gas_price)) = *cache.lock() {
+                if head_id == head.id() {
+                    return Ok(gas_price);
+                }
+            }
+            let fee_history = service
+                .get_fee_history(ORACLE_BLOCKS, Some(head.number()), vec![ORACLE_PERCENTILE])
+                .await?;
+            let gas_price = suggest_gas_price(&fee_history, min_gas_price);
+            *cache.lock() = Some((head.id(), gas_price));
+            Ok(gas_price)
+        }
+        .boxed()
+    }
+}
+
+/// Take the `ORACLE_PERCENTILE` of the gas prices of the blocks with user transactions
+/// if the chain is congested, the result is at least `min_gas_price`.
+fn suggest_gas_price(fee_history: &FeeHistory, min_gas_price: u64) -> u64 {
+    let block_count = fee_history.gas_used_ratio.len();
+    if block_count == 0 {
+        return min_gas_price;
+    }
+    let average_gas_used_ratio =
+        fee_history.gas_used_ratio.iter().sum::<f64>() / block_count as f64;
+    if average_gas_used_ratio < CONGESTED_GAS_USED_RATIO {
+        return min_gas_price;
+    }
+    let mut prices: Vec<u64> = fee_history
+        .gas_price_percentiles
+        .iter()
+        .filter_map(|prices| prices.first().copied())
+        .filter(|price| *price > 0)
+        .collect();
+    if prices.is_empty() {
+        return min_gas_price;
+    }
+    prices.sort_unstable();
+    let index = ((prices.len() - 1) as f64 * ORACLE_PERCENTILE / 100f64) as usize;
+    prices[index].max(min_gas_price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggest_gas_price() {
+        let mut fee_history = FeeHistory {
+            oldest_block: 1,
+            gas_used_ratio: vec![0.1, 0.2, 0.1],
+            gas_price_percentiles: vec![vec![5], vec![10], vec![0]],
+        };
+        // not congested
+        assert_eq!(suggest_gas_price(&fee_history, 1), 1);
+
+        fee_history.gas_used_ratio = vec![0.9, 1.0, 0.8, 0.9];
+        fee_history.gas_price_percentiles = vec![vec![5], vec![10], vec![0], vec![20]];
+        assert_eq!(suggest_gas_price(&fee_history, 1), 10);
+        assert_eq!(suggest_gas_price(&fee_history, 15), 15);
+
+        fee_history.gas_price_percentiles = vec![vec![0], vec![0], vec![0], vec![0]];
+        assert_eq!(suggest_gas_price(&fee_history, 1), 1);
+    }
+}
@@ -16,6 +16,7 @@ use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_miner::{MinerService, UpdateSubscriberNumRequest};
 use starcoin_rpc_api::metadata::Metadata;
+use starcoin_rpc_api::types::pubsub::TransactionStatusView;
 use starcoin_rpc_api::types::{BlockView, TransactionEventResponse, TransactionEventView};
 use starcoin_rpc_api::{errors, pubsub::StarcoinPubSub, types::pubsub};
 use starcoin_service_registry::{
@@ -23,11 +24,12 @@ use starcoin_service_registry::{
     ServiceHandler, ServiceRef, ServiceRequest,
 };
 use starcoin_statedb::ChainStateDB;
-use starcoin_storage::Storage;
+use starcoin_storage::{Storage, TransactionStore};
 use starcoin_txpool::TxPoolService;
-use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_txpool_api::{TxPoolSyncService, TxnStatusFullEvent};
 use starcoin_types::filter::Filter;
 use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::transaction::Transaction;
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fmt::Debug;
@@ -131,6 +133,19 @@ impl PubSubImpl {
                 subscriber,
                 errors::invalid_params("events", "Expected a filter object."),
             )),
+            (pubsub::Kind::TransactionStatus, None) => {
+                self.subscribe_txn_status(subscriber, pubsub::TxnStatusFilter::default())
+            }
+            (pubsub::Kind::TransactionStatus, Some(pubsub::Params::TransactionStatus(filter))) => {
+                self.subscribe_txn_status(subscriber, filter)
+            }
+            (pubsub::Kind::TransactionStatus, _) => Err((
+                subscriber,
+                errors::invalid_params(
+                    "transactionStatus",
+                    "Expected no parameters or a transaction status filter object.",
+                ),
+            )),
             (pubsub::Kind::NewMintBlock, _) => self
                 .service
                 .try_send(SubscribeMintBlock(subscriber))
@@ -146,6 +161,25 @@ impl PubSubImpl {
                 }),
         }
     }
+
+    fn subscribe_txn_status(
+        &self,
+        subscriber: Subscriber<pubsub::Result>,
+        filter: pubsub::TxnStatusFilter,
+    ) -> Result<(), (Subscriber<pubsub::Result>, jsonrpc_core::Error)> {
+        self.service
+            .try_send(SubscribeTxnStatus { subscriber, filter })
+            .map_err(|e| {
+                let msg = map_send_err(&e);
+                (
+                    match e {
+                        TrySendError::Disconnected(t) => t.subscriber,
+                        TrySendError::Full(t) => t.subscriber,
+                    },
+                    msg,
+                )
+            })
+    }
 }
 
 impl StarcoinPubSub for PubSubImpl {
@@ -155,8 +189,15 @@ impl StarcoinPubSub for PubSubImpl {
         _meta: Metadata,
         subscriber: Subscriber<pubsub::Result>,
         kind: pubsub::Kind,
-        params: Option<pubsub::Params>,
+        params: Option<serde_json::Value>,
     ) {
+        let params = match pubsub::Params::parse(&kind, params) {
+            Ok(params) => params,
+            Err(error) => {
+                let _ = subscriber.reject(error);
+                return;
+            }
+        };
         if let Err((subscriber, error)) = self.inner_subscribe(_meta, subscriber, kind, params) {
             let _ = subscriber.reject(error);
         }
@@ -206,6 +247,7 @@ pub struct PubSubService {
     new_event_subscribers:
         HashMap<SubscriptionId, mpsc::UnboundedSender<ContractEventNotification>>,
     mint_block_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<MintBlockEvent>>,
+    txn_status_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<TxnStatusNotification>>,
     new_pending_txn_tasks: Arc<RwLock<HashMap<SubscriptionId, AbortHandle>>>,
 }
 
@@ -224,6 +266,7 @@ impl PubSubService {
             new_event_subscribers: Default::default(),
             new_header_subscribers: Default::default(),
             mint_block_subscribers: Default::default(),
+            txn_status_subscribers: Default::default(),
             new_pending_txn_tasks: Arc::new(RwLock::new(HashMap::default())),
         }
     }
@@ -231,9 +274,46 @@ impl PubSubService {
         let id = self.subscriber_id.fetch_add(1, atomic::Ordering::SeqCst);
         SubscriptionId::Number(id)
     }
+
+    fn notify_included_txns(&mut self, block: &ThinBlock) {
+        let block_hash = block.header.id();
+        let block_number = block.header.number();
+        let txns = match self.storage.get_transactions(block.body.clone()) {
+            Ok(txns) => txns,
+            Err(e) => {
+                error!(
+                    "[pubsub] Failed to get transactions of block {}: {:?}",
+                    block_hash, e
+                );
+                return;
+            }
+        };
+        let statuses: Vec<TransactionStatusView> = block
+            .body
+            .iter()
+            .zip(txns)
+            .map(|(txn_hash, txn)| {
+                let sender = match txn {
+                    Some(Transaction::UserTransaction(txn)) => Some(txn.sender()),
+                    _ => None,
+                };
+                TransactionStatusView {
+                    txn_hash: *txn_hash,
+                    sender,
+                    status: pubsub::TxnStatus::Included,
+                    block_hash: Some(block_hash),
+                    block_number: Some(block_number.into()),
+                }
+            })
+            .collect();
+        if !statuses.is_empty() {
+            send_to_all(&mut self.txn_status_subscribers, statuses.into());
+        }
+    }
 }
 
 type NewHeadNotification = Notification<ThinBlock>;
+type TxnStatusNotification = Arc<[TransactionStatusView]>;
 // type NewTxns = Arc<[HashValue]>;
 
 impl ActorService for PubSubService {
@@ -242,6 +322,7 @@ impl ActorService for PubSubService {
         ctx.subscribe::<NewHeadNotification>();
         ctx.subscribe::<ContractEventNotification>();
         ctx.subscribe::<MintBlockEvent>();
+        ctx.add_stream(self.txpool.subscribe_txns());
 
         Ok(())
     }
@@ -249,6 +330,9 @@ impl ActorService for PubSubService {
 
 impl ActorEventHandler<Self, NewHeadNotification> for PubSubService {
     fn handle_event(&mut self, msg: NewHeadNotification, _ctx: &mut ServiceContext<Self>) {
+        if !self.txn_status_subscribers.is_empty() {
+            self.notify_included_txns(&msg.0);
+        }
         send_to_all(&mut self.new_header_subscribers, msg);
     }
 }
@@ -259,6 +343,25 @@ impl ActorEventHandler<Self, ContractEventNotification> for PubSubService {
     }
 }
 
+impl ActorEventHandler<Self, TxnStatusFullEvent> for PubSubService {
+    fn handle_event(&mut self, msg: TxnStatusFullEvent, _ctx: &mut ServiceContext<Self>) {
+        if self.txn_status_subscribers.is_empty() {
+            return;
+        }
+        let statuses: Vec<TransactionStatusView> = msg
+            .iter()
+            .map(|(txn_hash, sender, status)| TransactionStatusView {
+                txn_hash: *txn_hash,
+                sender: Some(*sender),
+                status: (*status).into(),
+                block_hash: None,
+                block_number: None,
+            })
+            .collect();
+        send_to_all(&mut self.txn_status_subscribers, statuses.into());
+    }
+}
+
 impl ActorEventHandler<Self, MintBlockEvent> for PubSubService {
     fn handle_event(&mut self, msg: MintBlockEvent, _ctx: &mut ServiceContext<Self>) {
         send_to_all(&mut self.mint_block_subscribers, msg);
@@ -402,6 +505,32 @@ impl ServiceHandler<Self, SubscribeNewPendingTxns> for PubSubService {
     }
 }
 
+#[derive(Debug)]
+struct SubscribeTxnStatus {
+    subscriber: Subscriber<pubsub::Result>,
+    filter: pubsub::TxnStatusFilter,
+}
+
+impl ServiceRequest for SubscribeTxnStatus {
+    type Response = ();
+}
+
+impl ServiceHandler<Self, SubscribeTxnStatus> for PubSubService {
+    fn handle(&mut self, msg: SubscribeTxnStatus, ctx: &mut ServiceContext<Self>) {
+        let SubscribeTxnStatus { subscriber, filter } = msg;
+        let (sender, receiver) = mpsc::unbounded();
+        let subscriber_id = self.next_id();
+        self.txn_status_subscribers
+            .insert(subscriber_id.clone(), sender);
+        ctx.spawn(run_subscription(
+            receiver,
+            subscriber_id,
+            subscriber,
+            TxnStatusHandler { filter },
+        ));
+    }
+}
+
 #[derive(Debug)]
 struct Unsubscribe(SubscriptionId);
 
@@ -414,6 +543,7 @@ impl ServiceHandler<Self, Unsubscribe> for PubSubService {
         self.new_header_subscribers.remove(&msg.0);
         self.new_event_subscribers.remove(&msg.0);
         self.mint_block_subscribers.remove(&msg.0);
+        self.txn_status_subscribers.remove(&msg.0);
         self.miner_service.do_send(UpdateSubscriberNumRequest {
             number: Some(self.mint_block_subscribers.len() as u32),
         });
@@ -485,6 +615,20 @@ impl EventHandler<Arc<[HashValue]>> for TxnEventHandler {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct TxnStatusHandler {
+    filter: pubsub::TxnStatusFilter,
+}
+
+impl EventHandler<TxnStatusNotification> for TxnStatusHandler {
+    fn handle(&self, msg: TxnStatusNotification) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        msg.iter()
+            .filter(|status| self.filter.matching(status))
+            .map(|status| Ok(pubsub::Result::TransactionStatus(Box::new(status.clone()))))
+            .collect()
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct NewHeadHandler;
 
@@ -16,6 +16,7 @@ use starcoin_crypto::{ed25519::Ed25519PrivateKey, Genesis, HashValue, PrivateKey
 use starcoin_logger::prelude::*;
 use starcoin_rpc_api::metadata::Metadata;
 use starcoin_rpc_api::pubsub::StarcoinPubSub;
+use starcoin_rpc_api::types::pubsub::{TransactionStatusView, TxnStatus};
 use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::RegistryAsyncService;
 use starcoin_state_api::StateReaderExt;
@@ -195,6 +196,70 @@ pub async fn test_subscribe_to_pending_transactions() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+pub async fn test_subscribe_to_txn_status() -> Result<()> {
+    let (txpool_service, _, config, _, registry, _dag) =
+        test_helper::start_txpool_with_miner(1000, true).await;
+    let service = registry
+        .register_by_factory::<PubSubService, PubSubServiceFactory>()
+        .await?;
+    let pubsub = PubSubImpl::new(service);
+    let pubsub = pubsub.to_delegate();
+
+    let mut io = MetaIoHandler::default();
+    io.extend_with(pubsub);
+
+    let mut metadata = Metadata::default();
+    let (sender, mut receiver) = futures::channel::mpsc::unbounded();
+    metadata.session = Some(Arc::new(Session::new(sender)));
+
+    let txn = {
+        let account = AccountInfo::random();
+        let txn = starcoin_transaction_builder::build_transfer_from_association(
+            account.address,
+            0,
+            10000,
+            starcoin_transaction_builder::DEFAULT_EXPIRATION_TIME,
+            config.net(),
+        );
+        txn.as_signed_user_txn()?.clone()
+    };
+
+    // Subscribe the status of txns from an unrelated sender, and the txns from the association.
+    let request = format!(
+        r#"{{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{{"type_name":"transactionStatus"}}, {{"senders": ["{}"]}}], "id": 1}}"#,
+        AccountInfo::random().address
+    );
+    let response = r#"{"jsonrpc":"2.0","result":0,"id":1}"#;
+    let resp = io.handle_request(&request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+    let request = format!(
+        r#"{{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{{"type_name":"transactionStatus"}}, {{"senders": ["{}"]}}], "id": 1}}"#,
+        txn.sender()
+    );
+    let response = r#"{"jsonrpc":"2.0","result":1,"id":1}"#;
+    let resp = io.handle_request(&request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    let txn_id = txn.id();
+    let txn_sender = txn.sender();
+    txpool_service.add_txns(vec![txn]).pop().unwrap().unwrap();
+    let res = timeout(Duration::from_secs(5), receiver.next())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Empty value"))?;
+    let r: Value = serde_json::from_str(&res).unwrap();
+    assert_eq!(r["params"]["subscription"], 1);
+    let status: TransactionStatusView = serde_json::from_value(r["params"]["result"].clone())?;
+    assert_eq!(status.txn_hash, txn_id);
+    assert_eq!(status.sender, Some(txn_sender));
+    assert_eq!(status.status, TxnStatus::Added);
+
+    // No notification for the unrelated sender.
+    let res = timeout(Duration::from_secs(1), receiver.next()).await;
+    assert!(res.is_err());
+    Ok(())
+}
+
 #[stest::test]
 pub async fn test_subscribe_to_mint_block() -> Result<()> {
     let (_txpool_service, .., registry, _dag) =
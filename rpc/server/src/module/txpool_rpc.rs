@@ -1,21 +1,26 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::module::gas_price_oracle::GasPriceOracle;
 use crate::module::{convert_to_rpc_error, map_err};
+use anyhow::format_err;
 use bcs_ext::BCSCodec;
+use futures::TryFutureExt;
 use starcoin_crypto::HashValue;
 use starcoin_rpc_api::types::{SignedUserTransactionView, StrView};
 use starcoin_rpc_api::{txpool::TxPoolApi, FutureResult};
 use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
 use starcoin_types::account_address::AccountAddress;
-use starcoin_types::transaction::SignedUserTransaction;
+use starcoin_types::transaction::{SignedUserTransaction, TransactionPayload};
 use std::convert::TryInto;
+use std::sync::Arc;
 
 pub struct TxPoolRpcImpl<S>
 where
     S: TxPoolSyncService + 'static,
 {
     service: S,
+    gas_price_oracle: Option<Arc<dyn GasPriceOracle>>,
 }
 
 impl<S> TxPoolRpcImpl<S>
@@ -23,7 +28,15 @@ where
     S: TxPoolSyncService,
 {
     pub fn new(service: S) -> Self {
-        Self { service }
+        Self {
+            service,
+            gas_price_oracle: None,
+        }
+    }
+
+    pub fn with_gas_price_oracle(mut self, gas_price_oracle: Arc<dyn GasPriceOracle>) -> Self {
+        self.gas_price_oracle = Some(gas_price_oracle);
+        self
     }
 }
 
@@ -60,9 +73,49 @@ where
         Box::pin(futures::future::ready(result))
     }
 
+    fn replace_transaction(
+        &self,
+        txn_hash: HashValue,
+        txn: SignedUserTransaction,
+    ) -> FutureResult<HashValue> {
+        let new_txn_hash = txn.id();
+        let result = self
+            .service
+            .replace_txn(txn_hash, txn)
+            .map(|_| new_txn_hash)
+            .map_err(map_err);
+        Box::pin(futures::future::ready(result))
+    }
+
+    fn cancel_transaction(
+        &self,
+        txn_hash: HashValue,
+        txn: SignedUserTransaction,
+    ) -> FutureResult<HashValue> {
+        let cancel_payload = TransactionPayload::ScriptFunction(
+            starcoin_transaction_builder::encode_cancel_script_function(txn.sender()),
+        );
+        if txn.payload() != &cancel_payload {
+            return Box::pin(futures::future::err(map_err(format_err!(
+                "The cancel txn should be a zero-value STC transfer from the sender to itself"
+            ))));
+        }
+        self.replace_transaction(txn_hash, txn)
+    }
+
     fn gas_price(&self) -> FutureResult<StrView<u64>> {
-        let gas_price = 1u64;
-        Box::pin(futures::future::ok(gas_price.into()))
+        match self.gas_price_oracle.as_ref() {
+            Some(oracle) => Box::pin(
+                oracle
+                    .suggest_gas_price()
+                    .map_ok(Into::into)
+                    .map_err(map_err),
+            ),
+            None => {
+                let gas_price = 1u64;
+                Box::pin(futures::future::ok(gas_price.into()))
+            }
+        }
     }
 
     fn pending_txns(
@@ -135,4 +188,24 @@ mod tests {
             response
         );
     }
+
+    #[test]
+    fn test_cancel_transaction_with_invalid_payload() {
+        let mut io = IoHandler::new();
+        let txpool_service = MockTxPoolService::new();
+        io.extend_with(TxPoolRpcImpl::new(txpool_service).to_delegate());
+        let txn = SignedUserTransaction::mock();
+        let request = format!(
+            r#"{{"jsonrpc":"2.0","method":"txpool.cancel_transaction","params":["{}",{}],"id":0}}"#,
+            HashValue::random(),
+            serde_json::to_string(&txn).expect("txn to json should success."),
+        );
+        let response: serde_json::Value = serde_json::from_str(
+            block_on(io.handle_request(request.as_str()))
+                .unwrap()
+                .as_str(),
+        )
+        .unwrap();
+        assert!(response.get("error").is_some());
+    }
 }
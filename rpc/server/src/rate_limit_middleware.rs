@@ -10,7 +10,7 @@ use jsonrpc_core::middleware::NoopCallFuture;
 use starcoin_config::{ApiQuotaConfig, ApiQuotaConfiguration, QuotaDuration};
 use starcoin_rpc_api::metadata::Metadata;
 
-struct QuotaWrapper(Quota);
+pub(crate) struct QuotaWrapper(pub Quota);
 
 impl From<ApiQuotaConfig> for QuotaWrapper {
     fn from(c: ApiQuotaConfig) -> Self {
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::api_registry::ApiRegistry;
+use crate::auth::RpcAuth;
 use crate::extractors::{RpcExtractor, WsExtractor};
 use anyhow::Result;
 use futures::stream::*;
@@ -36,6 +37,7 @@ use std::sync::Arc;
 pub struct RpcService {
     config: Arc<NodeConfig>,
     api_registry: ApiRegistry,
+    auth: Option<Arc<RpcAuth>>,
     ipc: Option<jsonrpc_ipc_server::Server>,
     http: Option<jsonrpc_http_server::Server>,
     tcp: Option<jsonrpc_tcp_server::Server>,
@@ -59,9 +61,11 @@ impl ActorService for RpcService {
 
 impl RpcService {
     pub fn new(config: Arc<NodeConfig>, api_registry: ApiRegistry) -> Self {
+        let auth = RpcAuth::new(&config.rpc.auth).map(Arc::new);
         Self {
             config,
             api_registry,
+            auth,
             ipc: None,
             http: None,
             tcp: None,
@@ -173,10 +177,13 @@ impl RpcService {
         Ok(if let Some(addr) = self.config.rpc.get_http_address() {
             let address = addr.into();
             let apis = self.config.rpc.http.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
+            let io_handler = self
+                .api_registry
+                .get_apis_with_auth(apis, self.auth.clone());
             let http = jsonrpc_http_server::ServerBuilder::new(io_handler)
                 .meta_extractor(RpcExtractor {
                     http_ip_headers: self.config.rpc.http.ip_headers(),
+                    auth: self.auth.clone(),
                 })
                 .cors(DomainsValidation::AllowOnly(vec![
                     AccessControlAllowOrigin::Null,
@@ -213,9 +220,13 @@ impl RpcService {
         Ok(if let Some(addr) = self.config.rpc.get_ws_address() {
             let address = addr.into();
             let apis = self.config.rpc.ws.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
+            let io_handler = self
+                .api_registry
+                .get_apis_with_auth(apis, self.auth.clone());
             let ws_server = jsonrpc_ws_server::ServerBuilder::new(io_handler)
-                .session_meta_extractor(WsExtractor)
+                .session_meta_extractor(WsExtractor {
+                    auth: self.auth.clone(),
+                })
                 .max_payload(self.config.rpc.ws.max_request_body_size())
                 .start(&address)?;
             info!("Rpc: websocket server start at: {}", address);
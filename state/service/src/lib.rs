@@ -1,6 +1,8 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+mod prune;
 mod service;
 
+pub use prune::StatePruneService;
 pub use service::ChainStateService;
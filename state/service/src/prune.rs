@@ -0,0 +1,115 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{format_err, Result};
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_config::NodeConfig;
+use starcoin_logger::prelude::*;
+use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
+use starcoin_storage::{BlockStore, Storage, Store};
+use starcoin_types::block::{BlockInfo, BlockNumber};
+use starcoin_types::system_events::NewHeadBlock;
+use std::sync::Arc;
+
+/// Max blocks pruned when handling one `NewHeadBlock`, avoid blocking the service too long.
+const MAX_PRUNE_BLOCKS_PER_ROUND: u64 = 64;
+
+/// StatePruneService watch `NewHeadBlock` and delete the state nodes which are only referenced
+/// by the states out of the retention window.
+/// The states are pruned in the order of the main chain, the forks of a pruned state are
+/// abandoned and pruned together. The states of the dag blocks are kept, as a dag block's state
+/// is not derived from the previous block of main chain, only their stale node index is dropped.
+pub struct StatePruneService {
+    storage: Arc<Storage>,
+    retain_blocks: u64,
+}
+
+impl StatePruneService {
+    pub fn new(storage: Arc<Storage>, retain_blocks: u64) -> Self {
+        Self {
+            storage,
+            retain_blocks,
+        }
+    }
+
+    /// Prune the states which are out of the retention window of `head_block_info`,
+    /// return the number of deleted nodes.
+    pub fn prune(&self, head_number: BlockNumber, head_block_info: &BlockInfo) -> Result<usize> {
+        let target = match head_number.checked_sub(self.retain_blocks) {
+            Some(target) => target,
+            None => return Ok(0),
+        };
+        let pruned_number = self.storage.get_state_pruned_number()?.unwrap_or(0);
+        if pruned_number >= target {
+            return Ok(0);
+        }
+        let end = std::cmp::min(target, pruned_number + MAX_PRUNE_BLOCKS_PER_ROUND);
+        let block_accumulator = MerkleAccumulator::new_with_info(
+            head_block_info.block_accumulator_info.clone(),
+            self.storage
+                .get_accumulator_store(AccumulatorStoreType::Block),
+        );
+        let mut deleted = 0;
+        for number in pruned_number + 1..=end {
+            let block_id = block_accumulator
+                .get_leaf(number)?
+                .ok_or_else(|| format_err!("Can not find block id by number: {}", number))?;
+            let header = self
+                .storage
+                .get_block_header_by_hash(block_id)?
+                .ok_or_else(|| format_err!("Can not find block header by id: {}", block_id))?;
+            if header.is_single() {
+                deleted += self
+                    .storage
+                    .prune_stale_state_nodes(number, header.state_root())?;
+            } else {
+                debug!(
+                    "Keep the state of dag block {}({}), its state is not derived from the previous main block.",
+                    number, block_id
+                );
+                self.storage
+                    .drop_stale_node_index(number, header.state_root())?;
+            }
+        }
+        Ok(deleted)
+    }
+}
+
+impl ServiceFactory<Self> for StatePruneService {
+    fn create(ctx: &mut ServiceContext<Self>) -> Result<Self> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let storage = ctx.get_shared::<Arc<Storage>>()?;
+        let retain_blocks = config
+            .storage
+            .state_prune_retain_blocks()
+            .ok_or_else(|| format_err!("State prune is not enabled by config."))?;
+        Ok(Self::new(storage, retain_blocks))
+    }
+}
+
+impl ActorService for StatePruneService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.subscribe::<NewHeadBlock>();
+        Ok(())
+    }
+
+    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.unsubscribe::<NewHeadBlock>();
+        Ok(())
+    }
+}
+
+impl EventHandler<Self, NewHeadBlock> for StatePruneService {
+    fn handle_event(&mut self, msg: NewHeadBlock, _ctx: &mut ServiceContext<Self>) {
+        let head_number = msg.executed_block.header().number();
+        match self.prune(head_number, msg.executed_block.block_info()) {
+            Ok(deleted) => {
+                if deleted > 0 {
+                    debug!("State prune deleted {} state nodes", deleted);
+                }
+            }
+            Err(e) => error!("State prune failed at head {}: {:?}", head_number, e),
+        }
+    }
+}
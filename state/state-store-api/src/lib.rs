@@ -52,9 +52,52 @@ impl Serialize for StateNode {
     }
 }
 
+/// The nodes created and made stale by flushing a state update, used by state pruning.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateNodeChanges {
+    /// The written nodes, a node appears once for every reference.
+    pub created_nodes: Vec<HashValue>,
+    /// The nodes of the previous state which are no longer referenced.
+    pub stale_nodes: Vec<HashValue>,
+}
+
+impl StateNodeChanges {
+    pub fn extend(&mut self, other: Self) {
+        self.created_nodes.extend(other.created_nodes);
+        self.stale_nodes.extend(other.stale_nodes);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.created_nodes.is_empty() && self.stale_nodes.is_empty()
+    }
+}
+
 pub trait StateNodeStore: std::marker::Send + std::marker::Sync {
     fn get(&self, hash: &HashValue) -> Result<Option<StateNode>>;
     fn put(&self, key: HashValue, node: StateNode) -> Result<()>;
     fn write_nodes(&self, nodes: BTreeMap<HashValue, StateNode>) -> Result<()>;
     fn get_table_info(&self, address: AccountAddress) -> Result<Option<TableInfo>>;
+
+    /// Write the nodes of a flushed state tree update, `refs` is how many times each node is
+    /// referenced by the update.
+    /// Stores which do not support state pruning just write the nodes.
+    fn write_nodes_with_refs(
+        &self,
+        nodes: BTreeMap<HashValue, StateNode>,
+        _refs: BTreeMap<HashValue, u64>,
+    ) -> Result<()> {
+        self.write_nodes(nodes)
+    }
+
+    /// Save the node changes of the state `state_root` derived from `parent_state_root`,
+    /// the stale nodes can be pruned once the parent state is out of the retention window,
+    /// and the created nodes are released if the state is on an abandoned fork.
+    fn save_stale_node_index(
+        &self,
+        _parent_state_root: HashValue,
+        _state_root: HashValue,
+        _changes: StateNodeChanges,
+    ) -> Result<()> {
+        Ok(())
+    }
 }
@@ -9,7 +9,7 @@ mod state_tree;
 #[cfg(test)]
 mod state_tree_test;
 
-pub use starcoin_state_store_api::{StateNode, StateNodeStore};
+pub use starcoin_state_store_api::{StateNode, StateNodeChanges, StateNodeStore};
 pub use state_tree::AccountStateSetIterator;
 pub use state_tree::StateTree;
 pub use state_tree::StorageTreeReader;
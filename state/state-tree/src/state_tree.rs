@@ -121,6 +121,11 @@ where
         self.cache.lock().root_hash
     }
 
+    /// The root hash of the state flushed into storage.
+    pub fn storage_root_hash(&self) -> HashValue {
+        *self.storage_root_hash.read()
+    }
+
     /// put a kv pair into tree.
     /// Users need to hash the origin key into a fixed-length(here is 256bit) HashValue,
     /// and use it as the `key_hash`.
@@ -203,6 +208,12 @@ where
 
     /// commit the state change into underline storage.
     pub fn flush(&self) -> Result<()> {
+        self.flush_with_node_changes().map(|_| ())
+    }
+
+    /// commit the state change into underline storage,
+    /// and return the nodes created and made stale by the flushed changes.
+    pub fn flush_with_node_changes(&self) -> Result<StateNodeChanges> {
         let change_set_list = {
             let mut cache_guard = self.cache.lock();
             cache_guard.split_off_idx = Some(cache_guard.change_set_list.len());
@@ -213,21 +224,32 @@ where
         // when self::commit call self::updates(&self, updates: Vec<(K, Option<Blob>)>)
         // the param updates is empty cause this situation
         if change_set_list.is_empty() {
-            return Ok(());
+            return Ok(StateNodeChanges::default());
         }
         let mut root_hash = HashValue::default();
         let mut node_map = BTreeMap::new();
+        // the same node may be created by different change sets, count every reference.
+        let mut node_refs = BTreeMap::new();
+        let mut changes = StateNodeChanges::default();
         for (hash, change_sets) in change_set_list.into_iter() {
             for (nk, n) in change_sets.node_batch.into_iter() {
+                *node_refs.entry(nk).or_insert(0u64) += 1;
+                changes.created_nodes.push(nk);
                 node_map.insert(nk, n.try_into()?);
             }
+            changes.stale_nodes.extend(
+                change_sets
+                    .stale_node_index_batch
+                    .into_iter()
+                    .map(|index| index.node_key),
+            );
             root_hash = hash;
         }
-        self.storage.write_nodes(node_map)?;
+        self.storage.write_nodes_with_refs(node_map, node_refs)?;
         // and then advance the storage root hash
         *self.storage_root_hash.write() = root_hash;
         self.cache.lock().reset(root_hash);
-        Ok(())
+        Ok(changes)
     }
 
     /// Dump tree to state set.
@@ -368,3 +368,38 @@ pub fn test_state_multi_commit_and_flush() -> Result<()> {
     assert_eq!(state.get(&hash_value3)?, None);
     Ok(())
 }
+
+#[test]
+pub fn test_state_prune_stale_nodes() -> Result<()> {
+    let tmpdir = starcoin_config::temp_dir();
+    let instance = StorageInstance::new_db_instance(DBStorage::new(
+        tmpdir.path(),
+        RocksdbConfig::default(),
+        None,
+    )?);
+    let mut storage = Storage::new(instance)?;
+    storage.enable_state_prune()?;
+    let state = StateTree::new(Arc::new(storage.clone()), None);
+    let hash_value1 = HashValueKey(HashValue::random());
+    let hash_value2 = HashValueKey(HashValue::random());
+    state.put(hash_value1, vec![1u8]);
+    state.put(hash_value2, vec![2u8]);
+    state.commit()?;
+    state.flush()?;
+    let root_hash1 = state.root_hash();
+
+    state.put(hash_value1, vec![3u8]);
+    state.commit()?;
+    let changes = state.flush_with_node_changes()?;
+    assert!(!changes.stale_nodes.is_empty());
+    let root_hash2 = state.root_hash();
+    storage.save_stale_node_index(root_hash1, root_hash2, changes)?;
+    assert!(storage.prune_stale_state_nodes(1, root_hash2)? > 0);
+
+    let state2 = StateTree::new(Arc::new(storage.clone()), Some(root_hash2));
+    assert_eq!(state2.get(&hash_value1)?, Some(vec![3u8]));
+    assert_eq!(state2.get(&hash_value2)?, Some(vec![2u8]));
+    let state1 = StateTree::<HashValueKey>::new(Arc::new(storage), Some(root_hash1));
+    assert!(state1.get(&hash_value1).is_err());
+    Ok(())
+}
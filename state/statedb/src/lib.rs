@@ -15,7 +15,7 @@ pub use starcoin_state_api::{ChainStateReader, ChainStateWriter, StateProof, Sta
 use starcoin_state_api::{StateWithTableItemProof, TABLE_PATH_LIST};
 use starcoin_state_tree::mock::MockStateNodeStore;
 use starcoin_state_tree::AccountStateSetIterator;
-use starcoin_state_tree::{StateNodeStore, StateTree};
+use starcoin_state_tree::{StateNodeChanges, StateNodeStore, StateTree};
 use starcoin_types::write_set::{WriteOp, WriteSet, WriteSetMut};
 use starcoin_types::{
     access_path::{AccessPath, DataType},
@@ -185,13 +185,14 @@ impl AccountStateObject {
         Ok(self.to_state())
     }
 
-    pub fn flush(&self) -> Result<()> {
-        self.resource_tree.lock().flush()?;
+    /// flush the account's trees, and return the nodes created and made stale.
+    pub fn flush(&self) -> Result<StateNodeChanges> {
+        let mut changes = self.resource_tree.lock().flush_with_node_changes()?;
         if let Some(code_tree) = self.code_tree.lock().as_ref() {
-            code_tree.flush()?;
+            changes.extend(code_tree.flush_with_node_changes()?);
         }
 
-        Ok(())
+        Ok(changes)
     }
 
     fn to_state_set(&self) -> Result<AccountStateSet> {
@@ -707,29 +708,39 @@ impl ChainStateWriter for ChainStateDB {
 
     /// flush data to db.
     fn flush(&self) -> Result<()> {
+        let parent_state_root = self.state_tree.storage_root_hash();
+        let mut changes = StateNodeChanges::default();
         //cache flush
         let mut locks_table_handle = self.updates_table_handle.write();
         for h in locks_table_handle.iter() {
             let table_handle_state_object = self.get_table_handle_state_object(h)?;
-            table_handle_state_object.flush()?;
+            changes.extend(table_handle_state_object.flush()?);
         }
         locks_table_handle.clear();
 
         for idx in self.update_table_handle_idx_list.lock().iter() {
             let state_tree_table_handle = self.get_state_tree_table_handles(*idx)?;
-            state_tree_table_handle.flush()?;
+            changes.extend(state_tree_table_handle.flush_with_node_changes()?);
         }
         self.update_table_handle_idx_list.lock().clear();
 
         let mut locks = self.updates.write();
         for address in locks.iter() {
             let account_state_object = self.get_account_state_object(address, false)?;
-            account_state_object.flush()?;
+            changes.extend(account_state_object.flush()?);
         }
         locks.clear();
 
         // self tree flush
-        self.state_tree.flush()
+        changes.extend(self.state_tree.flush_with_node_changes()?);
+        if !changes.is_empty() {
+            self.store.save_stale_node_index(
+                parent_state_root,
+                self.state_tree.root_hash(),
+                changes,
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -765,9 +776,8 @@ impl TableHandleStateObject {
         Ok(())
     }
 
-    pub fn flush(&self) -> Result<()> {
-        self.state_tree.lock().flush()?;
-        Ok(())
+    pub fn flush(&self) -> Result<StateNodeChanges> {
+        self.state_tree.lock().flush_with_node_changes()
     }
 
     pub fn root_hash(&self) -> HashValue {
@@ -0,0 +1,253 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::chain_info::ChainInfoStorage;
+use crate::storage::{
+    CodecKVStore, CodecWriteBatch, InnerStore, KeyCodec, StorageInstance, WriteBatchWithColumn,
+};
+use crate::{
+    define_storage, ADDRESS_TXN_COUNT_PREFIX_NAME, ADDRESS_TXN_INDEX_PREFIX_NAME,
+    CHAIN_INFO_PREFIX_NAME,
+};
+use anyhow::{ensure, Result};
+use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+
+/// The key of the address transaction index, `seq` is the position of the transaction
+/// in the transaction list of the `address`, start from 0.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct AddressTxnIndexKey {
+    pub address: AccountAddress,
+    pub seq: u64,
+}
+
+impl AddressTxnIndexKey {
+    pub fn new(address: AccountAddress, seq: u64) -> Self {
+        Self { address, seq }
+    }
+}
+
+impl KeyCodec for AddressTxnIndexKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut bytes = self.address.to_vec();
+        bytes.extend_from_slice(&self.seq.to_be_bytes());
+        Ok(bytes)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == AccountAddress::LENGTH + 8,
+            "Invalid address txn index key length: {}",
+            data.len()
+        );
+        let (address, seq) = data.split_at(AccountAddress::LENGTH);
+        let mut seq_bytes = [0u8; 8];
+        seq_bytes.copy_from_slice(seq);
+        Ok(Self {
+            address: AccountAddress::try_from(address).map_err(anyhow::Error::new)?,
+            seq: u64::from_be_bytes(seq_bytes),
+        })
+    }
+}
+
+impl KeyCodec for AccountAddress {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Self::try_from(data).map_err(anyhow::Error::new)
+    }
+}
+
+define_storage!(
+    AddressTxnIndexStorage,
+    AddressTxnIndexKey,
+    HashValue,
+    ADDRESS_TXN_INDEX_PREFIX_NAME
+);
+
+define_storage!(
+    AddressTxnCountStorage,
+    AccountAddress,
+    u64,
+    ADDRESS_TXN_COUNT_PREFIX_NAME
+);
+
+/// Index from account address to the hash of the transactions sent by or affecting the address,
+/// the transactions of an address are kept in the order of the main chain.
+/// The transactions, the counts and the index head are updated in one write batch.
+pub trait AddressTxnIndexStore {
+    /// Append the `(address, txn_hash)` pairs of the block `block_id` to the end of the address
+    /// transaction lists, and move the index head to the block.
+    fn append_address_txns(
+        &self,
+        block_id: HashValue,
+        address_txns: Vec<(AccountAddress, HashValue)>,
+    ) -> Result<()>;
+
+    /// Remove the `(address, txn_hash)` pairs of a block from the end of the address transaction
+    /// lists, and move the index head back to the block's parent `parent_id`.
+    /// The pairs should be given in the order they were appended.
+    fn remove_address_txns(
+        &self,
+        parent_id: HashValue,
+        address_txns: Vec<(AccountAddress, HashValue)>,
+    ) -> Result<()>;
+
+    /// Get the number of transactions indexed for the `address`.
+    fn get_address_txn_count(&self, address: AccountAddress) -> Result<u64>;
+
+    /// Get at most `max_size` transaction hashes of the `address` from position `start_seq`,
+    /// in descending order if `reverse` is true.
+    fn get_address_txns(
+        &self,
+        address: AccountAddress,
+        start_seq: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<HashValue>>;
+
+    /// Get the id of the last block whose transactions are indexed.
+    fn get_address_txn_index_head(&self) -> Result<Option<HashValue>>;
+}
+
+#[derive(Clone)]
+pub struct AddressTxnIndex {
+    index_storage: AddressTxnIndexStorage,
+    count_storage: AddressTxnCountStorage,
+    instance: StorageInstance,
+}
+
+impl AddressTxnIndex {
+    pub fn new(instance: StorageInstance) -> Self {
+        Self {
+            index_storage: AddressTxnIndexStorage::new(instance.clone()),
+            count_storage: AddressTxnCountStorage::new(instance.clone()),
+            instance,
+        }
+    }
+
+    fn write_batch(
+        &self,
+        head: HashValue,
+        index_batch: CodecWriteBatch<AddressTxnIndexKey, HashValue>,
+        counts: BTreeMap<AccountAddress, u64>,
+    ) -> Result<()> {
+        let mut batch = WriteBatchWithColumn::new();
+        batch.push(ADDRESS_TXN_INDEX_PREFIX_NAME, index_batch)?;
+        batch.push(
+            ADDRESS_TXN_COUNT_PREFIX_NAME,
+            CodecWriteBatch::new_puts(counts.into_iter().collect()),
+        )?;
+        batch.push(
+            CHAIN_INFO_PREFIX_NAME,
+            CodecWriteBatch::new_puts(vec![(
+                ChainInfoStorage::ADDRESS_TXN_INDEX_HEAD_KEY
+                    .as_bytes()
+                    .to_vec(),
+                head,
+            )]),
+        )?;
+        self.instance.write_batch_across_cf(batch)
+    }
+
+    fn get_counts(
+        &self,
+        address_txns: &[(AccountAddress, HashValue)],
+    ) -> Result<BTreeMap<AccountAddress, u64>> {
+        let addresses: Vec<AccountAddress> = address_txns
+            .iter()
+            .map(|(address, _)| *address)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let counts = self.count_storage.multiple_get(addresses.clone())?;
+        Ok(addresses
+            .into_iter()
+            .zip(counts)
+            .map(|(address, count)| (address, count.unwrap_or(0)))
+            .collect())
+    }
+
+    pub fn append_address_txns(
+        &self,
+        block_id: HashValue,
+        address_txns: Vec<(AccountAddress, HashValue)>,
+    ) -> Result<()> {
+        let mut counts = self.get_counts(&address_txns)?;
+        let mut index_batch = CodecWriteBatch::new();
+        for (address, txn_hash) in address_txns {
+            let count = counts.entry(address).or_insert(0);
+            index_batch.put(AddressTxnIndexKey::new(address, *count), txn_hash)?;
+            *count += 1;
+        }
+        self.write_batch(block_id, index_batch, counts)
+    }
+
+    pub fn remove_address_txns(
+        &self,
+        parent_id: HashValue,
+        address_txns: Vec<(AccountAddress, HashValue)>,
+    ) -> Result<()> {
+        let mut counts = self.get_counts(&address_txns)?;
+        let mut index_batch = CodecWriteBatch::new();
+        for (address, txn_hash) in address_txns.into_iter().rev() {
+            let count = counts.entry(address).or_insert(0);
+            ensure!(
+                *count > 0,
+                "The txn {} of address {} is not indexed",
+                txn_hash,
+                address
+            );
+            *count -= 1;
+            let key = AddressTxnIndexKey::new(address, *count);
+            let indexed = self.index_storage.get(key)?;
+            ensure!(
+                indexed == Some(txn_hash),
+                "The last indexed txn of address {} is {:?}, not {}",
+                address,
+                indexed,
+                txn_hash
+            );
+            index_batch.delete(key)?;
+        }
+        self.write_batch(parent_id, index_batch, counts)
+    }
+
+    pub fn get_address_txn_count(&self, address: AccountAddress) -> Result<u64> {
+        Ok(self.count_storage.get(address)?.unwrap_or(0))
+    }
+
+    pub fn get_address_txns(
+        &self,
+        address: AccountAddress,
+        start_seq: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<HashValue>> {
+        let count = self.get_address_txn_count(address)?;
+        if count == 0 || start_seq >= count {
+            return Ok(vec![]);
+        }
+        let seqs: Vec<u64> = if reverse {
+            let end = start_seq.saturating_add(1);
+            (end.saturating_sub(max_size)..end).rev().collect()
+        } else {
+            let end = std::cmp::min(count, start_seq.saturating_add(max_size));
+            (start_seq..end).collect()
+        };
+        let keys = seqs
+            .into_iter()
+            .map(|seq| AddressTxnIndexKey::new(address, seq))
+            .collect();
+        Ok(self
+            .index_storage
+            .multiple_get(keys)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+}
@@ -1,9 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::storage::{CodecWriteBatch, KeyCodec, ValueCodec, WriteOp};
+use crate::storage::{CodecWriteBatch, ColumnFamilyName, KeyCodec, ValueCodec, WriteOp};
 use anyhow::Result;
-use std::convert::TryFrom;
+use std::convert::{TryFrom, TryInto};
 
 pub type WriteBatch = GWriteBatch<Vec<u8>, Vec<u8>>;
 
@@ -56,3 +56,38 @@ where
         Ok(Self::new_with_rows(rows?))
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct WriteBatchData {
+    pub column: ColumnFamilyName,
+    pub row_data: WriteBatch,
+}
+
+/// The write batches of several column families, which are written atomically.
+#[derive(Debug, Default, Clone)]
+pub struct WriteBatchWithColumn {
+    pub data: Vec<WriteBatchData>,
+}
+
+impl WriteBatchWithColumn {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the batch of the column family `column`.
+    pub fn push<K, V>(
+        &mut self,
+        column: ColumnFamilyName,
+        batch: CodecWriteBatch<K, V>,
+    ) -> Result<()>
+    where
+        K: KeyCodec,
+        V: ValueCodec,
+    {
+        self.data.push(WriteBatchData {
+            column,
+            row_data: batch.try_into()?,
+        });
+        Ok(())
+    }
+}
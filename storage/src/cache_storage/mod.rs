@@ -3,7 +3,7 @@
 
 use crate::batch::GWriteBatch;
 use crate::{
-    batch::WriteBatch,
+    batch::{WriteBatch, WriteBatchWithColumn},
     metrics::{record_metrics, StorageMetrics},
     storage::{InnerStore, WriteOp},
 };
@@ -111,6 +111,13 @@ impl InnerStore for CacheStorage {
         self.write_batch(prefix_name, batch)
     }
 
+    fn write_batch_across_cf(&self, batch: WriteBatchWithColumn) -> Result<()> {
+        for data in batch.data {
+            self.write_batch(data.column, data.row_data)?;
+        }
+        Ok(())
+    }
+
     fn multi_get(&self, prefix_name: &str, keys: Vec<Vec<u8>>) -> Result<Vec<Option<Vec<u8>>>> {
         let composed_keys = keys
             .into_iter()
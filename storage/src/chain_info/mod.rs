@@ -1,10 +1,11 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::storage::{ColumnFamily, InnerStorage, KVStore};
+use crate::storage::{ColumnFamily, InnerStorage, KVStore, KeyCodec};
 use crate::{StorageVersion, CHAIN_INFO_PREFIX_NAME};
 use anyhow::Result;
 use starcoin_crypto::HashValue;
+use starcoin_types::block::BlockNumber;
 use starcoin_types::startup_info::{BarnardHardFork, DragonHardFork, SnapshotRange, StartupInfo};
 use std::convert::{TryFrom, TryInto};
 
@@ -29,6 +30,11 @@ impl ChainInfoStorage {
     const SNAPSHOT_RANGE_KEY: &'static str = "snapshot_height";
     const BARNARD_HARD_FORK: &'static str = "barnard_hard_fork";
     const DRAGON_HARD_FORK: &'static str = "dragon_hard_fork";
+    const STATE_PRUNE_ENABLED_KEY: &'static str = "state_prune_enabled";
+    pub(crate) const STATE_PRUNED_NUMBER_KEY: &'static str = "state_pruned_number";
+    const FAST_SYNC_PIVOT_KEY: &'static str = "fast_sync_pivot";
+    const LIGHT_HEAD_KEY: &'static str = "light_head";
+    pub(crate) const ADDRESS_TXN_INDEX_HEAD_KEY: &'static str = "address_txn_index_head";
 
     pub fn get_startup_info(&self) -> Result<Option<StartupInfo>> {
         self.get(Self::STARTUP_INFO_KEY.as_bytes())
@@ -127,4 +133,72 @@ impl ChainInfoStorage {
             dragon_hard_fork.try_into()?,
         )
     }
+
+    /// Once state prune is enabled, node references are tracked for ever,
+    /// otherwise the reference count of the tracked nodes become incorrect.
+    pub fn is_state_prune_enabled(&self) -> Result<bool> {
+        Ok(self
+            .get(Self::STATE_PRUNE_ENABLED_KEY.as_bytes())?
+            .is_some())
+    }
+
+    pub fn enable_state_prune(&self) -> Result<()> {
+        self.put_sync(Self::STATE_PRUNE_ENABLED_KEY.as_bytes().to_vec(), vec![1])
+    }
+
+    pub fn get_state_pruned_number(&self) -> Result<Option<BlockNumber>> {
+        self.get(Self::STATE_PRUNED_NUMBER_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(BlockNumber::decode_key(bytes.as_slice())?)),
+                None => Ok(None),
+            })
+    }
+
+    pub fn save_state_pruned_number(&self, number: BlockNumber) -> Result<()> {
+        self.put_sync(
+            Self::STATE_PRUNED_NUMBER_KEY.as_bytes().to_vec(),
+            number.encode_key()?,
+        )
+    }
+
+    /// The pivot block of an unfinished fast sync, it is removed once the fast sync is finished.
+    pub fn get_fast_sync_pivot(&self) -> Result<Option<HashValue>> {
+        self.get(Self::FAST_SYNC_PIVOT_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(HashValue::from_slice(bytes.as_slice())?)),
+                None => Ok(None),
+            })
+    }
+
+    pub fn save_fast_sync_pivot(&self, pivot: HashValue) -> Result<()> {
+        self.put_sync(
+            Self::FAST_SYNC_PIVOT_KEY.as_bytes().to_vec(),
+            pivot.to_vec(),
+        )
+    }
+
+    pub fn remove_fast_sync_pivot(&self) -> Result<()> {
+        self.remove(Self::FAST_SYNC_PIVOT_KEY.as_bytes().to_vec())
+    }
+
+    /// The latest verified block header of the light sync.
+    pub fn get_light_head(&self) -> Result<Option<HashValue>> {
+        self.get(Self::LIGHT_HEAD_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(HashValue::from_slice(bytes.as_slice())?)),
+                None => Ok(None),
+            })
+    }
+
+    pub fn save_light_head(&self, head: HashValue) -> Result<()> {
+        self.put_sync(Self::LIGHT_HEAD_KEY.as_bytes().to_vec(), head.to_vec())
+    }
+
+    pub fn get_address_txn_index_head(&self) -> Result<Option<HashValue>> {
+        self.get(Self::ADDRESS_TXN_INDEX_HEAD_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(HashValue::from_slice(bytes.as_slice())?)),
+                None => Ok(None),
+            })
+    }
 }
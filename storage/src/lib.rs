@@ -4,12 +4,19 @@
 use crate::accumulator::{
     AccumulatorStorage, BlockAccumulatorStorage, TransactionAccumulatorStorage,
 };
+use crate::address_txn_index::{AddressTxnIndex, AddressTxnIndexStore};
 use crate::block::BlockStorage;
 use crate::block_info::{BlockInfoStorage, BlockInfoStore};
 use crate::chain_info::ChainInfoStorage;
 use crate::contract_event::ContractEventStorage;
-use crate::state_node::StateStorage;
-use crate::storage::{CodecKVStore, CodecWriteBatch, ColumnFamilyName, StorageInstance};
+use crate::state_node::{
+    StaleNodeIndex, StaleNodeIndexStorage, StateChildIndexStorage, StateNodeRefCountStorage,
+    StateStorage,
+};
+use crate::storage::{
+    CodecKVStore, CodecWriteBatch, ColumnFamilyName, InnerStore, KeyCodec, StorageInstance,
+    WriteBatchWithColumn,
+};
 use crate::table_info::{TableInfoStorage, TableInfoStore};
 use crate::transaction::TransactionStorage;
 use crate::transaction_info::{TransactionInfoHashStorage, TransactionInfoStorage};
@@ -18,15 +25,17 @@ use block::DagSyncBlock;
 use network_p2p_types::peer_id::PeerId;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_accumulator::AccumulatorTreeStore;
 use starcoin_crypto::HashValue;
-use starcoin_state_store_api::{StateNode, StateNodeStore};
+use starcoin_logger::prelude::debug;
+use starcoin_state_store_api::{StateNode, StateNodeChanges, StateNodeStore};
 use starcoin_types::contract_event::ContractEvent;
 use starcoin_types::startup_info::{ChainInfo, ChainStatus, SnapshotRange};
 use starcoin_types::transaction::{RichTransactionInfo, Transaction};
 use starcoin_types::{
-    block::{Block, BlockBody, BlockHeader, BlockInfo},
+    block::{Block, BlockBody, BlockHeader, BlockInfo, BlockNumber},
     startup_info::StartupInfo,
 };
 //use starcoin_vm_types::state_store::table::{TableHandle, TableInfo};
@@ -39,6 +48,7 @@ pub use upgrade::BARNARD_HARD_FORK_HASH;
 pub use upgrade::BARNARD_HARD_FORK_HEIGHT;
 
 pub mod accumulator;
+pub mod address_txn_index;
 pub mod batch;
 pub mod block;
 pub mod block_info;
@@ -84,6 +94,11 @@ pub const BLOCK_PREFIX_NAME_V2: ColumnFamilyName = "block_v2";
 pub const BLOCK_HEADER_PREFIX_NAME_V2: ColumnFamilyName = "block_header_v2";
 pub const FAILED_BLOCK_PREFIX_NAME_V2: ColumnFamilyName = "failed_block_v2";
 pub const DAG_SYNC_BLOCK_PREFIX_NAME: ColumnFamilyName = "dag_sync_block";
+pub const STATE_NODE_REF_COUNT_PREFIX_NAME: ColumnFamilyName = "state_node_ref_count";
+pub const STATE_NODE_STALE_INDEX_PREFIX_NAME: ColumnFamilyName = "state_node_stale_index";
+pub const STATE_NODE_CHILD_INDEX_PREFIX_NAME: ColumnFamilyName = "state_node_child_index";
+pub const ADDRESS_TXN_INDEX_PREFIX_NAME: ColumnFamilyName = "address_txn_index";
+pub const ADDRESS_TXN_COUNT_PREFIX_NAME: ColumnFamilyName = "address_txn_count";
 
 ///db storage use prefix_name vec to init
 /// Please note that adding a prefix needs to be added in vec simultaneously, remember！！
@@ -204,6 +219,38 @@ static VEC_PREFIX_NAME_V5: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
     ]
 });
 
+static VEC_PREFIX_NAME_V6: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
+    vec![
+        BLOCK_ACCUMULATOR_NODE_PREFIX_NAME,
+        TRANSACTION_ACCUMULATOR_NODE_PREFIX_NAME,
+        BLOCK_PREFIX_NAME,
+        BLOCK_HEADER_PREFIX_NAME,
+        BLOCK_PREFIX_NAME_V2,
+        BLOCK_HEADER_PREFIX_NAME_V2,
+        BLOCK_BODY_PREFIX_NAME, // unused column
+        BLOCK_INFO_PREFIX_NAME,
+        BLOCK_TRANSACTIONS_PREFIX_NAME,
+        BLOCK_TRANSACTION_INFOS_PREFIX_NAME,
+        STATE_NODE_PREFIX_NAME,
+        CHAIN_INFO_PREFIX_NAME,
+        TRANSACTION_PREFIX_NAME,
+        TRANSACTION_PREFIX_NAME_V2,
+        TRANSACTION_INFO_PREFIX_NAME, // unused column
+        TRANSACTION_INFO_PREFIX_NAME_V2,
+        TRANSACTION_INFO_HASH_PREFIX_NAME,
+        CONTRACT_EVENT_PREFIX_NAME,
+        FAILED_BLOCK_PREFIX_NAME,
+        FAILED_BLOCK_PREFIX_NAME_V2,
+        TABLE_INFO_PREFIX_NAME,
+        DAG_SYNC_BLOCK_PREFIX_NAME,
+        STATE_NODE_REF_COUNT_PREFIX_NAME,
+        STATE_NODE_STALE_INDEX_PREFIX_NAME,
+        STATE_NODE_CHILD_INDEX_PREFIX_NAME,
+        ADDRESS_TXN_INDEX_PREFIX_NAME,
+        ADDRESS_TXN_COUNT_PREFIX_NAME,
+    ]
+});
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, IntoPrimitive, TryFromPrimitive)]
 #[repr(u8)]
 pub enum StorageVersion {
@@ -212,11 +259,12 @@ pub enum StorageVersion {
     V3 = 3,
     V4 = 4,
     V5 = 5,
+    V6 = 6,
 }
 
 impl StorageVersion {
     pub fn current_version() -> Self {
-        Self::V5
+        Self::V6
     }
 
     pub fn get_column_family_names(&self) -> &'static [ColumnFamilyName] {
@@ -226,6 +274,7 @@ impl StorageVersion {
             Self::V3 => &VEC_PREFIX_NAME_V3,
             Self::V4 => &VEC_PREFIX_NAME_V4,
             Self::V5 => &VEC_PREFIX_NAME_V5,
+            Self::V6 => &VEC_PREFIX_NAME_V6,
         }
     }
 }
@@ -348,11 +397,20 @@ pub struct Storage {
     event_storage: ContractEventStorage,
     chain_info_storage: ChainInfoStorage,
     table_info_storage: TableInfoStorage,
-    // instance: StorageInstance,
+    state_node_ref_count_storage: StateNodeRefCountStorage,
+    stale_node_index_storage: StaleNodeIndexStorage,
+    state_child_index_storage: StateChildIndexStorage,
+    address_txn_index: AddressTxnIndex,
+    state_prune_enabled: bool,
+    // serialize the reference count updates of state nodes between writing and pruning.
+    state_node_ref_lock: Arc<Mutex<()>>,
+    instance: StorageInstance,
 }
 
 impl Storage {
     pub fn new(instance: StorageInstance) -> Result<Self> {
+        let chain_info_storage = ChainInfoStorage::new(instance.clone());
+        let state_prune_enabled = chain_info_storage.is_state_prune_enabled()?;
         let storage = Self {
             transaction_info_storage: TransactionInfoStorage::new(instance.clone()),
             transaction_info_hash_storage: TransactionInfoHashStorage::new(instance.clone()),
@@ -366,13 +424,184 @@ impl Storage {
                 AccumulatorStorage::new_transaction_accumulator_storage(instance.clone()),
             block_info_storage: BlockInfoStorage::new(instance.clone()),
             event_storage: ContractEventStorage::new(instance.clone()),
-            chain_info_storage: ChainInfoStorage::new(instance.clone()),
-            table_info_storage: TableInfoStorage::new(instance),
-            // instance,
+            chain_info_storage,
+            table_info_storage: TableInfoStorage::new(instance.clone()),
+            state_node_ref_count_storage: StateNodeRefCountStorage::new(instance.clone()),
+            stale_node_index_storage: StaleNodeIndexStorage::new(instance.clone()),
+            state_child_index_storage: StateChildIndexStorage::new(instance.clone()),
+            address_txn_index: AddressTxnIndex::new(instance.clone()),
+            state_prune_enabled,
+            state_node_ref_lock: Arc::new(Mutex::new(())),
+            instance,
         };
         Ok(storage)
     }
 
+    pub fn is_state_prune_enabled(&self) -> bool {
+        self.state_prune_enabled
+    }
+
+    /// Start to track the references of state nodes, so the stale nodes can be pruned.
+    /// Nodes written before state prune is enabled are never pruned.
+    pub fn enable_state_prune(&mut self) -> Result<()> {
+        self.chain_info_storage.enable_state_prune()?;
+        self.state_prune_enabled = true;
+        Ok(())
+    }
+
+    pub fn get_state_pruned_number(&self) -> Result<Option<BlockNumber>> {
+        self.chain_info_storage.get_state_pruned_number()
+    }
+
+    pub fn save_state_pruned_number(&self, number: BlockNumber) -> Result<()> {
+        self.chain_info_storage.save_state_pruned_number(number)
+    }
+
+    pub fn get_fast_sync_pivot(&self) -> Result<Option<HashValue>> {
+        self.chain_info_storage.get_fast_sync_pivot()
+    }
+
+    pub fn save_fast_sync_pivot(&self, pivot: HashValue) -> Result<()> {
+        self.chain_info_storage.save_fast_sync_pivot(pivot)
+    }
+
+    pub fn remove_fast_sync_pivot(&self) -> Result<()> {
+        self.chain_info_storage.remove_fast_sync_pivot()
+    }
+
+    pub fn get_light_head(&self) -> Result<Option<HashValue>> {
+        self.chain_info_storage.get_light_head()
+    }
+
+    pub fn save_light_head(&self, head: HashValue) -> Result<()> {
+        self.chain_info_storage.save_light_head(head)
+    }
+
+    /// Save the block header only, the light sync does not download the block body.
+    pub fn save_block_header(&self, header: BlockHeader) -> Result<()> {
+        self.block_storage.save_header(header)
+    }
+
+    /// Release the node references dropped by the state `state_root` of the block `number`, and
+    /// delete the nodes which are no longer referenced. The other states derived from the same
+    /// parent state are abandoned forks, the nodes created by them and their descendants are
+    /// released too. The node deletes, the reference count updates, the index removals and the
+    /// state pruned number are written in one batch, so a crash never releases a state twice.
+    /// Return the number of deleted nodes.
+    /// The caller must make sure the states are pruned in the order of the chain.
+    pub fn prune_stale_state_nodes(
+        &self,
+        number: BlockNumber,
+        state_root: HashValue,
+    ) -> Result<usize> {
+        self.prune_state_index(number, state_root, true)
+    }
+
+    /// Remove the index of the state `state_root` of the dag block `number` and of its sibling
+    /// states, and save `number` as the state pruned number. A dag block's state is not derived
+    /// from the previous main block, so no node reference is released and its nodes are kept.
+    pub fn drop_stale_node_index(&self, number: BlockNumber, state_root: HashValue) -> Result<()> {
+        self.prune_state_index(number, state_root, false)
+            .map(|_| ())
+    }
+
+    fn prune_state_index(
+        &self,
+        number: BlockNumber,
+        state_root: HashValue,
+        release: bool,
+    ) -> Result<usize> {
+        let _guard = self.state_node_ref_lock.lock();
+        let mut stale_index_deletes = vec![];
+        let mut child_index_deletes = vec![];
+        let mut releases = vec![];
+        if let Some(index) = self.stale_node_index_storage.get(state_root)? {
+            let mut fork_roots = self
+                .state_child_index_storage
+                .get(index.parent_state_root)?
+                .unwrap_or_default();
+            fork_roots.retain(|root| *root != state_root);
+            // the fork states and all the states derived from them, the stale nodes of a fork
+            // state are still referenced by its parent, so only the created nodes are released.
+            while let Some(fork_root) = fork_roots.pop() {
+                if let Some(children) = self.state_child_index_storage.get(fork_root)? {
+                    fork_roots.extend(children);
+                    child_index_deletes.push(fork_root);
+                }
+                if let Some(fork_index) = self.stale_node_index_storage.get(fork_root)? {
+                    debug!("Prune abandoned fork state {}", fork_root);
+                    releases.extend(fork_index.changes.created_nodes);
+                    stale_index_deletes.push(fork_root);
+                }
+            }
+            releases.extend(index.changes.stale_nodes);
+            stale_index_deletes.push(state_root);
+            child_index_deletes.push(index.parent_state_root);
+        }
+        if !release {
+            releases.clear();
+        }
+        let (ref_count_batch, pruned_nodes) = self.stage_state_node_releases(releases)?;
+        let pruned = pruned_nodes.len();
+
+        let mut batch = WriteBatchWithColumn::new();
+        batch.push(
+            STATE_NODE_PREFIX_NAME,
+            CodecWriteBatch::<HashValue, StateNode>::new_deletes(pruned_nodes),
+        )?;
+        batch.push(STATE_NODE_REF_COUNT_PREFIX_NAME, ref_count_batch)?;
+        batch.push(
+            STATE_NODE_STALE_INDEX_PREFIX_NAME,
+            CodecWriteBatch::<HashValue, StaleNodeIndex>::new_deletes(stale_index_deletes),
+        )?;
+        batch.push(
+            STATE_NODE_CHILD_INDEX_PREFIX_NAME,
+            CodecWriteBatch::<HashValue, Vec<HashValue>>::new_deletes(child_index_deletes),
+        )?;
+        batch.push(
+            CHAIN_INFO_PREFIX_NAME,
+            CodecWriteBatch::new_puts(vec![(
+                ChainInfoStorage::STATE_PRUNED_NUMBER_KEY
+                    .as_bytes()
+                    .to_vec(),
+                number.encode_key()?,
+            )]),
+        )?;
+        self.instance.write_batch_across_cf(batch)?;
+        Ok(pruned)
+    }
+
+    /// Stage the release of one reference of `nodes` for every occurrence, return the reference
+    /// count updates and the nodes which are no longer referenced.
+    /// The caller must hold `state_node_ref_lock` until the updates are written.
+    fn stage_state_node_releases(
+        &self,
+        nodes: Vec<HashValue>,
+    ) -> Result<(CodecWriteBatch<HashValue, u64>, Vec<HashValue>)> {
+        let mut releases: BTreeMap<HashValue, u64> = BTreeMap::new();
+        for node_key in nodes {
+            *releases.entry(node_key).or_insert(0) += 1;
+        }
+
+        let ref_counts = self
+            .state_node_ref_count_storage
+            .multiple_get(releases.keys().copied().collect())?;
+        let mut ref_count_batch = CodecWriteBatch::new();
+        let mut pruned_nodes = vec![];
+        for ((node_key, release), ref_count) in releases.into_iter().zip(ref_counts) {
+            // untracked nodes are never pruned.
+            if let Some(ref_count) = ref_count {
+                if ref_count <= release {
+                    ref_count_batch.delete(node_key)?;
+                    pruned_nodes.push(node_key);
+                } else {
+                    ref_count_batch.put(node_key, ref_count - release)?;
+                }
+            }
+        }
+        Ok((ref_count_batch, pruned_nodes))
+    }
+
     pub fn get_block_accumulator_storage(&self) -> AccumulatorStorage<BlockAccumulatorStorage> {
         self.block_accumulator_storage.clone()
     }
@@ -402,6 +631,65 @@ impl StateNodeStore for Storage {
         let handle = TableHandle(address);
         self.table_info_storage.get(handle)
     }
+
+    fn write_nodes_with_refs(
+        &self,
+        nodes: BTreeMap<HashValue, StateNode>,
+        refs: BTreeMap<HashValue, u64>,
+    ) -> Result<()> {
+        if !self.state_prune_enabled {
+            return self.write_nodes(nodes);
+        }
+        let _guard = self.state_node_ref_lock.lock();
+        let keys: Vec<HashValue> = refs.keys().copied().collect();
+        let ref_counts = self
+            .state_node_ref_count_storage
+            .multiple_get(keys.clone())?;
+        let exist_nodes = self.state_node_storage.multiple_get(keys)?;
+        let mut ref_count_batch = CodecWriteBatch::new();
+        for ((node_key, count), (ref_count, exist_node)) in refs
+            .into_iter()
+            .zip(ref_counts.into_iter().zip(exist_nodes))
+        {
+            match (ref_count, exist_node) {
+                (Some(ref_count), _) => ref_count_batch.put(node_key, ref_count + count)?,
+                // the node is written before state prune is enabled, keep it untracked.
+                (None, Some(_)) => {}
+                (None, None) => ref_count_batch.put(node_key, count)?,
+            }
+        }
+        self.write_nodes(nodes)?;
+        self.state_node_ref_count_storage
+            .write_batch(ref_count_batch)
+    }
+
+    fn save_stale_node_index(
+        &self,
+        parent_state_root: HashValue,
+        state_root: HashValue,
+        changes: StateNodeChanges,
+    ) -> Result<()> {
+        if !self.state_prune_enabled || parent_state_root == state_root {
+            return Ok(());
+        }
+        let _guard = self.state_node_ref_lock.lock();
+        let mut children = self
+            .state_child_index_storage
+            .get(parent_state_root)?
+            .unwrap_or_default();
+        if !children.contains(&state_root) {
+            children.push(state_root);
+            self.state_child_index_storage
+                .put(parent_state_root, children)?;
+        }
+        self.stale_node_index_storage.put(
+            state_root,
+            StaleNodeIndex {
+                parent_state_root,
+                changes,
+            },
+        )
+    }
 }
 
 impl Display for Storage {
@@ -669,6 +957,7 @@ pub trait Store:
     + ContractEventStore
     + IntoSuper<dyn StateNodeStore>
     + TableInfoStore
+    + AddressTxnIndexStore
 {
     fn get_transaction_info_by_block_and_index(
         &self,
@@ -745,6 +1034,45 @@ impl Store for Storage {
     }
 }
 
+impl AddressTxnIndexStore for Storage {
+    fn append_address_txns(
+        &self,
+        block_id: HashValue,
+        address_txns: Vec<(AccountAddress, HashValue)>,
+    ) -> Result<()> {
+        self.address_txn_index
+            .append_address_txns(block_id, address_txns)
+    }
+
+    fn remove_address_txns(
+        &self,
+        parent_id: HashValue,
+        address_txns: Vec<(AccountAddress, HashValue)>,
+    ) -> Result<()> {
+        self.address_txn_index
+            .remove_address_txns(parent_id, address_txns)
+    }
+
+    fn get_address_txn_count(&self, address: AccountAddress) -> Result<u64> {
+        self.address_txn_index.get_address_txn_count(address)
+    }
+
+    fn get_address_txns(
+        &self,
+        address: AccountAddress,
+        start_seq: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<HashValue>> {
+        self.address_txn_index
+            .get_address_txns(address, start_seq, reverse, max_size)
+    }
+
+    fn get_address_txn_index_head(&self) -> Result<Option<HashValue>> {
+        self.chain_info_storage.get_address_txn_index_head()
+    }
+}
+
 impl TableInfoStore for Storage {
     fn get_table_info(&self, key: TableHandle) -> Result<Option<TableInfo>> {
         self.table_info_storage.get(key)
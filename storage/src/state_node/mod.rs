@@ -3,12 +3,55 @@
 
 use crate::define_storage;
 use crate::storage::ValueCodec;
-use crate::STATE_NODE_PREFIX_NAME;
+use crate::{
+    STATE_NODE_CHILD_INDEX_PREFIX_NAME, STATE_NODE_PREFIX_NAME, STATE_NODE_REF_COUNT_PREFIX_NAME,
+    STATE_NODE_STALE_INDEX_PREFIX_NAME,
+};
 use anyhow::Result;
+use bcs_ext::BCSCodec;
+use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
-use starcoin_state_store_api::StateNode;
+use starcoin_state_store_api::{StateNode, StateNodeChanges};
 
 define_storage!(StateStorage, HashValue, StateNode, STATE_NODE_PREFIX_NAME);
+// node hash -> how many times the node is referenced by state trees,
+// only nodes created after state prune is enabled have a reference count.
+define_storage!(
+    StateNodeRefCountStorage,
+    HashValue,
+    u64,
+    STATE_NODE_REF_COUNT_PREFIX_NAME
+);
+// state root -> the parent state root and the node changes of the state.
+define_storage!(
+    StaleNodeIndexStorage,
+    HashValue,
+    StaleNodeIndex,
+    STATE_NODE_STALE_INDEX_PREFIX_NAME
+);
+// state root -> the state roots derived from it, more than one child means forks.
+define_storage!(
+    StateChildIndexStorage,
+    HashValue,
+    Vec<HashValue>,
+    STATE_NODE_CHILD_INDEX_PREFIX_NAME
+);
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StaleNodeIndex {
+    pub parent_state_root: HashValue,
+    pub changes: StateNodeChanges,
+}
+
+impl ValueCodec for StaleNodeIndex {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
 
 impl ValueCodec for StateNode {
     fn encode_value(&self) -> Result<Vec<u8>> {
@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-pub use crate::batch::WriteBatch;
+pub use crate::batch::{WriteBatch, WriteBatchWithColumn};
 use crate::{
     cache_storage::CacheStorage,
     db_storage::{DBStorage, SchemaIterator},
@@ -44,6 +44,8 @@ pub trait InnerStore: Send + Sync {
     fn put_sync(&self, prefix_name: &str, key: Vec<u8>, value: Vec<u8>) -> Result<()>;
     fn write_batch_sync(&self, prefix_name: &str, batch: WriteBatch) -> Result<()>;
     fn multi_get(&self, prefix_name: &str, keys: Vec<Vec<u8>>) -> Result<Vec<Option<Vec<u8>>>>;
+    /// Writes the batches of several column families atomically.
+    fn write_batch_across_cf(&self, batch: WriteBatchWithColumn) -> Result<()>;
 }
 
 pub trait RawDBStorage: Send + Sync {
@@ -240,6 +242,17 @@ impl InnerStore for StorageInstance {
         }
     }
 
+    fn write_batch_across_cf(&self, batch: WriteBatchWithColumn) -> Result<()> {
+        match self {
+            Self::CACHE { cache } => cache.write_batch_across_cf(batch),
+            Self::DB { db } => db.write_batch_across_cf(batch),
+            Self::CacheAndDb { cache, db } => match db.write_batch_across_cf(batch.clone()) {
+                Ok(_) => cache.write_batch_across_cf(batch),
+                Err(err) => bail!("write batch across column families db error: {}", err),
+            },
+        }
+    }
+
     fn multi_get(&self, prefix_name: &str, keys: Vec<Vec<u8>>) -> Result<Vec<Option<Vec<u8>>>> {
         match self {
             Self::CACHE { cache } => cache.multi_get(prefix_name, keys),
@@ -520,6 +533,17 @@ impl KeyCodec for u64 {
     }
 }
 
+impl ValueCodec for u64 {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    #[allow(clippy::redundant_slicing)]
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok((&data[..]).read_u64::<BigEndian>()?)
+    }
+}
+
 impl KeyCodec for HashValue {
     fn encode_key(&self) -> Result<Vec<u8>> {
         Ok(self.to_vec())
@@ -14,7 +14,7 @@ use crate::table_info::TableInfoStore;
 use crate::transaction::LegacyTransactionStorage;
 use crate::transaction_info::{BlockTransactionInfo, OldTransactionInfoStorage};
 use crate::{
-    BlockInfoStore, BlockStore, BlockTransactionInfoStore, Storage,
+    AddressTxnIndexStore, BlockInfoStore, BlockStore, BlockTransactionInfoStore, Storage,
     StorageVersion, /*TableInfoStore,*/
     DEFAULT_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME_V2,
 };
@@ -23,6 +23,7 @@ use starcoin_accumulator::accumulator_info::AccumulatorInfo;
 use starcoin_config::RocksdbConfig;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::info;
+use starcoin_state_store_api::{StateNode, StateNodeChanges, StateNodeStore};
 use starcoin_types::block::{Block, BlockBody, BlockHeader, BlockInfo};
 use starcoin_types::startup_info::SnapshotRange;
 use starcoin_types::transaction::{RichTransactionInfo, SignedUserTransaction, TransactionInfo};
@@ -32,6 +33,7 @@ use starcoin_vm_types::block_metadata::LegacyBlockMetadata;
 use starcoin_vm_types::language_storage::TypeTag;
 use starcoin_vm_types::state_store::table::{TableHandle, TableInfo};
 use starcoin_vm_types::transaction::LegacyTransaction;
+use std::collections::BTreeMap;
 use std::path::Path;
 
 #[test]
@@ -603,3 +605,176 @@ fn test_table_info_storage() -> Result<()> {
     assert_eq!(vals, vals2);
     Ok(())
 }
+
+#[test]
+fn test_prune_stale_state_nodes() -> Result<()> {
+    let tmpdir = starcoin_config::temp_dir();
+    let instance = StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(None),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default(), None)?,
+    );
+    let mut storage = Storage::new(instance.clone())?;
+    // written before state prune is enabled, should never be pruned.
+    let untracked = HashValue::random();
+    storage.put(untracked, StateNode(vec![0]))?;
+    storage.enable_state_prune()?;
+    assert!(Storage::new(instance)?.is_state_prune_enabled());
+
+    let shared = HashValue::random();
+    let stale = HashValue::random();
+    let mut nodes = BTreeMap::new();
+    nodes.insert(shared, StateNode(vec![1]));
+    nodes.insert(stale, StateNode(vec![2]));
+    nodes.insert(untracked, StateNode(vec![0]));
+    let mut refs = BTreeMap::new();
+    refs.insert(shared, 2);
+    refs.insert(stale, 1);
+    refs.insert(untracked, 1);
+    storage.write_nodes_with_refs(nodes, refs)?;
+
+    let state_root = HashValue::random();
+    storage.save_stale_node_index(
+        HashValue::random(),
+        state_root,
+        StateNodeChanges {
+            created_nodes: vec![],
+            stale_nodes: vec![shared, stale, untracked],
+        },
+    )?;
+    assert_eq!(storage.prune_stale_state_nodes(1, state_root)?, 1);
+    assert_eq!(storage.get_state_pruned_number()?, Some(1));
+    assert!(StateNodeStore::get(&storage, &stale)?.is_none());
+    assert!(StateNodeStore::get(&storage, &shared)?.is_some());
+    assert!(StateNodeStore::get(&storage, &untracked)?.is_some());
+    // the index is removed after prune.
+    assert_eq!(storage.prune_stale_state_nodes(2, state_root)?, 0);
+    assert!(StateNodeStore::get(&storage, &shared)?.is_some());
+    Ok(())
+}
+
+#[test]
+fn test_prune_fork_state_nodes() -> Result<()> {
+    let tmpdir = starcoin_config::temp_dir();
+    let mut storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(None),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default(), None)?,
+    ))?;
+    storage.enable_state_prune()?;
+    let write_node = |node_key: HashValue| -> Result<()> {
+        let mut nodes = BTreeMap::new();
+        nodes.insert(node_key, StateNode(node_key.to_vec()));
+        let mut refs = BTreeMap::new();
+        refs.insert(node_key, 1);
+        storage.write_nodes_with_refs(nodes, refs)
+    };
+
+    // parent -> main -> main_child
+    //        -> fork -> fork_child
+    let parent_root = HashValue::random();
+    let parent_node = HashValue::random();
+    write_node(parent_node)?;
+    let (main_root, main_node) = (HashValue::random(), HashValue::random());
+    let (fork_root, fork_node) = (HashValue::random(), HashValue::random());
+    let (fork_child_root, fork_child_node) = (HashValue::random(), HashValue::random());
+    for (parent, root, node) in [
+        (parent_root, main_root, main_node),
+        (parent_root, fork_root, fork_node),
+        (fork_root, fork_child_root, fork_child_node),
+    ] {
+        write_node(node)?;
+        storage.save_stale_node_index(
+            parent,
+            root,
+            StateNodeChanges {
+                created_nodes: vec![node],
+                stale_nodes: vec![parent_node],
+            },
+        )?;
+    }
+
+    // the fork and its descendants are pruned, the stale node is released only once by the main chain.
+    assert_eq!(storage.prune_stale_state_nodes(1, main_root)?, 3);
+    assert!(StateNodeStore::get(&storage, &parent_node)?.is_none());
+    assert!(StateNodeStore::get(&storage, &fork_node)?.is_none());
+    assert!(StateNodeStore::get(&storage, &fork_child_node)?.is_none());
+    assert!(StateNodeStore::get(&storage, &main_node)?.is_some());
+    assert_eq!(storage.prune_stale_state_nodes(2, fork_child_root)?, 0);
+    Ok(())
+}
+
+#[test]
+fn test_drop_dag_stale_node_index() -> Result<()> {
+    let tmpdir = starcoin_config::temp_dir();
+    let mut storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(None),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default(), None)?,
+    ))?;
+    storage.enable_state_prune()?;
+    let node = HashValue::random();
+    let mut nodes = BTreeMap::new();
+    nodes.insert(node, StateNode(vec![1]));
+    let mut refs = BTreeMap::new();
+    refs.insert(node, 1);
+    storage.write_nodes_with_refs(nodes, refs)?;
+    let state_root = HashValue::random();
+    storage.save_stale_node_index(
+        HashValue::random(),
+        state_root,
+        StateNodeChanges {
+            created_nodes: vec![],
+            stale_nodes: vec![node],
+        },
+    )?;
+
+    // the index of a dag block state is consumed, but the nodes are kept.
+    storage.drop_stale_node_index(1, state_root)?;
+    assert_eq!(storage.get_state_pruned_number()?, Some(1));
+    assert!(StateNodeStore::get(&storage, &node)?.is_some());
+    assert_eq!(storage.prune_stale_state_nodes(2, state_root)?, 0);
+    assert!(StateNodeStore::get(&storage, &node)?.is_some());
+    Ok(())
+}
+
+#[test]
+fn test_address_txn_index() -> Result<()> {
+    let tmpdir = starcoin_config::temp_dir();
+    let storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(None),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default(), None)?,
+    ))?;
+    let alice = AccountAddress::random();
+    let bob = AccountAddress::random();
+    let txns: Vec<HashValue> = (0..4).map(|_| HashValue::random()).collect();
+    let blocks: Vec<HashValue> = (0..2).map(|_| HashValue::random()).collect();
+    storage.append_address_txns(
+        blocks[0],
+        vec![(alice, txns[0]), (bob, txns[0]), (alice, txns[1])],
+    )?;
+    storage.append_address_txns(blocks[1], vec![(alice, txns[2]), (alice, txns[3])])?;
+    assert_eq!(storage.get_address_txn_index_head()?, Some(blocks[1]));
+    assert_eq!(storage.get_address_txn_count(alice)?, 4);
+    assert_eq!(storage.get_address_txn_count(bob)?, 1);
+    assert_eq!(
+        storage.get_address_txns(alice, 1, false, 2)?,
+        txns[1..3].to_vec()
+    );
+    assert_eq!(
+        storage.get_address_txns(alice, 3, true, 10)?,
+        txns.iter().rev().cloned().collect::<Vec<_>>()
+    );
+    assert!(storage.get_address_txns(alice, 4, false, 10)?.is_empty());
+
+    // only the tail of the address txn list can be removed.
+    assert!(storage
+        .remove_address_txns(blocks[0], vec![(alice, txns[2])])
+        .is_err());
+    assert_eq!(storage.get_address_txn_index_head()?, Some(blocks[1]));
+    storage.remove_address_txns(blocks[0], vec![(alice, txns[2]), (alice, txns[3])])?;
+    assert_eq!(storage.get_address_txn_index_head()?, Some(blocks[0]));
+    assert_eq!(storage.get_address_txn_count(alice)?, 2);
+    assert_eq!(
+        storage.get_address_txns(alice, 0, false, 10)?,
+        txns[0..2].to_vec()
+    );
+    Ok(())
+}
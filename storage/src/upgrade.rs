@@ -7,6 +7,7 @@ use crate::block::{
 };
 use crate::block_info::BlockInfoStorage;
 use crate::chain_info::ChainInfoStorage;
+use crate::state_node::{StaleNodeIndexStorage, StateChildIndexStorage, StateNodeRefCountStorage};
 use crate::storage::{CodecWriteBatch, ColumnFamily, KeyCodec, SchemaStorage, ValueCodec};
 use crate::transaction::{LegacyTransactionStorage, TransactionStorage};
 use crate::transaction_info::OldTransactionInfoStorage;
@@ -188,6 +189,15 @@ impl DBUpgrade {
         Ok(())
     }
 
+    fn db_upgrade_v5_v6(instance: &mut StorageInstance) -> Result<()> {
+        // the state prune and address txn index column families are created on open,
+        // and only used after the features are enabled, nothing to migrate.
+        let _ = StateNodeRefCountStorage::new(instance.clone());
+        let _ = StaleNodeIndexStorage::new(instance.clone());
+        let _ = StateChildIndexStorage::new(instance.clone());
+        Ok(())
+    }
+
     pub fn do_upgrade(
         version_in_db: StorageVersion,
         version_in_code: StorageVersion,
@@ -237,9 +247,34 @@ impl DBUpgrade {
                 Self::db_upgrade_v3_v4(instance)?;
                 Self::db_upgrade_v4_v5(instance)?;
             }
+            (StorageVersion::V1, StorageVersion::V6) => {
+                Self::db_upgrade_v1_v2(instance)?;
+                Self::db_upgrade_v2_v3(instance)?;
+                Self::db_upgrade_v3_v4(instance)?;
+                Self::db_upgrade_v4_v5(instance)?;
+                Self::db_upgrade_v5_v6(instance)?;
+            }
+            (StorageVersion::V2, StorageVersion::V6) => {
+                Self::db_upgrade_v2_v3(instance)?;
+                Self::db_upgrade_v3_v4(instance)?;
+                Self::db_upgrade_v4_v5(instance)?;
+                Self::db_upgrade_v5_v6(instance)?;
+            }
+            (StorageVersion::V3, StorageVersion::V6) => {
+                Self::db_upgrade_v3_v4(instance)?;
+                Self::db_upgrade_v4_v5(instance)?;
+                Self::db_upgrade_v5_v6(instance)?;
+            }
             (StorageVersion::V4, StorageVersion::V5) => {
                 Self::db_upgrade_v4_v5(instance)?;
             }
+            (StorageVersion::V4, StorageVersion::V6) => {
+                Self::db_upgrade_v4_v5(instance)?;
+                Self::db_upgrade_v5_v6(instance)?;
+            }
+            (StorageVersion::V5, StorageVersion::V6) => {
+                Self::db_upgrade_v5_v6(instance)?;
+            }
             _ => bail!(
                 "Can not upgrade db from {:?} to {:?}",
                 version_in_db,
@@ -0,0 +1,116 @@
+use crate::rpc::{MinerWorker, WorkerId};
+use crate::stratum::Stratum;
+use futures::FutureExt;
+use jsonrpc_core::{BoxFuture, Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use starcoin_service_registry::{ServiceRef, ServiceRequest};
+use starcoin_types::U256;
+
+/// The share accounting of a worker, for the pool operators.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct WorkerStats {
+    /// The hex of the worker id, which is also the extranonce of the worker.
+    pub worker_id: String,
+    pub login: String,
+    pub agent: String,
+    pub login_time: u64,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+    pub stale_shares: u64,
+    pub last_share_time: Option<u64>,
+    /// The hash rate estimated by the shares.
+    pub hash_rate: u64,
+    pub difficulty: U256,
+}
+
+impl From<&MinerWorker> for WorkerStats {
+    fn from(worker: &MinerWorker) -> Self {
+        let (hash_rate, difficulty) = {
+            let diff_manager = worker.diff_manager.read().unwrap();
+            (diff_manager.hash_rate, diff_manager.difficulty)
+        };
+        Self {
+            worker_id: worker.worker_id.to_hex(),
+            login: worker.base_info.login.clone(),
+            agent: worker.base_info.agent.clone(),
+            login_time: worker.login_time,
+            accepted_shares: worker.share_stats.accepted,
+            rejected_shares: worker.share_stats.rejected,
+            stale_shares: worker.share_stats.stale,
+            last_share_time: worker.share_stats.last_share_time,
+            hash_rate,
+            difficulty,
+        }
+    }
+}
+
+/// Get the stats of the connected workers, or of the worker with the id.
+#[derive(Debug, Clone)]
+pub struct WorkerStatsRequest(pub Option<WorkerId>);
+
+impl ServiceRequest for WorkerStatsRequest {
+    type Response = Vec<WorkerStats>;
+}
+
+/// The admin rpc of the stratum server, it is served on a separate address from the stratum
+/// protocol, so it is not exposed to the workers.
+#[allow(clippy::needless_return)]
+#[rpc]
+pub trait StratumAdminRpc {
+    /// List the stats of all connected workers.
+    #[rpc(name = "stratum_workers")]
+    fn workers(&self) -> BoxFuture<Result<Vec<WorkerStats>>>;
+
+    /// Get the stats of the worker by the hex of worker id.
+    #[rpc(name = "stratum_worker")]
+    fn worker(&self, worker_id: String) -> BoxFuture<Result<Option<WorkerStats>>>;
+}
+
+pub struct StratumAdminRpcImpl {
+    service: ServiceRef<Stratum>,
+}
+
+impl StratumAdminRpcImpl {
+    pub fn new(service: ServiceRef<Stratum>) -> Self {
+        Self { service }
+    }
+}
+
+fn map_err(e: anyhow::Error) -> Error {
+    Error {
+        code: ErrorCode::InternalError,
+        message: e.to_string(),
+        data: None,
+    }
+}
+
+impl StratumAdminRpc for StratumAdminRpcImpl {
+    fn workers(&self) -> BoxFuture<Result<Vec<WorkerStats>>> {
+        let service = self.service.clone();
+        async move {
+            service
+                .send(WorkerStatsRequest(None))
+                .await
+                .map_err(map_err)
+        }
+        .boxed()
+    }
+
+    fn worker(&self, worker_id: String) -> BoxFuture<Result<Option<WorkerStats>>> {
+        let service = self.service.clone();
+        async move {
+            let worker_id = WorkerId::from_hex(worker_id).map_err(|e| Error {
+                code: ErrorCode::InvalidParams,
+                message: e.to_string(),
+                data: None,
+            })?;
+            let stats = service
+                .send(WorkerStatsRequest(Some(worker_id)))
+                .await
+                .map_err(map_err)?;
+            Ok(stats.into_iter().next())
+        }
+        .boxed()
+    }
+}
@@ -1,15 +1,45 @@
 use crate::difficulty_to_target_hex;
+use starcoin_config::StratumConfig;
 use starcoin_logger::prelude::*;
 use starcoin_types::U256;
 pub const SHARE_SUBMIT_PERIOD: u64 = 2;
 pub const INIT_HASH_RATE: u64 = 10000;
 pub const MINI_UPDATE_PERIOD: u64 = 20;
 
+/// The targets of the variable difficulty of workers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VardiffConfig {
+    pub share_submit_period: u64,
+    pub update_period: u64,
+    pub init_hash_rate: u64,
+}
+
+impl Default for VardiffConfig {
+    fn default() -> Self {
+        Self {
+            share_submit_period: SHARE_SUBMIT_PERIOD,
+            update_period: MINI_UPDATE_PERIOD,
+            init_hash_rate: INIT_HASH_RATE,
+        }
+    }
+}
+
+impl From<&StratumConfig> for VardiffConfig {
+    fn from(config: &StratumConfig) -> Self {
+        Self {
+            share_submit_period: config.vardiff_share_period.unwrap_or(SHARE_SUBMIT_PERIOD),
+            update_period: config.vardiff_retarget_period.unwrap_or(MINI_UPDATE_PERIOD),
+            init_hash_rate: config.vardiff_init_hash_rate.unwrap_or(INIT_HASH_RATE),
+        }
+    }
+}
+
 pub struct DifficultyManager {
     pub timestamp_since_last_update: u64,
     pub submits_since_last_update: u32,
     pub hash_rate: u64,
     pub difficulty: U256,
+    pub config: VardiffConfig,
 }
 impl Default for DifficultyManager {
     fn default() -> Self {
@@ -22,11 +52,19 @@ impl DifficultyManager {
     }
 
     pub fn new() -> Self {
+        Self::new_with_config(VardiffConfig::default())
+    }
+
+    pub fn new_with_config(config: VardiffConfig) -> Self {
         Self {
             timestamp_since_last_update: Self::current_timestamp(),
             submits_since_last_update: 0,
-            hash_rate: INIT_HASH_RATE,
-            difficulty: Self::get_difficulty_from_hashrate(INIT_HASH_RATE, SHARE_SUBMIT_PERIOD),
+            hash_rate: config.init_hash_rate,
+            difficulty: Self::get_difficulty_from_hashrate(
+                config.init_hash_rate,
+                config.share_submit_period,
+            ),
+            config,
         }
     }
 
@@ -39,7 +77,7 @@ impl DifficultyManager {
         let current_timestamp = Self::current_timestamp();
 
         let pass_time = current_timestamp - self.timestamp_since_last_update;
-        if pass_time < MINI_UPDATE_PERIOD {
+        if pass_time < self.config.update_period {
             return false;
         }
 
@@ -52,7 +90,8 @@ impl DifficultyManager {
         }
         info!("Miner:{} hash rate is:{}", worker, self.hash_rate);
         self.timestamp_since_last_update = current_timestamp;
-        self.difficulty = Self::get_difficulty_from_hashrate(self.hash_rate, SHARE_SUBMIT_PERIOD);
+        self.difficulty =
+            Self::get_difficulty_from_hashrate(self.hash_rate, self.config.share_submit_period);
         self.submits_since_last_update = 0;
         true
     }
@@ -0,0 +1,67 @@
+use crate::rpc::WorkerId;
+use anyhow::{ensure, Result};
+use std::collections::HashSet;
+
+/// Allocate the extranonce of every worker, it is written to the `extra` of the minting blob,
+/// so the workers of one job search in different nonce spaces, even if they use the same login.
+/// The extranonce is also the `WorkerId` of the worker.
+pub struct ExtranonceAllocator {
+    next: u32,
+    allocated: HashSet<WorkerId>,
+}
+
+impl Default for ExtranonceAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExtranonceAllocator {
+    pub fn new() -> Self {
+        Self {
+            next: 1,
+            allocated: HashSet::new(),
+        }
+    }
+
+    /// Allocate an extranonce which is not used by any connected worker, the zero extranonce is
+    /// kept for the local miner.
+    pub fn allocate(&mut self) -> Result<WorkerId> {
+        ensure!(
+            (self.allocated.len() as u64) < u64::from(u32::MAX),
+            "The extranonce is exhausted"
+        );
+        loop {
+            let worker_id = WorkerId::from(self.next);
+            self.next = self.next.checked_add(1).unwrap_or(1);
+            if self.allocated.insert(worker_id) {
+                return Ok(worker_id);
+            }
+        }
+    }
+
+    pub fn release(&mut self, worker_id: &WorkerId) {
+        self.allocated.remove(worker_id);
+    }
+
+    pub fn allocated(&self) -> usize {
+        self.allocated.len()
+    }
+}
+
+#[test]
+fn test_extranonce_allocator() {
+    let mut allocator = ExtranonceAllocator::new();
+    let first = allocator.allocate().unwrap();
+    let second = allocator.allocate().unwrap();
+    assert_ne!(first, second);
+    assert_eq!(first.to_hex(), "00000001");
+    assert_eq!(allocator.allocated(), 2);
+
+    // the allocation wraps around and skips the extranonce in use.
+    allocator.release(&second);
+    allocator.next = u32::MAX;
+    assert_eq!(allocator.allocate().unwrap(), WorkerId::from(u32::MAX));
+    assert_eq!(allocator.allocate().unwrap(), second);
+    assert_eq!(allocator.allocated(), 3);
+}
@@ -1,6 +1,9 @@
 use starcoin_types::U256;
 
+pub mod admin_rpc;
 pub mod diff_manager;
+pub mod extranonce;
+pub mod metrics;
 pub mod rpc;
 pub mod service;
 pub mod stratum;
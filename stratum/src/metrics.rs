@@ -0,0 +1,59 @@
+use starcoin_metrics::{
+    register, Opts, PrometheusError, Registry, UIntCounterVec, UIntGauge, UIntGaugeVec,
+};
+
+#[derive(Clone)]
+pub struct StratumMetrics {
+    pub stratum_workers: UIntGauge,
+    pub stratum_worker_shares_total: UIntCounterVec,
+    pub stratum_worker_hash_rate: UIntGaugeVec,
+}
+
+impl StratumMetrics {
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        let stratum_workers = register(
+            UIntGauge::with_opts(Opts::new(
+                "stratum_workers",
+                "Number of the connected stratum workers",
+            ))?,
+            registry,
+        )?;
+        let stratum_worker_shares_total = register(
+            UIntCounterVec::new(
+                Opts::new(
+                    "stratum_worker_shares_total",
+                    "Counters of the shares of every worker, by status accepted|rejected|stale",
+                ),
+                &["worker", "login", "status"],
+            )?,
+            registry,
+        )?;
+        let stratum_worker_hash_rate = register(
+            UIntGaugeVec::new(
+                Opts::new(
+                    "stratum_worker_hash_rate",
+                    "The estimated hash rate of every worker",
+                ),
+                &["worker", "login"],
+            )?,
+            registry,
+        )?;
+        Ok(Self {
+            stratum_workers,
+            stratum_worker_shares_total,
+            stratum_worker_hash_rate,
+        })
+    }
+
+    /// Remove the metrics of a disconnected worker, the worker id may be allocated to others.
+    pub fn remove_worker(&self, worker: &str, login: &str) {
+        for status in ["accepted", "rejected", "stale"] {
+            let _ = self
+                .stratum_worker_shares_total
+                .remove_label_values(&[worker, login, status]);
+        }
+        let _ = self
+            .stratum_worker_hash_rate
+            .remove_label_values(&[worker, login]);
+    }
+}
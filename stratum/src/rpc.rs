@@ -1,4 +1,4 @@
-use crate::diff_manager::DifficultyManager;
+use crate::diff_manager::{DifficultyManager, VardiffConfig};
 use crate::stratum::Stratum;
 use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
 use futures::FutureExt;
@@ -8,12 +8,12 @@ use jsonrpc_core::{BoxFuture, ErrorCode, Params, Result};
 use jsonrpc_derive::rpc;
 use jsonrpc_pubsub::typed::Subscriber;
 use jsonrpc_pubsub::{typed, PubSubMetadata, Session, SubscriptionId};
-use starcoin_crypto::hash::DefaultHasher;
 use starcoin_logger::prelude::*;
 use starcoin_miner::SubmitSealRequest as MinerSubmitSealRequest;
 use starcoin_service_registry::{ServiceRef, ServiceRequest};
 use starcoin_types::block::BlockHeaderExtra;
 use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::U256;
 use std::borrow::BorrowMut;
 use std::convert::TryInto;
 use std::io::Write;
@@ -168,34 +168,82 @@ impl WorkerId {
         hex::encode(self.buff)
     }
 }
+impl From<u32> for WorkerId {
+    fn from(id: u32) -> Self {
+        Self {
+            buff: id.to_be_bytes(),
+        }
+    }
+}
+
+/// The result of a share submitted by a worker.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ShareStatus {
+    Accepted,
+    Rejected,
+    /// The share is for an outdated job.
+    Stale,
+}
+
+impl ShareStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "accepted",
+            Self::Rejected => "rejected",
+            Self::Stale => "stale",
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct ShareStats {
+    pub accepted: u64,
+    pub rejected: u64,
+    pub stale: u64,
+    /// The timestamp in seconds of the last share.
+    pub last_share_time: Option<u64>,
+}
+
+impl ShareStats {
+    pub fn record(&mut self, status: ShareStatus, now_secs: u64) {
+        match status {
+            ShareStatus::Accepted => self.accepted = self.accepted.saturating_add(1),
+            ShareStatus::Rejected => self.rejected = self.rejected.saturating_add(1),
+            ShareStatus::Stale => self.stale = self.stale.saturating_add(1),
+        }
+        self.last_share_time = Some(now_secs);
+    }
+}
+
 pub struct MinerWorker {
     pub base_info: LoginRequest,
     pub sub_id: u32,
     pub worker_id: WorkerId,
     pub diff_manager: Arc<RwLock<DifficultyManager>>,
+    pub share_stats: ShareStats,
+    /// The difficulty of the last job dispatched to the worker, its shares are checked against it.
+    pub job_difficulty: U256,
+    /// The timestamp in seconds when the worker login.
+    pub login_time: u64,
 }
 impl MinerWorker {
-    fn generate_worker_id(login_name: String, sub_id: u32) -> WorkerId {
-        let mut hash = DefaultHasher::new(b"");
-        hash.update(login_name.as_bytes());
-        let mut output: [u8; 4] = hash.finish().to_vec()[0..4]
-            .try_into()
-            .expect("Hash len should have 8 bytes");
-        output
-            .iter_mut()
-            .zip(u32::to_le_bytes(sub_id).iter())
-            .for_each(|(x1, x2)| *x1 ^= *x2);
-        WorkerId { buff: output }
-    }
-
-    pub fn new(sub_id: u32, base_info: LoginRequest) -> Self {
-        let worker_id = Self::generate_worker_id(base_info.login.clone(), sub_id);
-        let diff_manager = Arc::new(RwLock::new(DifficultyManager::new()));
+    pub fn new(
+        sub_id: u32,
+        worker_id: WorkerId,
+        base_info: LoginRequest,
+        vardiff_config: VardiffConfig,
+        login_time: u64,
+    ) -> Self {
+        let diff_manager = DifficultyManager::new_with_config(vardiff_config);
+        let job_difficulty = diff_manager.difficulty;
         Self {
             base_info,
             sub_id,
             worker_id,
-            diff_manager,
+            diff_manager: Arc::new(RwLock::new(diff_manager)),
+            share_stats: ShareStats::default(),
+            job_difficulty,
+            login_time,
         }
     }
     pub fn diff_manager(&self) -> Arc<RwLock<DifficultyManager>> {
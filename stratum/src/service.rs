@@ -1,7 +1,8 @@
+use crate::admin_rpc::{StratumAdminRpc, StratumAdminRpcImpl};
 use crate::rpc::{Metadata, StratumRpc, StratumRpcImpl};
 use crate::stratum::Stratum;
 use anyhow::Result;
-use jsonrpc_core::MetaIoHandler;
+use jsonrpc_core::{IoHandler, MetaIoHandler};
 use jsonrpc_pubsub::Session;
 use starcoin_config::NodeConfig;
 use starcoin_service_registry::{ActorService, ServiceContext, ServiceFactory};
@@ -10,6 +11,7 @@ use std::sync::Arc;
 pub struct StratumService {
     config: Arc<NodeConfig>,
     tcp: Option<jsonrpc_tcp_server::Server>,
+    admin_tcp: Option<jsonrpc_tcp_server::Server>,
 }
 
 impl ActorService for StratumService {
@@ -29,12 +31,22 @@ impl ActorService for StratumService {
             .start(&address)?;
             self.tcp = Some(server);
         }
+        if let Some(admin_address) = self.config.stratum.get_admin_address() {
+            let mut io = IoHandler::default();
+            let stratum = ctx.service_ref::<Stratum>()?.clone();
+            io.extend_with(StratumAdminRpcImpl::new(stratum).to_delegate());
+            let server = jsonrpc_tcp_server::ServerBuilder::new(io).start(&admin_address)?;
+            self.admin_tcp = Some(server);
+        }
         Ok(())
     }
     fn stopped(&mut self, _ctx: &mut ServiceContext<Self>) -> Result<()> {
         if let Some(tcp) = self.tcp.take() {
             tcp.close()
         }
+        if let Some(admin_tcp) = self.admin_tcp.take() {
+            admin_tcp.close()
+        }
         Ok(())
     }
 }
@@ -44,6 +56,10 @@ pub struct StratumServiceFactory;
 impl ServiceFactory<StratumService> for StratumServiceFactory {
     fn create(ctx: &mut ServiceContext<StratumService>) -> Result<StratumService> {
         let config = ctx.get_shared::<Arc<NodeConfig>>()?;
-        Ok(StratumService { config, tcp: None })
+        Ok(StratumService {
+            config,
+            tcp: None,
+            admin_tcp: None,
+        })
     }
 }
@@ -1,19 +1,31 @@
+use crate::admin_rpc::{WorkerStats, WorkerStatsRequest};
+use crate::diff_manager::VardiffConfig;
+use crate::extranonce::ExtranonceAllocator;
+use crate::metrics::StratumMetrics;
 use crate::{rpc::*, target_hex_to_difficulty};
-use anyhow::Result;
+use anyhow::{bail, ensure, Result};
 use futures::channel::mpsc;
 use futures::StreamExt;
 use jsonrpc_pubsub::SubscriptionId;
+use starcoin_config::NodeConfig;
+use starcoin_consensus::{difficult_to_target, Consensus};
+use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_miner::{
-    MinerService, SubmitSealRequest as MinerSubmitSealRequest, UpdateSubscriberNumRequest,
+    MinerError, MinerService, SubmitSealRequest as MinerSubmitSealRequest,
+    UpdateSubscriberNumRequest,
 };
 use starcoin_service_registry::{
     ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler, ServiceRef,
 };
+use starcoin_types::block::BlockHeaderExtra;
+use starcoin_types::genesis_config::ConsensusStrategy;
 use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::U256;
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::sync::atomic;
+use std::sync::Arc;
 use std::time::Duration;
 
 pub struct Stratum {
@@ -21,14 +33,24 @@ pub struct Stratum {
     mint_block_subscribers:
         HashMap<WorkerId, (mpsc::UnboundedSender<StratumJobResponse>, MinerWorker)>,
     miner_service: ServiceRef<MinerService>,
+    extranonce_allocator: ExtranonceAllocator,
+    vardiff_config: VardiffConfig,
+    metrics: Option<StratumMetrics>,
 }
 
 impl Stratum {
-    fn new(miner_service: ServiceRef<MinerService>) -> Self {
+    fn new(
+        miner_service: ServiceRef<MinerService>,
+        vardiff_config: VardiffConfig,
+        metrics: Option<StratumMetrics>,
+    ) -> Self {
         Self {
             miner_service,
             uid: atomic::AtomicU32::new(1),
             mint_block_subscribers: Default::default(),
+            extranonce_allocator: ExtranonceAllocator::new(),
+            vardiff_config,
+            metrics,
         }
     }
 
@@ -36,6 +58,46 @@ impl Stratum {
         self.uid.fetch_add(1, atomic::Ordering::SeqCst)
     }
 
+    fn current_timestamp() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_secs()
+    }
+
+    fn remove_worker(&mut self, worker_id: &WorkerId) {
+        if let Some((_, worker)) = self.mint_block_subscribers.remove(worker_id) {
+            self.extranonce_allocator.release(worker_id);
+            if let Some(metrics) = self.metrics.as_ref() {
+                metrics.stratum_workers.dec();
+                metrics.remove_worker(&worker_id.to_hex(), &worker.base_info.login);
+            }
+        }
+    }
+
+    /// Account the share of the worker, and update the hash rate metrics if the difficulty of
+    /// the worker is updated.
+    fn record_share(&mut self, worker_id: &WorkerId, status: ShareStatus, diff_updated: bool) {
+        if let Some((_, worker)) = self.mint_block_subscribers.get_mut(worker_id) {
+            worker.share_stats.record(status, Self::current_timestamp());
+            if let Some(metrics) = self.metrics.as_ref() {
+                let worker_hex = worker_id.to_hex();
+                let login = worker.base_info.login.as_str();
+                metrics
+                    .stratum_worker_shares_total
+                    .with_label_values(&[worker_hex.as_str(), login, status.as_str()])
+                    .inc();
+                if diff_updated {
+                    let hash_rate = worker.diff_manager.read().unwrap().hash_rate;
+                    metrics
+                        .stratum_worker_hash_rate
+                        .with_label_values(&[worker_hex.as_str(), login])
+                        .set(hash_rate);
+                }
+            }
+        }
+    }
+
     fn sync_upstream_job(&mut self) -> Result<MintBlockEvent> {
         let service = self.miner_service.clone();
         let subscribers_num = self.mint_block_subscribers.len() as u32;
@@ -70,13 +132,17 @@ impl Stratum {
     }
 
     fn get_downstream_job(
-        miner: &MinerWorker,
+        miner: &mut MinerWorker,
         set_login: bool,
         upstreaum_event: &MintBlockEvent,
     ) -> StratumJobResponse {
         let login = miner.base_info.clone();
 
-        let target = miner.diff_manager.read().unwrap().get_target();
+        let (difficulty, target) = {
+            let diff_manager = miner.diff_manager.read().unwrap();
+            (diff_manager.difficulty, diff_manager.get_target())
+        };
+        miner.job_difficulty = difficulty;
         info!(
             "set downstream job diff:{:?}",
             target_hex_to_difficulty(&target).unwrap()
@@ -91,7 +157,7 @@ impl Stratum {
 
     fn dispatch_job_to_clients(&mut self, event: MintBlockEvent) {
         let mut remove_outdated = vec![];
-        for (id, (ch, worker)) in self.mint_block_subscribers.iter() {
+        for (id, (ch, worker)) in self.mint_block_subscribers.iter_mut() {
             let job = Self::get_downstream_job(worker, false, &event);
             info!(target: "stratum", "dispatch startum job:{:?}", job);
             if let Err(err) = ch.unbounded_send(job) {
@@ -104,7 +170,7 @@ impl Stratum {
             }
         }
         for id in remove_outdated {
-            self.mint_block_subscribers.remove(&id);
+            self.remove_worker(&id);
         }
     }
 }
@@ -155,11 +221,27 @@ impl ServiceHandler<Self, SubscribeJobEvent> for Stratum {
                 error!(target: "stratum", "Subscriber assign is failed");
             }
         });
+        let worker_id = match self.extranonce_allocator.allocate() {
+            Ok(worker_id) => worker_id,
+            Err(e) => {
+                error!(target: "stratum", "allocate extranonce failed: {}", e);
+                return;
+            }
+        };
         if let Ok(event) = self.sync_upstream_job() {
-            let miner_worker = MinerWorker::new(sub_id, login);
-            let downstream_job = Self::get_downstream_job(&miner_worker, true, &event);
+            let mut miner_worker = MinerWorker::new(
+                sub_id,
+                worker_id,
+                login,
+                self.vardiff_config,
+                Self::current_timestamp(),
+            );
+            let downstream_job = Self::get_downstream_job(&mut miner_worker, true, &event);
             self.mint_block_subscribers
                 .insert(miner_worker.worker_id, (sender.clone(), miner_worker));
+            if let Some(metrics) = self.metrics.as_ref() {
+                metrics.stratum_workers.inc();
+            }
             ctx.spawn(async move {
                 info!(target:"stratum", "Respond to stratum subscribe:{:?}", downstream_job);
                 if let Err(err) = sender.unbounded_send(downstream_job) {
@@ -167,6 +249,7 @@ impl ServiceHandler<Self, SubscribeJobEvent> for Stratum {
                 }
             });
         } else {
+            self.extranonce_allocator.release(&worker_id);
             error!(target: "stratum", "get current mint job is failed");
         }
     }
@@ -177,25 +260,118 @@ impl ServiceHandler<Self, SubmitShareEvent> for Stratum {
         info!(target: "stratum", "received submit share event:{:?}", &msg.0);
         let current_mint_event = self.sync_upstream_job()?;
         let worker_id = WorkerId::from_hex(msg.0.id.clone())?;
-        if let Some((_job_sender, worker)) = self.mint_block_subscribers.get(&worker_id) {
-            let _updated_diff = worker
-                .diff_manager()
-                .write()
-                .unwrap()
-                .try_update(worker.base_info.login.clone());
+        let (share_difficulty, diff_updated) = match self.mint_block_subscribers.get(&worker_id) {
+            Some((_job_sender, worker)) => (
+                worker.job_difficulty,
+                worker
+                    .diff_manager()
+                    .write()
+                    .unwrap()
+                    .try_update(worker.base_info.login.clone()),
+            ),
+            None => bail!("Unknown stratum worker: {}", msg.0.id),
+        };
+        let job_id = match JobId::new(&msg.0.job_id) {
+            Ok(job_id) => job_id,
+            Err(e) => {
+                self.record_share(&worker_id, ShareStatus::Rejected, diff_updated);
+                return Err(e);
+            }
         };
-        let job_id = JobId::new(&msg.0.job_id)?;
         let submit_job_id = JobId::from_bob(&current_mint_event.minting_blob);
         if job_id != submit_job_id {
             warn!(target: "stratum", "received job mismatch with current job,{:?},{:?}",job_id, submit_job_id);
+            self.record_share(&worker_id, ShareStatus::Stale, diff_updated);
             return Ok(());
         };
 
-        let mut seal: MinerSubmitSealRequest = msg.0.try_into()?;
+        let (status, result) = match TryInto::<MinerSubmitSealRequest>::try_into(msg.0) {
+            Ok(mut seal) => {
+                seal.minting_blob = current_mint_event.minting_blob;
+                let check = check_share(
+                    current_mint_event.strategy,
+                    &seal.minting_blob,
+                    seal.nonce,
+                    seal.extra,
+                    share_difficulty,
+                    current_mint_event.difficulty,
+                );
+                let miner_service = self.miner_service.clone();
+                // wait for the verdict of the miner, the share is stale if the task is changed.
+                submit_share(check, move || {
+                    futures::executor::block_on(miner_service.send(seal))?
+                })
+            }
+            Err(e) => (ShareStatus::Rejected, Err(e)),
+        };
+        self.record_share(&worker_id, status, diff_updated);
+        result
+    }
+}
 
-        seal.minting_blob = current_mint_event.minting_blob;
-        self.miner_service.try_send(seal)?;
-        Ok(())
+/// Check the pow of the share, it should meet the target of the difficulty of the worker's job,
+/// or the target of the block if the block difficulty is lower.
+/// Return whether the share meets the block difficulty, which means a block is found.
+pub fn check_share(
+    strategy: ConsensusStrategy,
+    minting_blob: &[u8],
+    nonce: u32,
+    extra: BlockHeaderExtra,
+    share_difficulty: U256,
+    block_difficulty: U256,
+) -> Result<bool> {
+    let pow_hash: U256 = strategy
+        .calculate_pow_hash(minting_blob, nonce, &extra)?
+        .into();
+    let share_target = difficult_to_target(share_difficulty.min(block_difficulty))?;
+    ensure!(
+        pow_hash <= share_target,
+        "The share with nonce {} does not meet the target {}",
+        nonce,
+        share_target
+    );
+    Ok(pow_hash <= difficult_to_target(block_difficulty)?)
+}
+
+/// Submit the checked share to the miner if it meets the block difficulty,
+/// return the status of the share and the result for the worker.
+fn submit_share<F>(check: Result<bool>, submit_seal: F) -> (ShareStatus, Result<()>)
+where
+    F: FnOnce() -> Result<HashValue>,
+{
+    match check {
+        Err(e) => (ShareStatus::Rejected, Err(e)),
+        Ok(false) => (ShareStatus::Accepted, Ok(())),
+        Ok(true) => match submit_seal() {
+            Ok(block_hash) => {
+                info!(target: "stratum", "share found block: {:?}", block_hash);
+                (ShareStatus::Accepted, Ok(()))
+            }
+            // the share is valid, but the miner has moved to another task.
+            Err(e) if e.downcast_ref::<MinerError>().is_some() => (ShareStatus::Stale, Err(e)),
+            Err(e) => (ShareStatus::Rejected, Err(e)),
+        },
+    }
+}
+
+impl ServiceHandler<Self, WorkerStatsRequest> for Stratum {
+    fn handle(
+        &mut self,
+        msg: WorkerStatsRequest,
+        _ctx: &mut ServiceContext<Self>,
+    ) -> Vec<WorkerStats> {
+        match msg.0 {
+            Some(worker_id) => self
+                .mint_block_subscribers
+                .get(&worker_id)
+                .map(|(_, worker)| vec![WorkerStats::from(worker)])
+                .unwrap_or_default(),
+            None => self
+                .mint_block_subscribers
+                .values()
+                .map(|(_, worker)| WorkerStats::from(worker))
+                .collect(),
+        }
     }
 }
 
@@ -203,7 +379,139 @@ pub struct StratumFactory;
 
 impl ServiceFactory<Stratum> for StratumFactory {
     fn create(ctx: &mut ServiceContext<Stratum>) -> Result<Stratum> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
         let miner_service = ctx.service_ref::<MinerService>()?.clone();
-        Ok(Stratum::new(miner_service))
+        let metrics = config.metrics.registry().and_then(|registry| {
+            StratumMetrics::register(registry)
+                .map_err(|e| error!(target: "stratum", "register stratum metrics failed: {:?}", e))
+                .ok()
+        });
+        Ok(Stratum::new(
+            miner_service,
+            VardiffConfig::from(&config.stratum),
+            metrics,
+        ))
     }
 }
+
+#[cfg(test)]
+fn find_nonce(strategy: ConsensusStrategy, minting_blob: &[u8], difficulty: U256) -> u32 {
+    let target = difficult_to_target(difficulty).unwrap();
+    (0..u32::MAX)
+        .find(|nonce| {
+            let pow_hash: U256 = strategy
+                .calculate_pow_hash(minting_blob, *nonce, &BlockHeaderExtra::new([0u8; 4]))
+                .unwrap()
+                .into();
+            pow_hash <= target
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_check_share() {
+    let strategy = ConsensusStrategy::Keccak;
+    let minting_blob = vec![0u8; 76];
+    let extra = BlockHeaderExtra::new([0u8; 4]);
+    let share_difficulty = U256::from(4);
+    let nonce = find_nonce(strategy, &minting_blob, share_difficulty);
+
+    // the share meets the target of the worker, but not the block.
+    assert!(!check_share(
+        strategy,
+        &minting_blob,
+        nonce,
+        extra,
+        share_difficulty,
+        U256::max_value()
+    )
+    .unwrap());
+    // the share meets the target of the block.
+    assert!(check_share(
+        strategy,
+        &minting_blob,
+        nonce,
+        extra,
+        share_difficulty,
+        share_difficulty
+    )
+    .unwrap());
+    // the share is checked against the block target if the block difficulty is lower.
+    assert!(check_share(
+        strategy,
+        &minting_blob,
+        nonce,
+        extra,
+        U256::max_value(),
+        share_difficulty
+    )
+    .unwrap());
+    // the share does not meet the target of the worker.
+    let nonce = (0..u32::MAX)
+        .find(|nonce| {
+            let pow_hash: U256 = strategy
+                .calculate_pow_hash(&minting_blob, *nonce, &extra)
+                .unwrap()
+                .into();
+            pow_hash > difficult_to_target(share_difficulty).unwrap()
+        })
+        .unwrap();
+    assert!(check_share(
+        strategy,
+        &minting_blob,
+        nonce,
+        extra,
+        share_difficulty,
+        U256::max_value()
+    )
+    .is_err());
+}
+
+#[test]
+fn test_share_accounting() {
+    let mut stats = ShareStats::default();
+    let mut account = |check: Result<bool>, submit_result: Option<Result<HashValue>>| {
+        let mut submitted = false;
+        let (status, result) = submit_share(check, || {
+            submitted = true;
+            submit_result.expect("the share should not be submitted")
+        });
+        stats.record(status, 1);
+        (status, result.is_ok(), submitted)
+    };
+
+    // the invalid share is not submitted to the miner.
+    assert_eq!(
+        account(Err(anyhow::anyhow!("invalid share")), None),
+        (ShareStatus::Rejected, false, false)
+    );
+    // the share below the block difficulty is accepted without submitting.
+    assert_eq!(
+        account(Ok(false), None),
+        (ShareStatus::Accepted, true, false)
+    );
+    // the block found by the share is accepted by the miner.
+    assert_eq!(
+        account(Ok(true), Some(Ok(HashValue::zero()))),
+        (ShareStatus::Accepted, true, true)
+    );
+    // the miner has moved to another task.
+    assert_eq!(
+        account(Ok(true), Some(Err(MinerError::TaskEmptyError.into()))),
+        (ShareStatus::Stale, false, true)
+    );
+    // the seal is refused by the miner.
+    assert_eq!(
+        account(Ok(true), Some(Err(anyhow::anyhow!("invalid seal")))),
+        (ShareStatus::Rejected, false, true)
+    );
+    assert_eq!(
+        stats,
+        ShareStats {
+            accepted: 2,
+            rejected: 2,
+            stale: 1,
+            last_share_time: Some(1),
+        }
+    );
+}
@@ -0,0 +1,102 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{format_err, Result};
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_chain::BlockChain;
+use starcoin_chain_api::ChainReader;
+use starcoin_crypto::HashValue;
+use starcoin_storage::Store;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::{Block, BlockNumber};
+use std::collections::BTreeSet;
+
+/// Max blocks indexed in one round, avoid blocking the block connector too long
+/// when building the index for an existing chain.
+const MAX_INDEX_BLOCKS_PER_ROUND: u64 = 256;
+
+/// Update the address transaction index to the head of `main`.
+/// The indexed blocks which have been retracted from the main chain are removed from the
+/// index first, then the main chain blocks are indexed in order.
+pub fn update_address_txn_index(storage: &dyn Store, main: &BlockChain) -> Result<()> {
+    let mut next_number = match storage.get_address_txn_index_head()? {
+        Some(indexed_head) => rollback_retracted_blocks(storage, main, indexed_head)?,
+        None => 0,
+    };
+    let head_number = main.current_header().number();
+    let end = std::cmp::min(
+        head_number,
+        next_number.saturating_add(MAX_INDEX_BLOCKS_PER_ROUND.saturating_sub(1)),
+    );
+    while next_number <= end {
+        let block = main
+            .get_block_by_number(next_number)?
+            .ok_or_else(|| format_err!("Can not find block by number: {}", next_number))?;
+        storage.append_address_txns(block.id(), block_address_txns(storage, &block)?)?;
+        next_number = next_number.saturating_add(1);
+    }
+    Ok(())
+}
+
+/// Remove the indexed blocks not in the main chain, return the next block number to index.
+fn rollback_retracted_blocks(
+    storage: &dyn Store,
+    main: &BlockChain,
+    indexed_head: HashValue,
+) -> Result<BlockNumber> {
+    let indexed_info = storage
+        .get_block_info(indexed_head)?
+        .ok_or_else(|| format_err!("Can not find block info by id: {}", indexed_head))?;
+    let indexed_accumulator = MerkleAccumulator::new_with_info(
+        indexed_info.block_accumulator_info,
+        storage.get_accumulator_store(AccumulatorStoreType::Block),
+    );
+    let mut number = indexed_accumulator.num_leaves().saturating_sub(1);
+    loop {
+        let indexed_id = indexed_accumulator
+            .get_leaf(number)?
+            .ok_or_else(|| format_err!("Can not find indexed block id by number: {}", number))?;
+        if main.get_hash_by_number(number)? == Some(indexed_id) {
+            return Ok(number.saturating_add(1));
+        }
+        let block = storage
+            .get_block_by_hash(indexed_id)?
+            .ok_or_else(|| format_err!("Can not find block by id: {}", indexed_id))?;
+        number = number
+            .checked_sub(1)
+            .ok_or_else(|| format_err!("The indexed genesis is not in the main chain"))?;
+        let parent_id = indexed_accumulator
+            .get_leaf(number)?
+            .ok_or_else(|| format_err!("Can not find indexed block id by number: {}", number))?;
+        storage.remove_address_txns(parent_id, block_address_txns(storage, &block)?)?;
+    }
+}
+
+/// The `(address, txn_hash)` pairs of the user transactions in the block, the addresses
+/// of a transaction are its sender and the creators of the events it emitted.
+fn block_address_txns(
+    storage: &dyn Store,
+    block: &Block,
+) -> Result<Vec<(AccountAddress, HashValue)>> {
+    let txn_infos = storage.get_block_transaction_infos(block.id())?;
+    let mut address_txns = vec![];
+    for txn in block.transactions() {
+        let txn_hash = txn.id();
+        let mut addresses = BTreeSet::new();
+        addresses.insert(txn.sender());
+        if let Some(txn_info) = txn_infos
+            .iter()
+            .find(|txn_info| txn_info.transaction_hash() == txn_hash)
+        {
+            for event in storage
+                .get_contract_events(txn_info.id())?
+                .unwrap_or_default()
+            {
+                addresses.insert(event.key().get_creator_address());
+            }
+        }
+        address_txns.extend(addresses.into_iter().map(|address| (address, txn_hash)));
+    }
+    Ok(address_txns)
+}
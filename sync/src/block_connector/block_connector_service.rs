@@ -21,6 +21,7 @@ use starcoin_crypto::HashValue;
 use starcoin_dag::blockdag::BlockDAG;
 use starcoin_executor::VMMetrics;
 use starcoin_logger::prelude::*;
+use starcoin_logger::LogContext;
 use starcoin_network::NetworkServiceRef;
 use starcoin_service_registry::{
     ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler, ServiceRequest,
@@ -317,6 +318,7 @@ where
             return;
         }
         let peer_id = msg.get_peer_id();
+        let _log_context = LogContext::with_peer_id(&peer_id);
         if let Err(e) = self.chain_service.try_connect(msg.get_block().clone()) {
             match e.downcast::<ConnectBlockError>() {
                 std::result::Result::Ok(connect_error) => {
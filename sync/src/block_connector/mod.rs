@@ -5,9 +5,12 @@ use starcoin_crypto::HashValue;
 use starcoin_service_registry::ServiceRequest;
 use starcoin_types::block::{Block, ExecutedBlock};
 
+mod address_txn_indexer;
 mod block_connector_service;
 mod metrics;
 #[cfg(test)]
+mod test_address_txn_indexer;
+#[cfg(test)]
 mod test_illegal_block;
 #[cfg(test)]
 mod test_write_block_chain;
@@ -0,0 +1,120 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::block_connector::address_txn_indexer::update_address_txn_index;
+use crate::block_connector::create_writeable_block_chain;
+use crate::block_connector::WriteBlockChainService;
+use starcoin_account_api::AccountInfo;
+use starcoin_chain::{BlockChain, ChainReader};
+use starcoin_config::NodeConfig;
+use starcoin_consensus::Consensus;
+use starcoin_crypto::HashValue;
+use starcoin_transaction_builder::{peer_to_peer_txn_sent_as_association, DEFAULT_EXPIRATION_TIME};
+use starcoin_txpool_mock_service::MockTxPoolService;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::Block;
+
+fn new_transfer_block(
+    node_config: &NodeConfig,
+    service: &WriteBlockChainService<MockTxPoolService>,
+    parent_id: HashValue,
+    recipient: AccountAddress,
+    seq_num: u64,
+) -> Block {
+    let net = node_config.net();
+    let chain = BlockChain::new(
+        net.time_service(),
+        parent_id,
+        service.get_main().get_storage(),
+        None,
+        service.get_main().dag(),
+    )
+    .unwrap();
+    let txn = peer_to_peer_txn_sent_as_association(
+        recipient,
+        seq_num,
+        10000,
+        net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        net,
+    );
+    let (template, _) = chain
+        .create_block_template(
+            *AccountInfo::random().address(),
+            None,
+            vec![txn],
+            vec![],
+            None,
+            Some(vec![parent_id]),
+        )
+        .unwrap();
+    chain
+        .consensus()
+        .create_block(template, net.time_service().as_ref())
+        .unwrap()
+}
+
+#[stest::test(timeout = 120)]
+async fn test_address_txn_index_connect_rollback_restart() -> anyhow::Result<()> {
+    let (mut service, node_config, storage) = create_writeable_block_chain().await;
+    let genesis_id = service.get_main().current_header().id();
+    let alice = AccountAddress::random();
+    let bob = AccountAddress::random();
+
+    // connect: genesis -> a1 -> a2 -> a3, every block sends a txn to alice.
+    let mut parent_id = genesis_id;
+    let mut main_blocks = vec![];
+    for seq_num in 0..3 {
+        let block = new_transfer_block(&node_config, &service, parent_id, alice, seq_num);
+        parent_id = block.id();
+        main_blocks.push(block.clone());
+        service.try_connect(block)?;
+    }
+    update_address_txn_index(storage.as_ref(), service.get_main())?;
+    assert_eq!(storage.get_address_txn_count(alice)?, 3);
+    assert_eq!(
+        storage.get_address_txns(alice, 0, false, 10)?,
+        main_blocks
+            .iter()
+            .map(|block| block.transactions()[0].id())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        storage.get_address_txn_index_head()?,
+        Some(main_blocks[2].id())
+    );
+
+    // rollback: a1 -> b2 -> b3 -> b4 becomes the main chain, a2 and a3 are removed from the index.
+    let mut parent_id = main_blocks[0].id();
+    for seq_num in 1..4 {
+        let block = new_transfer_block(&node_config, &service, parent_id, bob, seq_num);
+        parent_id = block.id();
+        service.try_connect(block)?;
+    }
+    assert_eq!(service.get_main().current_header().id(), parent_id);
+    update_address_txn_index(storage.as_ref(), service.get_main())?;
+    assert_eq!(storage.get_address_txn_count(alice)?, 1);
+    assert_eq!(
+        storage.get_address_txns(alice, 0, false, 10)?,
+        vec![main_blocks[0].transactions()[0].id()]
+    );
+    assert_eq!(storage.get_address_txn_count(bob)?, 3);
+    assert_eq!(storage.get_address_txn_index_head()?, Some(parent_id));
+
+    // restart: the blocks connected while the indexer is not running are indexed
+    // by a chain loaded from the storage, and indexing again changes nothing.
+    let block = new_transfer_block(&node_config, &service, parent_id, alice, 4);
+    service.try_connect(block.clone())?;
+    let main = BlockChain::new(
+        node_config.net().time_service(),
+        block.id(),
+        storage.clone(),
+        None,
+        service.get_main().dag(),
+    )?;
+    update_address_txn_index(storage.as_ref(), &main)?;
+    update_address_txn_index(storage.as_ref(), &main)?;
+    assert_eq!(storage.get_address_txn_count(alice)?, 2);
+    assert_eq!(storage.get_address_txn_count(bob)?, 3);
+    assert_eq!(storage.get_address_txn_index_head()?, Some(block.id()));
+    Ok(())
+}
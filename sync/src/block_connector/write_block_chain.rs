@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::block_connector::address_txn_indexer::update_address_txn_index;
 use crate::block_connector::metrics::ChainMetrics;
 use anyhow::{format_err, Ok, Result};
 use starcoin_chain::BlockChain;
@@ -14,6 +15,7 @@ use starcoin_crypto::HashValue;
 use starcoin_dag::blockdag::BlockDAG;
 use starcoin_executor::VMMetrics;
 use starcoin_logger::prelude::*;
+use starcoin_logger::LogContext;
 use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::{ServiceContext, ServiceRef};
 use starcoin_storage::Store;
@@ -76,12 +78,28 @@ where
     P: TxPoolSyncService + 'static,
 {
     fn try_connect(&mut self, block: Block) -> Result<()> {
+        let span = tracing::info_span!(
+            "connect_block",
+            id = %block.id(),
+            number = block.header().number(),
+            result = tracing::field::Empty
+        );
+        let _enter = span.enter();
+        let _log_context = LogContext::with_block_number(block.header().number());
         let _timer = self
             .metrics
             .as_ref()
             .map(|metrics| metrics.chain_block_connect_time.start_timer());
 
         let result = self.connect_inner(block);
+        match result.as_ref() {
+            std::result::Result::Ok(connect) => {
+                span.record("result", &tracing::field::display(connect));
+            }
+            Err(err) => {
+                span.record("result", &tracing::field::display(err));
+            }
+        }
 
         if let Some(metrics) = self.metrics.as_ref() {
             let result = match result.as_ref() {
@@ -286,6 +304,7 @@ where
         if branch_total_difficulty > main_total_difficulty {
             self.main = new_branch;
             self.update_startup_info(self.main.head_block().header())?;
+            self.update_address_txn_index();
             ctx.broadcast(NewHeadBlock {
                 executed_block: Arc::new(self.main.head_block()),
             });
@@ -341,6 +360,7 @@ where
             }
         }
         self.commit_2_txpool(enacted_blocks, retracted_blocks);
+        self.update_address_txn_index();
         self.config
             .net()
             .time_service()
@@ -432,6 +452,14 @@ where
         }
     }
 
+    fn update_address_txn_index(&self) {
+        if self.config.storage.address_txn_index() {
+            if let Err(e) = update_address_txn_index(self.storage.as_ref(), &self.main) {
+                error!("Update address txn index error: {:?}", e);
+            }
+        }
+    }
+
     fn find_ancestors_from_accumulator(
         &self,
         new_branch: &BlockChain,
@@ -0,0 +1,202 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The state service of the light node, it has no state of its own, the states are read from
+//! the full peers and verified by the proofs against the state root of the light head.
+
+use anyhow::{bail, ensure, format_err, Result};
+use network_api::{PeerId, PeerProvider, PeerSelector};
+use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_network::NetworkServiceRef;
+use starcoin_network_rpc_api::gen_client::NetworkRpcClient;
+use starcoin_network_rpc_api::{GetStateWithProof, GetStateWithTableItemProof};
+use starcoin_state_api::{ChainStateAsyncService, StateWithProof, StateWithTableItemProof};
+use starcoin_storage::{BlockStore, Storage};
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::AccountResource;
+use starcoin_types::account_state::AccountState;
+use starcoin_types::state_set::AccountStateSet;
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::state_store::table::{TableHandle, TableInfo};
+use std::sync::Arc;
+
+/// Read the states from the full peers asynchronously, so the reads never block on the network.
+#[derive(Clone)]
+pub struct LightChainStateService {
+    config: Arc<NodeConfig>,
+    storage: Arc<Storage>,
+    network: NetworkServiceRef,
+}
+
+impl LightChainStateService {
+    pub fn new(config: Arc<NodeConfig>, storage: Arc<Storage>, network: NetworkServiceRef) -> Self {
+        Self {
+            config,
+            storage,
+            network,
+        }
+    }
+
+    /// The state root of the light head, or of the main chain head if the light sync
+    /// is not started yet.
+    fn light_state_root(&self) -> Result<HashValue> {
+        let head_id = match self.storage.get_light_head()? {
+            Some(head_id) => head_id,
+            None => {
+                self.storage
+                    .get_startup_info()?
+                    .ok_or_else(|| format_err!("Startup info should exist."))?
+                    .main
+            }
+        };
+        self.storage
+            .get_block_header_by_hash(head_id)?
+            .map(|header| header.state_root())
+            .ok_or_else(|| format_err!("Can not find block header by id: {}", head_id))
+    }
+
+    /// Select a full peer to read the states from.
+    async fn select_peer(&self) -> Result<PeerId> {
+        let peers = self.network.peer_set().await?;
+        let peer_selector = PeerSelector::new(peers, self.config.sync.peer_select_strategy(), None);
+        peer_selector.retain_rpc_peers();
+        peer_selector
+            .select_peer()
+            .ok_or_else(|| format_err!("No peers to read the state from."))
+    }
+
+    fn client(&self) -> NetworkRpcClient {
+        NetworkRpcClient::new(self.network.clone())
+    }
+
+    async fn get_with_proof_at(
+        &self,
+        access_path: AccessPath,
+        state_root: HashValue,
+    ) -> Result<StateWithProof> {
+        let peer_id = self.select_peer().await?;
+        let req = GetStateWithProof {
+            state_root,
+            access_path: access_path.clone(),
+        };
+        let state_proof = self.client().get_state_with_proof(peer_id, req).await?;
+        state_proof
+            .proof
+            .verify(state_root, access_path, state_proof.state.as_deref())?;
+        Ok(state_proof)
+    }
+
+    /// The account state is verified by the proof of the account's `AccountResource`.
+    async fn get_account_state_at(
+        &self,
+        address: AccountAddress,
+        state_root: HashValue,
+    ) -> Result<Option<AccountState>> {
+        let access_path = AccessPath::new(address, AccountResource::resource_path());
+        self.get_with_proof_at(access_path, state_root)
+            .await?
+            .proof
+            .account_state
+            .map(|blob| AccountState::try_from(blob.as_ref()))
+            .transpose()
+    }
+
+    async fn get_with_table_item_proof_at(
+        &self,
+        handle: TableHandle,
+        key: Vec<u8>,
+        state_root: HashValue,
+    ) -> Result<StateWithTableItemProof> {
+        let peer_id = self.select_peer().await?;
+        let req = GetStateWithTableItemProof {
+            state_root,
+            handle,
+            key: key.clone(),
+        };
+        let proof = self
+            .client()
+            .get_state_with_table_item_proof(peer_id, req)
+            .await?;
+        ensure!(
+            proof.state_proof.1 == state_root,
+            "Table item proof state root mismatch, expect: {}, got: {}",
+            state_root,
+            proof.state_proof.1
+        );
+        proof.verify(&handle, key.as_slice())?;
+        Ok(proof)
+    }
+}
+
+#[async_trait::async_trait]
+impl ChainStateAsyncService for LightChainStateService {
+    async fn get(self, access_path: AccessPath) -> Result<Option<Vec<u8>>> {
+        let state_root = self.light_state_root()?;
+        Ok(self.get_with_proof_at(access_path, state_root).await?.state)
+    }
+
+    async fn get_with_proof(self, access_path: AccessPath) -> Result<StateWithProof> {
+        let state_root = self.light_state_root()?;
+        self.get_with_proof_at(access_path, state_root).await
+    }
+
+    async fn get_account_state(self, address: AccountAddress) -> Result<Option<AccountState>> {
+        let state_root = self.light_state_root()?;
+        self.get_account_state_at(address, state_root).await
+    }
+
+    async fn get_account_state_set(
+        self,
+        _address: AccountAddress,
+        _state_root: Option<HashValue>,
+    ) -> Result<Option<AccountStateSet>> {
+        bail!("Get account state set is not supported by the light node.")
+    }
+
+    async fn state_root(self) -> Result<HashValue> {
+        self.light_state_root()
+    }
+
+    async fn get_with_proof_by_root(
+        self,
+        access_path: AccessPath,
+        state_root: HashValue,
+    ) -> Result<StateWithProof> {
+        self.get_with_proof_at(access_path, state_root).await
+    }
+
+    async fn get_account_state_by_root(
+        self,
+        address: AccountAddress,
+        state_root: HashValue,
+    ) -> Result<Option<AccountState>> {
+        self.get_account_state_at(address, state_root).await
+    }
+
+    async fn get_with_table_item_proof(
+        self,
+        handle: TableHandle,
+        key: Vec<u8>,
+    ) -> Result<StateWithTableItemProof> {
+        let state_root = self.light_state_root()?;
+        self.get_with_table_item_proof_at(handle, key, state_root)
+            .await
+    }
+
+    async fn get_with_table_item_proof_by_root(
+        self,
+        handle: TableHandle,
+        key: Vec<u8>,
+        state_root: HashValue,
+    ) -> Result<StateWithTableItemProof> {
+        self.get_with_table_item_proof_at(handle, key, state_root)
+            .await
+    }
+
+    async fn get_table_info(self, _address: AccountAddress) -> Result<Option<TableInfo>> {
+        // the table info is returned by the peer without a proof, it can not be verified.
+        bail!("Get table info is not supported by the light node.")
+    }
+}
@@ -4,7 +4,10 @@
 use crate::block_connector::BlockConnectorService;
 use crate::store::sync_dag_store::{SyncDagStore, SyncDagStoreConfig};
 use crate::sync_metrics::SyncMetrics;
-use crate::tasks::{full_sync_task, AncestorEvent, SyncFetcher};
+use crate::tasks::{
+    fast_sync_task, full_sync_task, light_sync_task, AncestorEvent, BlockConnectAction,
+    BlockConnectedEvent, SyncFetcher,
+};
 use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
 use anyhow::{format_err, Result};
 use futures::FutureExt;
@@ -29,7 +32,7 @@ use starcoin_sync_api::{
     SyncProgressRequest, SyncServiceHandler, SyncStartRequest, SyncStatusRequest, SyncTarget,
 };
 use starcoin_txpool::TxPoolService;
-use starcoin_types::block::{Block, BlockIdAndNumber};
+use starcoin_types::block::{Block, BlockHeader, BlockIdAndNumber};
 use starcoin_types::startup_info::ChainStatus;
 use starcoin_types::sync_status::SyncStatus;
 use starcoin_types::system_events::{NewHeadBlock, SyncStatusChangeEvent, SystemStarted};
@@ -39,6 +42,7 @@ use std::time::Duration;
 use stream_task::{TaskError, TaskEventCounterHandle, TaskHandle};
 
 const REPUTATION_THRESHOLD: i32 = -1000;
+const LIGHT_SYNC_CHECK_INTERVAL: Duration = Duration::from_secs(10);
 
 //TODO combine task_handle and task_event_handle in stream_task
 pub struct SyncTaskHandle {
@@ -77,10 +81,14 @@ impl SyncService {
         let startup_info = storage
             .get_startup_info()?
             .ok_or_else(|| format_err!("can't get startup info"))?;
-        let head_block_hash = startup_info.main;
-        let head_block = storage
-            .get_block(head_block_hash)?
-            .ok_or_else(|| format_err!("can't get block by hash {}", head_block_hash))?;
+        // the light node reports the light head, only its header is stored.
+        let head_block_hash = match storage.get_light_head()? {
+            Some(light_head) if config.sync.sync_mode().is_light() => light_head,
+            _ => startup_info.main,
+        };
+        let head_header = storage
+            .get_block_header_by_hash(head_block_hash)?
+            .ok_or_else(|| format_err!("can't get block header by hash {}", head_block_hash))?;
         let head_block_info = storage
             .get_block_info(head_block_hash)?
             .ok_or_else(|| format_err!("can't get block info by hash {}", head_block_hash))?;
@@ -101,7 +109,7 @@ impl SyncService {
             .registry()
             .and_then(|registry| PeerScoreMetrics::register(registry).ok());
         Ok(Self {
-            sync_status: SyncStatus::new(ChainStatus::new(head_block.header, head_block_info)),
+            sync_status: SyncStatus::new(ChainStatus::new(head_header, head_block_info)),
             stage: SyncStage::NotStart,
             config,
             storage,
@@ -227,10 +235,17 @@ impl SyncService {
         let network = ctx.get_shared::<NetworkServiceRef>()?;
         let storage = self.storage.clone();
         let self_ref = ctx.self_ref();
-        let connector_service = ctx
-            .service_ref::<BlockConnectorService<TxPoolService>>()?
-            .clone();
         let config = self.config.clone();
+        let light_mode = config.sync.sync_mode().is_light();
+        // the light node does not connect blocks, the block connector is not registered.
+        let connector_service = if light_mode {
+            None
+        } else {
+            Some(
+                ctx.service_ref::<BlockConnectorService<TxPoolService>>()?
+                    .clone(),
+            )
+        };
         let peer_score_metrics = self.peer_score_metrics.clone();
         let sync_metrics = self.metrics.clone();
         let vm_metrics = self.vm_metrics.clone();
@@ -240,7 +255,11 @@ impl SyncService {
             let startup_info = storage
                 .get_startup_info()?
                 .ok_or_else(|| format_err!("Startup info should exist."))?;
-            let current_block_id = startup_info.main;
+            // the light sync follows the light head instead of the main chain.
+            let current_block_id = match storage.get_light_head()? {
+                Some(light_head) if light_mode => light_head,
+                _ => startup_info.main,
+            };
             let current_block_info =
                 storage.get_block_info(current_block_id)?.ok_or_else(|| {
                     format_err!("Can not find block info by id: {}", current_block_id)
@@ -259,6 +278,44 @@ impl SyncService {
             {
                 info!("[sync] Find target({}), total_difficulty:{}, current head({})'s total_difficulty({})", target.target_id.id(), target.block_info.total_difficulty, current_block_id, current_block_info.total_difficulty);
 
+                let connector_service = match connector_service {
+                    Some(connector_service) => connector_service,
+                    None => {
+                        if let Some(head) = light_sync_task(
+                            target,
+                            storage.clone(),
+                            rpc_client.clone(),
+                            dag_fork_height,
+                        )
+                        .await?
+                        {
+                            self_ref.notify(LightHeadChangedEvent { head })?;
+                        }
+                        return Ok(None);
+                    }
+                };
+
+                let mut current_block_id = current_block_id;
+                if config.sync.sync_mode().is_fast() {
+                    if let Some(pivot_block) = fast_sync_task(
+                        target.clone(),
+                        config.sync.fast_sync_pivot_distance(),
+                        storage.clone(),
+                        rpc_client.clone(),
+                        config.sync.max_retry_times(),
+                        dag_fork_height,
+                    )
+                    .await?
+                    {
+                        current_block_id = pivot_block.id();
+                        connector_service.notify(BlockConnectedEvent {
+                            block: pivot_block,
+                            feedback: None,
+                            action: BlockConnectAction::ConnectExecutedBlock,
+                        })?;
+                    }
+                }
+
                 let (fut, task_handle, task_event_handle) = full_sync_task(
                     current_block_id,
                     target.clone(),
@@ -415,6 +472,12 @@ impl ActorService for SyncService {
         ctx.subscribe::<SystemStarted>();
         ctx.subscribe::<PeerEvent>();
         ctx.subscribe::<NewHeadBlock>();
+        // the light node does not connect new blocks, so check the new headers periodically.
+        if self.config.sync.sync_mode().is_light() {
+            ctx.run_interval(LIGHT_SYNC_CHECK_INTERVAL, |ctx| {
+                ctx.notify(CheckSyncEvent::default());
+            });
+        }
         Ok(())
     }
 
@@ -612,6 +675,38 @@ impl EventHandler<Self, SyncDoneEvent> for SyncService {
     }
 }
 
+/// The light head is moved by the light sync, the light node reports it as its chain status.
+#[derive(Clone, Debug)]
+pub struct LightHeadChangedEvent {
+    head: BlockHeader,
+}
+
+impl EventHandler<Self, LightHeadChangedEvent> for SyncService {
+    fn handle_event(&mut self, msg: LightHeadChangedEvent, ctx: &mut ServiceContext<Self>) {
+        let head = msg.head;
+        info!(
+            "[sync] Light head changed to {:?}, number: {}",
+            head.id(),
+            head.number()
+        );
+        match self.storage.get_block_info(head.id()) {
+            Ok(Some(block_info)) => {
+                if self
+                    .sync_status
+                    .update_chain_status(ChainStatus::new(head, block_info))
+                {
+                    ctx.broadcast(SyncStatusChangeEvent(self.sync_status.clone()));
+                }
+            }
+            Ok(None) => error!(
+                "[sync] Can not find block info of the light head: {:?}",
+                head.id()
+            ),
+            Err(e) => error!("[sync] Get block info of the light head error: {:?}", e),
+        }
+    }
+}
+
 impl EventHandler<Self, NewHeadBlock> for SyncService {
     fn handle_event(&mut self, msg: NewHeadBlock, ctx: &mut ServiceContext<Self>) {
         if self.sync_status.update_chain_status(ChainStatus::new(
@@ -0,0 +1,651 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tasks::{
+    fetcher_err_map, AccumulatorCollector, BlockAccumulatorSyncTask, ExtSyncTaskErrorHandle,
+    SyncFetcher,
+};
+use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
+use anyhow::{ensure, format_err, Result};
+use forkable_jellyfish_merkle::node_type::{LeafNode, Node};
+use forkable_jellyfish_merkle::RawKey;
+use futures::future::BoxFuture;
+use futures::{FutureExt, StreamExt, TryFutureExt, TryStreamExt};
+use network_api::PeerId;
+use starcoin_accumulator::accumulator_info::AccumulatorInfo;
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::node_index::NodeIndex;
+use starcoin_accumulator::proof::AccumulatorProof;
+use starcoin_accumulator::{
+    Accumulator, AccumulatorNode, AccumulatorTreeStore, MerkleAccumulator,
+    MAX_ACCUMULATOR_PROOF_DEPTH,
+};
+use starcoin_crypto::hash::SPARSE_MERKLE_PLACEHOLDER_HASH;
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_network_rpc_api::MAX_BLOCK_HEADER_REQUEST_SIZE;
+use starcoin_state_api::{AccountStateReader, TABLE_PATH_LIST};
+use starcoin_state_tree::{StateNode, StateNodeStore};
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::block_info::BlockInfoStore;
+use starcoin_storage::{BlockStore, Storage, Store};
+use starcoin_sync_api::SyncTarget;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::genesis_address;
+use starcoin_types::account_state::AccountState;
+use starcoin_types::block::{Block, BlockHeader, BlockIdAndNumber, BlockInfo, BlockNumber};
+use starcoin_types::startup_info::StartupInfo;
+use starcoin_vm_types::access_path::ModuleName;
+use starcoin_vm_types::account_config::TABLE_HANDLE_ADDRESS_LIST;
+use starcoin_vm_types::language_storage::StructTag;
+use starcoin_vm_types::on_chain_resource::Epoch;
+use starcoin_vm_types::state_store::table::TableHandle;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use stream_task::{Generator, TaskEventCounterHandle, TaskGenerator, TaskState};
+
+/// State nodes are traversed in batches, nodes of a batch are written to storage together.
+const STATE_NODE_BATCH_SIZE: usize = 256;
+/// Max concurrent state node requests.
+const STATE_NODE_CONCURRENCY: usize = 16;
+const BLOCK_BATCH_SIZE: usize = 10;
+
+pub trait StateSyncFetcher: Send + Sync {
+    fn fetch_state_node(
+        &self,
+        node_hash: HashValue,
+    ) -> BoxFuture<Result<(Option<PeerId>, Option<StateNode>)>>;
+
+    fn fetch_accumulator_node(
+        &self,
+        node_hash: HashValue,
+        store_type: AccumulatorStoreType,
+    ) -> BoxFuture<Result<AccumulatorNode>>;
+}
+
+impl<T> StateSyncFetcher for Arc<T>
+where
+    T: StateSyncFetcher,
+{
+    fn fetch_state_node(
+        &self,
+        node_hash: HashValue,
+    ) -> BoxFuture<Result<(Option<PeerId>, Option<StateNode>)>> {
+        StateSyncFetcher::fetch_state_node(self.as_ref(), node_hash)
+    }
+
+    fn fetch_accumulator_node(
+        &self,
+        node_hash: HashValue,
+        store_type: AccumulatorStoreType,
+    ) -> BoxFuture<Result<AccumulatorNode>> {
+        StateSyncFetcher::fetch_accumulator_node(self.as_ref(), node_hash, store_type)
+    }
+}
+
+impl StateSyncFetcher for VerifiedRpcClient {
+    fn fetch_state_node(
+        &self,
+        node_hash: HashValue,
+    ) -> BoxFuture<Result<(Option<PeerId>, Option<StateNode>)>> {
+        self.get_state_node_by_node_hash(node_hash)
+            .map_ok(|(peer_id, node)| (Some(peer_id), node))
+            .map_err(fetcher_err_map)
+            .boxed()
+    }
+
+    fn fetch_accumulator_node(
+        &self,
+        node_hash: HashValue,
+        store_type: AccumulatorStoreType,
+    ) -> BoxFuture<Result<AccumulatorNode>> {
+        self.get_accumulator_node_by_node_hash(node_hash, store_type)
+            .map_ok(|(_peer_id, node)| node)
+            .map_err(fetcher_err_map)
+            .boxed()
+    }
+}
+
+/// The kind of state tree a state node belongs to, it decides how to decode the node
+/// and where the sub trees hang on the leaves.
+#[derive(Clone, Debug)]
+enum StateTreeKind {
+    /// AccountAddress -> AccountState, the leaves point to the code and resource trees.
+    Global,
+    /// ModuleName -> module bytes.
+    Code,
+    /// StructTag -> resource bytes, the leaf of the given `TableHandles` resource holds
+    /// the root of a table handle tree.
+    Resource(Option<StructTag>),
+    /// TableHandle -> root of the table item tree.
+    TableHandles,
+    /// Table key -> table value.
+    TableItems,
+}
+
+/// Decode the node and verify it by `hash`, then return the children of the node.
+fn children_of<K, F>(
+    hash: HashValue,
+    node: &StateNode,
+    kind: &StateTreeKind,
+    leaf_children: F,
+) -> Result<Vec<(HashValue, StateTreeKind)>>
+where
+    K: RawKey,
+    F: FnOnce(&LeafNode<K>) -> Result<Vec<(HashValue, StateTreeKind)>>,
+{
+    let decoded = Node::<K>::decode(node.0.as_slice())?;
+    ensure!(
+        decoded.hash() == hash,
+        "State node hash mismatch, expect: {}, got: {}",
+        hash,
+        decoded.hash()
+    );
+    let children = match decoded {
+        Node::Null => vec![],
+        Node::Internal(internal) => internal
+            .all_child()
+            .into_iter()
+            .map(|child| (child, kind.clone()))
+            .collect(),
+        Node::Leaf(leaf) => leaf_children(&leaf)?,
+    };
+    Ok(children
+        .into_iter()
+        .filter(|(child, _)| *child != *SPARSE_MERKLE_PLACEHOLDER_HASH)
+        .collect())
+}
+
+fn state_node_children(
+    hash: HashValue,
+    node: &StateNode,
+    kind: &StateTreeKind,
+) -> Result<Vec<(HashValue, StateTreeKind)>> {
+    match kind {
+        StateTreeKind::Global => children_of::<AccountAddress, _>(hash, node, kind, |leaf| {
+            let account_state = AccountState::try_from(leaf.blob().as_ref())?;
+            let table_handles = TABLE_HANDLE_ADDRESS_LIST
+                .iter()
+                .position(|address| address == leaf.raw_key())
+                .and_then(|idx| TABLE_PATH_LIST.get(idx))
+                .and_then(|path| path.as_struct_tag().cloned());
+            let mut children = vec![(
+                account_state.resource_root(),
+                StateTreeKind::Resource(table_handles),
+            )];
+            if let Some(code_root) = account_state.code_root() {
+                children.push((code_root, StateTreeKind::Code));
+            }
+            Ok(children)
+        }),
+        StateTreeKind::Code => children_of::<ModuleName, _>(hash, node, kind, |_| Ok(vec![])),
+        StateTreeKind::Resource(table_handles) => {
+            children_of::<StructTag, _>(hash, node, kind, |leaf| {
+                if table_handles.as_ref() == Some(leaf.raw_key()) {
+                    Ok(vec![(
+                        HashValue::from_slice(leaf.blob().as_ref())?,
+                        StateTreeKind::TableHandles,
+                    )])
+                } else {
+                    Ok(vec![])
+                }
+            })
+        }
+        StateTreeKind::TableHandles => children_of::<TableHandle, _>(hash, node, kind, |leaf| {
+            Ok(vec![(
+                HashValue::from_slice(leaf.blob().as_ref())?,
+                StateTreeKind::TableItems,
+            )])
+        }),
+        StateTreeKind::TableItems => children_of::<Vec<u8>, _>(hash, node, kind, |_| Ok(vec![])),
+    }
+}
+
+/// Fast sync downloads the state of a pivot block instead of executing all the blocks before it.
+///
+/// The pivot is `pivot_distance` blocks behind the sync target. The positions of the pivot and its
+/// child are proved by the block accumulator of the sync target, the pivot block info is verified
+/// by the child header, whose difficulty and pow are verified once the pivot state and the
+/// difficulty window blocks are downloaded. The block accumulator is rebuilt from the block ids and
+/// verified by the pivot block info, the total difficulty of the pivot block info is derived from
+/// the headers of the rebuilt block accumulator, and every state node is verified by its hash from the
+/// pivot header's `state_root`. All downloaded data is written to storage directly and the
+/// pivot is saved before downloading, so an interrupted fast sync resumes from where it stopped,
+/// the local chain is switched to the pivot only after everything is downloaded.
+///
+/// Fast sync only starts from genesis, and is skipped if the pivot is a dag block.
+/// Returns the pivot block if the local chain is switched to it.
+pub async fn fast_sync_task<F>(
+    target: SyncTarget,
+    pivot_distance: u64,
+    storage: Arc<Storage>,
+    fetcher: Arc<F>,
+    max_retry_times: u64,
+    dag_fork_number: Option<BlockNumber>,
+) -> Result<Option<Block>>
+where
+    F: SyncFetcher + StateSyncFetcher + 'static,
+{
+    let startup_info = storage
+        .get_startup_info()?
+        .ok_or_else(|| format_err!("Startup info should exist."))?;
+    let current_block_id = startup_info.main;
+    let current_header = storage
+        .get_block_header_by_hash(current_block_id)?
+        .ok_or_else(|| format_err!("Can not find block header by id: {}", current_block_id))?;
+    let current_block_info = storage
+        .get_block_info(current_block_id)?
+        .ok_or_else(|| format_err!("Can not find block info by id: {}", current_block_id))?;
+
+    let pivot_id = match storage.get_fast_sync_pivot()? {
+        Some(pivot_id) => {
+            info!("[sync] Resume fast sync with pivot: {}", pivot_id);
+            Some(pivot_id)
+        }
+        None if current_header.number() == 0 => {
+            let pivot_number = target.target_id.number().saturating_sub(pivot_distance);
+            if pivot_number == 0 {
+                None
+            } else if dag_fork_number.map_or(false, |number| pivot_number >= number) {
+                info!(
+                    "[sync] Skip fast sync, the pivot number {} is after the dag fork number {:?}",
+                    pivot_number, dag_fork_number
+                );
+                None
+            } else {
+                fetcher
+                    .fetch_block_id(target.peers.first().cloned(), pivot_number)
+                    .await?
+            }
+        }
+        None => None,
+    };
+    let pivot_id = match pivot_id {
+        Some(pivot_id) => pivot_id,
+        None => return Ok(None),
+    };
+
+    let (pivot_block, pivot_block_info, child_header) = fetch_pivot(
+        pivot_id,
+        &target.block_info.block_accumulator_info,
+        fetcher.as_ref(),
+    )
+    .await?;
+    let pivot = BlockIdAndNumber::new(pivot_id, pivot_block.header().number());
+    storage.save_fast_sync_pivot(pivot_id)?;
+    info!("[sync] Start fast sync to pivot: {:?}", pivot);
+
+    // rebuild the block accumulator and verify it by the pivot block info.
+    let event_handle = Arc::new(TaskEventCounterHandle::new());
+    let accumulator_task = BlockAccumulatorSyncTask::new(
+        current_header.number().saturating_add(1),
+        pivot_block_info.block_accumulator_info.clone(),
+        fetcher.clone(),
+        100,
+    )?;
+    let buffer_size = std::cmp::min(
+        accumulator_task
+            .total_items()
+            .ok_or_else(|| format_err!("Block accumulator sync task has no total items"))?
+            as usize,
+        std::cmp::max(target.peers.len(), 1),
+    );
+    let (_, block_accumulator) = TaskGenerator::new(
+        accumulator_task,
+        buffer_size,
+        max_retry_times,
+        100,
+        AccumulatorCollector::new(
+            storage.get_accumulator_store(AccumulatorStoreType::Block),
+            BlockIdAndNumber::new(current_block_id, current_header.number()),
+            current_block_info.block_accumulator_info.clone(),
+            pivot_block_info.block_accumulator_info.clone(),
+            None,
+        ),
+        event_handle,
+        Arc::new(ExtSyncTaskErrorHandle::new(fetcher.clone())),
+    )
+    .generate()
+    .await?;
+    ensure!(
+        block_accumulator.get_leaf(pivot.number)? == Some(pivot.id),
+        "Pivot {:?} is not in the block accumulator",
+        pivot
+    );
+    verify_total_difficulty(
+        &block_accumulator,
+        &current_block_info,
+        current_header.number(),
+        &pivot_block_info,
+        pivot.number,
+        fetcher.as_ref(),
+    )
+    .await?;
+
+    // Only the frozen subtree roots of the transaction accumulator are required to append new
+    // transactions, the nodes below them are not downloaded.
+    let txn_accumulator_store = storage.get_accumulator_store(AccumulatorStoreType::Transaction);
+    for root in pivot_block_info
+        .txn_accumulator_info
+        .get_frozen_subtree_roots()
+    {
+        let node = fetcher
+            .fetch_accumulator_node(*root, AccumulatorStoreType::Transaction)
+            .await?;
+        txn_accumulator_store.save_node(node)?;
+    }
+
+    let state_root = pivot_block.header().state_root();
+    let node_count = sync_state_nodes(state_root, storage.as_ref(), fetcher.as_ref()).await?;
+    info!(
+        "[sync] Fast sync state of pivot {:?} done, state root: {}, downloaded nodes: {}",
+        pivot, state_root, node_count
+    );
+
+    // The blocks of the pivot's epoch and difficulty window are required to verify and
+    // execute the blocks after the pivot.
+    let statedb = ChainStateDB::new(storage.clone(), Some(state_root));
+    let epoch = AccountStateReader::new(&statedb)
+        .get_resource::<Epoch>(genesis_address())?
+        .ok_or_else(|| format_err!("Epoch is none."))?;
+    let start_number = std::cmp::max(
+        1,
+        std::cmp::min(
+            epoch.start_block_number(),
+            pivot
+                .number
+                .saturating_add(1)
+                .saturating_sub(epoch.block_difficulty_window()),
+        ),
+    );
+    let block_ids = (start_number..pivot.number)
+        .map(|number| {
+            block_accumulator
+                .get_leaf(number)?
+                .ok_or_else(|| format_err!("Can not find block id by number: {}", number))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    for ids in block_ids.chunks(BLOCK_BATCH_SIZE) {
+        let blocks = fetcher.fetch_blocks(ids.to_vec()).await?;
+        let block_infos = fetcher.fetch_block_infos(None, ids.to_vec()).await?;
+        for ((block, _), block_info) in blocks.into_iter().zip(block_infos) {
+            let block_info = block_info
+                .ok_or_else(|| format_err!("Can not find block info by id: {}", block.id()))?;
+            verify_block_info(&block, &block_info)?;
+            storage.commit_block(block)?;
+            storage.save_block_info(block_info)?;
+        }
+    }
+    storage.commit_block(pivot_block.clone())?;
+    storage.save_block_info(pivot_block_info)?;
+
+    // the window of the child is the pivot and the blocks before it.
+    let window_start = pivot
+        .number
+        .saturating_add(1)
+        .saturating_sub(epoch.block_difficulty_window());
+    let window = (window_start..=pivot.number)
+        .rev()
+        .map(|number| {
+            let block_id = block_accumulator
+                .get_leaf(number)?
+                .ok_or_else(|| format_err!("Can not find block id by number: {}", number))?;
+            storage
+                .get_block_header_by_hash(block_id)?
+                .ok_or_else(|| format_err!("Can not find block header by id: {}", block_id))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    starcoin_consensus::verify_header_by_headers(&epoch, window, &child_header).map_err(|e| {
+        format_err!(
+            "Verify the child {} of pivot {:?} failed: {:?}",
+            child_header.id(),
+            pivot,
+            e
+        )
+    })?;
+    storage.save_startup_info(StartupInfo::new(pivot_id))?;
+    storage.remove_fast_sync_pivot()?;
+    info!("[sync] Fast sync to pivot {:?} done", pivot);
+    Ok(Some(pivot_block))
+}
+
+fn verify_block_info(block: &Block, block_info: &BlockInfo) -> Result<()> {
+    ensure!(
+        block_info.block_id == block.id()
+            && block_info.txn_accumulator_info.accumulator_root
+                == block.header().txn_accumulator_root()
+            && block_info.block_accumulator_info.num_leaves
+                == block.header().number().saturating_add(1),
+        "Block info {:?} does not match block {}",
+        block_info,
+        block.id()
+    );
+    Ok(())
+}
+
+/// The block accumulator does not cover the total difficulty of the pivot block info, so it is
+/// derived from the headers of the blocks after the current block, whose ids are read from the
+/// verified block accumulator, and every header is verified by its id.
+async fn verify_total_difficulty<F>(
+    block_accumulator: &MerkleAccumulator,
+    current_block_info: &BlockInfo,
+    current_number: BlockNumber,
+    pivot_block_info: &BlockInfo,
+    pivot_number: BlockNumber,
+    fetcher: &F,
+) -> Result<()>
+where
+    F: SyncFetcher,
+{
+    let block_ids = (current_number.saturating_add(1)..=pivot_number)
+        .map(|number| {
+            block_accumulator
+                .get_leaf(number)?
+                .ok_or_else(|| format_err!("Can not find block id by number: {}", number))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut total_difficulty = current_block_info.total_difficulty;
+    for ids in block_ids.chunks(usize::try_from(MAX_BLOCK_HEADER_REQUEST_SIZE)?) {
+        let headers = fetcher.fetch_block_headers(ids.to_vec()).await?;
+        ensure!(
+            headers.len() == ids.len(),
+            "Fetch block headers size mismatch, expect: {}, got: {}",
+            ids.len(),
+            headers.len()
+        );
+        for (id, (_, header)) in ids.iter().zip(headers) {
+            let header =
+                header.ok_or_else(|| format_err!("Can not find block header by id: {}", id))?;
+            ensure!(
+                header.id() == *id,
+                "Block header id mismatch, expect: {}, got: {}",
+                id,
+                header.id()
+            );
+            total_difficulty = total_difficulty
+                .checked_add(header.difficulty())
+                .ok_or_else(|| format_err!("total difficulty overflow"))?;
+        }
+    }
+    ensure!(
+        pivot_block_info.total_difficulty == total_difficulty,
+        "Pivot block info total difficulty mismatch, expect: {}, got: {}",
+        total_difficulty,
+        pivot_block_info.total_difficulty
+    );
+    Ok(())
+}
+
+/// Fetch the pivot block, its block info and its child header. The positions of the pivot and
+/// the child are proved by `target_accumulator_info`, and the block info is verified by the child,
+/// whose block accumulator root is the pivot's block accumulator root.
+async fn fetch_pivot<F>(
+    pivot_id: HashValue,
+    target_accumulator_info: &AccumulatorInfo,
+    fetcher: &F,
+) -> Result<(Block, BlockInfo, BlockHeader)>
+where
+    F: SyncFetcher + StateSyncFetcher,
+{
+    let (pivot_block, peer) = fetcher
+        .fetch_blocks(vec![pivot_id])
+        .await?
+        .pop()
+        .ok_or_else(|| format_err!("Can not find pivot block by id: {}", pivot_id))?;
+    ensure!(
+        pivot_block.header().is_single(),
+        "Pivot block {} is a dag block",
+        pivot_id
+    );
+    let pivot_number = pivot_block.header().number();
+    verify_block_position(fetcher, target_accumulator_info, pivot_id, pivot_number).await?;
+    let pivot_block_info = fetcher
+        .fetch_block_info(peer.clone(), pivot_id)
+        .await?
+        .ok_or_else(|| format_err!("Can not find pivot block info by id: {}", pivot_id))?;
+    let child_number = pivot_number.saturating_add(1);
+    let child_header = match fetcher.fetch_block_id(peer.clone(), child_number).await? {
+        Some(child_id) => fetcher
+            .fetch_block_headers(vec![child_id])
+            .await?
+            .pop()
+            .and_then(|(_, header)| header),
+        None => None,
+    }
+    .ok_or_else(|| format_err!("Can not find block header by number: {}", child_number))?;
+    ensure!(
+        child_header.number() == child_number,
+        "Invalid pivot child header number, expect: {}, got: {}",
+        child_number,
+        child_header.number()
+    );
+    verify_block_position(
+        fetcher,
+        target_accumulator_info,
+        child_header.id(),
+        child_number,
+    )
+    .await?;
+    let verified = verify_block_info(&pivot_block, &pivot_block_info).is_ok()
+        && child_header.parent_hash() == pivot_id
+        && child_header.block_accumulator_root()
+            == pivot_block_info.block_accumulator_info.accumulator_root;
+    if !verified {
+        let msg = format!("Invalid fast sync pivot block info: {:?}", pivot_block_info);
+        return Err(match peer {
+            Some(peer) => RpcVerifyError::new(peer, msg).into(),
+            None => format_err!("{}", msg),
+        });
+    }
+    Ok((pivot_block, pivot_block_info, child_header))
+}
+
+/// Verify the block `block_id` is the leaf `number` of the block accumulator `accumulator_info`,
+/// the proof is collected by walking down the accumulator nodes from the root.
+async fn verify_block_position<F>(
+    fetcher: &F,
+    accumulator_info: &AccumulatorInfo,
+    block_id: HashValue,
+    number: BlockNumber,
+) -> Result<()>
+where
+    F: StateSyncFetcher,
+{
+    ensure!(
+        number < accumulator_info.num_leaves,
+        "Block number {} is out of the block accumulator, leaves: {}",
+        number,
+        accumulator_info.num_leaves
+    );
+    let leaf_index = NodeIndex::from_leaf_index(number).to_inorder_index();
+    let mut node_hash = accumulator_info.accumulator_root;
+    let mut siblings = vec![];
+    loop {
+        ensure!(
+            siblings.len() <= MAX_ACCUMULATOR_PROOF_DEPTH,
+            "Block accumulator proof of block {} is too deep",
+            number
+        );
+        match fetcher
+            .fetch_accumulator_node(node_hash, AccumulatorStoreType::Block)
+            .await?
+        {
+            AccumulatorNode::Internal(internal) => {
+                if leaf_index < internal.index().to_inorder_index() {
+                    siblings.push(internal.right());
+                    node_hash = internal.left();
+                } else {
+                    siblings.push(internal.left());
+                    node_hash = internal.right();
+                }
+            }
+            AccumulatorNode::Leaf(_) => break,
+            AccumulatorNode::Empty => {
+                return Err(format_err!(
+                    "Block accumulator leaf {} is a placeholder",
+                    number
+                ))
+            }
+        }
+    }
+    // the siblings are ordered from the bottom level in the proof.
+    siblings.reverse();
+    AccumulatorProof::new(siblings)
+        .verify(accumulator_info.accumulator_root, block_id, number)
+        .map_err(|e| {
+            format_err!(
+                "Block {} is not the block {} of the sync target: {:?}",
+                block_id,
+                number,
+                e
+            )
+        })
+}
+
+/// Download the state tree of `state_root` to `storage`, return the number of downloaded nodes.
+/// The nodes already in storage are not downloaded again, but their children are still visited,
+/// so an interrupted download can be resumed by calling this again.
+async fn sync_state_nodes<F>(state_root: HashValue, storage: &Storage, fetcher: &F) -> Result<u64>
+where
+    F: StateSyncFetcher,
+{
+    let mut node_count: u64 = 0;
+    let mut pending = vec![];
+    if state_root != *SPARSE_MERKLE_PLACEHOLDER_HASH {
+        pending.push((state_root, StateTreeKind::Global));
+    }
+    while !pending.is_empty() {
+        // visit the tree depth first, to keep the pending list small.
+        let batch = pending.split_off(pending.len().saturating_sub(STATE_NODE_BATCH_SIZE));
+        let mut to_fetch = vec![];
+        for (hash, kind) in batch {
+            match StateNodeStore::get(storage, &hash)? {
+                Some(node) => pending.extend(state_node_children(hash, &node, &kind)?),
+                None => to_fetch.push((hash, kind)),
+            }
+        }
+        let fetched = futures::stream::iter(to_fetch.into_iter().map(|(hash, kind)| {
+            fetcher
+                .fetch_state_node(hash)
+                .map_ok(move |(peer, node)| (hash, kind, peer, node))
+        }))
+        .buffer_unordered(STATE_NODE_CONCURRENCY)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        let mut nodes = BTreeMap::new();
+        for (hash, kind, peer, node) in fetched {
+            let node =
+                node.ok_or_else(|| format_err!("Can not find state node by hash: {}", hash))?;
+            let children = state_node_children(hash, &node, &kind).map_err(|e| match &peer {
+                Some(peer) => RpcVerifyError::new(peer.clone(), e.to_string()).into(),
+                None => e,
+            })?;
+            pending.extend(children);
+            nodes.insert(hash, node);
+        }
+        node_count = node_count.saturating_add(nodes.len() as u64);
+        storage.write_nodes(nodes)?;
+    }
+    Ok(node_count)
+}
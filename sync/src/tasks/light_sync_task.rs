@@ -0,0 +1,411 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tasks::{fetcher_err_map, SyncFetcher};
+use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
+use anyhow::{ensure, format_err, Result};
+use futures::future::BoxFuture;
+use futures::{FutureExt, TryFutureExt};
+use network_api::PeerId;
+use starcoin_accumulator::inmemory::InMemoryAccumulator;
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_state_api::StateWithProof;
+use starcoin_storage::block_info::BlockInfoStore;
+use starcoin_storage::{BlockStore, Storage};
+use starcoin_sync_api::SyncTarget;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_config::genesis_address;
+use starcoin_types::block::{BlockHeader, BlockInfo, BlockNumber};
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::on_chain_resource::Epoch;
+use std::sync::Arc;
+
+/// The number of block headers requested at once.
+const LIGHT_SYNC_BATCH_SIZE: u64 = 100;
+
+pub trait StateProofFetcher: Send + Sync {
+    /// Fetch the state of `access_path` with a proof verified by the `state_root`.
+    fn fetch_state_with_proof(
+        &self,
+        peer: Option<PeerId>,
+        state_root: HashValue,
+        access_path: AccessPath,
+    ) -> BoxFuture<Result<StateWithProof>>;
+}
+
+impl<T> StateProofFetcher for Arc<T>
+where
+    T: StateProofFetcher,
+{
+    fn fetch_state_with_proof(
+        &self,
+        peer: Option<PeerId>,
+        state_root: HashValue,
+        access_path: AccessPath,
+    ) -> BoxFuture<Result<StateWithProof>> {
+        StateProofFetcher::fetch_state_with_proof(self.as_ref(), peer, state_root, access_path)
+    }
+}
+
+impl StateProofFetcher for VerifiedRpcClient {
+    fn fetch_state_with_proof(
+        &self,
+        peer: Option<PeerId>,
+        state_root: HashValue,
+        access_path: AccessPath,
+    ) -> BoxFuture<Result<StateWithProof>> {
+        self.get_state_with_proof(peer, state_root, access_path)
+            .map_ok(|(_peer_id, state)| state)
+            .map_err(fetcher_err_map)
+            .boxed()
+    }
+}
+
+/// Verify the block headers one by one from the light head, like `BlockVerifier` does for a
+/// full chain, but with the headers and block infos only.
+struct LightHeaderVerifier<F> {
+    fetcher: Arc<F>,
+    head: BlockHeader,
+    head_info: BlockInfo,
+    /// The epoch of the head, read from the state of the head with proof.
+    epoch: Option<Epoch>,
+    /// The headers of the difficulty window, ordered from the head backwards.
+    window: Vec<BlockHeader>,
+}
+
+impl<F> LightHeaderVerifier<F>
+where
+    F: StateProofFetcher,
+{
+    fn new(storage: &Storage, fetcher: Arc<F>, head_id: HashValue) -> Result<Self> {
+        let head = storage
+            .get_block_header_by_hash(head_id)?
+            .ok_or_else(|| format_err!("Can not find block header by id: {}", head_id))?;
+        let head_info = storage
+            .get_block_info(head_id)?
+            .ok_or_else(|| format_err!("Can not find block info by id: {}", head_id))?;
+        Ok(Self {
+            fetcher,
+            head,
+            head_info,
+            epoch: None,
+            window: vec![],
+        })
+    }
+
+    /// Load the headers of the difficulty window from storage, the window may be shorter than
+    /// the epoch's difficulty window near the genesis.
+    fn load_window(&mut self, storage: &Storage, window_size: u64) -> Result<()> {
+        let mut window = vec![self.head.clone()];
+        while (window.len() as u64) < window_size {
+            let last = window.last().expect("window is not empty");
+            if last.number() == 0 {
+                break;
+            }
+            let parent_hash = last.parent_hash();
+            let parent = storage
+                .get_block_header_by_hash(parent_hash)?
+                .ok_or_else(|| format_err!("Can not find block header by id: {}", parent_hash))?;
+            window.push(parent);
+        }
+        self.window = window;
+        Ok(())
+    }
+
+    /// The epoch to verify the child of head, it only changes when the child is after the
+    /// end of the current epoch.
+    async fn epoch_for_child(&mut self, storage: &Storage, peer: Option<PeerId>) -> Result<Epoch> {
+        let child_number = self.head.number().saturating_add(1);
+        if let Some(epoch) = &self.epoch {
+            if child_number <= epoch.end_block_number() {
+                return Ok(epoch.clone());
+            }
+        }
+        let access_path = AccessPath::new(genesis_address(), Epoch::resource_path());
+        let state = self
+            .fetcher
+            .fetch_state_with_proof(peer, self.head.state_root(), access_path)
+            .await?
+            .state
+            .ok_or_else(|| format_err!("Epoch is none at block {}", self.head.id()))?;
+        let epoch = bcs_ext::from_bytes::<Epoch>(state.as_slice())?;
+        if (self.window.len() as u64) < epoch.block_difficulty_window() {
+            self.load_window(storage, epoch.block_difficulty_window())?;
+        }
+        self.epoch = Some(epoch.clone());
+        Ok(epoch)
+    }
+
+    /// Verify the `header` and its `block_info` as the child of head, then move the head to it.
+    fn verify_and_apply(
+        &mut self,
+        epoch: &Epoch,
+        header: BlockHeader,
+        block_info: BlockInfo,
+    ) -> Result<()> {
+        let parent = &self.head;
+        let parent_info = &self.head_info;
+        ensure!(
+            header.parent_hash() == parent.id(),
+            "Invalid block: Parent id mismatch, expect:{}, got: {}, number:{}.",
+            parent.id(),
+            header.parent_hash(),
+            header.number()
+        );
+        ensure!(
+            header.number() == parent.number().saturating_add(1),
+            "Invalid block: Unexpect block number, expect:{}, got: {}.",
+            parent.number().saturating_add(1),
+            header.number()
+        );
+        ensure!(
+            header.chain_id() == parent.chain_id(),
+            "Invalid block: chain id mismatch, expect:{}, got: {}.",
+            parent.chain_id(),
+            header.chain_id()
+        );
+        ensure!(
+            header.timestamp() > parent.timestamp(),
+            "Invalid block: block timestamp too old, parent time:{}, block time: {}, number:{}.",
+            parent.timestamp(),
+            header.timestamp(),
+            header.number()
+        );
+        ensure!(
+            header.number() > epoch.start_block_number()
+                && header.number() <= epoch.end_block_number(),
+            "block number is {:?}, epoch start number is {:?}, epoch end number is {:?}",
+            header.number(),
+            epoch.start_block_number(),
+            epoch.end_block_number(),
+        );
+        ensure!(
+            header.gas_used() <= epoch.block_gas_limit(),
+            "invalid block: gas_used should not greater than block_gas_limit"
+        );
+        ensure!(
+            header.block_accumulator_root() == parent_info.block_accumulator_info.accumulator_root,
+            "Block accumulator root miss match {:?} : {:?}",
+            parent_info.block_accumulator_info.accumulator_root,
+            header.block_accumulator_root(),
+        );
+        starcoin_consensus::verify_header_by_headers(epoch, self.window.clone(), &header)?;
+
+        ensure!(
+            block_info.block_id == header.id()
+                && block_info.txn_accumulator_info.accumulator_root
+                    == header.txn_accumulator_root(),
+            "Block info {:?} does not match block {}",
+            block_info,
+            header.id()
+        );
+        let total_difficulty = parent_info
+            .total_difficulty
+            .checked_add(header.difficulty())
+            .ok_or_else(|| format_err!("total difficulty overflow"))?;
+        ensure!(
+            block_info.total_difficulty == total_difficulty,
+            "Block info total difficulty mismatch, expect: {}, got: {}",
+            total_difficulty,
+            block_info.total_difficulty
+        );
+        // the block accumulator of the block info is the parent's appended by the block id.
+        let block_accumulator = InMemoryAccumulator::new(
+            parent_info
+                .block_accumulator_info
+                .frozen_subtree_roots
+                .clone(),
+            parent_info.block_accumulator_info.num_leaves,
+        )?
+        .append(&[header.id()]);
+        ensure!(
+            block_accumulator.root_hash() == block_info.block_accumulator_info.accumulator_root
+                && block_accumulator.num_leaves() == block_info.block_accumulator_info.num_leaves,
+            "Block info {:?} block accumulator mismatch",
+            block_info
+        );
+
+        self.window.insert(0, header.clone());
+        self.window
+            .truncate(std::cmp::max(epoch.block_difficulty_window(), 1) as usize);
+        self.head = header;
+        self.head_info = block_info;
+        Ok(())
+    }
+}
+
+impl<F> LightHeaderVerifier<F>
+where
+    F: SyncFetcher + StateProofFetcher,
+{
+    /// Roll the head back to the latest common ancestor with the chain of `peer` if the head is
+    /// not on it, the search starts from the ancestor of the head at `number`.
+    /// The headers after the ancestor are kept in storage, but they are no longer on the light chain.
+    /// Returns whether the head is rolled back.
+    async fn roll_back_to_ancestor(
+        &mut self,
+        storage: &Storage,
+        peer: Option<PeerId>,
+        number: BlockNumber,
+    ) -> Result<bool> {
+        let mut ancestor = self.head.clone();
+        while ancestor.number() > number {
+            ancestor = get_parent_header(storage, &ancestor)?;
+        }
+        'search: loop {
+            let block_ids = self
+                .fetcher
+                .fetch_block_ids(peer.clone(), ancestor.number(), true, LIGHT_SYNC_BATCH_SIZE)
+                .await?;
+            ensure!(
+                !block_ids.is_empty(),
+                "Can not find block ids from number {}",
+                ancestor.number()
+            );
+            // the block ids are in the reverse order of the block number, from the ancestor's.
+            for block_id in block_ids {
+                if block_id == ancestor.id() {
+                    break 'search;
+                }
+                ensure!(
+                    ancestor.number() > 0,
+                    "Can not find the common ancestor of the light head {:?}",
+                    self.head.id()
+                );
+                ancestor = get_parent_header(storage, &ancestor)?;
+            }
+        }
+        if ancestor.id() == self.head.id() {
+            return Ok(false);
+        }
+        info!(
+            "[sync] Light head {:?}, number: {} is not on the main chain, roll back to {:?}, number: {}",
+            self.head.id(),
+            self.head.number(),
+            ancestor.id(),
+            ancestor.number()
+        );
+        *self = Self::new(storage, self.fetcher.clone(), ancestor.id())?;
+        storage.save_light_head(ancestor.id())?;
+        Ok(true)
+    }
+}
+
+fn get_parent_header(storage: &Storage, header: &BlockHeader) -> Result<BlockHeader> {
+    storage
+        .get_block_header_by_hash(header.parent_hash())?
+        .ok_or_else(|| format_err!("Can not find block header by id: {}", header.parent_hash()))
+}
+
+/// Light sync only downloads the block headers and block infos of the main chain, instead of
+/// executing the blocks.
+///
+/// Every header is verified as the child of the previous one: the block number, parent hash,
+/// timestamp, the difficulty and pow by the consensus of the epoch, and the block accumulator
+/// root. The block info is verified by appending the block id to the parent's block accumulator,
+/// and by the total difficulty. The epoch is read from the state of the parent with proof.
+/// The verified headers and block infos are saved to storage, and the light head is moved on
+/// after every batch, so the state can be read from full peers with proofs against the state
+/// root of the light head.
+///
+/// If the chain of the target is forked from the light chain, before the sync or during it,
+/// the light head is rolled back to the common ancestor first.
+///
+/// Light sync stops at the dag fork number, dag blocks are not supported yet.
+/// Returns the new light head if it is moved.
+pub async fn light_sync_task<F>(
+    target: SyncTarget,
+    storage: Arc<Storage>,
+    fetcher: Arc<F>,
+    dag_fork_number: Option<BlockNumber>,
+) -> Result<Option<BlockHeader>>
+where
+    F: SyncFetcher + StateProofFetcher + 'static,
+{
+    let head_id = match storage.get_light_head()? {
+        Some(head_id) => head_id,
+        None => {
+            storage
+                .get_startup_info()?
+                .ok_or_else(|| format_err!("Startup info should exist."))?
+                .main
+        }
+    };
+    let mut verifier = LightHeaderVerifier::new(storage.as_ref(), fetcher.clone(), head_id)?;
+    let mut target_number = target.target_id.number();
+    if let Some(dag_fork_number) = dag_fork_number {
+        target_number = std::cmp::min(target_number, dag_fork_number.saturating_sub(1));
+    }
+    let peer = target.peers.first().cloned();
+    let ancestor_number = std::cmp::min(verifier.head.number(), target_number);
+    let rolled_back = verifier
+        .roll_back_to_ancestor(storage.as_ref(), peer.clone(), ancestor_number)
+        .await?;
+    if target_number <= verifier.head.number() {
+        return Ok(rolled_back.then(|| verifier.head.clone()));
+    }
+    info!(
+        "[sync] Start light sync from {:?} to {}",
+        verifier.head.id(),
+        target_number
+    );
+    while verifier.head.number() < target_number {
+        let start_number = verifier.head.number().saturating_add(1);
+        let max_size = std::cmp::min(
+            LIGHT_SYNC_BATCH_SIZE,
+            target_number.saturating_sub(start_number).saturating_add(1),
+        );
+        let block_ids = fetcher
+            .fetch_block_ids(peer.clone(), start_number, false, max_size)
+            .await?;
+        ensure!(
+            !block_ids.is_empty(),
+            "Can not find block ids from number {}",
+            start_number
+        );
+        let headers = fetcher.fetch_block_headers(block_ids.clone()).await?;
+        // the chain of the peer is reorganized after the light head.
+        if let Some((_, Some(first))) = headers.first() {
+            if first.parent_hash() != verifier.head.id()
+                && verifier
+                    .roll_back_to_ancestor(storage.as_ref(), peer.clone(), verifier.head.number())
+                    .await?
+            {
+                continue;
+            }
+        }
+        let block_infos = fetcher
+            .fetch_block_infos(peer.clone(), block_ids.clone())
+            .await?;
+        for ((block_id, header), block_info) in headers.into_iter().zip(block_infos) {
+            let header = header
+                .ok_or_else(|| format_err!("Can not find block header by id: {}", block_id))?;
+            let block_info = block_info
+                .ok_or_else(|| format_err!("Can not find block info by id: {}", block_id))?;
+            let epoch = verifier
+                .epoch_for_child(storage.as_ref(), peer.clone())
+                .await?;
+            if let Err(e) = verifier.verify_and_apply(&epoch, header.clone(), block_info.clone()) {
+                return Err(match &peer {
+                    Some(peer) => RpcVerifyError::new(peer.clone(), e.to_string()).into(),
+                    None => e,
+                });
+            }
+            storage.save_block_header(header)?;
+            storage.save_block_info(block_info)?;
+        }
+        storage.save_light_head(verifier.head.id())?;
+        debug!(
+            "[sync] Light sync to block {}, number: {}",
+            verifier.head.id(),
+            verifier.head.number()
+        );
+    }
+    info!(
+        "[sync] Light sync done, head: {:?}, number: {}",
+        verifier.head.id(),
+        verifier.head.number()
+    );
+    Ok(Some(verifier.head))
+}
@@ -3,7 +3,8 @@
 
 use crate::store::sync_dag_store::SyncDagStore;
 use crate::tasks::{
-    BlockConnectedEvent, BlockFetcher, BlockIdFetcher, BlockInfoFetcher, PeerOperator, SyncFetcher,
+    BlockConnectedEvent, BlockFetcher, BlockIdFetcher, BlockInfoFetcher, PeerOperator,
+    StateProofFetcher, StateSyncFetcher, SyncFetcher,
 };
 use anyhow::{format_err, Context, Ok, Result};
 use async_std::task::JoinHandle;
@@ -16,7 +17,8 @@ use network_api::{PeerId, PeerInfo, PeerSelector, PeerStrategy};
 use network_p2p_core::{NetRpcError, RpcErrorCode};
 use rand::Rng;
 use starcoin_account_api::AccountInfo;
-use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, AccumulatorNode, MerkleAccumulator};
 use starcoin_chain::BlockChain;
 use starcoin_chain_api::ChainReader;
 use starcoin_chain_mock::MockChain;
@@ -24,8 +26,12 @@ use starcoin_config::{BuiltinNetworkID, ChainNetwork};
 use starcoin_crypto::HashValue;
 use starcoin_dag::blockdag::BlockDAG;
 use starcoin_network_rpc_api::G_RPC_INFO;
-use starcoin_storage::Storage;
+use starcoin_state_api::{ChainStateReader, StateWithProof};
+use starcoin_state_tree::{StateNode, StateNodeStore};
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::{Storage, Store};
 use starcoin_sync_api::SyncTarget;
+use starcoin_types::access_path::AccessPath;
 use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
 use starcoin_types::startup_info::ChainInfo;
 use std::sync::Arc;
@@ -438,3 +444,54 @@ impl BlockInfoFetcher for SyncNodeMocker {
 }
 
 impl SyncFetcher for SyncNodeMocker {}
+
+impl StateSyncFetcher for SyncNodeMocker {
+    fn fetch_state_node(
+        &self,
+        node_hash: HashValue,
+    ) -> BoxFuture<Result<(Option<PeerId>, Option<StateNode>)>> {
+        let result = StateNodeStore::get(self.get_storage().as_ref(), &node_hash);
+        async move {
+            let peer_id = self.select_a_peer()?;
+            self.err_mocker.random_err().await?;
+            Ok((Some(peer_id), result?))
+        }
+        .boxed()
+    }
+
+    fn fetch_accumulator_node(
+        &self,
+        node_hash: HashValue,
+        store_type: AccumulatorStoreType,
+    ) -> BoxFuture<Result<AccumulatorNode>> {
+        let result = self
+            .get_storage()
+            .get_accumulator_store(store_type)
+            .get_node(node_hash);
+        async move {
+            let _ = self.select_a_peer()?;
+            self.err_mocker.random_err().await?;
+            result?
+                .ok_or_else(|| format_err!("Can not find accumulator node by hash: {}", node_hash))
+        }
+        .boxed()
+    }
+}
+
+impl StateProofFetcher for SyncNodeMocker {
+    fn fetch_state_with_proof(
+        &self,
+        _peer: Option<PeerId>,
+        state_root: HashValue,
+        access_path: AccessPath,
+    ) -> BoxFuture<Result<StateWithProof>> {
+        let result =
+            ChainStateDB::new(self.get_storage(), Some(state_root)).get_with_proof(&access_path);
+        async move {
+            let _ = self.select_a_peer()?;
+            self.err_mocker.random_err().await?;
+            result
+        }
+        .boxed()
+    }
+}
@@ -589,8 +589,10 @@ where
 mod accumulator_sync_task;
 mod block_sync_task;
 mod continue_execute_absent_block;
+mod fast_sync_task;
 mod find_ancestor_task;
 mod inner_sync_task;
+mod light_sync_task;
 #[cfg(test)]
 pub(crate) mod mock;
 #[cfg(test)]
@@ -603,7 +605,9 @@ mod tests_dag;
 use crate::sync_metrics::SyncMetrics;
 pub use accumulator_sync_task::{AccumulatorCollector, BlockAccumulatorSyncTask};
 pub use block_sync_task::{BlockCollector, BlockSyncTask};
+pub use fast_sync_task::{fast_sync_task, StateSyncFetcher};
 pub use find_ancestor_task::{AncestorCollector, FindAncestorTask};
+pub use light_sync_task::{light_sync_task, StateProofFetcher};
 use starcoin_executor::VMMetrics;
 
 #[allow(clippy::too_many_arguments)]
@@ -6,8 +6,9 @@ use crate::store::sync_dag_store::SyncDagStore;
 use crate::tasks::block_sync_task::SyncBlockData;
 use crate::tasks::mock::{ErrorStrategy, MockBlockIdFetcher, SyncNodeMocker};
 use crate::tasks::{
-    full_sync_task, AccumulatorCollector, AncestorCollector, BlockAccumulatorSyncTask,
-    BlockCollector, BlockFetcher, BlockLocalStore, BlockSyncTask, FindAncestorTask, SyncFetcher,
+    fast_sync_task, full_sync_task, light_sync_task, AccumulatorCollector, AncestorCollector,
+    BlockAccumulatorSyncTask, BlockCollector, BlockFetcher, BlockLocalStore, BlockSyncTask,
+    FindAncestorTask, SyncFetcher,
 };
 use anyhow::{format_err, Result};
 use anyhow::{Context, Ok};
@@ -28,6 +29,7 @@ use starcoin_crypto::HashValue;
 use starcoin_dag::blockdag::BlockDAG;
 use starcoin_genesis::Genesis;
 use starcoin_logger::prelude::*;
+use starcoin_storage::block_info::BlockInfoStore;
 use starcoin_storage::{BlockStore, Storage};
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::block::BlockNumber;
@@ -240,6 +242,255 @@ pub async fn test_full_sync_fork_from_genesis() -> Result<()> {
     Ok(())
 }
 
+#[stest::test(timeout = 120)]
+pub async fn test_fast_sync_reject_pivot_out_of_target() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 300, 0)?;
+    node1.produce_block(20)?;
+    let arc_node1 = Arc::new(node1);
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let node2 = SyncNodeMocker::new(net2, 300, 0)?;
+    let dag_fork_height = node2.dag_fork_number()?;
+    let storage = node2.get_storage();
+    let mut target = arc_node1.sync_target();
+    // the pivot served by the peer is not in the block accumulator of the target.
+    target.block_info.block_accumulator_info.accumulator_root = HashValue::random();
+
+    assert!(fast_sync_task(
+        target,
+        5,
+        storage.clone(),
+        arc_node1,
+        15,
+        Some(dag_fork_height),
+    )
+    .await
+    .is_err());
+    assert_eq!(
+        storage.get_startup_info()?.map(|info| info.main),
+        Some(node2.chain().current_header().id())
+    );
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+pub async fn test_fast_sync_reject_pivot_total_difficulty() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 300, 0)?;
+    node1.produce_block(20)?;
+    let target = node1.sync_target();
+    // the peer serves a pivot block info with a forged total difficulty.
+    let pivot_id = node1
+        .chain()
+        .get_block_by_number(15)?
+        .expect("pivot block should exist")
+        .id();
+    let mut pivot_block_info = node1
+        .chain()
+        .get_block_info(Some(pivot_id))?
+        .expect("pivot block info should exist");
+    pivot_block_info.total_difficulty += 1u64;
+    node1.get_storage().save_block_info(pivot_block_info)?;
+    let arc_node1 = Arc::new(node1);
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let node2 = SyncNodeMocker::new(net2, 300, 0)?;
+    let dag_fork_height = node2.dag_fork_number()?;
+    let storage = node2.get_storage();
+
+    assert!(fast_sync_task(
+        target,
+        5,
+        storage.clone(),
+        arc_node1,
+        15,
+        Some(dag_fork_height),
+    )
+    .await
+    .is_err());
+    assert_eq!(
+        storage.get_startup_info()?.map(|info| info.main),
+        Some(node2.chain().current_header().id())
+    );
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+pub async fn test_fast_sync_new_node() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 300, 0)?;
+    node1.produce_block(20)?;
+    let arc_node1 = Arc::new(node1);
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let node2 = SyncNodeMocker::new(net2.clone(), 300, 0)?;
+    let dag_fork_height = node2.dag_fork_number()?;
+    let storage = node2.get_storage();
+    let target = arc_node1.sync_target();
+
+    let pivot_block = fast_sync_task(
+        target.clone(),
+        5,
+        storage.clone(),
+        arc_node1.clone(),
+        15,
+        Some(dag_fork_height),
+    )
+    .await?
+    .expect("fast sync should switch to the pivot");
+    assert_eq!(pivot_block.header().number(), 15);
+    assert_eq!(
+        storage.get_startup_info()?.map(|info| info.main),
+        Some(pivot_block.id())
+    );
+    assert!(storage.get_fast_sync_pivot()?.is_none());
+    // fast sync only starts from genesis.
+    assert!(fast_sync_task(
+        target.clone(),
+        5,
+        storage.clone(),
+        arc_node1.clone(),
+        15,
+        Some(dag_fork_height),
+    )
+    .await?
+    .is_none());
+
+    let (sender, receiver) = unbounded();
+    let (sender_2, _receiver_2) = unbounded();
+    let (sync_task, _task_handle, _task_event_counter) = full_sync_task(
+        pivot_block.id(),
+        target.clone(),
+        false,
+        net2.time_service(),
+        storage.clone(),
+        sender,
+        arc_node1.clone(),
+        sender_2,
+        DummyNetworkService::default(),
+        15,
+        None,
+        None,
+        Some(dag_fork_height),
+        node2.chain().dag(),
+        node2.sync_dag_store.clone(),
+    )?;
+    let join_handle = node2.process_block_connect_event(receiver).await;
+    let branch = sync_task.await?;
+    let node2 = join_handle.await;
+    assert_eq!(branch.current_header().id(), target.target_id.id());
+    assert_eq!(
+        node2.chain().current_header().state_root(),
+        arc_node1.chain().current_header().state_root()
+    );
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+pub async fn test_light_sync_new_node() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 300, 0)?;
+    node1.produce_block(10)?;
+    let arc_node1 = Arc::new(node1);
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let node2 = SyncNodeMocker::new(net2, 300, 0)?;
+    let dag_fork_height = node2.dag_fork_number()?;
+    let storage = node2.get_storage();
+    let genesis_id = storage.get_startup_info()?.map(|info| info.main);
+
+    let target = arc_node1.sync_target_by_number(5)?;
+    let head = light_sync_task(
+        target,
+        storage.clone(),
+        arc_node1.clone(),
+        Some(dag_fork_height),
+    )
+    .await?
+    .expect("light sync should move the light head");
+    assert_eq!(head.number(), 5);
+    assert_eq!(storage.get_light_head()?, Some(head.id()));
+
+    let target = arc_node1.sync_target();
+    let head = light_sync_task(
+        target.clone(),
+        storage.clone(),
+        arc_node1.clone(),
+        Some(dag_fork_height),
+    )
+    .await?
+    .expect("light sync should continue from the light head");
+    assert_eq!(head.id(), target.target_id.id());
+    assert_eq!(
+        storage
+            .get_block_info(head.id())?
+            .map(|info| info.total_difficulty),
+        Some(target.block_info.total_difficulty)
+    );
+    assert!(
+        light_sync_task(target, storage.clone(), arc_node1, Some(dag_fork_height))
+            .await?
+            .is_none()
+    );
+    // the full chain is not moved by the light sync.
+    assert_eq!(
+        storage.get_startup_info()?.map(|info| info.main),
+        genesis_id
+    );
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+pub async fn test_light_sync_reorg() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 1, 0)?;
+    node1.produce_block(10)?;
+    let mut arc_node1 = Arc::new(node1);
+
+    let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let node2 = SyncNodeMocker::new(net2, 1, 0)?;
+    let dag_fork_height = node2.dag_fork_number()?;
+    let storage = node2.get_storage();
+    let old_head = light_sync_task(
+        arc_node1.sync_target(),
+        storage.clone(),
+        arc_node1.clone(),
+        Some(dag_fork_height),
+    )
+    .await?
+    .expect("light sync should move the light head");
+    assert_eq!(old_head.number(), 10);
+
+    // the main chain of node1 is switched to a longer fork from block 5.
+    let fork_point = arc_node1
+        .chain()
+        .get_block_by_number(5)?
+        .ok_or_else(|| format_err!("Can not find block by number: 5"))?;
+    let node1 = Arc::get_mut(&mut arc_node1).expect("node1 should not be shared");
+    node1.chain_mocker = node1.chain_mocker.fork(Some(fork_point.id()))?;
+    node1.produce_block(8)?;
+
+    let target = arc_node1.sync_target();
+    let head = light_sync_task(
+        target.clone(),
+        storage.clone(),
+        arc_node1.clone(),
+        Some(dag_fork_height),
+    )
+    .await?
+    .expect("light sync should switch to the fork");
+    assert_eq!(head.id(), target.target_id.id());
+    assert_eq!(storage.get_light_head()?, Some(head.id()));
+    assert_eq!(
+        storage
+            .get_block_info(head.id())?
+            .map(|info| info.total_difficulty),
+        Some(target.block_info.total_difficulty)
+    );
+    Ok(())
+}
+
 #[stest::test(timeout = 120)]
 pub async fn test_full_sync_continue() -> Result<()> {
     let test_system = SyncTestSystem::initialize_sync_system().await?;
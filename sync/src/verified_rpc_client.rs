@@ -13,9 +13,11 @@ use starcoin_crypto::hash::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_network_rpc_api::{
     gen_client::NetworkRpcClient, BlockBody, GetAccumulatorNodeByNodeHash, GetBlockHeadersByNumber,
-    GetBlockIds, GetTxnsWithHash, RawRpcClient,
+    GetBlockIds, GetStateWithProof, GetTxnsWithHash, RawRpcClient,
 };
+use starcoin_state_api::StateWithProof;
 use starcoin_state_tree::StateNode;
+use starcoin_types::access_path::AccessPath;
 use starcoin_types::block::Block;
 use starcoin_types::transaction::{SignedUserTransaction, Transaction};
 use starcoin_types::{
@@ -549,6 +551,68 @@ impl VerifiedRpcClient {
         .into())
     }
 
+    /// Get the state of `access_path` with proof, the proof is verified by the `state_root`.
+    pub async fn get_state_with_proof(
+        &self,
+        peer_id: Option<PeerId>,
+        state_root: HashValue,
+        access_path: AccessPath,
+    ) -> Result<(PeerId, StateWithProof)> {
+        let peer_id = match peer_id {
+            None => self.select_a_peer()?,
+            Some(p) => p,
+        };
+        let req = GetStateWithProof {
+            state_root,
+            access_path: access_path.clone(),
+        };
+        let mut count = 0;
+        while count < G_RPC_RETRY_COUNT {
+            match self
+                .client
+                .get_state_with_proof(peer_id.clone(), req.clone())
+                .await
+            {
+                Ok(result) => {
+                    return match result.proof.verify(
+                        state_root,
+                        access_path,
+                        result.state.as_deref(),
+                    ) {
+                        Ok(()) => Ok((peer_id, result)),
+                        Err(e) => Err(RpcVerifyError::new(
+                            peer_id.clone(),
+                            format!(
+                                "Invalid state proof from peer : {:?}. error: {:?}",
+                                peer_id, e
+                            ),
+                        )
+                        .into()),
+                    };
+                }
+                Err(e) => {
+                    count = count.saturating_add(1);
+                    if count == G_RPC_RETRY_COUNT {
+                        return Err(RpcVerifyError::new(
+                            peer_id.clone(),
+                            format!(
+                                "failed to get state with proof from peer : {:?}. error: {:?}",
+                                peer_id, e
+                            ),
+                        )
+                        .into());
+                    }
+                    continue;
+                }
+            }
+        }
+        Err(RpcVerifyError::new(
+            peer_id.clone(),
+            format!("failed to get state with proof from peer : {:?}.", peer_id),
+        )
+        .into())
+    }
+
     async fn get_accumulator_node_by_node_hash_inner(
         &self,
         peer_id: PeerId,
@@ -11,7 +11,18 @@ use starcoin_types::{
 };
 use std::fmt::Debug;
 use std::sync::Arc;
-pub type TxnStatusFullEvent = Arc<[(HashValue, transaction::TxStatus)]>;
+/// The status changes of the txpool transactions, with the sender of every transaction.
+pub type TxnStatusFullEvent = Arc<[(HashValue, AccountAddress, transaction::TxStatus)]>;
+
+/// The default percent a replacement transaction's gas price must be higher than the pending one.
+pub const DEFAULT_REPLACE_GAS_PRICE_BUMP: u64 = 10;
+
+/// The minimal gas price required to replace a pending transaction whose gas price is `old_gas_price`,
+/// it's at least `bump_percent`% higher than `old_gas_price`, rounded up.
+pub fn min_replace_gas_price(old_gas_price: u64, bump_percent: u64) -> u64 {
+    let bump = (old_gas_price as u128 * bump_percent as u128 + 99) / 100;
+    u64::try_from((old_gas_price as u128).saturating_add(bump)).unwrap_or(u64::MAX)
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct TxPoolStatus {
@@ -35,6 +46,12 @@ pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
     /// there is a good chance that the transaction will actually be removed.
     fn remove_txn(&self, txn_hash: HashValue, is_invalid: bool) -> Option<SignedUserTransaction>;
 
+    /// Replace the pending transaction `txn_hash` with `new_txn`.
+    ///
+    /// The new transaction must have the same sender and sequence number as the pending one,
+    /// and its gas price must be at least the configured percent higher, see `min_replace_gas_price`.
+    fn replace_txn(&self, txn_hash: HashValue, new_txn: SignedUserTransaction) -> Result<()>;
+
     /// Get all pending txns which is ok to be packaged to mining.
     /// `now` is the current timestamp in secs, if it's None, it default to real world's current timestamp.
     /// It's an Option to make mock time easier.
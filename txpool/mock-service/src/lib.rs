@@ -50,6 +50,10 @@ impl TxPoolSyncService for MockTxPoolService {
         unimplemented!()
     }
 
+    fn replace_txn(&self, _txn_hash: HashValue, _new_txn: SignedUserTransaction) -> Result<()> {
+        unimplemented!()
+    }
+
     /// Get all pending txns which is ok to be packaged to mining.
     fn get_pending_txns(
         &self,
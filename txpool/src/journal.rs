@@ -0,0 +1,149 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The journal persist the transactions of the pool to disk, so the pending transactions
+//! can survive node restart.
+//!
+//! The journal file is a sequence of records, every record is a u32 little endian length
+//! followed by the bcs bytes of a `JournalEntry`. New transactions are appended to the
+//! journal, and the journal is rewritten with the transactions in the pool periodically,
+//! so the transactions that have left the pool are dropped from it.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::hash::HashValue;
+use starcoin_types::transaction::SignedUserTransaction;
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+const LEN_PREFIX_SIZE: usize = 4;
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub(crate) struct JournalEntry {
+    /// Whether the transaction is a local transaction.
+    pub local: bool,
+    pub txn: SignedUserTransaction,
+}
+
+impl JournalEntry {
+    pub fn new(local: bool, txn: SignedUserTransaction) -> Self {
+        Self { local, txn }
+    }
+}
+
+pub(crate) struct TxPoolJournal {
+    path: PathBuf,
+    writer: Option<BufWriter<File>>,
+}
+
+impl TxPoolJournal {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, writer: None }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Load the entries from the journal, the duplicated entries are ignored.
+    /// A broken tail left by an interrupted write is discarded.
+    pub fn load(&self) -> Result<Vec<JournalEntry>> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+        let bytes = std::fs::read(&self.path)?;
+        let mut entries = vec![];
+        let mut seen = HashSet::<HashValue>::new();
+        let mut offset = 0usize;
+        while offset < bytes.len() {
+            let body_start = offset + LEN_PREFIX_SIZE;
+            if body_start > bytes.len() {
+                warn!(
+                    "[txpool] Discard broken tail of journal {:?} at offset {}",
+                    self.path, offset
+                );
+                break;
+            }
+            let mut len_bytes = [0u8; LEN_PREFIX_SIZE];
+            len_bytes.copy_from_slice(&bytes[offset..body_start]);
+            let body_end = body_start + u32::from_le_bytes(len_bytes) as usize;
+            if body_end > bytes.len() {
+                warn!(
+                    "[txpool] Discard broken tail of journal {:?} at offset {}",
+                    self.path, offset
+                );
+                break;
+            }
+            match bcs_ext::from_bytes::<JournalEntry>(&bytes[body_start..body_end]) {
+                Ok(entry) => {
+                    if seen.insert(entry.txn.id()) {
+                        entries.push(entry);
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "[txpool] Skip invalid journal entry at offset {}: {:?}",
+                        offset, e
+                    );
+                }
+            }
+            offset = body_end;
+        }
+        Ok(entries)
+    }
+
+    /// Append the entries to the journal.
+    pub fn append(&mut self, entries: &[JournalEntry]) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        if self.writer.is_none() {
+            self.writer = Some(Self::open_writer(&self.path)?);
+        }
+        let writer = self.writer.as_mut().expect("journal writer should exist");
+        for entry in entries {
+            Self::write_entry(writer, entry)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Replace the journal with the given entries.
+    /// The entries are written to a temp file first, then renamed to the journal,
+    /// so the journal is never left half written.
+    pub fn rotate(&mut self, entries: &[JournalEntry]) -> Result<()> {
+        self.writer = None;
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = self.path.with_extension("new");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            for entry in entries {
+                Self::write_entry(&mut writer, entry)?;
+            }
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+        }
+        std::fs::rename(&tmp_path, &self.path)?;
+        self.writer = Some(Self::open_writer(&self.path)?);
+        Ok(())
+    }
+
+    fn open_writer(path: &Path) -> Result<BufWriter<File>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(BufWriter::new(file))
+    }
+
+    fn write_entry<W: Write>(writer: &mut W, entry: &JournalEntry) -> Result<()> {
+        let bytes = bcs_ext::to_bytes(entry)?;
+        writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+}
@@ -8,10 +8,13 @@ extern crate log;
 extern crate trace_time;
 extern crate transaction_pool as tx_pool;
 
+use crate::journal::{JournalEntry, TxPoolJournal};
 use anyhow::{format_err, Result};
 use network_api::messages::PeerTransactionsMessage;
+use parking_lot::Mutex;
 pub use pool::TxStatus;
 use starcoin_config::NodeConfig;
+use starcoin_crypto::hash::HashValue;
 use starcoin_executor::VMMetrics;
 use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
 use starcoin_state_api::AccountStateReader;
@@ -27,6 +30,7 @@ use std::time::Duration;
 use tx_pool_service_impl::Inner;
 pub use tx_pool_service_impl::TxPoolService;
 
+mod journal;
 mod metrics;
 mod pool;
 mod pool_client;
@@ -39,6 +43,7 @@ pub struct TxPoolActorService {
     inner: Inner,
     new_txs_received: Arc<AtomicBool>,
     sync_status: Option<SyncStatus>,
+    journal: Option<Arc<Mutex<TxPoolJournal>>>,
 }
 
 impl std::fmt::Debug for TxPoolActorService {
@@ -49,13 +54,21 @@ impl std::fmt::Debug for TxPoolActorService {
 
 const MIN_TXN_TO_PROPAGATE: usize = 256;
 const PROPAGATE_FOR_BLOCKS: u64 = 4;
+/// Interval(s) of reporting the pool content metrics.
+const POOL_METRICS_INTERVAL: u64 = 10;
 
 impl TxPoolActorService {
     fn new(inner: Inner) -> Self {
+        let journal = inner
+            .node_config
+            .tx_pool
+            .journal_path()
+            .map(|path| Arc::new(Mutex::new(TxPoolJournal::new(path))));
         Self {
             inner,
             sync_status: None,
             new_txs_received: Arc::new(AtomicBool::new(false)),
+            journal,
         }
     }
 
@@ -90,6 +103,69 @@ impl TxPoolActorService {
     }
 }
 
+impl TxPoolActorService {
+    /// Replay the transactions in the journal, the transactions are verified as the new
+    /// transactions, so the stale transactions are dropped.
+    fn load_journal(&self) -> Result<()> {
+        let journal = match self.journal.as_ref() {
+            Some(journal) => journal,
+            None => return Ok(()),
+        };
+        let entries = journal.lock().load()?;
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let total = entries.len();
+        let (local_txns, remote_txns): (Vec<_>, Vec<_>) =
+            entries.into_iter().partition(|entry| entry.local);
+        let imported = self
+            .inner
+            .import_local_txns(local_txns.into_iter().map(|entry| entry.txn).collect())
+            .into_iter()
+            .chain(
+                self.inner
+                    .import_txns(remote_txns.into_iter().map(|entry| entry.txn).collect()),
+            )
+            .filter(|r| r.is_ok())
+            .count();
+        info!(
+            "[txpool] Loaded {} transactions from journal, dropped {} stale transactions.",
+            imported,
+            total - imported
+        );
+        Ok(())
+    }
+
+    /// Rewrite the journal with the transactions in the pool.
+    fn rotate_journal(&self) {
+        if let Some(journal) = self.journal.as_ref() {
+            let entries: Vec<_> = self
+                .inner
+                .all_txns()
+                .into_iter()
+                .map(|txn| JournalEntry::new(txn.is_local(), txn.signed().clone()))
+                .collect();
+            if let Err(e) = journal.lock().rotate(&entries) {
+                error!("[txpool] Fail to rotate journal: {:?}", e);
+            }
+        }
+    }
+
+    fn append_journal(&self, txn_hashes: &[HashValue]) {
+        if let Some(journal) = self.journal.as_ref() {
+            let queue = self.inner.queue();
+            let entries: Vec<_> = txn_hashes
+                .iter()
+                .filter_map(|hash| queue.find(hash))
+                .map(|txn| JournalEntry::new(txn.is_local(), txn.signed().clone()))
+                .collect();
+            if let Err(e) = journal.lock().append(&entries) {
+                error!("[txpool] Fail to append transactions to journal: {:?}", e);
+            }
+        }
+    }
+}
+
 impl ServiceFactory<Self> for TxPoolActorService {
     fn create(ctx: &mut ServiceContext<Self>) -> Result<Self> {
         let storage = ctx.get_shared::<Arc<Storage>>()?;
@@ -153,11 +229,31 @@ impl ActorService for TxPoolActorService {
             myself.try_propagate_txns(ctx)
         });
 
+        if self.inner.metrics.is_some() {
+            let myself = self.clone();
+            ctx.run_interval(Duration::from_secs(POOL_METRICS_INTERVAL), move |_ctx| {
+                myself.inner.report_pool_metrics()
+            });
+        }
+
+        if self.journal.is_some() {
+            if let Err(e) = self.load_journal() {
+                error!("[txpool] Fail to load journal: {:?}", e);
+            }
+            self.rotate_journal();
+            let myself = self.clone();
+            let interval = self.inner.node_config.tx_pool.journal_interval();
+            ctx.run_interval(Duration::from_secs(interval), move |_ctx| {
+                myself.rotate_journal()
+            });
+        }
+
         Ok(())
     }
 
     fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.unsubscribe::<SyncStatusChangeEvent>();
+        self.rotate_journal();
         Ok(())
     }
 }
@@ -191,8 +287,8 @@ impl EventHandler<Self, TxnStatusFullEvent> for TxPoolActorService {
                 .with_label_values(&["count"])
                 .set(txn_count as u64);
         }
-        let mut has_new_txns = false;
-        for (_, s) in item.iter() {
+        let mut new_txns = vec![];
+        for (hash, _, s) in item.iter() {
             if let Some(metrics) = self.inner.metrics.as_ref() {
                 metrics
                     .txpool_txn_event_total
@@ -201,10 +297,11 @@ impl EventHandler<Self, TxnStatusFullEvent> for TxPoolActorService {
             }
 
             if *s == TxStatus::Added {
-                has_new_txns = true;
+                new_txns.push(*hash);
             }
         }
-        if has_new_txns {
+        if !new_txns.is_empty() {
+            self.append_journal(&new_txns);
             // notify txn-broadcaster.
             self.new_txs_received.store(true, Ordering::Relaxed);
         }
@@ -2,15 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use starcoin_metrics::{
-    register, HistogramOpts, HistogramVec, Opts, PrometheusError, Registry, UIntCounterVec,
-    UIntGaugeVec,
+    register, Histogram, HistogramOpts, HistogramVec, Opts, PrometheusError, Registry,
+    UIntCounterVec, UIntGaugeVec,
 };
 
+/// Max number of senders reported by `txpool_top_senders`.
+pub const TOP_SENDERS_COUNT: usize = 10;
+
 #[derive(Clone)]
 pub struct TxPoolMetrics {
     pub txpool_txn_event_total: UIntCounterVec,
     pub txpool_status: UIntGaugeVec,
     pub txpool_service_time: HistogramVec,
+    pub txpool_txn_gas_price: Histogram,
+    pub txpool_txn_age: HistogramVec,
+    pub txpool_txn_rejected_total: UIntCounterVec,
+    pub txpool_top_senders: UIntGaugeVec,
 }
 
 impl TxPoolMetrics {
@@ -39,10 +46,59 @@ impl TxPoolMetrics {
             )?,
             registry,
         )?;
+        let txpool_txn_gas_price = register(
+            Histogram::with_opts(
+                HistogramOpts::new(
+                    "txpool_txn_gas_price",
+                    "Histogram of the gas price of the txns added to the pool.",
+                )
+                .buckets(vec![
+                    1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1000.0, 10000.0,
+                ]),
+            )?,
+            registry,
+        )?;
+        let txpool_txn_age = register(
+            HistogramVec::new(
+                HistogramOpts::new(
+                    "txpool_txn_age",
+                    "Histogram of the seconds txns stay in the pool, by the status leaving the pool.",
+                )
+                .buckets(vec![
+                    1.0, 5.0, 10.0, 30.0, 60.0, 300.0, 600.0, 1800.0, 3600.0, 7200.0,
+                ]),
+                &["status"],
+            )?,
+            registry,
+        )?;
+        let txpool_txn_rejected_total = register(
+            UIntCounterVec::new(
+                Opts::new(
+                    "txpool_txn_rejected_total",
+                    "Counters of txns rejected by the pool, by the reason",
+                ),
+                &["reason"],
+            )?,
+            registry,
+        )?;
+        let txpool_top_senders = register(
+            UIntGaugeVec::new(
+                Opts::new(
+                    "txpool_top_senders",
+                    "Gauge of the txn count of the senders with the most txns in the pool",
+                ),
+                &["sender"],
+            )?,
+            registry,
+        )?;
         Ok(Self {
             txpool_txn_event_total,
             txpool_status,
             txpool_service_time,
+            txpool_txn_gas_price,
+            txpool_txn_age,
+            txpool_txn_rejected_total,
+            txpool_top_senders,
         })
     }
 }
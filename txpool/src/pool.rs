@@ -194,6 +194,11 @@ impl VerifiedTransaction {
     pub fn pending(&self) -> &transaction::PendingTransaction {
         &self.transaction
     }
+
+    /// Returns true if the transaction is a local transaction.
+    pub fn is_local(&self) -> bool {
+        self.priority.is_local()
+    }
 }
 
 impl tx_pool::VerifiedTransaction for VerifiedTransaction {
@@ -1,12 +1,15 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{fmt, sync::Arc};
+use std::{collections::HashMap, fmt, sync::Arc, time::Instant};
 
-use super::{TxStatus, VerifiedTransaction as Transaction};
+use super::{ScoredTransaction, TxStatus, VerifiedTransaction as Transaction};
+use crate::metrics::TxPoolMetrics;
 use futures_channel::mpsc;
 use starcoin_crypto::hash::HashValue as H256;
 use starcoin_logger::prelude::*;
+use starcoin_txpool_api::TxnStatusFullEvent;
+use starcoin_types::account_address::AccountAddress;
 use transaction_pool as tx_pool;
 use tx_pool::VerifiedTransaction;
 /// Transaction pool logger.
@@ -131,14 +134,14 @@ impl tx_pool::Listener<Transaction> for StatusLogger {
 /// Transactions pool notifier
 #[derive(Default)]
 pub struct TransactionsPoolNotifier {
-    full_listeners: Vec<mpsc::UnboundedSender<Arc<[(H256, TxStatus)]>>>,
+    full_listeners: Vec<mpsc::UnboundedSender<TxnStatusFullEvent>>,
     pending_listeners: Vec<mpsc::UnboundedSender<Arc<[H256]>>>,
-    tx_statuses: Vec<(H256, TxStatus)>,
+    tx_statuses: Vec<(H256, AccountAddress, TxStatus)>,
 }
 
 impl TransactionsPoolNotifier {
     /// Add new full listener to receive notifications.
-    pub fn add_full_listener(&mut self, f: mpsc::UnboundedSender<Arc<[(H256, TxStatus)]>>) {
+    pub fn add_full_listener(&mut self, f: mpsc::UnboundedSender<TxnStatusFullEvent>) {
         self.full_listeners.push(f);
     }
 
@@ -157,13 +160,13 @@ impl TransactionsPoolNotifier {
             .tx_statuses
             .clone()
             .into_iter()
-            .map(|(hash, _)| hash)
+            .map(|(hash, _, _)| hash)
             .collect::<Vec<_>>()
             .into();
         self.pending_listeners
             .retain(|listener| listener.unbounded_send(to_pending_send.clone()).is_ok());
 
-        let to_full_send: Arc<[(H256, TxStatus)]> = std::mem::take(&mut self.tx_statuses).into();
+        let to_full_send: TxnStatusFullEvent = std::mem::take(&mut self.tx_statuses).into();
         self.full_listeners
             .retain(|listener| listener.unbounded_send(to_full_send.clone()).is_ok());
     }
@@ -180,7 +183,8 @@ impl fmt::Debug for TransactionsPoolNotifier {
 
 impl tx_pool::Listener<Transaction> for TransactionsPoolNotifier {
     fn added(&mut self, tx: &Arc<Transaction>, _old: Option<&Arc<Transaction>>) {
-        self.tx_statuses.push((tx.hash, TxStatus::Added));
+        self.tx_statuses
+            .push((tx.hash, *tx.sender(), TxStatus::Added));
     }
 
     fn rejected<H: fmt::Debug + fmt::LowerHex>(
@@ -188,23 +192,90 @@ impl tx_pool::Listener<Transaction> for TransactionsPoolNotifier {
         tx: &Arc<Transaction>,
         _reason: &tx_pool::Error<H>,
     ) {
-        self.tx_statuses.push((tx.hash, TxStatus::Rejected));
+        self.tx_statuses
+            .push((tx.hash, *tx.sender(), TxStatus::Rejected));
     }
 
     fn dropped(&mut self, tx: &Arc<Transaction>, _new: Option<&Transaction>) {
-        self.tx_statuses.push((tx.hash, TxStatus::Dropped));
+        self.tx_statuses
+            .push((tx.hash, *tx.sender(), TxStatus::Dropped));
     }
 
     fn invalid(&mut self, tx: &Arc<Transaction>) {
-        self.tx_statuses.push((tx.hash, TxStatus::Invalid));
+        self.tx_statuses
+            .push((tx.hash, *tx.sender(), TxStatus::Invalid));
     }
 
     fn canceled(&mut self, tx: &Arc<Transaction>) {
-        self.tx_statuses.push((tx.hash, TxStatus::Canceled));
+        self.tx_statuses
+            .push((tx.hash, *tx.sender(), TxStatus::Canceled));
     }
 
     fn culled(&mut self, tx: &Arc<Transaction>) {
-        self.tx_statuses.push((tx.hash, TxStatus::Culled));
+        self.tx_statuses
+            .push((tx.hash, *tx.sender(), TxStatus::Culled));
+    }
+}
+
+/// Transaction pool metrics reporter, reports the gas price of the added transactions,
+/// and how long the transactions stay in the pool.
+#[derive(Default)]
+pub struct MetricsListener {
+    metrics: Option<TxPoolMetrics>,
+    added_at: HashMap<H256, Instant>,
+}
+
+impl MetricsListener {
+    pub fn set_metrics(&mut self, metrics: TxPoolMetrics) {
+        self.metrics = Some(metrics);
+    }
+
+    fn observe_age(&mut self, tx: &Transaction, status: TxStatus) {
+        if let Some(added_at) = self.added_at.remove(&tx.hash) {
+            if let Some(metrics) = self.metrics.as_ref() {
+                metrics
+                    .txpool_txn_age
+                    .with_label_values(&[status.to_string().as_str()])
+                    .observe(added_at.elapsed().as_secs_f64());
+            }
+        }
+    }
+}
+
+impl fmt::Debug for MetricsListener {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("MetricsListener")
+            .field("enabled", &self.metrics.is_some())
+            .field("txns", &self.added_at.len())
+            .finish()
+    }
+}
+
+impl tx_pool::Listener<Transaction> for MetricsListener {
+    fn added(&mut self, tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics.txpool_txn_gas_price.observe(tx.gas_price() as f64);
+            self.added_at.insert(tx.hash, Instant::now());
+        }
+        if let Some(old) = old {
+            self.observe_age(old, TxStatus::Dropped);
+        }
+    }
+
+    fn dropped(&mut self, tx: &Arc<Transaction>, _new: Option<&Transaction>) {
+        self.observe_age(tx, TxStatus::Dropped);
+    }
+
+    fn invalid(&mut self, tx: &Arc<Transaction>) {
+        self.observe_age(tx, TxStatus::Invalid);
+    }
+
+    fn canceled(&mut self, tx: &Arc<Transaction>) {
+        self.observe_age(tx, TxStatus::Canceled);
+    }
+
+    fn culled(&mut self, tx: &Arc<Transaction>) {
+        self.observe_age(tx, TxStatus::Culled);
     }
 }
 
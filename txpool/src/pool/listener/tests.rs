@@ -29,7 +29,10 @@ fn should_notify_listeners() {
     tx_listener.notify();
     let full_res = full_receiver.try_next().unwrap();
     let pending_res = pending_receiver.try_next().unwrap();
-    assert_eq!(full_res, Some(vec![(*tx.hash(), TxStatus::Added)].into()));
+    assert_eq!(
+        full_res,
+        Some(vec![(*tx.hash(), *tx.sender(), TxStatus::Added)].into())
+    );
     assert_eq!(pending_res, Some(vec![*tx.hash()].into()));
 }
 
@@ -47,14 +50,17 @@ fn test_notify() {
     let full_res = full_receiver.try_next().unwrap();
     assert_eq!(
         full_res,
-        Some(vec![(*tx.hash(), TxStatus::Rejected)].into())
+        Some(vec![(*tx.hash(), *tx.sender(), TxStatus::Rejected)].into())
     );
 
     // dropped
     tx_listener.dropped(&tx, None);
     tx_listener.notify();
     let full_res = full_receiver.try_next().unwrap();
-    assert_eq!(full_res, Some(vec![(*tx.hash(), TxStatus::Dropped)].into()));
+    assert_eq!(
+        full_res,
+        Some(vec![(*tx.hash(), *tx.sender(), TxStatus::Dropped)].into())
+    );
 
     // canceled
     tx_listener.canceled(&tx);
@@ -62,20 +68,26 @@ fn test_notify() {
     let full_res = full_receiver.try_next().unwrap();
     assert_eq!(
         full_res,
-        Some(vec![(*tx.hash(), TxStatus::Canceled)].into())
+        Some(vec![(*tx.hash(), *tx.sender(), TxStatus::Canceled)].into())
     );
 
     // culled
     tx_listener.culled(&tx);
     tx_listener.notify();
     let full_res = full_receiver.try_next().unwrap();
-    assert_eq!(full_res, Some(vec![(*tx.hash(), TxStatus::Culled)].into()));
+    assert_eq!(
+        full_res,
+        Some(vec![(*tx.hash(), *tx.sender(), TxStatus::Culled)].into())
+    );
 
     // invalid
     tx_listener.invalid(&tx);
     tx_listener.notify();
     let full_res = full_receiver.try_next().unwrap();
-    assert_eq!(full_res, Some(vec![(*tx.hash(), TxStatus::Invalid)].into()));
+    assert_eq!(
+        full_res,
+        Some(vec![(*tx.hash(), *tx.sender(), TxStatus::Invalid)].into())
+    );
 }
 
 fn new_tx() -> Arc<Transaction> {
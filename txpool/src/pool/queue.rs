@@ -5,14 +5,15 @@
 
 use super::{
     client, listener, local_transactions::LocalTransactionsList, ready, replace, scoring, verifier,
-    PendingOrdering, PendingSettings, PrioritizationStrategy, SeqNumber, TxStatus,
+    PendingOrdering, PendingSettings, PrioritizationStrategy, SeqNumber,
 };
+use crate::metrics::TxPoolMetrics;
 use crate::pool::ready::Expiration;
 use crate::{pool, pool::PoolTransaction};
 use futures_channel::mpsc;
 use parking_lot::RwLock;
 use starcoin_crypto::hash::HashValue;
-use starcoin_txpool_api::TxPoolStatus;
+use starcoin_txpool_api::{TxPoolStatus, TxnStatusFullEvent};
 use starcoin_types::{account_address::AccountAddress as Address, transaction};
 use std::{
     cmp,
@@ -29,7 +30,10 @@ type Listener = (
     LocalTransactionsList,
     (
         listener::TransactionsPoolNotifier,
-        (listener::Logger, listener::StatusLogger),
+        (
+            listener::Logger,
+            (listener::StatusLogger, listener::MetricsListener),
+        ),
     ),
 );
 type Pool = tx_pool::Pool<pool::VerifiedTransaction, scoring::SeqNumberAndGasPrice, Listener>;
@@ -231,13 +235,14 @@ impl TransactionQueue {
         limits: tx_pool::Options,
         verification_options: verifier::Options,
         strategy: PrioritizationStrategy,
+        replace_gas_price_bump: u64,
     ) -> Self {
         let max_count = limits.max_count;
         Self {
             insertion_id: Default::default(),
             pool: RwLock::new(tx_pool::Pool::new(
                 Default::default(),
-                scoring::SeqNumberAndGasPrice(strategy),
+                scoring::SeqNumberAndGasPrice::new(strategy, replace_gas_price_bump),
                 limits,
             )),
             options: RwLock::new(verification_options),
@@ -359,6 +364,13 @@ impl TransactionQueue {
             .collect()
     }
 
+    /// Returns all the transactions in the pool, including the future transactions.
+    pub fn all_transactions(&self) -> Vec<Arc<pool::VerifiedTransaction>> {
+        // always ready
+        let ready = Expiration::new(0);
+        self.pool.read().unordered_pending(ready).collect()
+    }
+
     /// Returns current pending transactions ordered by priority.
     ///
     /// NOTE: This may return a cached version of pending transaction set.
@@ -510,13 +522,20 @@ impl TransactionQueue {
         }
     }
 
-    pub(crate) fn inner_status<C>(&self, client: C, block_number: u64, current_timestamp: u64)
+    /// Returns the count of the stalled, pending(ready) and future transactions in the pool.
+    pub(crate) fn inner_status<C>(
+        &self,
+        client: C,
+        block_number: u64,
+        current_timestamp: u64,
+    ) -> tx_pool::Status
     where
         C: client::AccountSeqNumberClient,
     {
         let ready = Self::ready(client, block_number, current_timestamp);
         let status = self.pool.read().status(ready);
         debug!("txpool queue inner status: {:?}", status);
+        status
     }
 
     /// Returns a status of the queue.
@@ -564,10 +583,17 @@ impl TransactionQueue {
     }
 
     /// Add a listener to be notified about all transactions the pool
-    pub fn add_full_listener(&self, f: mpsc::UnboundedSender<Arc<[(HashValue, TxStatus)]>>) {
+    pub fn add_full_listener(&self, f: mpsc::UnboundedSender<TxnStatusFullEvent>) {
         (self.pool.write().listener_mut().1).0.add_full_listener(f);
     }
 
+    /// Report the added transactions and the transactions leaving the pool to `metrics`.
+    pub(crate) fn set_metrics(&self, metrics: TxPoolMetrics) {
+        (((self.pool.write().listener_mut().1).1).1)
+            .1
+            .set_metrics(metrics);
+    }
+
     /// Check if pending set is cached.
     #[cfg(test)]
     pub fn is_pending_cached(&self) -> bool {
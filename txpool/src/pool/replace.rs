@@ -9,6 +9,10 @@
 //!
 //! Here we decide based on the sender, the nonce and gas price, and finally
 //! on the `Readiness` of the transactions when comparing them
+//!
+//! A transaction with the same sender and sequence number as a pending one
+//! replaces it only if its gas price is at least `txpool-replace-gas-price-bump`
+//! percent (default to 10) higher, rounded up, see `SeqNumberAndGasPrice`.
 
 use crate::pool::{client, ScoredTransaction};
 use starcoin_types::account_address::AccountAddress as Address;
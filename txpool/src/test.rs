@@ -1,8 +1,10 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::journal::TxPoolJournal;
 use crate::pool::AccountSeqNumberClient;
-use crate::TxStatus;
+use crate::{TxPoolActorService, TxPoolService, TxStatus};
+use anyhow::format_err;
 use anyhow::Result;
 use network_api::messages::{PeerTransactionsMessage, TransactionsMessage};
 use network_api::PeerId;
@@ -19,11 +21,14 @@ use starcoin_statedb::ChainStateDB;
 use starcoin_storage::BlockStore;
 use starcoin_txpool_api::{TxPoolSyncService, TxnStatusFullEvent};
 use starcoin_types::{
+    account::Account,
     account_address::{self, AccountAddress},
     account_config,
-    transaction::{SignedUserTransaction, Transaction, TransactionPayload},
+    transaction::{SignedUserTransaction, Transaction, TransactionError, TransactionPayload},
     U256,
 };
+use std::fs::OpenOptions;
+use std::io::Write;
 use std::time::Duration;
 use std::{collections::HashMap, sync::Arc};
 use tokio::time::sleep;
@@ -93,6 +98,37 @@ async fn test_tx_pool() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+async fn test_replace_txn() -> Result<()> {
+    let (txpool_service, _storage, config, _, _, _) = test_helper::start_txpool().await;
+    let txn = generate_txn_with_gas_price(config.clone(), 0, 100);
+    txpool_service.add_txns(vec![txn.clone()]).pop().unwrap()?;
+
+    // the gas price should be at least 10% higher.
+    let too_cheap = generate_txn_with_gas_price(config.clone(), 0, 109);
+    let err = txpool_service
+        .replace_txn(txn.id(), too_cheap)
+        .expect_err("replace with a too cheap txn should fail");
+    assert_eq!(
+        err.downcast::<TransactionError>()?,
+        TransactionError::TooCheapToReplace {
+            prev: Some(100),
+            new: Some(109),
+        }
+    );
+    // the sequence number should be same.
+    let other_seq = generate_txn_with_gas_price(config.clone(), 1, 200);
+    assert!(txpool_service.replace_txn(txn.id(), other_seq).is_err());
+
+    let new_txn = generate_txn_with_gas_price(config, 0, 110);
+    txpool_service.replace_txn(txn.id(), new_txn.clone())?;
+    assert!(txpool_service.find_txn(&txn.id()).is_none());
+    let pending_txns = txpool_service.get_pending_txns(None, Some(0));
+    assert_eq!(pending_txns.len(), 1);
+    assert_eq!(pending_txns[0].id(), new_txn.id());
+    Ok(())
+}
+
 #[stest::test]
 async fn test_subscribe_txns() {
     let (pool, ..) = test_helper::start_txpool().await;
@@ -177,6 +213,66 @@ async fn test_pool_pending() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+async fn test_pool_content_metrics() -> Result<()> {
+    let (txpool_service, _storage, node_config, _, _, _) = test_helper::start_txpool().await;
+    let metrics_config: &MetricsConfig = &node_config.metrics;
+    let txns = vec![
+        generate_txn_with_gas_price(node_config.clone(), 0, 10),
+        generate_txn_with_gas_price(node_config.clone(), 1, 20),
+        // gas price lower than the min gas price.
+        generate_txn_with_gas_price(node_config.clone(), 2, 0),
+        // the sender does not exist, rejected by the VM validation.
+        Account::new().create_signed_txn_with_args(
+            TransactionPayload::ScriptFunction(
+                starcoin_transaction_builder::encode_transfer_script_function(
+                    account_config::association_address(),
+                    10000,
+                ),
+            ),
+            0,
+            starcoin_transaction_builder::DEFAULT_MAX_GAS_AMOUNT,
+            1,
+            2,
+            node_config.net().chain_id(),
+        ),
+    ];
+    let _ = txpool_service.add_txns(txns);
+    txpool_service.get_inner().report_pool_metrics();
+
+    for reason in ["insufficient_gas_price", "SENDING_ACCOUNT_DOES_NOT_EXIST"] {
+        let rejected_metric = metrics_config
+            .get_metric("txpool_txn_rejected_total", Some(("reason", reason)))
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(rejected_metric.get_counter().get_value() as u64, 1);
+    }
+
+    let gas_price_metric = metrics_config
+        .get_metric("txpool_txn_gas_price", None)
+        .unwrap()
+        .pop()
+        .unwrap();
+    assert_eq!(gas_price_metric.get_histogram().get_sample_count(), 2);
+
+    let ready_metric = metrics_config
+        .get_metric("txpool_status", Some(("name", "ready")))
+        .unwrap()
+        .pop()
+        .unwrap();
+    assert_eq!(ready_metric.get_gauge().get_value() as u64, 2);
+
+    let sender = account_config::association_address().to_string();
+    let top_sender_metric = metrics_config
+        .get_metric("txpool_top_senders", Some(("sender", sender.as_str())))
+        .unwrap()
+        .pop()
+        .unwrap();
+    assert_eq!(top_sender_metric.get_gauge().get_value() as u64, 2);
+    Ok(())
+}
+
 #[stest::test]
 async fn test_rollback() -> Result<()> {
     let (pool, storage, config, _, _, _) = test_helper::start_txpool().await;
@@ -288,6 +384,7 @@ async fn test_txpool_actor_service() {
     tx_pool_actor
         .notify(Into::<TxnStatusFullEvent>::into(vec![(
             txn.id(),
+            txn.sender(),
             TxStatus::Added,
         )]))
         .unwrap();
@@ -295,7 +392,56 @@ async fn test_txpool_actor_service() {
     sleep(Duration::from_millis(300)).await;
 }
 
+#[stest::test]
+async fn test_txpool_journal() -> Result<()> {
+    let (txpool_service, storage, config, _, _, _) = test_helper::start_txpool().await;
+    let txn1 = generate_txn(config.clone(), 0);
+    let txn2 = generate_txn(config.clone(), 1);
+    for result in txpool_service.add_txns(vec![txn1.clone(), txn2.clone()]) {
+        result?;
+    }
+    // wait the actor to append the new txns to the journal.
+    sleep(Duration::from_millis(500)).await;
+
+    let journal_path = config
+        .tx_pool
+        .journal_path()
+        .ok_or_else(|| format_err!("txpool journal should be enabled"))?;
+    let entries = TxPoolJournal::new(journal_path.clone()).load()?;
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|entry| !entry.local));
+
+    // simulate a broken tail left by an interrupted write.
+    OpenOptions::new()
+        .append(true)
+        .open(&journal_path)?
+        .write_all(&[1u8, 0, 0])?;
+
+    // replay the journal to a new pool, as the node restart.
+    let startup_info = storage
+        .get_startup_info()?
+        .ok_or_else(|| format_err!("startup info should exist"))?;
+    let header = storage
+        .get_block_header_by_hash(startup_info.main)?
+        .ok_or_else(|| format_err!("head block header should exist"))?;
+    let new_pool = TxPoolService::new(config, storage, header, None);
+    TxPoolActorService::new(new_pool.get_inner()).load_journal()?;
+    let pending_txns = new_pool.get_pending_txns(None, Some(0));
+    assert_eq!(pending_txns.len(), 2);
+    assert_eq!(pending_txns[0].id(), txn1.id());
+    assert_eq!(pending_txns[1].id(), txn2.id());
+    Ok(())
+}
+
 fn generate_txn(config: Arc<NodeConfig>, seq: u64) -> SignedUserTransaction {
+    generate_txn_with_gas_price(config, seq, 1)
+}
+
+fn generate_txn_with_gas_price(
+    config: Arc<NodeConfig>,
+    seq: u64,
+    gas_price: u64,
+) -> SignedUserTransaction {
     let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
     let account_address = account_address::from_public_key(&public_key);
     let txn = starcoin_transaction_builder::create_signed_txn_with_association_account(
@@ -304,7 +450,7 @@ fn generate_txn(config: Arc<NodeConfig>, seq: u64) -> SignedUserTransaction {
         ),
         seq,
         starcoin_transaction_builder::DEFAULT_MAX_GAS_AMOUNT,
-        1,
+        gas_price,
         2,
         config.net(),
     );
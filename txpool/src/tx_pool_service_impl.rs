@@ -10,9 +10,9 @@ use crate::{
     pool_client::{NonceCache, PoolClient},
 };
 
-use crate::metrics::TxPoolMetrics;
+use crate::metrics::{TxPoolMetrics, TOP_SENDERS_COUNT};
 use crate::pool::{Client, TransactionQueue};
-use anyhow::Result;
+use anyhow::{ensure, format_err, Result};
 use futures_channel::mpsc;
 use parking_lot::RwLock;
 use starcoin_config::NodeConfig;
@@ -20,13 +20,17 @@ use starcoin_crypto::hash::HashValue;
 use starcoin_executor::VMMetrics;
 use starcoin_statedb::ChainStateDB;
 use starcoin_storage::Store;
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService, TxnStatusFullEvent};
+use starcoin_txpool_api::{
+    min_replace_gas_price, TxPoolStatus, TxPoolSyncService, TxnStatusFullEvent,
+};
 use starcoin_types::{
     account_address::AccountAddress,
     block::{Block, BlockHeader},
     transaction,
     transaction::SignedUserTransaction,
 };
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
@@ -58,7 +62,11 @@ impl TxPoolService {
             },
             verifier_options,
             PrioritizationStrategy::GasPriceOnly,
+            pool_config.replace_gas_price_bump(),
         );
+        if let Some(metrics) = metrics.as_ref() {
+            queue.set_metrics(metrics.clone());
+        }
         let queue = Arc::new(queue);
         let inner = Inner {
             node_config,
@@ -122,6 +130,16 @@ impl TxPoolSyncService for TxPoolService {
             .map(|t| t.signed().clone())
     }
 
+    fn replace_txn(&self, txn_hash: HashValue, new_txn: SignedUserTransaction) -> Result<()> {
+        let _timer = self.inner.metrics.as_ref().map(|metrics| {
+            metrics
+                .txpool_service_time
+                .with_label_values(&["replace_txn"])
+                .start_timer()
+        });
+        self.inner.replace_txn(txn_hash, new_txn)
+    }
+
     /// Get all pending txns which is ok to be packaged to mining.
     fn get_pending_txns(
         &self,
@@ -271,7 +289,105 @@ impl Inner {
         let txns = txns
             .into_iter()
             .map(|t| PoolTransaction::Unverified(UnverifiedUserTransaction::from(t)));
-        self.queue.import(self.get_pool_client(), txns)
+        let results = self.queue.import(self.get_pool_client(), txns);
+        self.report_rejected_txns(&results);
+        results
+    }
+    pub(crate) fn import_local_txns(
+        &self,
+        txns: Vec<transaction::SignedUserTransaction>,
+    ) -> Vec<Result<(), transaction::TransactionError>> {
+        let txns = txns
+            .into_iter()
+            .map(|t| PoolTransaction::Local(transaction::PendingTransaction::from(t)));
+        let results = self.queue.import(self.get_pool_client(), txns);
+        self.report_rejected_txns(&results);
+        results
+    }
+
+    fn report_rejected_txns(&self, results: &[Result<(), transaction::TransactionError>]) {
+        if let Some(metrics) = self.metrics.as_ref() {
+            for err in results.iter().filter_map(|result| result.as_ref().err()) {
+                metrics
+                    .txpool_txn_rejected_total
+                    .with_label_values(&[rejected_reason(err).as_ref()])
+                    .inc();
+            }
+        }
+    }
+
+    /// Report the status of the txns in the pool, the count of the ready and future txns,
+    /// and the senders with the most txns.
+    pub(crate) fn report_pool_metrics(&self) {
+        let metrics = match self.metrics.as_ref() {
+            Some(metrics) => metrics,
+            None => return,
+        };
+        let now_secs = self.node_config.net().time_service().now_secs();
+        let status = self
+            .queue
+            .inner_status(self.get_pool_client(), u64::max_value(), now_secs);
+        metrics
+            .txpool_status
+            .with_label_values(&["ready"])
+            .set(status.pending as u64);
+        metrics
+            .txpool_status
+            .with_label_values(&["future"])
+            .set(status.future as u64);
+        metrics
+            .txpool_status
+            .with_label_values(&["stalled"])
+            .set(status.stalled as u64);
+
+        let mut sender_txns: HashMap<AccountAddress, u64> = HashMap::new();
+        for txn in self.queue.all_transactions() {
+            *sender_txns.entry(txn.signed().sender()).or_default() += 1;
+        }
+        let mut sender_txns: Vec<_> = sender_txns.into_iter().collect();
+        sender_txns.sort_by(|a, b| b.1.cmp(&a.1));
+        metrics.txpool_top_senders.reset();
+        for (sender, count) in sender_txns.into_iter().take(TOP_SENDERS_COUNT) {
+            metrics
+                .txpool_top_senders
+                .with_label_values(&[sender.to_string().as_str()])
+                .set(count);
+        }
+    }
+    pub(crate) fn replace_txn(
+        &self,
+        txn_hash: HashValue,
+        new_txn: transaction::SignedUserTransaction,
+    ) -> Result<()> {
+        let old_txn = self
+            .queue
+            .find(&txn_hash)
+            .ok_or_else(|| format_err!("Can not find pending txn by hash: {}", txn_hash))?;
+        let old_txn = old_txn.signed();
+        ensure!(
+            old_txn.sender() == new_txn.sender()
+                && old_txn.sequence_number() == new_txn.sequence_number(),
+            "The replacement txn should have the same sender and sequence number as txn {}",
+            txn_hash
+        );
+        let min_gas_price = min_replace_gas_price(
+            old_txn.gas_unit_price(),
+            self.node_config.tx_pool.replace_gas_price_bump(),
+        );
+        if new_txn.gas_unit_price() < min_gas_price {
+            return Err(transaction::TransactionError::TooCheapToReplace {
+                prev: Some(old_txn.gas_unit_price()),
+                new: Some(new_txn.gas_unit_price()),
+            }
+            .into());
+        }
+        self.import_txns(vec![new_txn])
+            .pop()
+            .expect("import should return one result per txn")
+            .map_err(Into::into)
+    }
+    pub(crate) fn all_txns(&self) -> Vec<Arc<VerifiedTransaction>> {
+        self.queue.all_transactions()
     }
     pub(crate) fn remove_txn(
         &self,
@@ -363,3 +479,31 @@ impl Inner {
         )
     }
 }
+
+/// The reason label of the `txpool_txn_rejected_total` metric, the name of the status code if the
+/// txn fails the VM validation.
+fn rejected_reason(err: &transaction::TransactionError) -> Cow<'static, str> {
+    use transaction::{CallError, TransactionError};
+    let reason = match err {
+        TransactionError::AlreadyImported => "already_imported",
+        TransactionError::Old => "old",
+        TransactionError::LimitReached => "limit_reached",
+        TransactionError::InsufficientGasPrice { .. } => "insufficient_gas_price",
+        TransactionError::TooCheapToReplace { .. } => "too_cheap_to_replace",
+        TransactionError::InsufficientGas { .. } => "insufficient_gas",
+        TransactionError::InsufficientBalance { .. } => "insufficient_balance",
+        TransactionError::GasLimitExceeded { .. } => "gas_limit_exceeded",
+        TransactionError::SenderBanned => "sender_banned",
+        TransactionError::RecipientBanned => "recipient_banned",
+        TransactionError::CodeBanned => "code_banned",
+        TransactionError::InvalidChainId => "invalid_chain_id",
+        TransactionError::NotAllowed => "not_allowed",
+        TransactionError::InvalidSignature(_) => "invalid_signature",
+        TransactionError::TooBig => "too_big",
+        TransactionError::CallErr(CallError::ExecutionError(status)) => {
+            return Cow::Owned(format!("{:?}", status.status_code()));
+        }
+        TransactionError::CallErr(_) => "call_error",
+    };
+    Cow::Borrowed(reason)
+}
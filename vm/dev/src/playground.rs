@@ -6,6 +6,7 @@ use starcoin_abi_decoder::decode_move_value;
 use starcoin_abi_resolver::ABIResolver;
 use starcoin_abi_types::TypeInstantiation;
 use starcoin_crypto::HashValue;
+use starcoin_gas::CallFrame;
 use starcoin_resource_viewer::module_cache::ModuleCache;
 use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue, MoveValueAnnotator};
 use starcoin_rpc_api::types::{DryRunOutputView, TransactionOutputView, WriteOpValueView};
@@ -18,7 +19,9 @@ use starcoin_vm_types::file_format::CompiledModule;
 use starcoin_vm_types::identifier::{IdentStr, Identifier};
 use starcoin_vm_types::language_storage::{ModuleId, StructTag, TypeTag};
 use starcoin_vm_types::state_view::StateView;
-use starcoin_vm_types::transaction::{DryRunTransaction, TransactionOutput, TransactionPayload};
+use starcoin_vm_types::transaction::{
+    DryRunTransaction, SignedUserTransaction, TransactionOutput, TransactionPayload,
+};
 use starcoin_vm_types::transaction_argument::convert_txn_args;
 use starcoin_vm_types::transaction_argument::TransactionArgument;
 use starcoin_vm_types::vm_status::VMStatus;
@@ -40,6 +43,10 @@ impl PlaygroudService {
 }
 
 impl PlaygroudService {
+    pub fn state_store(&self) -> Arc<dyn StateNodeStore> {
+        self.state.clone()
+    }
+
     pub fn dry_run(
         &self,
         state_root: HashValue,
@@ -49,6 +56,17 @@ impl PlaygroudService {
         dry_run(&state_view, txn, self.metrics.clone())
     }
 
+    /// Re-execute the `txn` at the `state_root` with a call tracer,
+    /// the `state_root` should be the state root before the `txn` is executed.
+    pub fn trace_transaction(
+        &self,
+        state_root: HashValue,
+        txn: SignedUserTransaction,
+    ) -> Result<(VMStatus, TransactionOutput, Vec<CallFrame>)> {
+        let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
+        trace_transaction(&state_view, txn, self.metrics.clone())
+    }
+
     pub fn call_contract(
         &self,
         state_root: HashValue,
@@ -102,6 +120,26 @@ pub fn dry_run<S: StateView>(
     vm.dry_run_transaction(&state_view_cache.as_move_resolver(), txn)
 }
 
+pub fn dry_run_with_trace<S: StateView>(
+    state_view: &S,
+    txn: DryRunTransaction,
+    metrics: Option<VMMetrics>,
+) -> Result<(VMStatus, TransactionOutput, Vec<CallFrame>)> {
+    let mut vm = StarcoinVM::new(metrics);
+    let state_view_cache = StateViewCache::new(state_view);
+    vm.dry_run_transaction_with_trace(&state_view_cache.as_move_resolver(), txn)
+}
+
+pub fn trace_transaction<S: StateView>(
+    state_view: &S,
+    txn: SignedUserTransaction,
+    metrics: Option<VMMetrics>,
+) -> Result<(VMStatus, TransactionOutput, Vec<CallFrame>)> {
+    let mut vm = StarcoinVM::new(metrics);
+    let state_view_cache = StateViewCache::new(state_view);
+    vm.trace_transaction(&state_view_cache.as_move_resolver(), txn)
+}
+
 pub fn dry_run_explain<S: StateView>(
     state_view: &S,
     txn: DryRunTransaction,
@@ -149,6 +187,7 @@ pub fn dry_run_explain<S: StateView>(
     Ok(DryRunOutputView {
         explained_status: vm_status_explain,
         txn_output,
+        trace: None,
     })
 }
 
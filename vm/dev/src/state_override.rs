@@ -0,0 +1,220 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Overlay the `StateOverrides` of a contract call or dry run request on a state view.
+//! The overrides only live in memory, they are never written to the storage.
+
+use anyhow::{bail, ensure, format_err, Result};
+use move_core_types::u256;
+use serde_json::Value;
+use starcoin_abi_resolver::ABIResolver;
+use starcoin_abi_types::TypeInstantiation;
+use starcoin_rpc_api::types::{ResourceOverrideView, StateOverrides};
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::file_format::CompiledModule;
+use starcoin_vm_types::state_store::state_key::StateKey;
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::value::{MoveStruct, MoveValue};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// A state view which reads the overridden modules and resources first, and falls back to the `inner` view.
+pub struct StateOverrideView<S> {
+    inner: S,
+    overrides: HashMap<StateKey, Vec<u8>>,
+}
+
+impl<S> StateOverrideView<S>
+where
+    S: StateView,
+{
+    pub fn new(inner: S, overrides: StateOverrides) -> Result<Self> {
+        let mut view = Self {
+            inner,
+            overrides: HashMap::new(),
+        };
+        for code in overrides.modules {
+            let module = CompiledModule::deserialize(code.0.as_slice())
+                .map_err(|e| format_err!("Invalid module bytecode: {:?}", e))?;
+            view.overrides.insert(
+                StateKey::AccessPath(AccessPath::from(&module.self_id())),
+                code.0,
+            );
+        }
+        let mut json_resources = vec![];
+        for (access_path, value) in overrides.resources {
+            match value {
+                ResourceOverrideView::Bcs(data) => {
+                    view.overrides
+                        .insert(StateKey::AccessPath(access_path), data.0);
+                }
+                ResourceOverrideView::Json(json) => json_resources.push((access_path, json)),
+            }
+        }
+        // The JSON resources are encoded after the modules are overlaid,
+        // so the resources defined in the overridden modules can be resolved too.
+        let encoded = {
+            let resolver = ABIResolver::new(&view);
+            json_resources
+                .into_iter()
+                .map(|(access_path, json)| {
+                    let struct_tag = access_path.path.as_struct_tag().ok_or_else(|| {
+                        format_err!("Access path {} is not a resource path", access_path)
+                    })?;
+                    let struct_abi = resolver.resolve_struct_tag(struct_tag)?;
+                    let data =
+                        encode_json_value(&TypeInstantiation::Struct(Box::new(struct_abi)), &json)
+                            .map_err(|e| {
+                                format_err!("Invalid resource value of {}: {}", access_path, e)
+                            })?;
+                    Ok((StateKey::AccessPath(access_path), data))
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
+        view.overrides.extend(encoded);
+        Ok(view)
+    }
+}
+
+impl<S> StateView for StateOverrideView<S>
+where
+    S: StateView,
+{
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        match self.overrides.get(state_key) {
+            Some(data) => Ok(Some(data.clone())),
+            None => self.inner.get_state_value(state_key),
+        }
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.inner.is_genesis()
+    }
+}
+
+/// Encode the JSON `value` to BCS bytes through the type abi, it accepts the JSON format of `decode_move_value`.
+pub fn encode_json_value(ty: &TypeInstantiation, value: &Value) -> Result<Vec<u8>> {
+    json_to_move_value(ty, value)?
+        .simple_serialize()
+        .ok_or_else(|| format_err!("Serialize move value failed"))
+}
+
+fn json_to_move_value(ty: &TypeInstantiation, value: &Value) -> Result<MoveValue> {
+    Ok(match ty {
+        TypeInstantiation::Bool => MoveValue::Bool(
+            value
+                .as_bool()
+                .ok_or_else(|| format_err!("Expect a bool, got {}", value))?,
+        ),
+        TypeInstantiation::U8 => MoveValue::U8(u8::try_from(json_to_u128(value)?)?),
+        TypeInstantiation::U16 => MoveValue::U16(u16::try_from(json_to_u128(value)?)?),
+        TypeInstantiation::U32 => MoveValue::U32(u32::try_from(json_to_u128(value)?)?),
+        TypeInstantiation::U64 => MoveValue::U64(u64::try_from(json_to_u128(value)?)?),
+        TypeInstantiation::U128 => MoveValue::U128(json_to_u128(value)?),
+        TypeInstantiation::U256 => MoveValue::U256(json_to_u256(value)?),
+        TypeInstantiation::Address => match value {
+            Value::String(s) => MoveValue::Address(AccountAddress::from_str(s)?),
+            _ => bail!("Expect an address string, got {}", value),
+        },
+        TypeInstantiation::Vector(sub_ty) => match (sub_ty.as_ref(), value) {
+            (TypeInstantiation::U8, Value::String(s)) => {
+                MoveValue::vector_u8(hex::decode(s.strip_prefix("0x").unwrap_or(s))?)
+            }
+            (_, Value::Array(values)) => MoveValue::Vector(
+                values
+                    .iter()
+                    .map(|v| json_to_move_value(sub_ty, v))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            _ => bail!("Expect an array, got {}", value),
+        },
+        TypeInstantiation::Struct(s) => {
+            let fields = value
+                .as_object()
+                .ok_or_else(|| format_err!("Expect a struct {}, got {}", s.name(), value))?;
+            ensure!(
+                fields.len() == s.fields().len(),
+                "Struct {} expect {} fields, got {}",
+                s.name(),
+                s.fields().len(),
+                fields.len()
+            );
+            MoveValue::Struct(MoveStruct::new(
+                s.fields()
+                    .iter()
+                    .map(|field| {
+                        let v = fields.get(field.name()).ok_or_else(|| {
+                            format_err!("Missing field {} of struct {}", field.name(), s.name())
+                        })?;
+                        json_to_move_value(field.type_abi(), v)
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+            ))
+        }
+        TypeInstantiation::Signer
+        | TypeInstantiation::TypeParameter(_)
+        | TypeInstantiation::Reference(_, _) => bail!("Unsupported type {:?}", ty),
+    })
+}
+
+fn json_to_u128(value: &Value) -> Result<u128> {
+    match value {
+        // the number may exceed u64 if the `arbitrary_precision` feature of serde_json is enabled.
+        Value::Number(n) => n
+            .to_string()
+            .parse::<u128>()
+            .map_err(|_| format_err!("Expect an unsigned integer, got {}", n)),
+        Value::String(s) => Ok(s.parse::<u128>()?),
+        _ => bail!("Expect an unsigned integer, got {}", value),
+    }
+}
+
+fn json_to_u256(value: &Value) -> Result<u256::U256> {
+    match value {
+        Value::String(s) => {
+            let s = s.strip_suffix("u256").unwrap_or(s);
+            u256::U256::from_str_radix(s, 10)
+                .map_err(|e| format_err!("Invalid u256 {}: {:?}", s, e))
+        }
+        _ => Ok(u256::U256::from(json_to_u128(value)?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_abi_types::{FieldABI, StructInstantiation};
+    use starcoin_vm_types::identifier::Identifier;
+    use starcoin_vm_types::language_storage::{ModuleId, CORE_CODE_ADDRESS};
+
+    #[test]
+    fn test_encode_json_value() -> Result<()> {
+        let ty = TypeInstantiation::new_struct_instantiation(StructInstantiation::new(
+            "Foo".to_string(),
+            ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("Foo")?),
+            "".to_string(),
+            vec![],
+            vec![
+                FieldABI::new("a".to_string(), "".to_string(), TypeInstantiation::U64),
+                FieldABI::new(
+                    "b".to_string(),
+                    "".to_string(),
+                    TypeInstantiation::new_vector(TypeInstantiation::U8),
+                ),
+                FieldABI::new("c".to_string(), "".to_string(), TypeInstantiation::U128),
+            ],
+            starcoin_vm_types::file_format::AbilitySet::EMPTY,
+        ));
+        let json = serde_json::json!({"a": 1, "b": "0x0102", "c": "340282366920938463463374607431768211455"});
+        let data = encode_json_value(&ty, &json)?;
+        let expect = bcs_ext::to_bytes(&(1u64, vec![1u8, 2u8], u128::MAX))?;
+        assert_eq!(data, expect);
+        let decoded = starcoin_abi_decoder::decode_move_value(&ty, data.as_slice())?;
+        assert_eq!(encode_json_value(&ty, &decoded.0)?, data);
+
+        assert!(encode_json_value(&ty, &serde_json::json!({"a": 1, "b": "0x01"})).is_err());
+        Ok(())
+    }
+}
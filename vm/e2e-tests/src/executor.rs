@@ -26,7 +26,7 @@ use starcoin_vm_types::{
     account_config::{AccountResource, BalanceResource, CORE_CODE_ADDRESS},
     block_metadata::BlockMetadata,
     errors::Location,
-    genesis_config::ChainId,
+    genesis_config::{ChainId, StdlibVersion},
     identifier::Identifier,
     language_storage::{ModuleId, TypeTag},
     move_resource::MoveResource,
@@ -525,7 +525,7 @@ impl FakeExecutor {
     ) -> Result<(), VMStatus> {
         let write_set = {
             let gas_params = StarcoinGasParameters::initial();
-            let vm = MoveVmExt::new(gas_params.natives.clone()).unwrap();
+            let vm = MoveVmExt::new(gas_params.natives.clone(), StdlibVersion::Latest).unwrap();
             let remote_view = RemoteStorage::new(&self.data_store);
 
             let balance = gas_params.txn.maximum_number_of_gas_units.clone();
@@ -583,7 +583,7 @@ impl FakeExecutor {
         args: Vec<Vec<u8>>,
     ) -> Result<WriteSet, VMStatus> {
         let gas_params = StarcoinGasParameters::initial();
-        let vm = MoveVmExt::new(gas_params.natives.clone()).unwrap();
+        let vm = MoveVmExt::new(gas_params.natives.clone(), StdlibVersion::Latest).unwrap();
         let remote_view = RemoteStorage::new(&self.data_store);
 
         let balance = gas_params.txn.maximum_number_of_gas_units.clone();
@@ -35,4 +35,14 @@ crate::natives::define_gas_parameters_for_natives!(GasParameters, "starcoin_nati
     [.u256.pow.base,  optional "u256.pow.base",  (8 + 1) * MUL],
     [.from_bcs.base, optional "frombcs.base", (4 + 1)  * MUL],
     [.secp256k1.base, optional "secp256k1.base", (4 + 1)  * MUL],
+    [.bls12381.validate_pubkey.base, optional "bls12381.validate_pubkey.base", (2000 + 1) * MUL],
+    [.bls12381.verify_signature.base, optional "bls12381.verify_signature.base", (20000 + 1) * MUL],
+    [.bls12381.verify_signature.per_byte, optional "bls12381.verify_signature.per_byte", (8 + 1) * MUL],
+    [.bls12381.aggregate_verify.base, optional "bls12381.aggregate_verify.base", (10000 + 1) * MUL],
+    [.bls12381.aggregate_verify.per_pubkey, optional "bls12381.aggregate_verify.per_pubkey", (20000 + 1) * MUL],
+    [.bls12381.aggregate_verify.per_byte, optional "bls12381.aggregate_verify.per_byte", (8 + 1) * MUL],
+    [.bls12381.fast_aggregate_verify.base, optional "bls12381.fast_aggregate_verify.base", (20000 + 1) * MUL],
+    [.bls12381.fast_aggregate_verify.per_pubkey, optional "bls12381.fast_aggregate_verify.per_pubkey", (400 + 1) * MUL],
+    [.bls12381.fast_aggregate_verify.per_byte, optional "bls12381.fast_aggregate_verify.per_byte", (8 + 1) * MUL],
+    [.bls12381.verify_proof_of_possession.base, optional "bls12381.verify_proof_of_possession.base", (20000 + 1) * MUL],
 ], allow_unmapped = 3 /* signature */ + 2 /* hash */ + 1 /* u256 */ + 1 /* from_bcs */ + 1 /* secp256k1 */);
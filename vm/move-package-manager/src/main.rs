@@ -16,6 +16,7 @@ use move_package_manager::{run_integration_test, IntegrationTestCommand};
 use move_vm_test_utils::gas_schedule::CostTable;
 use starcoin_config::genesis_config::G_LATEST_GAS_PARAMS;
 use starcoin_vm_runtime::natives::starcoin_natives;
+use starcoin_vm_types::genesis_config::StdlibVersion;
 use starcoin_vm_types::on_chain_config::G_LATEST_INSTRUCTION_TABLE;
 use std::path::PathBuf;
 
@@ -81,7 +82,7 @@ fn main() -> Result<()> {
 
     let move_args = &args.move_args;
     let gas_params = G_LATEST_GAS_PARAMS.clone();
-    let natives = starcoin_natives(gas_params.natives);
+    let natives = starcoin_natives(gas_params.natives, StdlibVersion::Latest);
     let cost_table = CostTable {
         instruction_table: G_LATEST_INSTRUCTION_TABLE.clone(),
     };
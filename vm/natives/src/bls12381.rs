@@ -0,0 +1,464 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! BLS12-381 signatures in the minimal-pubkey-size setting: the public keys are 48 bytes
+//! compressed G1 points and the signatures are 96 bytes compressed G2 points.
+//! The proof-of-possession scheme is used, so the signatures on the same message can be
+//! verified by the aggregated public key, once the proofs of possession of the keys are checked.
+//!
+//! The natives are bound to the `0x1::BLS12381` module (vm/stdlib/sources/BLS12381.move), which is
+//! released by the stdlib upgrade of version 13. The VM only registers them from that version,
+//! once the on-chain gas schedule prices all of them. The module declares them as:
+//!
+//! ```move
+//! native public fun bls12381_validate_pubkey(public_key: vector<u8>): bool;
+//! native public fun bls12381_verify_signature(signature: vector<u8>, public_key: vector<u8>, message: vector<u8>): bool;
+//! native public fun bls12381_aggregate_verify(signature: vector<u8>, public_keys: vector<vector<u8>>, messages: vector<vector<u8>>): bool;
+//! native public fun bls12381_fast_aggregate_verify(signature: vector<u8>, public_keys: vector<vector<u8>>, message: vector<u8>): bool;
+//! native public fun bls12381_verify_proof_of_possession(public_key: vector<u8>, proof: vector<u8>): bool;
+//! ```
+
+use crate::util::make_native_from_func;
+use blst::min_pk::{AggregatePublicKey, PublicKey, Signature};
+use blst::BLST_ERROR;
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::{
+    InternalGas, InternalGasPerArg, InternalGasPerByte, NumArgs, NumBytes,
+};
+use move_vm_runtime::native_functions::{NativeContext, NativeFunction};
+use move_vm_types::{
+    loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+/// The domain separation tag of the signatures.
+pub const DST_SIGNATURE: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+/// The domain separation tag of the proofs of possession.
+pub const DST_PROOF_OF_POSSESSION: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+pub const PUBLIC_KEY_LENGTH: usize = 48;
+pub const SIGNATURE_LENGTH: usize = 96;
+
+/// Deserialize a compressed public key, with the subgroup check, the identity is rejected.
+fn public_key_from_bytes(bytes: &[u8]) -> Option<PublicKey> {
+    if bytes.len() != PUBLIC_KEY_LENGTH {
+        return None;
+    }
+    PublicKey::key_validate(bytes).ok()
+}
+
+/// Deserialize a compressed signature, with the subgroup check, the identity is rejected.
+fn signature_from_bytes(bytes: &[u8]) -> Option<Signature> {
+    if bytes.len() != SIGNATURE_LENGTH {
+        return None;
+    }
+    Signature::sig_validate(bytes, true).ok()
+}
+
+pub fn validate_pubkey(public_key: &[u8]) -> bool {
+    public_key_from_bytes(public_key).is_some()
+}
+
+pub fn verify_signature(signature: &[u8], public_key: &[u8], message: &[u8]) -> bool {
+    match (
+        signature_from_bytes(signature),
+        public_key_from_bytes(public_key),
+    ) {
+        (Some(signature), Some(public_key)) => {
+            signature.verify(false, message, DST_SIGNATURE, &[], &public_key, false)
+                == BLST_ERROR::BLST_SUCCESS
+        }
+        _ => false,
+    }
+}
+
+/// Verify the aggregated signature of the messages, every message is signed by the public key of
+/// the same index.
+pub fn aggregate_verify(signature: &[u8], public_keys: &[Vec<u8>], messages: &[Vec<u8>]) -> bool {
+    if public_keys.is_empty() || public_keys.len() != messages.len() {
+        return false;
+    }
+    let signature = match signature_from_bytes(signature) {
+        Some(signature) => signature,
+        None => return false,
+    };
+    let public_keys = match public_keys
+        .iter()
+        .map(|public_key| public_key_from_bytes(public_key))
+        .collect::<Option<Vec<_>>>()
+    {
+        Some(public_keys) => public_keys,
+        None => return false,
+    };
+    let public_keys = public_keys.iter().collect::<Vec<_>>();
+    let messages = messages.iter().map(Vec::as_slice).collect::<Vec<_>>();
+    signature.aggregate_verify(false, &messages, DST_SIGNATURE, &public_keys, false)
+        == BLST_ERROR::BLST_SUCCESS
+}
+
+/// Verify the aggregated signature of the same message signed by all of the public keys.
+/// It is only secure if the proofs of possession of the public keys have been verified.
+pub fn fast_aggregate_verify(signature: &[u8], public_keys: &[Vec<u8>], message: &[u8]) -> bool {
+    if public_keys.is_empty() {
+        return false;
+    }
+    let signature = match signature_from_bytes(signature) {
+        Some(signature) => signature,
+        None => return false,
+    };
+    let public_keys = match public_keys
+        .iter()
+        .map(|public_key| public_key_from_bytes(public_key))
+        .collect::<Option<Vec<_>>>()
+    {
+        Some(public_keys) => public_keys,
+        None => return false,
+    };
+    let public_keys = public_keys.iter().collect::<Vec<_>>();
+    let public_key = match AggregatePublicKey::aggregate(&public_keys, false) {
+        Ok(public_key) => public_key.to_public_key(),
+        Err(_) => return false,
+    };
+    signature.verify(false, message, DST_SIGNATURE, &[], &public_key, false)
+        == BLST_ERROR::BLST_SUCCESS
+}
+
+/// The proof of possession is the signature of the public key itself, with a different
+/// domain separation tag.
+pub fn verify_proof_of_possession(public_key: &[u8], proof: &[u8]) -> bool {
+    match (
+        public_key_from_bytes(public_key),
+        signature_from_bytes(proof),
+    ) {
+        (Some(pk), Some(proof)) => {
+            proof.verify(false, public_key, DST_PROOF_OF_POSSESSION, &[], &pk, false)
+                == BLST_ERROR::BLST_SUCCESS
+        }
+        _ => false,
+    }
+}
+
+fn pop_vector_of_bytes(arguments: &mut VecDeque<Value>) -> PartialVMResult<Vec<Vec<u8>>> {
+    pop_arg!(arguments, Vec<Value>)
+        .into_iter()
+        .map(|value| value.value_as::<Vec<u8>>())
+        .collect()
+}
+
+/***************************************************************************************************
+ * native fun bls12381_validate_pubkey
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatePubkeyGasParameters {
+    pub base: InternalGas,
+}
+
+fn native_validate_pubkey(
+    gas_params: &ValidatePubkeyGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let public_key = pop_arg!(arguments, Vec<u8>);
+
+    let cost = gas_params.base;
+
+    let valid = validate_pubkey(&public_key);
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(valid)]))
+}
+
+/***************************************************************************************************
+ * native fun bls12381_verify_signature
+ *
+ *   gas cost: base_cost + per_byte * message_length
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifySignatureGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_verify_signature(
+    gas_params: &VerifySignatureGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let message = pop_arg!(arguments, Vec<u8>);
+    let public_key = pop_arg!(arguments, Vec<u8>);
+    let signature = pop_arg!(arguments, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(message.len() as u64);
+
+    let valid = verify_signature(&signature, &public_key, &message);
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(valid)]))
+}
+
+/***************************************************************************************************
+ * native fun bls12381_aggregate_verify
+ *
+ *   gas cost: base_cost + per_pubkey * num_pubkeys + per_byte * total_message_length
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregateVerifyGasParameters {
+    pub base: InternalGas,
+    pub per_pubkey: InternalGasPerArg,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_aggregate_verify(
+    gas_params: &AggregateVerifyGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let messages = pop_vector_of_bytes(&mut arguments)?;
+    let public_keys = pop_vector_of_bytes(&mut arguments)?;
+    let signature = pop_arg!(arguments, Vec<u8>);
+
+    let message_length = messages.iter().map(|message| message.len() as u64).sum();
+    let cost = gas_params.base
+        + gas_params.per_pubkey * NumArgs::new(public_keys.len() as u64)
+        + gas_params.per_byte * NumBytes::new(message_length);
+
+    let valid = aggregate_verify(&signature, &public_keys, &messages);
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(valid)]))
+}
+
+/***************************************************************************************************
+ * native fun bls12381_fast_aggregate_verify
+ *
+ *   gas cost: base_cost + per_pubkey * num_pubkeys + per_byte * message_length
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastAggregateVerifyGasParameters {
+    pub base: InternalGas,
+    pub per_pubkey: InternalGasPerArg,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_fast_aggregate_verify(
+    gas_params: &FastAggregateVerifyGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let message = pop_arg!(arguments, Vec<u8>);
+    let public_keys = pop_vector_of_bytes(&mut arguments)?;
+    let signature = pop_arg!(arguments, Vec<u8>);
+
+    let cost = gas_params.base
+        + gas_params.per_pubkey * NumArgs::new(public_keys.len() as u64)
+        + gas_params.per_byte * NumBytes::new(message.len() as u64);
+
+    let valid = fast_aggregate_verify(&signature, &public_keys, &message);
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(valid)]))
+}
+
+/***************************************************************************************************
+ * native fun bls12381_verify_proof_of_possession
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyProofOfPossessionGasParameters {
+    pub base: InternalGas,
+}
+
+fn native_verify_proof_of_possession(
+    gas_params: &VerifyProofOfPossessionGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let proof = pop_arg!(arguments, Vec<u8>);
+    let public_key = pop_arg!(arguments, Vec<u8>);
+
+    let cost = gas_params.base;
+
+    let valid = verify_proof_of_possession(&public_key, &proof);
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(valid)]))
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasParameters {
+    pub validate_pubkey: ValidatePubkeyGasParameters,
+    pub verify_signature: VerifySignatureGasParameters,
+    pub aggregate_verify: AggregateVerifyGasParameters,
+    pub fast_aggregate_verify: FastAggregateVerifyGasParameters,
+    pub verify_proof_of_possession: VerifyProofOfPossessionGasParameters,
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [
+        (
+            "bls12381_validate_pubkey",
+            make_native_from_func(gas_params.validate_pubkey, native_validate_pubkey),
+        ),
+        (
+            "bls12381_verify_signature",
+            make_native_from_func(gas_params.verify_signature, native_verify_signature),
+        ),
+        (
+            "bls12381_aggregate_verify",
+            make_native_from_func(gas_params.aggregate_verify, native_aggregate_verify),
+        ),
+        (
+            "bls12381_fast_aggregate_verify",
+            make_native_from_func(
+                gas_params.fast_aggregate_verify,
+                native_fast_aggregate_verify,
+            ),
+        ),
+        (
+            "bls12381_verify_proof_of_possession",
+            make_native_from_func(
+                gas_params.verify_proof_of_possession,
+                native_verify_proof_of_possession,
+            ),
+        ),
+    ];
+
+    crate::helpers::make_module_natives(natives)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use blst::min_pk::{AggregateSignature, SecretKey};
+    use rand::RngCore;
+
+    fn key_pair() -> (SecretKey, Vec<u8>) {
+        let mut ikm = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut ikm);
+        let secret_key = SecretKey::key_gen(&ikm, &[]).unwrap();
+        let public_key = secret_key.sk_to_pk().compress().to_vec();
+        (secret_key, public_key)
+    }
+
+    fn sign(secret_key: &SecretKey, message: &[u8]) -> Signature {
+        secret_key.sign(message, DST_SIGNATURE, &[])
+    }
+
+    fn aggregate(signatures: &[Signature]) -> Vec<u8> {
+        let signatures = signatures.iter().collect::<Vec<_>>();
+        AggregateSignature::aggregate(&signatures, false)
+            .unwrap()
+            .to_signature()
+            .compress()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_validate_pubkey() {
+        let (_, public_key) = key_pair();
+        assert!(validate_pubkey(&public_key));
+        assert!(!validate_pubkey(&public_key[1..]));
+        assert!(!validate_pubkey(&[0u8; PUBLIC_KEY_LENGTH]));
+        // the compressed identity point.
+        let mut identity = [0u8; PUBLIC_KEY_LENGTH];
+        identity[0] = 0xc0;
+        assert!(!validate_pubkey(&identity));
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let (secret_key, public_key) = key_pair();
+        let (_, other_public_key) = key_pair();
+        let signature = sign(&secret_key, b"hello").compress().to_vec();
+        assert!(verify_signature(&signature, &public_key, b"hello"));
+        assert!(!verify_signature(&signature, &public_key, b"world"));
+        assert!(!verify_signature(&signature, &other_public_key, b"hello"));
+        assert!(!verify_signature(&signature[1..], &public_key, b"hello"));
+    }
+
+    #[test]
+    fn test_aggregate_verify() {
+        let key_pairs = (0..3).map(|_| key_pair()).collect::<Vec<_>>();
+        let public_keys = key_pairs
+            .iter()
+            .map(|(_, public_key)| public_key.clone())
+            .collect::<Vec<_>>();
+        let messages = (0..3u8).map(|i| vec![i; 8]).collect::<Vec<_>>();
+        let signatures = key_pairs
+            .iter()
+            .zip(messages.iter())
+            .map(|((secret_key, _), message)| sign(secret_key, message))
+            .collect::<Vec<_>>();
+        let signature = aggregate(&signatures);
+        assert!(aggregate_verify(&signature, &public_keys, &messages));
+        assert!(!aggregate_verify(
+            &signature,
+            &public_keys[1..],
+            &messages[1..]
+        ));
+        let mut swapped = messages.clone();
+        swapped.swap(0, 1);
+        assert!(!aggregate_verify(&signature, &public_keys, &swapped));
+        assert!(!aggregate_verify(&signature, &[], &[]));
+    }
+
+    #[test]
+    fn test_fast_aggregate_verify() {
+        let key_pairs = (0..3).map(|_| key_pair()).collect::<Vec<_>>();
+        let public_keys = key_pairs
+            .iter()
+            .map(|(_, public_key)| public_key.clone())
+            .collect::<Vec<_>>();
+        let signatures = key_pairs
+            .iter()
+            .map(|(secret_key, _)| sign(secret_key, b"hello"))
+            .collect::<Vec<_>>();
+        let signature = aggregate(&signatures);
+        assert!(fast_aggregate_verify(&signature, &public_keys, b"hello"));
+        assert!(!fast_aggregate_verify(&signature, &public_keys, b"world"));
+        assert!(!fast_aggregate_verify(
+            &signature,
+            &public_keys[1..],
+            b"hello"
+        ));
+        assert!(!fast_aggregate_verify(&signature, &[], b"hello"));
+    }
+
+    #[test]
+    fn test_verify_proof_of_possession() {
+        let (secret_key, public_key) = key_pair();
+        let (_, other_public_key) = key_pair();
+        let proof = secret_key
+            .sign(&public_key, DST_PROOF_OF_POSSESSION, &[])
+            .compress()
+            .to_vec();
+        assert!(verify_proof_of_possession(&public_key, &proof));
+        assert!(!verify_proof_of_possession(&other_public_key, &proof));
+        // a signature of the public key with the signature tag is not a proof of possession.
+        let signature = sign(&secret_key, &public_key).compress().to_vec();
+        assert!(!verify_proof_of_possession(&public_key, &signature));
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod account;
+pub mod bls12381;
 pub mod hash;
 pub mod signature;
 pub mod token;
@@ -23,6 +24,7 @@ pub struct GasParameters {
     pub u256: u256::GasParameters,
     pub secp256k1: secp256k1::GasParameters,
     pub from_bcs: from_bcs::GasParameters,
+    pub bls12381: bls12381::GasParameters,
 }
 
 impl GasParameters {
@@ -79,6 +81,26 @@ impl GasParameters {
                 base: 0.into(),
                 per_byte: 0.into(),
             },
+            bls12381: bls12381::GasParameters {
+                validate_pubkey: bls12381::ValidatePubkeyGasParameters { base: 0.into() },
+                verify_signature: bls12381::VerifySignatureGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                aggregate_verify: bls12381::AggregateVerifyGasParameters {
+                    base: 0.into(),
+                    per_pubkey: 0.into(),
+                    per_byte: 0.into(),
+                },
+                fast_aggregate_verify: bls12381::FastAggregateVerifyGasParameters {
+                    base: 0.into(),
+                    per_pubkey: 0.into(),
+                    per_byte: 0.into(),
+                },
+                verify_proof_of_possession: bls12381::VerifyProofOfPossessionGasParameters {
+                    base: 0.into(),
+                },
+            },
         }
     }
 }
@@ -0,0 +1,556 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A call tracer driven by the gas meter hooks.
+//!
+//! It records the Move call tree of a transaction execution, with the arguments, return values,
+//! emitted events, abort status and the gas charged by every frame.
+//!
+//! The gas meter is not aware of the values returned by a Move function, so the tracer follows
+//! the operand stack of every frame by the values passed to the hooks, the stack left by `Ret` is
+//! the return values. A value computed by an instruction which does not pass it to the gas meter,
+//! such as an arithmetic operation, is rendered as `_`.
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::gas_algebra::InternalGas;
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use move_core_types::u256::U256;
+use move_vm_types::gas::SimpleInstruction;
+use move_vm_types::views::{TypeView, ValueView, ValueVisitor};
+use std::iter::Peekable;
+
+const EVENT_MODULE_NAME: &str = "Event";
+const WRITE_TO_EVENT_STORE: &str = "write_to_event_store";
+/// The rendering of a value not known by the tracer.
+pub(crate) const UNKNOWN_VALUE: &str = "_";
+
+/// A frame of the call tree.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CallFrame {
+    /// The module of the function, `None` for a script.
+    pub module: Option<ModuleId>,
+    pub function: String,
+    pub ty_args: Vec<TypeTag>,
+    /// The arguments of the function. The arguments of the transaction entry are the hex of the
+    /// BCS encoded bytes, others are rendered from the Move values.
+    pub args: Vec<String>,
+    /// The return values, empty if the frame does not return.
+    pub return_values: Vec<String>,
+    pub is_native: bool,
+    /// The gas used by the frame, including the gas used by the calls in it.
+    pub gas_used: u64,
+    /// The events emitted by the function.
+    pub events: Vec<TracedEvent>,
+    /// The error that stops the execution of the frame, such as an abort.
+    pub error: Option<String>,
+    pub calls: Vec<CallFrame>,
+}
+
+impl CallFrame {
+    fn map_gas(mut self, f: &impl Fn(InternalGas) -> u64) -> Self {
+        self.gas_used = f(InternalGas::new(self.gas_used));
+        self.calls = self.calls.into_iter().map(|call| call.map_gas(f)).collect();
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TracedEvent {
+    pub type_tag: TypeTag,
+    pub data: String,
+}
+
+/// Build the call tree from the gas meter hooks, the `gas_used` of the frames is in internal gas
+/// units until the trace is taken.
+#[derive(Debug, Default)]
+pub(crate) struct CallTracer {
+    active: bool,
+    /// The top frame is just entered and no gas charged in it yet,
+    /// a native function execution follows the call immediately.
+    just_called: bool,
+    stack: Vec<CallFrame>,
+    /// The operand stack of every frame in `stack`, with the rendered values.
+    operands: Vec<Vec<String>>,
+    roots: Vec<CallFrame>,
+}
+
+impl CallTracer {
+    pub fn enter_entry(
+        &mut self,
+        module: Option<&ModuleId>,
+        function: &str,
+        ty_args: &[TypeTag],
+        args: &[Vec<u8>],
+    ) {
+        self.active = true;
+        self.push(CallFrame {
+            module: module.cloned(),
+            function: function.to_string(),
+            ty_args: ty_args.to_vec(),
+            args: args
+                .iter()
+                .map(|arg| format!("0x{}", hex_encode(arg)))
+                .collect(),
+            ..Default::default()
+        });
+    }
+
+    pub fn exit_entry(&mut self, error: Option<String>) {
+        if !self.active {
+            return;
+        }
+        if let Some(error) = error {
+            match self.stack.last_mut() {
+                Some(frame) => frame.error = Some(error),
+                None => {
+                    if let Some(root) = self.roots.last_mut() {
+                        root.error = Some(error);
+                    }
+                }
+            }
+        }
+        while !self.stack.is_empty() {
+            self.pop();
+        }
+        self.active = false;
+        self.just_called = false;
+    }
+
+    pub fn record_gas(&mut self, amount: InternalGas) {
+        self.just_called = false;
+        if !self.active {
+            return;
+        }
+        if let Some(frame) = self.stack.last_mut() {
+            frame.gas_used += u64::from(amount);
+        }
+    }
+
+    pub fn enter_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: Vec<TypeTag>,
+        args: impl Iterator<Item = impl ValueView>,
+    ) {
+        if !self.active {
+            return;
+        }
+        let args: Vec<String> = args.map(|arg| render_value(&arg)).collect();
+        self.operate(args.len(), vec![]);
+        self.push(CallFrame {
+            module: Some(module_id.clone()),
+            function: func_name.to_string(),
+            ty_args,
+            args,
+            ..Default::default()
+        });
+        self.just_called = true;
+    }
+
+    pub fn enter_native(
+        &mut self,
+        ty_args: impl Iterator<Item = impl TypeView>,
+        args: impl Iterator<Item = impl ValueView>,
+    ) {
+        if !self.active {
+            return;
+        }
+        if !self.just_called || self.stack.is_empty() {
+            self.push(CallFrame {
+                function: "<native>".to_string(),
+                ty_args: ty_args.map(|ty| ty.to_type_tag()).collect(),
+                args: args.map(|arg| render_value(&arg)).collect(),
+                ..Default::default()
+            });
+        }
+        if let Some(frame) = self.stack.last_mut() {
+            frame.is_native = true;
+        }
+        self.just_called = false;
+    }
+
+    pub fn exit_native(&mut self, ret_vals: Option<impl Iterator<Item = impl ValueView>>) {
+        if !self.active {
+            return;
+        }
+        match ret_vals {
+            Some(ret_vals) => self.ret(ret_vals.map(|val| render_value(&val)).collect()),
+            // The native function aborts, keep the frame open to record the error.
+            None => {
+                if let Some(frame) = self.stack.last_mut() {
+                    frame.error = Some("ABORTED".to_string());
+                }
+            }
+        }
+    }
+
+    pub fn exit_call(&mut self) {
+        if !self.active {
+            return;
+        }
+        // the verifier ensures only the return values are left on the stack of the frame.
+        let return_values = self
+            .operands
+            .last_mut()
+            .map(std::mem::take)
+            .unwrap_or_default();
+        self.ret(return_values);
+    }
+
+    /// Follow the operand stack changed by a simple instruction.
+    pub fn simple_instr(&mut self, instr: SimpleInstruction) {
+        if !self.active {
+            return;
+        }
+        match instr {
+            SimpleInstruction::Ret => self.exit_call(),
+            SimpleInstruction::Abort => {
+                self.operate(1, vec![]);
+                self.abort();
+            }
+            SimpleInstruction::Nop | SimpleInstruction::Branch => {}
+            SimpleInstruction::BrTrue | SimpleInstruction::BrFalse => self.operate(1, vec![]),
+            SimpleInstruction::LdU8
+            | SimpleInstruction::LdU16
+            | SimpleInstruction::LdU32
+            | SimpleInstruction::LdU64
+            | SimpleInstruction::LdU128
+            | SimpleInstruction::LdU256
+            | SimpleInstruction::ImmBorrowLoc
+            | SimpleInstruction::MutBorrowLoc => self.operate(0, vec![UNKNOWN_VALUE.to_string()]),
+            SimpleInstruction::LdTrue => self.operate(0, vec![true.to_string()]),
+            SimpleInstruction::LdFalse => self.operate(0, vec![false.to_string()]),
+            SimpleInstruction::ImmBorrowField
+            | SimpleInstruction::MutBorrowField
+            | SimpleInstruction::ImmBorrowFieldGeneric
+            | SimpleInstruction::MutBorrowFieldGeneric
+            | SimpleInstruction::CastU8
+            | SimpleInstruction::CastU16
+            | SimpleInstruction::CastU32
+            | SimpleInstruction::CastU64
+            | SimpleInstruction::CastU128
+            | SimpleInstruction::CastU256
+            | SimpleInstruction::Not => self.operate(1, vec![UNKNOWN_VALUE.to_string()]),
+            // the value of the reference is not changed.
+            SimpleInstruction::FreezeRef => {}
+            SimpleInstruction::Add
+            | SimpleInstruction::Sub
+            | SimpleInstruction::Mul
+            | SimpleInstruction::Mod
+            | SimpleInstruction::Div
+            | SimpleInstruction::BitOr
+            | SimpleInstruction::BitAnd
+            | SimpleInstruction::Xor
+            | SimpleInstruction::Shl
+            | SimpleInstruction::Shr
+            | SimpleInstruction::Or
+            | SimpleInstruction::And
+            | SimpleInstruction::Lt
+            | SimpleInstruction::Gt
+            | SimpleInstruction::Le
+            | SimpleInstruction::Ge => self.operate(2, vec![UNKNOWN_VALUE.to_string()]),
+        }
+    }
+
+    /// Pop `pops` values from the operand stack of the current frame, then push the `pushes`.
+    pub fn operate(&mut self, pops: usize, pushes: Vec<String>) {
+        if !self.active {
+            return;
+        }
+        if let Some(operands) = self.operands.last_mut() {
+            operands.truncate(operands.len().saturating_sub(pops));
+            operands.extend(pushes);
+        }
+    }
+
+    pub fn abort(&mut self) {
+        if !self.active {
+            return;
+        }
+        if let Some(frame) = self.stack.last_mut() {
+            frame.error = Some("ABORTED".to_string());
+        }
+    }
+
+    pub fn finish(mut self, to_gas: impl Fn(InternalGas) -> u64) -> Vec<CallFrame> {
+        while !self.stack.is_empty() {
+            self.pop();
+        }
+        self.roots
+            .into_iter()
+            .map(|root| root.map_gas(&to_gas))
+            .collect()
+    }
+
+    fn push(&mut self, frame: CallFrame) {
+        self.stack.push(frame);
+        self.operands.push(vec![]);
+    }
+
+    /// Return from the current frame, the return values are pushed to the caller's stack.
+    fn ret(&mut self, return_values: Vec<String>) {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.return_values = return_values.clone();
+        }
+        self.pop();
+        self.operate(0, return_values);
+    }
+
+    fn pop(&mut self) {
+        let frame = match self.stack.pop() {
+            Some(frame) => frame,
+            None => return,
+        };
+        self.operands.pop();
+        match self.stack.last_mut() {
+            Some(parent) => {
+                parent.gas_used += frame.gas_used;
+                if frame.is_native && is_write_to_event_store(&frame) {
+                    if let (Some(type_tag), Some(data)) = (frame.ty_args.first(), frame.args.get(2))
+                    {
+                        parent.events.push(TracedEvent {
+                            type_tag: type_tag.clone(),
+                            data: data.clone(),
+                        });
+                    }
+                }
+                parent.calls.push(frame);
+            }
+            None => self.roots.push(frame),
+        }
+    }
+}
+
+fn is_write_to_event_store(frame: &CallFrame) -> bool {
+    frame.function == WRITE_TO_EVENT_STORE
+        && frame
+            .module
+            .as_ref()
+            .map(|module| {
+                module
+                    .name()
+                    .as_str()
+                    .eq_ignore_ascii_case(EVENT_MODULE_NAME)
+            })
+            .unwrap_or(false)
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[derive(Debug)]
+enum Token {
+    U8(u8),
+    Value(String),
+    Struct(usize),
+    Vector(usize),
+    Ref(bool),
+}
+
+/// Collect the tokens of a value in pre-order, then render them recursively.
+#[derive(Debug, Default)]
+struct ValueRenderer {
+    tokens: Vec<Token>,
+}
+
+impl ValueVisitor for ValueRenderer {
+    fn visit_u8(&mut self, _depth: usize, val: u8) {
+        self.tokens.push(Token::U8(val));
+    }
+
+    fn visit_u16(&mut self, _depth: usize, val: u16) {
+        self.tokens.push(Token::Value(val.to_string()));
+    }
+
+    fn visit_u32(&mut self, _depth: usize, val: u32) {
+        self.tokens.push(Token::Value(val.to_string()));
+    }
+
+    fn visit_u64(&mut self, _depth: usize, val: u64) {
+        self.tokens.push(Token::Value(val.to_string()));
+    }
+
+    fn visit_u128(&mut self, _depth: usize, val: u128) {
+        self.tokens.push(Token::Value(val.to_string()));
+    }
+
+    fn visit_u256(&mut self, _depth: usize, val: U256) {
+        self.tokens.push(Token::Value(val.to_string()));
+    }
+
+    fn visit_bool(&mut self, _depth: usize, val: bool) {
+        self.tokens.push(Token::Value(val.to_string()));
+    }
+
+    fn visit_address(&mut self, _depth: usize, val: AccountAddress) {
+        self.tokens.push(Token::Value(val.to_hex_literal()));
+    }
+
+    fn visit_struct(&mut self, _depth: usize, len: usize) -> bool {
+        self.tokens.push(Token::Struct(len));
+        true
+    }
+
+    fn visit_vec(&mut self, _depth: usize, len: usize) -> bool {
+        self.tokens.push(Token::Vector(len));
+        true
+    }
+
+    fn visit_ref(&mut self, _depth: usize, is_global: bool) -> bool {
+        self.tokens.push(Token::Ref(is_global));
+        true
+    }
+}
+
+/// Render a Move value, the `vector<u8>` is rendered as hex.
+pub(crate) fn render_value(value: &impl ValueView) -> String {
+    let mut renderer = ValueRenderer::default();
+    value.visit(&mut renderer);
+    render_tokens(&mut renderer.tokens.into_iter().peekable())
+}
+
+/// Render the struct packed from the `fields`.
+pub(crate) fn render_struct(fields: &[impl ValueView]) -> String {
+    render_values(Token::Struct(fields.len()), fields)
+}
+
+/// Render the vector packed from the `elements`.
+pub(crate) fn render_vector(elements: &[impl ValueView]) -> String {
+    render_values(Token::Vector(elements.len()), elements)
+}
+
+fn render_values(head: Token, values: &[impl ValueView]) -> String {
+    let mut renderer = ValueRenderer { tokens: vec![head] };
+    for value in values {
+        value.visit(&mut renderer);
+    }
+    render_tokens(&mut renderer.tokens.into_iter().peekable())
+}
+
+fn render_tokens(tokens: &mut Peekable<impl Iterator<Item = Token>>) -> String {
+    match tokens.next() {
+        None => String::new(),
+        Some(Token::U8(val)) => val.to_string(),
+        Some(Token::Value(val)) => val,
+        Some(Token::Struct(len)) => {
+            let fields: Vec<String> = (0..len).map(|_| render_tokens(tokens)).collect();
+            format!("{{{}}}", fields.join(", "))
+        }
+        Some(Token::Vector(len)) => {
+            if len > 0 && matches!(tokens.peek(), Some(Token::U8(_))) {
+                let bytes: Vec<u8> = (0..len)
+                    .filter_map(|_| match tokens.next() {
+                        Some(Token::U8(val)) => Some(val),
+                        _ => None,
+                    })
+                    .collect();
+                format!("0x{}", hex_encode(&bytes))
+            } else {
+                let elements: Vec<String> = (0..len).map(|_| render_tokens(tokens)).collect();
+                format!("[{}]", elements.join(", "))
+            }
+        }
+        Some(Token::Ref(is_global)) => {
+            let value = render_tokens(tokens);
+            if is_global {
+                format!("&global {}", value)
+            } else {
+                format!("&{}", value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_vm_types::values::Value;
+
+    #[test]
+    fn test_render_tokens() {
+        let tokens = vec![
+            Token::Struct(3),
+            Token::Value("0x1".to_string()),
+            Token::Vector(2),
+            Token::U8(0xab),
+            Token::U8(0x01),
+            Token::Ref(false),
+            Token::Vector(2),
+            Token::Value("1".to_string()),
+            Token::Value("2".to_string()),
+        ];
+        assert_eq!(
+            render_tokens(&mut tokens.into_iter().peekable()),
+            "{0x1, 0xab01, &[1, 2]}"
+        );
+    }
+
+    #[test]
+    fn test_call_tree() {
+        let mut tracer = CallTracer::default();
+        tracer.enter_entry(None, "main", &[], &[vec![1u8, 2u8]]);
+        tracer.record_gas(InternalGas::new(10));
+        tracer.push(CallFrame {
+            module: Some(ModuleId::new(
+                AccountAddress::ONE,
+                move_core_types::identifier::Identifier::new("Event").unwrap(),
+            )),
+            function: WRITE_TO_EVENT_STORE.to_string(),
+            ty_args: vec![TypeTag::U64],
+            args: vec!["0x00".to_string(), "0".to_string(), "100".to_string()],
+            is_native: true,
+            ..Default::default()
+        });
+        tracer.record_gas(InternalGas::new(5));
+        tracer.exit_call();
+        tracer.abort();
+        tracer.exit_entry(Some("ABORTED with code 1".to_string()));
+
+        let roots = tracer.finish(u64::from);
+        assert_eq!(roots.len(), 1);
+        let root = &roots[0];
+        assert_eq!(root.args, vec!["0x0102".to_string()]);
+        assert_eq!(root.gas_used, 15);
+        assert_eq!(root.calls.len(), 1);
+        assert_eq!(root.calls[0].gas_used, 5);
+        assert_eq!(root.events.len(), 1);
+        assert_eq!(root.events[0].data, "100");
+        assert_eq!(root.error.as_deref(), Some("ABORTED with code 1"));
+    }
+
+    #[test]
+    fn test_return_values() {
+        let module = ModuleId::new(
+            AccountAddress::ONE,
+            move_core_types::identifier::Identifier::new("M").unwrap(),
+        );
+        let mut tracer = CallTracer::default();
+        tracer.enter_entry(None, "main", &[], &[]);
+        tracer.operate(0, vec!["7".to_string()]);
+        tracer.enter_call(&module, "f", vec![], vec![Value::u64(7)].into_iter());
+        // f: move_loc, ld_u64, lt, br_false, move_loc, ld_true, ret
+        tracer.operate(0, vec!["7".to_string()]);
+        tracer.simple_instr(SimpleInstruction::LdU64);
+        tracer.simple_instr(SimpleInstruction::Lt);
+        tracer.simple_instr(SimpleInstruction::BrFalse);
+        tracer.operate(0, vec!["7".to_string()]);
+        tracer.simple_instr(SimpleInstruction::LdTrue);
+        tracer.simple_instr(SimpleInstruction::Ret);
+        // main: pop, st_loc, ret
+        tracer.operate(1, vec![]);
+        tracer.operate(1, vec![]);
+        tracer.simple_instr(SimpleInstruction::Ret);
+        tracer.exit_entry(None);
+
+        let roots = tracer.finish(u64::from);
+        let root = &roots[0];
+        assert!(root.return_values.is_empty());
+        assert_eq!(root.calls[0].args, vec!["7".to_string()]);
+        assert_eq!(
+            root.calls[0].return_values,
+            vec!["7".to_string(), "true".to_string()]
+        );
+    }
+}
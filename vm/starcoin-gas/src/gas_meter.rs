@@ -9,7 +9,7 @@ use move_core_types::gas_algebra::{
     AbstractMemorySize, InternalGasPerAbstractMemoryUnit, InternalGasPerArg, InternalGasPerByte,
     NumArgs,
 };
-use move_core_types::language_storage::ModuleId;
+use move_core_types::language_storage::{ModuleId, TypeTag};
 use move_core_types::{
     gas_algebra::{InternalGas, NumBytes},
     vm_status::StatusCode,
@@ -23,6 +23,10 @@ use starcoin_gas_algebra_ext::{
 use starcoin_logger::prelude::*;
 use std::collections::BTreeMap;
 
+use crate::call_tracer::{
+    render_struct, render_value, render_vector, CallFrame, CallTracer, UNKNOWN_VALUE,
+};
+
 use move_binary_format::file_format_common::Opcodes;
 use starcoin_gas_algebra_ext::InstructionGasParameters;
 use starcoin_gas_algebra_ext::TransactionGasParameters;
@@ -142,6 +146,7 @@ pub struct StarcoinGasMeter {
     gas_params: StarcoinGasParameters,
     balance: InternalGas,
     charge: bool,
+    tracer: Option<CallTracer>,
 }
 
 impl StarcoinGasMeter {
@@ -151,6 +156,7 @@ impl StarcoinGasMeter {
             gas_params,
             balance,
             charge: true,
+            tracer: None,
         }
     }
 
@@ -163,6 +169,9 @@ impl StarcoinGasMeter {
         if !self.charge {
             return Ok(());
         }
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.record_gas(std::cmp::min(amount, self.balance));
+        }
         match self.balance.checked_sub(amount) {
             Some(new_balance) => {
                 self.balance = new_balance;
@@ -183,6 +192,48 @@ impl StarcoinGasMeter {
         self.charge
     }
 
+    /// Record the call tree of the transaction entry executed between `trace_enter` and
+    /// `trace_exit`, the trace can be taken by `take_trace`.
+    pub fn enable_tracing(&mut self) {
+        self.tracer = Some(CallTracer::default());
+    }
+
+    /// Enter the script or the entry function of the transaction, the `module` is `None` for a script.
+    pub fn trace_enter(
+        &mut self,
+        module: Option<&ModuleId>,
+        function: &str,
+        ty_args: &[TypeTag],
+        args: &[Vec<u8>],
+    ) {
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.enter_entry(module, function, ty_args, args);
+        }
+    }
+
+    /// Exit the transaction entry, the `error` is recorded to the innermost frame not returned.
+    pub fn trace_exit(&mut self, error: Option<String>) {
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.exit_entry(error);
+        }
+    }
+
+    /// Follow the operand stack of the traced frame, the `pushes` are only rendered if tracing.
+    fn trace_operands(&mut self, pops: usize, pushes: impl FnOnce() -> Vec<String>) {
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.operate(pops, pushes());
+        }
+    }
+
+    /// Take the recorded call tree, the gas used is in gas units.
+    /// Return `None` if the tracing is not enabled.
+    pub fn take_trace(&mut self) -> Option<Vec<CallFrame>> {
+        let txn_params = &self.gas_params.txn;
+        self.tracer.take().map(|tracer| {
+            tracer.finish(|gas| u64::from(gas.to_unit_round_down_with_params(txn_params)))
+        })
+    }
+
     pub fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()> {
         let cost = self.gas_params.txn.calculate_intrinsic_gas(txn_size);
         #[cfg(testing)]
@@ -297,7 +348,11 @@ impl GasMeter for StarcoinGasMeter {
             cost,
             self.charge
         );
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.simple_instr(instr);
+        }
+        Ok(())
     }
 
     fn charge_pop(&mut self, _popped_val: impl ValueView) -> PartialVMResult<()> {
@@ -308,14 +363,16 @@ impl GasMeter for StarcoinGasMeter {
             "simple_instr pop cost InternalGasUnits({}) {}",
             cost, self.charge
         );
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(1, Vec::new);
+        Ok(())
     }
 
     #[inline]
     fn charge_call(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         args: impl ExactSizeIterator<Item = impl ValueView>,
         _num_locals: NumArgs,
     ) -> PartialVMResult<()> {
@@ -327,14 +384,18 @@ impl GasMeter for StarcoinGasMeter {
         let cost2 = cal_instr_with_arg(params.call_per_arg, NumArgs::new(args.len() as u64));
         #[cfg(testing)]
         info!("CALL cost InternalGasUnits({}) {}", cost2, self.charge);
-        self.deduct_gas(cost1 + cost2)
+        self.deduct_gas(cost1 + cost2)?;
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.enter_call(module_id, func_name, vec![], args);
+        }
+        Ok(())
     }
 
     #[inline]
     fn charge_call_generic(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         ty_args: impl ExactSizeIterator<Item = impl TypeView>,
         args: impl ExactSizeIterator<Item = impl ValueView>,
         _num_locals: NumArgs,
@@ -357,7 +418,12 @@ impl GasMeter for StarcoinGasMeter {
             "CALL_GENERIC cost InternalGasUnits({}) {}",
             cost2, self.charge
         );
-        self.deduct_gas(cost1 + cost2)
+        self.deduct_gas(cost1 + cost2)?;
+        if let Some(tracer) = self.tracer.as_mut() {
+            let ty_args = ty_args.map(|ty| ty.to_type_tag()).collect();
+            tracer.enter_call(module_id, func_name, ty_args, args);
+        }
+        Ok(())
     }
 
     #[inline]
@@ -366,13 +432,17 @@ impl GasMeter for StarcoinGasMeter {
         let cost = cal_instr_with_byte(instr.ld_const_per_byte, size);
         #[cfg(testing)]
         info!("LD_CONST cost InternalGasUnits({}) {}", cost, self.charge);
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(0, || vec![UNKNOWN_VALUE.to_string()]);
+        Ok(())
     }
 
     fn charge_ld_const_after_deserialization(
         &mut self,
-        _val: impl ValueView,
+        val: impl ValueView,
     ) -> PartialVMResult<()> {
+        // replace the constant pushed by `charge_ld_const` with its value.
+        self.trace_operands(1, || vec![render_value(&val)]);
         Ok(())
     }
 
@@ -385,7 +455,9 @@ impl GasMeter for StarcoinGasMeter {
         );
         #[cfg(testing)]
         info!("COPY_LOC cost InternalGasUnits({}) {}", cost, self.charge);
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(0, || vec![render_value(&val)]);
+        Ok(())
     }
 
     #[inline]
@@ -397,7 +469,9 @@ impl GasMeter for StarcoinGasMeter {
         );
         #[cfg(testing)]
         info!("MOVE_LOC cost InternalGasUnits({}) {}", cost, self.charge);
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(0, || vec![render_value(&val)]);
+        Ok(())
     }
 
     #[inline]
@@ -409,7 +483,9 @@ impl GasMeter for StarcoinGasMeter {
         );
         #[cfg(testing)]
         info!("ST_LOC cost InternalGasUnits({}) {}", cost, self.charge);
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(1, Vec::new);
+        Ok(())
     }
 
     #[inline]
@@ -418,9 +494,10 @@ impl GasMeter for StarcoinGasMeter {
         is_generic: bool,
         args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
+        let args = args.collect::<Vec<_>>();
         let field_count = AbstractMemorySize::new(args.len() as u64);
         let params = &self.gas_params.instr;
-        let size = args.fold(field_count, |acc, val| {
+        let size = args.iter().fold(field_count, |acc, val| {
             acc + val.legacy_abstract_memory_size()
         });
         let cost = match is_generic {
@@ -438,7 +515,9 @@ impl GasMeter for StarcoinGasMeter {
                 info!("PACK cost InternalGasUnits({}) {}", cost, self.charge);
             }
         }
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(args.len(), || vec![render_struct(&args)]);
+        Ok(())
     }
 
     #[inline]
@@ -461,6 +540,7 @@ impl GasMeter for StarcoinGasMeter {
         } else {
             params.unpack_per_abs_mem_unit
         };
+        let args = args.collect::<Vec<_>>();
         let field_count = AbstractMemorySize::new(args.len() as u64);
         let mut cost = cal_instr_with_size(param, field_count);
         #[cfg(testing)]
@@ -468,7 +548,7 @@ impl GasMeter for StarcoinGasMeter {
             "{:#?} cost InternalGasUnits({}) {}",
             opcode, cost, self.charge
         );
-        for val in args {
+        for val in &args {
             let cost2 = cal_instr_with_size(param, val.legacy_abstract_memory_size());
             #[cfg(testing)]
             info!(
@@ -477,7 +557,9 @@ impl GasMeter for StarcoinGasMeter {
             );
             cost += cost2;
         }
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(1, || args.iter().map(render_value).collect());
+        Ok(())
     }
 
     #[inline]
@@ -488,7 +570,9 @@ impl GasMeter for StarcoinGasMeter {
         );
         #[cfg(testing)]
         info!("READ_REF cost InternalGasUnits({}) {}", cost, self.charge);
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(1, || vec![render_value(&val)]);
+        Ok(())
     }
 
     #[inline]
@@ -503,7 +587,9 @@ impl GasMeter for StarcoinGasMeter {
         );
         #[cfg(testing)]
         info!("WRITE_REF cost InternalGasUnits({}) {}", cost, self.charge);
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(2, Vec::new);
+        Ok(())
     }
 
     #[inline]
@@ -515,7 +601,11 @@ impl GasMeter for StarcoinGasMeter {
         );
         #[cfg(testing)]
         info!("EQ cost InternalGasUnits({}) {}", cost, self.charge);
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(2, || {
+            vec![(render_value(&lhs) == render_value(&rhs)).to_string()]
+        });
+        Ok(())
     }
 
     #[inline]
@@ -527,7 +617,11 @@ impl GasMeter for StarcoinGasMeter {
         );
         #[cfg(testing)]
         info!("NEQ cost InternalGasUnits({}) {}", cost, self.charge);
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(2, || {
+            vec![(render_value(&lhs) != render_value(&rhs)).to_string()]
+        });
+        Ok(())
     }
 
     #[inline]
@@ -559,7 +653,9 @@ impl GasMeter for StarcoinGasMeter {
             "{:#?} cost InternalGasUnits({}) {}",
             opcode, cost, self.charge
         );
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(1, || vec![UNKNOWN_VALUE.to_string()]);
+        Ok(())
     }
 
     #[inline]
@@ -589,7 +685,9 @@ impl GasMeter for StarcoinGasMeter {
             "{:#?} cost InternalGasUnits({}) {}",
             opcode, cost, self.charge
         );
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(1, || vec![exists.to_string()]);
+        Ok(())
     }
 
     #[inline]
@@ -616,7 +714,8 @@ impl GasMeter for StarcoinGasMeter {
                 "MOVE_FROM {:#?} cost InternalGasUnits({}) {}",
                 opcode, cost, self.charge
             );
-            return self.deduct_gas(cost);
+            self.deduct_gas(cost)?;
+            self.trace_operands(1, || vec![render_value(&val)]);
         }
         Ok(())
     }
@@ -649,7 +748,9 @@ impl GasMeter for StarcoinGasMeter {
             "charge_MOVE_TO {:#?} cost InternalGasUnits({}) {}",
             opcode, cost, self.charge
         );
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(2, Vec::new);
+        Ok(())
     }
 
     #[inline]
@@ -658,12 +759,15 @@ impl GasMeter for StarcoinGasMeter {
         _ty: impl TypeView + 'a,
         args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
+        let args = args.collect::<Vec<_>>();
         let num_args = NumArgs::new(args.len() as u64);
         let params = &self.gas_params.instr;
         let cost = cal_instr_with_arg(params.vec_pack_per_elem, num_args);
         #[cfg(testing)]
         info!("VEC_PACK cost InternalGasUnits({}) {}", cost, self.charge);
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(args.len(), || vec![render_vector(&args)]);
+        Ok(())
     }
 
     #[inline]
@@ -671,7 +775,9 @@ impl GasMeter for StarcoinGasMeter {
         let cost = self.gas_params.instr.vec_len_base;
         #[cfg(testing)]
         info!("VEC_LEN cost InternalGasUnits({}) {}", cost, self.charge);
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(1, || vec![UNKNOWN_VALUE.to_string()]);
+        Ok(())
     }
 
     #[inline]
@@ -700,7 +806,9 @@ impl GasMeter for StarcoinGasMeter {
             "{:#?} cost InternalGasUnits({}) {}",
             opcode, cost, self.charge
         );
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(2, || vec![UNKNOWN_VALUE.to_string()]);
+        Ok(())
     }
 
     #[inline]
@@ -718,14 +826,16 @@ impl GasMeter for StarcoinGasMeter {
             "VEC_PUSH_BACK cost InternalGasUnits({}) {}",
             cost, self.charge
         );
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(2, Vec::new);
+        Ok(())
     }
 
     #[inline]
     fn charge_vec_pop_back(
         &mut self,
         _ty: impl TypeView,
-        _val: Option<impl ValueView>,
+        val: Option<impl ValueView>,
     ) -> PartialVMResult<()> {
         let cost = self.gas_params.instr.vec_pop_back_base;
         #[cfg(testing)]
@@ -733,7 +843,9 @@ impl GasMeter for StarcoinGasMeter {
             "VEC_POP_BACK cost InternalGasUnits({}) {}",
             cost, self.charge
         );
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(1, || val.iter().map(render_value).collect());
+        Ok(())
     }
 
     #[inline]
@@ -741,7 +853,7 @@ impl GasMeter for StarcoinGasMeter {
         &mut self,
         _ty: impl TypeView,
         expect_num_elements: NumArgs,
-        _elems: impl ExactSizeIterator<Item = impl ValueView>,
+        elems: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
         let cost = cal_instr_with_arg(
             self.gas_params.instr.vec_unpack_per_expected_elem,
@@ -749,7 +861,9 @@ impl GasMeter for StarcoinGasMeter {
         );
         #[cfg(testing)]
         info!("VEC_UNPACK cost InternalGasUnits({}) {}", cost, self.charge);
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(1, || elems.map(|elem| render_value(&elem)).collect());
+        Ok(())
     }
 
     #[inline]
@@ -757,7 +871,9 @@ impl GasMeter for StarcoinGasMeter {
         let cost = self.gas_params.instr.vec_swap_base;
         #[cfg(testing)]
         info!("VEC_SWAP cost InternalGasUnits({}) {}", cost, self.charge);
-        self.deduct_gas(cost)
+        self.deduct_gas(cost)?;
+        self.trace_operands(3, Vec::new);
+        Ok(())
     }
 
     #[inline]
@@ -772,21 +888,28 @@ impl GasMeter for StarcoinGasMeter {
     fn charge_native_function(
         &mut self,
         amount: InternalGas,
-        _ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView>>,
+        ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView>>,
     ) -> PartialVMResult<()> {
         #[cfg(testing)]
         info!(
             "NATIVE_FUNCTION cost InternalGasUnits({}) {}",
             amount, self.charge
         );
-        self.deduct_gas(amount)
+        self.deduct_gas(amount)?;
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.exit_native(ret_vals);
+        }
+        Ok(())
     }
 
     fn charge_native_function_before_execution(
         &mut self,
-        _ty_args: impl ExactSizeIterator<Item = impl TypeView>,
-        _args: impl ExactSizeIterator<Item = impl ValueView>,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.enter_native(ty_args, args);
+        }
         Ok(())
     }
 
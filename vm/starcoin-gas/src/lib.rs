@@ -18,8 +18,10 @@
 //!   - The on-chain gas schedule needs to be extensible and unordered so we can upgrate it easily
 //!     in the future.
 
+mod call_tracer;
 mod gas_meter;
 
+pub use call_tracer::{CallFrame, TracedEvent};
 pub use gas_meter::{NativeGasParameters, StarcoinGasMeter, StarcoinGasParameters};
 pub use move_core_types::gas_algebra::{
     Arg, Byte, GasQuantity, InternalGas, InternalGasPerArg, InternalGasPerByte, InternalGasUnit,
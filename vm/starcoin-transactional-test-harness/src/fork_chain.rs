@@ -32,6 +32,7 @@ use starcoin_types::block::{Block, BlockInfo, BlockNumber};
 use starcoin_types::startup_info::{ChainInfo, ChainStatus};
 use starcoin_types::transaction::{Transaction, TransactionInfo, TransactionOutput};
 use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_address::AccountAddress;
 use std::hash::Hash;
 use std::option::Option::{None, Some};
 use std::sync::{Arc, Mutex};
@@ -470,6 +471,31 @@ impl ChainApi for MockChainApi {
         Box::pin(fut.boxed().map_err(map_err))
     }
 
+    fn get_transactions_by_address(
+        &self,
+        _address: AccountAddress,
+        _start_index: Option<u64>,
+        _reverse: bool,
+        _max_size: u64,
+    ) -> starcoin_rpc_api::FutureResult<Vec<starcoin_rpc_api::types::TransactionInfoView>> {
+        let fut = async move {
+            bail!("not implemented.");
+        };
+        Box::pin(fut.boxed().map_err(map_err))
+    }
+
+    fn fee_history(
+        &self,
+        _block_count: u64,
+        _newest_block: Option<BlockNumber>,
+        _percentiles: Option<Vec<f64>>,
+    ) -> starcoin_rpc_api::FutureResult<starcoin_rpc_api::types::FeeHistoryView> {
+        let fut = async move {
+            bail!("not implemented.");
+        };
+        Box::pin(fut.boxed().map_err(map_err))
+    }
+
     fn get_transaction_proof(
         &self,
         _block_hash: HashValue,
@@ -497,6 +523,31 @@ impl ChainApi for MockChainApi {
         Box::pin(fut.boxed().map_err(map_err))
     }
 
+    fn get_accumulator_range_proof(
+        &self,
+        _block_hash: HashValue,
+        _accumulator_type: AccumulatorStoreType,
+        _first_leaf_index: u64,
+        _num_leaves: u64,
+    ) -> FutureResult<Option<starcoin_rpc_api::types::AccumulatorRangeProofView>> {
+        let fut = async move {
+            bail!("not implemented.");
+        };
+        Box::pin(fut.boxed().map_err(map_err))
+    }
+
+    fn get_accumulator_consistency_proof(
+        &self,
+        _block_hash: HashValue,
+        _accumulator_type: AccumulatorStoreType,
+        _old_num_leaves: u64,
+    ) -> FutureResult<Option<starcoin_rpc_api::types::AccumulatorConsistencyProofView>> {
+        let fut = async move {
+            bail!("not implemented.");
+        };
+        Box::pin(fut.boxed().map_err(map_err))
+    }
+
     #[doc = r" Get the state of a dag."]
     fn get_dag_state(&self) -> FutureResult<DagStateView> {
         todo!("not implement yet")
@@ -36,8 +36,8 @@ use starcoin_crypto::hash::PlainCryptoHash;
 use starcoin_crypto::HashValue;
 use starcoin_dev::playground::call_contract;
 use starcoin_rpc_api::types::{
-    ContractCall, FunctionIdView, SignedUserTransactionView, TransactionArgumentView,
-    TransactionOutputView, TransactionStatusView, TypeTagView,
+    ContractCall, ContractStateOption, FunctionIdView, SignedUserTransactionView,
+    TransactionArgumentView, TransactionOutputView, TransactionStatusView, TypeTagView,
 };
 use starcoin_rpc_api::Params;
 use starcoin_state_api::{ChainStateReader, StateReaderExt};
@@ -708,6 +708,7 @@ impl<'a> StarcoinTestAdapter<'a> {
             function_id,
             type_args,
             args,
+            ..
         } = call;
         let rets = call_contract(
             &self.context.storage,
@@ -1389,6 +1390,8 @@ impl<'a> MoveTestAdapter<'a> for StarcoinTestAdapter<'a> {
                 function_id: name,
                 args,
                 type_args,
+                state_option: ContractStateOption::default(),
+                state_overrides: None,
             }),
             StarcoinSubcommands::CallAPI { method, params } => self.handle_call_api(method, params),
             StarcoinSubcommands::Package {
@@ -0,0 +1,42 @@
+/// BLS12-381 signatures in the minimal-pubkey-size setting: the public keys are 48 bytes
+/// compressed G1 points and the signatures are 96 bytes compressed G2 points.
+/// The proof-of-possession scheme is used, the proof of a public key must be verified by
+/// `bls12381_verify_proof_of_possession` before the key is aggregated with other keys.
+module StarcoinFramework::BLS12381 {
+
+    spec module {
+        pragma verify = false;
+        pragma aborts_if_is_strict = true;
+    }
+
+    /// Return `true` if `public_key` is a valid, non-identity G1 point in the prime-order subgroup.
+    native public fun bls12381_validate_pubkey(public_key: vector<u8>): bool;
+
+    /// Return `true` if `signature` is the signature of `message` by `public_key`.
+    native public fun bls12381_verify_signature(
+        signature: vector<u8>,
+        public_key: vector<u8>,
+        message: vector<u8>
+    ): bool;
+
+    /// Return `true` if `signature` aggregates the signatures of `messages[i]` by `public_keys[i]`,
+    /// the messages must be distinct.
+    native public fun bls12381_aggregate_verify(
+        signature: vector<u8>,
+        public_keys: vector<vector<u8>>,
+        messages: vector<vector<u8>>
+    ): bool;
+
+    /// Return `true` if `signature` aggregates the signatures of the same `message` by `public_keys`.
+    native public fun bls12381_fast_aggregate_verify(
+        signature: vector<u8>,
+        public_keys: vector<vector<u8>>,
+        message: vector<u8>
+    ): bool;
+
+    /// Return `true` if `proof` is the proof of possession of the secret key of `public_key`.
+    native public fun bls12381_verify_proof_of_possession(
+        public_key: vector<u8>,
+        proof: vector<u8>
+    ): bool;
+}
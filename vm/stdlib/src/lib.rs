@@ -61,7 +61,7 @@ pub const STDLIB_DIR: Dir = starcoin_framework::SOURCES_DIR;
 // The current stdlib that is freshly built. This will never be used in deployment so we don't need
 // to pull the same trick here in order to include this in the Rust binary.
 static G_FRESH_MOVE_LANG_STDLIB: Lazy<Vec<Vec<u8>>> = Lazy::new(|| {
-    build_stdlib(STARCOIN_FRAMEWORK_SOURCES.files.as_slice())
+    build_stdlib(stdlib_files().as_slice())
         .values()
         .map(|m| {
             let mut blob = vec![];
@@ -104,6 +104,27 @@ pub const SCRIPT_HASH_LENGTH: usize = HashValue::LENGTH;
 
 pub use starcoin_framework::STARCOIN_FRAMEWORK_SOURCES;
 
+/// The stdlib modules kept in this repository on top of the starcoin-framework sources,
+/// they are released with the stdlib of the version which registers their natives.
+pub const STDLIB_EXTRA_SOURCES: &[(&str, &str)] =
+    &[("BLS12381.move", include_str!("../sources/BLS12381.move"))];
+
+// The extra sources are written out, the compiler only takes the source files by path.
+static G_STDLIB_EXTRA_FILES: Lazy<(tempfile::TempDir, Vec<String>)> = Lazy::new(|| {
+    let tempdir = tempfile::tempdir().expect("create temp dir for stdlib sources should success");
+    let files = STDLIB_EXTRA_SOURCES
+        .iter()
+        .map(|(name, source)| {
+            let path = tempdir.path().join(name);
+            fs::write(path.as_path(), source).expect("write stdlib source should success");
+            path.to_str()
+                .expect("stdlib source path must be str")
+                .to_string()
+        })
+        .collect();
+    (tempdir, files)
+});
+
 /// Return all versions of stdlib, include latest.
 pub fn stdlib_versions() -> Vec<StdlibVersion> {
     G_STDLIB_VERSIONS.clone()
@@ -161,7 +182,9 @@ pub fn module_to_package(
 }
 
 pub fn stdlib_files() -> Vec<String> {
-    STARCOIN_FRAMEWORK_SOURCES.files.clone()
+    let mut files = STARCOIN_FRAMEWORK_SOURCES.files.clone();
+    files.extend(G_STDLIB_EXTRA_FILES.1.iter().cloned());
+    files
 }
 
 pub fn build_stdlib(targets: &[String]) -> BTreeMap<String, CompiledModule> {
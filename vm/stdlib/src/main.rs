@@ -27,8 +27,8 @@ use std::path::Path;
 use std::{collections::BTreeMap, fs::File, io::Read, path::PathBuf};
 use stdlib::{
     build_stdlib, build_stdlib_error_code_map, load_compiled_modules,
-    load_latest_stable_compiled_modules, save_binary, COMPILED_EXTENSION, COMPILED_OUTPUT_PATH,
-    LATEST_COMPILED_OUTPUT_PATH, STDLIB_DIR_NAME,
+    load_latest_stable_compiled_modules, save_binary, stdlib_files, COMPILED_EXTENSION,
+    COMPILED_OUTPUT_PATH, LATEST_COMPILED_OUTPUT_PATH, STDLIB_DIR_NAME,
 };
 
 fn compiled_modules(stdlib_path: &mut PathBuf) -> BTreeMap<ModuleId, CompiledModule> {
@@ -308,7 +308,7 @@ fn main() {
     std::env::set_current_dir(base_path).expect("failed to change directory");
 
     let sources = &STARCOIN_FRAMEWORK_SOURCES;
-    let new_modules = build_stdlib(&sources.files);
+    let new_modules = build_stdlib(&stdlib_files());
 
     if !no_check_compatibility {
         if let Some((pre_stable_version, pre_stable_modules)) = pre_version
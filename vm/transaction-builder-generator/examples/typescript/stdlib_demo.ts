@@ -0,0 +1,45 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+import {
+  decode_script_function,
+  deserialize_script_function_payload,
+  encode_peer_to_peer_with_metadata_script_function,
+  serialize_script_function_payload,
+} from "./starcoin_stdlib/index.ts";
+
+function main() {
+  const token = "0x1::STC::STC";
+  const payee = "0x22222222222222222222222222222222";
+  const payeeAuthKey = new Uint8Array(32).fill(0x22);
+  const amount = 1234567n;
+  const script = encode_peer_to_peer_with_metadata_script_function(
+    token,
+    payee,
+    payeeAuthKey,
+    amount,
+    new Uint8Array(),
+  );
+
+  const call = decode_script_function(script);
+  if (call.type !== "PeerToPeerWithMetadata") {
+    throw new Error("wrong script function");
+  }
+  if (call.amount !== amount || call.payee !== payee) {
+    throw new Error("wrong arguments");
+  }
+
+  const payload = serialize_script_function_payload(script);
+  const decoded = deserialize_script_function_payload(payload);
+  if (decoded.ty_args[0] !== "0x00000000000000000000000000000001::STC::STC") {
+    throw new Error("wrong type argument");
+  }
+
+  let output = "";
+  for (const b of payload) {
+    output += `${b} `;
+  }
+  console.log(output);
+}
+
+main();
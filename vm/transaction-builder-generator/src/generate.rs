@@ -7,7 +7,7 @@
 //! cargo run -p transaction-builder-generator -- --help
 //! '''
 
-use clap::Parser;
+use clap::{CommandFactory, ErrorKind, Parser};
 use serde_generate as serdegen;
 use serde_reflection::Registry;
 use std::path::PathBuf;
@@ -22,10 +22,11 @@ enum Language {
     Cpp,
     Java,
     Dart,
+    TypeScript,
 }
 impl Language {
-    fn variants() -> [&'static str; 5] {
-        ["python3", "rust", "cpp", "java", "dart"]
+    fn variants() -> [&'static str; 6] {
+        ["python3", "rust", "cpp", "java", "dart", "typescript"]
     }
 }
 impl FromStr for Language {
@@ -37,6 +38,7 @@ impl FromStr for Language {
             "cpp" => Ok(Self::Cpp),
             "java" => Ok(Self::Java),
             "dart" => Ok(Self::Dart),
+            "typescript" => Ok(Self::TypeScript),
             _ => Err(format!("Unsupported language: {}", s)),
         }
     }
@@ -92,6 +94,14 @@ struct Options {
 
 fn main() {
     let options = Options::parse();
+    if matches!(options.language, Language::TypeScript) && options.with_starcoin_types.is_some() {
+        Options::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--with-starcoin-types is not supported by --language typescript, the TypeScript builders are self-contained",
+            )
+            .exit();
+    }
     let abis =
         buildgen::read_abis(&options.abi_directory).expect("Failed to read ABI in directory");
     let abis = abis
@@ -131,6 +141,7 @@ fn main() {
                     // buildgen::dart::output(&mut out, &abis, class_name).unwrap()
                     panic!("Code generation in dart requires --target_source_dir");
                 }
+                Language::TypeScript => buildgen::typescript::output(&mut out, &abis).unwrap(),
             }
             return;
         }
@@ -149,6 +160,7 @@ fn main() {
                 Language::Cpp => Box::new(serdegen::cpp::Installer::new(install_dir.clone())),
                 Language::Java => Box::new(serdegen::java::Installer::new(install_dir.clone())),
                 Language::Dart => Box::new(serdegen::dart::Installer::new(install_dir.clone())),
+                Language::TypeScript => unreachable!("rejected when parsing the options"),
             };
 
         match options.language {
@@ -201,6 +213,7 @@ fn main() {
             Language::Cpp => Box::new(buildgen::cpp::Installer::new(install_dir)),
             Language::Java => Box::new(buildgen::java::Installer::new(install_dir)),
             Language::Dart => Box::new(buildgen::dart::Installer::new(install_dir)),
+            Language::TypeScript => Box::new(buildgen::typescript::Installer::new(install_dir)),
         };
 
     if let Some(name) = options.module_name {
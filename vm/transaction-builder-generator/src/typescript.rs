@@ -0,0 +1,738 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common;
+use heck::CamelCase;
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use serde_generate::indent::{IndentConfig, IndentedWriter};
+use starcoin_vm_types::transaction::{ArgumentABI, ScriptABI, ScriptFunctionABI, TypeArgumentABI};
+
+use std::{
+    io::{Result, Write},
+    path::PathBuf,
+};
+
+/// Output script function builders and decoders in TypeScript for the given ABIs.
+/// The generated module has no dependency, it carries the BCS (de)serializers of the
+/// script function payload. Transaction script ABIs are skipped.
+pub fn output(out: &mut dyn Write, abis: &[ScriptABI]) -> Result<()> {
+    let abis = common::script_function_abis(abis);
+    let mut emitter = TypeScriptEmitter {
+        out: IndentedWriter::new(out, IndentConfig::Space(2)),
+    };
+    emitter.output_preamble()?;
+    emitter.output_script_function_call_types(&abis)?;
+
+    emitter.output_encode_method(&abis)?;
+    emitter.output_decode_method()?;
+
+    for abi in &abis {
+        emitter.output_script_function_encoder_function(abi)?;
+    }
+    for abi in &abis {
+        emitter.output_script_function_decoder_function(abi)?;
+    }
+
+    emitter.output_script_function_decoder_map(&abis)?;
+    emitter.output_bcs_runtime()
+}
+
+/// Shared state for the TypeScript code generator.
+struct TypeScriptEmitter<T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+}
+
+impl<T> TypeScriptEmitter<T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"// Generated by the starcoin transaction builder generator, do not edit.
+
+/** A type tag in the string form, e.g. `0x1::STC::STC` or `vector<u8>`. */
+export type TypeTag = string;
+
+/** A hex encoded account address, e.g. `0x1`. */
+export type AccountAddress = string;
+
+/** A call into a Move script function, with the arguments encoded in BCS. */
+export interface ScriptFunction {{
+  module: {{ address: AccountAddress; name: string }};
+  function: string;
+  ty_args: TypeTag[];
+  args: Uint8Array[];
+}}"#
+        )
+    }
+
+    fn output_script_function_call_types(&mut self, abis: &[ScriptFunctionABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            "\n/** Structured representation of a call into a known Move script function. */"
+        )?;
+        if abis.is_empty() {
+            return writeln!(self.out, "export type ScriptFunctionCall = never;");
+        }
+        writeln!(self.out, "export type ScriptFunctionCall =")?;
+        self.out.indent();
+        for (index, abi) in abis.iter().enumerate() {
+            writeln!(
+                self.out,
+                "| ScriptFunctionCall{}{}",
+                abi.name().to_camel_case(),
+                if index + 1 == abis.len() { ";" } else { "" }
+            )?;
+        }
+        self.out.unindent();
+
+        for abi in abis {
+            writeln!(
+                self.out,
+                "\n{}export interface ScriptFunctionCall{} {{",
+                Self::quote_doc(abi.doc(), std::iter::empty()),
+                abi.name().to_camel_case()
+            )?;
+            self.out.indent();
+            writeln!(self.out, "type: \"{}\";", abi.name().to_camel_case())?;
+            for ty_arg in abi.ty_args() {
+                writeln!(self.out, "{}: TypeTag;", Self::quote_name(ty_arg.name()))?;
+            }
+            for arg in abi.args() {
+                writeln!(
+                    self.out,
+                    "{}: {};",
+                    Self::quote_name(arg.name()),
+                    Self::quote_type(arg.type_tag())
+                )?;
+            }
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+        }
+        Ok(())
+    }
+
+    fn output_encode_method(&mut self, abis: &[ScriptFunctionABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+/** Build a `ScriptFunction` from a structured object `ScriptFunctionCall`. */
+export function encode_script_function(call: ScriptFunctionCall): ScriptFunction {{"#
+        )?;
+        self.out.indent();
+        writeln!(self.out, "switch (call.type) {{")?;
+        self.out.indent();
+        for abi in abis {
+            let params = Self::param_names(abi.ty_args(), abi.args())
+                .into_iter()
+                .map(|name| format!("call.{}", name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(
+                self.out,
+                "case \"{}\":\n  return encode_{}_script_function({});",
+                abi.name().to_camel_case(),
+                abi.name(),
+                params
+            )?;
+        }
+        writeln!(
+            self.out,
+            "default:\n  throw new Error(\"Unknown script function call\");"
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_decode_method(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+/** Try to recognize a `ScriptFunction` and convert it into a structured object `ScriptFunctionCall`. */
+export function decode_script_function(script: ScriptFunction): ScriptFunctionCall {{
+  const helper = SCRIPT_FUNCTION_DECODER_MAP[script.module.name + script.function];
+  if (helper === undefined) {{
+    throw new Error("Unknown script function");
+  }}
+  return helper(script);
+}}"#
+        )
+    }
+
+    fn output_script_function_encoder_function(&mut self, abi: &ScriptFunctionABI) -> Result<()> {
+        let params_doc = std::iter::empty()
+            .chain(
+                abi.ty_args()
+                    .iter()
+                    .map(|ty_arg| format!("@param {} TypeTag value", ty_arg.name())),
+            )
+            .chain(abi.args().iter().map(|arg| {
+                format!(
+                    "@param {} {} value",
+                    arg.name(),
+                    Self::quote_move_type(arg.type_tag())
+                )
+            }));
+        let params = std::iter::empty()
+            .chain(
+                abi.ty_args()
+                    .iter()
+                    .map(|ty_arg| format!("{}: TypeTag", Self::quote_name(ty_arg.name()))),
+            )
+            .chain(abi.args().iter().map(|arg| {
+                format!(
+                    "{}: {}",
+                    Self::quote_name(arg.name()),
+                    Self::quote_type(arg.type_tag())
+                )
+            }))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            self.out,
+            "\n{}export function encode_{}_script_function({}): ScriptFunction {{",
+            Self::quote_doc(abi.doc(), params_doc),
+            abi.name(),
+            params
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            r#"return {{
+  module: {},
+  function: "{}",
+  ty_args: [{}],
+  args: [{}],
+}};"#,
+            Self::quote_module_id(abi.module_name()),
+            abi.name(),
+            abi.ty_args()
+                .iter()
+                .map(|ty_arg| Self::quote_name(ty_arg.name()))
+                .collect::<Vec<_>>()
+                .join(", "),
+            abi.args()
+                .iter()
+                .map(|arg| Self::quote_serialize_argument(arg.type_tag(), arg.name()))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_script_function_decoder_function(&mut self, abi: &ScriptFunctionABI) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nexport function decode_{}_script_function(script: ScriptFunction): ScriptFunctionCall {{",
+            abi.name(),
+        )?;
+        self.out.indent();
+        writeln!(self.out, "return {{")?;
+        self.out.indent();
+        writeln!(self.out, "type: \"{}\",", abi.name().to_camel_case())?;
+        for (index, ty_arg) in abi.ty_args().iter().enumerate() {
+            writeln!(
+                self.out,
+                "{}: script.ty_args[{}],",
+                Self::quote_name(ty_arg.name()),
+                index
+            )?;
+        }
+        for (index, arg) in abi.args().iter().enumerate() {
+            writeln!(
+                self.out,
+                "{}: {},",
+                Self::quote_name(arg.name()),
+                Self::quote_deserialize_argument(
+                    arg.type_tag(),
+                    &format!("script.args[{}]", index)
+                )
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}};")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_script_function_decoder_map(&mut self, abis: &[ScriptFunctionABI]) -> Result<()> {
+        writeln!(
+            self.out,
+            "\nconst SCRIPT_FUNCTION_DECODER_MAP: Record<string, (script: ScriptFunction) => ScriptFunctionCall> = {{"
+        )?;
+        self.out.indent();
+        for abi in abis {
+            writeln!(
+                self.out,
+                "\"{0}{1}\": decode_{1}_script_function,",
+                abi.module_name().name(),
+                abi.name()
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}};")
+    }
+
+    /// The BCS (de)serializers of the argument types and of the script function payload.
+    fn output_bcs_runtime(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+/** Serialize the script function into the BCS bytes of a `TransactionPayload::ScriptFunction`. */
+export function serialize_script_function_payload(script: ScriptFunction): Uint8Array {{
+  const writer = new BcsWriter();
+  writer.writeLen(TRANSACTION_PAYLOAD_SCRIPT_FUNCTION);
+  writer.writeAddress(script.module.address);
+  writer.writeStr(script.module.name);
+  writer.writeStr(script.function);
+  writer.writeLen(script.ty_args.length);
+  script.ty_args.forEach((tag) => writer.writeTypeTag(tag));
+  writer.writeLen(script.args.length);
+  script.args.forEach((arg) => writer.writeBytes(arg));
+  return writer.getBytes();
+}}
+
+/** Deserialize the BCS bytes of a `TransactionPayload::ScriptFunction`. */
+export function deserialize_script_function_payload(bytes: Uint8Array): ScriptFunction {{
+  const reader = new BcsReader(bytes);
+  if (reader.readLen() !== TRANSACTION_PAYLOAD_SCRIPT_FUNCTION) {{
+    throw new Error("Transaction payload not a script function");
+  }}
+  const script = {{
+    module: {{ address: reader.readAddress(), name: reader.readStr() }},
+    function: reader.readStr(),
+    ty_args: reader.readSeq(() => reader.readTypeTag()),
+    args: reader.readSeq(() => reader.readBytes()),
+  }};
+  reader.checkEnd();
+  return script;
+}}
+
+const TRANSACTION_PAYLOAD_SCRIPT_FUNCTION = 2;
+const ADDRESS_LENGTH = 16;
+const TYPE_TAG_VECTOR = 6;
+const TYPE_TAG_STRUCT = 7;
+const PRIMITIVE_TYPE_TAGS: Record<string, number> = {{
+  bool: 0,
+  u8: 1,
+  u64: 2,
+  u128: 3,
+  address: 4,
+  signer: 5,
+  u16: 8,
+  u32: 9,
+  u256: 10,
+}};
+
+function serialize(write: (writer: BcsWriter) => void): Uint8Array {{
+  const writer = new BcsWriter();
+  write(writer);
+  return writer.getBytes();
+}}
+
+function deserialize<V>(bytes: Uint8Array, read: (reader: BcsReader) => V): V {{
+  const reader = new BcsReader(bytes);
+  const value = read(reader);
+  reader.checkEnd();
+  return value;
+}}
+
+class BcsWriter {{
+  private bytes: number[] = [];
+
+  writeU8(value: number) {{
+    if (!Number.isInteger(value) || value < 0 || value > 0xff) {{
+      throw new Error(`Invalid u8: ${{value}}`);
+    }}
+    this.bytes.push(value);
+  }}
+
+  writeBool(value: boolean) {{
+    this.writeU8(value ? 1 : 0);
+  }}
+
+  writeUint(value: bigint | number, size: number) {{
+    let rest = BigInt(value);
+    if (rest < BigInt(0) || rest >> BigInt(8 * size) !== BigInt(0)) {{
+      throw new Error(`Invalid u${{8 * size}}: ${{value}}`);
+    }}
+    for (let i = 0; i < size; i++) {{
+      this.writeU8(Number(rest & BigInt(0xff)));
+      rest >>= BigInt(8);
+    }}
+  }}
+
+  writeLen(value: number) {{
+    let rest = value;
+    while (rest >= 0x80) {{
+      this.writeU8((rest & 0x7f) | 0x80);
+      rest = Math.floor(rest / 0x80);
+    }}
+    this.writeU8(rest);
+  }}
+
+  writeBytes(value: Uint8Array) {{
+    this.writeLen(value.length);
+    value.forEach((byte) => this.writeU8(byte));
+  }}
+
+  writeStr(value: string) {{
+    this.writeBytes(new TextEncoder().encode(value));
+  }}
+
+  writeAddress(value: AccountAddress) {{
+    let hex = value.startsWith("0x") ? value.slice(2) : value;
+    if (!/^[0-9a-fA-F]*$/.test(hex) || hex.length > ADDRESS_LENGTH * 2) {{
+      throw new Error(`Invalid address: ${{value}}`);
+    }}
+    hex = hex.padStart(ADDRESS_LENGTH * 2, "0");
+    for (let i = 0; i < ADDRESS_LENGTH; i++) {{
+      this.writeU8(parseInt(hex.slice(i * 2, i * 2 + 2), 16));
+    }}
+  }}
+
+  writeTypeTag(value: TypeTag) {{
+    const tokens = value.match(/[^<>,\s]+|[<>,]/g) ?? [];
+    let pos = 0;
+    const write = () => {{
+      const name = tokens[pos++];
+      if (name === undefined || name === "<" || name === ">" || name === ",") {{
+        throw new Error(`Invalid type tag: ${{value}}`);
+      }}
+      const typeArgs: (() => void)[] = [];
+      if (tokens[pos] === "<") {{
+        pos++;
+        for (;;) {{
+          const start = pos;
+          // Check the syntax of the type argument, it is written after the outer type.
+          skip();
+          typeArgs.push(() => {{
+            const end = pos;
+            pos = start;
+            write();
+            pos = end;
+          }});
+          if (tokens[pos] === ",") {{
+            pos++;
+          }} else if (tokens[pos] === ">") {{
+            pos++;
+            break;
+          }} else {{
+            throw new Error(`Invalid type tag: ${{value}}`);
+          }}
+        }}
+      }}
+      if (name in PRIMITIVE_TYPE_TAGS && typeArgs.length === 0) {{
+        this.writeLen(PRIMITIVE_TYPE_TAGS[name]);
+      }} else if (name === "vector" && typeArgs.length === 1) {{
+        this.writeLen(TYPE_TAG_VECTOR);
+        typeArgs[0]();
+      }} else {{
+        const parts = name.split("::");
+        if (parts.length !== 3) {{
+          throw new Error(`Invalid type tag: ${{value}}`);
+        }}
+        this.writeLen(TYPE_TAG_STRUCT);
+        this.writeAddress(parts[0]);
+        this.writeStr(parts[1]);
+        this.writeStr(parts[2]);
+        this.writeLen(typeArgs.length);
+        typeArgs.forEach((writeTypeArg) => writeTypeArg());
+      }}
+    }};
+    const skip = () => {{
+      const name = tokens[pos++];
+      if (name === undefined || name === "<" || name === ">" || name === ",") {{
+        throw new Error(`Invalid type tag: ${{value}}`);
+      }}
+      if (tokens[pos] === "<") {{
+        pos++;
+        for (;;) {{
+          skip();
+          const separator = tokens[pos++];
+          if (separator === ">") {{
+            break;
+          }} else if (separator !== ",") {{
+            throw new Error(`Invalid type tag: ${{value}}`);
+          }}
+        }}
+      }}
+    }};
+    write();
+    if (pos !== tokens.length) {{
+      throw new Error(`Invalid type tag: ${{value}}`);
+    }}
+  }}
+
+  getBytes(): Uint8Array {{
+    return Uint8Array.from(this.bytes);
+  }}
+}}
+
+class BcsReader {{
+  private pos = 0;
+
+  constructor(private readonly bytes: Uint8Array) {{}}
+
+  readU8(): number {{
+    if (this.pos >= this.bytes.length) {{
+      throw new Error("Unexpected end of input");
+    }}
+    return this.bytes[this.pos++];
+  }}
+
+  readBool(): boolean {{
+    const value = this.readU8();
+    if (value > 1) {{
+      throw new Error(`Invalid bool: ${{value}}`);
+    }}
+    return value === 1;
+  }}
+
+  readUint(size: number): bigint {{
+    let value = BigInt(0);
+    for (let i = 0; i < size; i++) {{
+      value |= BigInt(this.readU8()) << BigInt(8 * i);
+    }}
+    return value;
+  }}
+
+  readLen(): number {{
+    let value = 0;
+    for (let shift = 1; ; shift *= 0x80) {{
+      const byte = this.readU8();
+      value += (byte & 0x7f) * shift;
+      if ((byte & 0x80) === 0) {{
+        return value;
+      }}
+    }}
+  }}
+
+  readBytes(): Uint8Array {{
+    const len = this.readLen();
+    if (this.pos + len > this.bytes.length) {{
+      throw new Error("Unexpected end of input");
+    }}
+    const value = this.bytes.slice(this.pos, this.pos + len);
+    this.pos += len;
+    return value;
+  }}
+
+  readStr(): string {{
+    return new TextDecoder().decode(this.readBytes());
+  }}
+
+  readSeq<V>(read: () => V): V[] {{
+    const len = this.readLen();
+    const values: V[] = [];
+    for (let i = 0; i < len; i++) {{
+      values.push(read());
+    }}
+    return values;
+  }}
+
+  readAddress(): AccountAddress {{
+    let hex = "0x";
+    for (let i = 0; i < ADDRESS_LENGTH; i++) {{
+      hex += this.readU8().toString(16).padStart(2, "0");
+    }}
+    return hex;
+  }}
+
+  readTypeTag(): TypeTag {{
+    const index = this.readLen();
+    const primitive = Object.keys(PRIMITIVE_TYPE_TAGS).find((name) => PRIMITIVE_TYPE_TAGS[name] === index);
+    if (primitive !== undefined) {{
+      return primitive;
+    }} else if (index === TYPE_TAG_VECTOR) {{
+      return `vector<${{this.readTypeTag()}}>`;
+    }} else if (index === TYPE_TAG_STRUCT) {{
+      const name = `${{this.readAddress()}}::${{this.readStr()}}::${{this.readStr()}}`;
+      const typeArgs = this.readSeq(() => this.readTypeTag());
+      return typeArgs.length === 0 ? name : `${{name}}<${{typeArgs.join(", ")}}>`;
+    }}
+    throw new Error(`Invalid type tag index: ${{index}}`);
+  }}
+
+  checkEnd() {{
+    if (this.pos !== this.bytes.length) {{
+      throw new Error("Unexpected trailing bytes");
+    }}
+  }}
+}}"#
+        )
+    }
+
+    fn quote_doc<I>(doc: &str, params_doc: I) -> String
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut doc = common::prepare_doc_string(doc).replace("*/", "*\\/");
+        for text in params_doc {
+            doc = format!("{}\n{}", doc, text);
+        }
+        let text = textwrap::indent(&doc, " * ").replace("\n\n", "\n *\n");
+        format!("/**\n{} */\n", text)
+    }
+
+    fn quote_module_id(module_id: &ModuleId) -> String {
+        format!(
+            "{{ address: \"{}\", name: \"{}\" }}",
+            module_id.address().to_hex_literal(),
+            module_id.name()
+        )
+    }
+
+    /// Avoid the reserved words of TypeScript in the names of parameters and fields.
+    fn quote_name(name: &str) -> String {
+        const RESERVED_WORDS: &[&str] = &[
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "debugger",
+            "default",
+            "delete",
+            "do",
+            "else",
+            "enum",
+            "export",
+            "extends",
+            "false",
+            "finally",
+            "for",
+            "function",
+            "if",
+            "import",
+            "in",
+            "instanceof",
+            "new",
+            "null",
+            "return",
+            "super",
+            "switch",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "type",
+            "typeof",
+            "var",
+            "void",
+            "while",
+            "with",
+        ];
+        if RESERVED_WORDS.contains(&name) {
+            format!("{}_", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn param_names(ty_args: &[TypeArgumentABI], args: &[ArgumentABI]) -> Vec<String> {
+        std::iter::empty()
+            .chain(ty_args.iter().map(TypeArgumentABI::name))
+            .chain(args.iter().map(ArgumentABI::name))
+            .map(Self::quote_name)
+            .collect()
+    }
+
+    fn quote_move_type(type_tag: &TypeTag) -> String {
+        match type_tag {
+            TypeTag::Struct(_) | TypeTag::Signer => common::type_not_allowed(type_tag),
+            _ => type_tag.to_string(),
+        }
+    }
+
+    fn quote_type(type_tag: &TypeTag) -> String {
+        use TypeTag::*;
+        match type_tag {
+            Bool => "boolean".into(),
+            U8 | U16 | U32 => "number".into(),
+            U64 | U128 | U256 => "bigint".into(),
+            Address => "AccountAddress".into(),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => "Uint8Array".into(),
+                _ => common::type_not_allowed(type_tag),
+            },
+
+            Struct(_) | Signer => common::type_not_allowed(type_tag),
+        }
+    }
+
+    fn quote_serialize_argument(type_tag: &TypeTag, name: &str) -> String {
+        use TypeTag::*;
+        let name = Self::quote_name(name);
+        let write = match type_tag {
+            Bool => format!("writer.writeBool({})", name),
+            U8 => format!("writer.writeU8({})", name),
+            U16 => format!("writer.writeUint({}, 2)", name),
+            U32 => format!("writer.writeUint({}, 4)", name),
+            U64 => format!("writer.writeUint({}, 8)", name),
+            U128 => format!("writer.writeUint({}, 16)", name),
+            U256 => format!("writer.writeUint({}, 32)", name),
+            Address => format!("writer.writeAddress({})", name),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => format!("writer.writeBytes({})", name),
+                _ => common::type_not_allowed(type_tag),
+            },
+
+            Struct(_) | Signer => common::type_not_allowed(type_tag),
+        };
+        format!("serialize((writer) => {})", write)
+    }
+
+    fn quote_deserialize_argument(type_tag: &TypeTag, bytes: &str) -> String {
+        use TypeTag::*;
+        let read = match type_tag {
+            Bool => "reader.readBool()",
+            U8 => "reader.readU8()",
+            U16 => "Number(reader.readUint(2))",
+            U32 => "Number(reader.readUint(4))",
+            U64 => "reader.readUint(8)",
+            U128 => "reader.readUint(16)",
+            U256 => "reader.readUint(32)",
+            Address => "reader.readAddress()",
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => "reader.readBytes()",
+                _ => common::type_not_allowed(type_tag),
+            },
+
+            Struct(_) | Signer => common::type_not_allowed(type_tag),
+        };
+        format!("deserialize({}, (reader) => {})", bytes, read)
+    }
+}
+
+pub struct Installer {
+    install_dir: PathBuf,
+}
+
+impl Installer {
+    pub fn new(install_dir: PathBuf) -> Self {
+        Self { install_dir }
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Box<dyn std::error::Error>;
+
+    fn install_transaction_builders(
+        &self,
+        name: &str,
+        abis: &[ScriptABI],
+    ) -> std::result::Result<(), Self::Error> {
+        let dir_path = self.install_dir.join(name);
+        std::fs::create_dir_all(&dir_path)?;
+        let mut file = std::fs::File::create(dir_path.join("index.ts"))?;
+        output(&mut file, abis)?;
+        Ok(())
+    }
+}
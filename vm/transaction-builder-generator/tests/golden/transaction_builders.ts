@@ -0,0 +1,413 @@
+// Generated by the starcoin transaction builder generator, do not edit.
+
+/** A type tag in the string form, e.g. `0x1::STC::STC` or `vector<u8>`. */
+export type TypeTag = string;
+
+/** A hex encoded account address, e.g. `0x1`. */
+export type AccountAddress = string;
+
+/** A call into a Move script function, with the arguments encoded in BCS. */
+export interface ScriptFunction {
+  module: { address: AccountAddress; name: string };
+  function: string;
+  ty_args: TypeTag[];
+  args: Uint8Array[];
+}
+
+/** Structured representation of a call into a known Move script function. */
+export type ScriptFunctionCall =
+  | ScriptFunctionCallPeerToPeer
+  | ScriptFunctionCallSetFlags;
+
+/**
+ * Transfer `amount` of `token_type` to `payee`.
+ *
+ * The account of `payee` is created with `payee_auth_key` if it does not exist.
+ */
+export interface ScriptFunctionCallPeerToPeer {
+  type: "PeerToPeer";
+  token_type: TypeTag;
+  payee: AccountAddress;
+  payee_auth_key: Uint8Array;
+  amount: bigint;
+}
+
+/**
+ * Set the flags, `type` is a reserved word of TypeScript.
+ */
+export interface ScriptFunctionCallSetFlags {
+  type: "SetFlags";
+  type_: boolean;
+  level: number;
+  port: number;
+  index: number;
+  count: bigint;
+  total: bigint;
+}
+
+/** Build a `ScriptFunction` from a structured object `ScriptFunctionCall`. */
+export function encode_script_function(call: ScriptFunctionCall): ScriptFunction {
+  switch (call.type) {
+    case "PeerToPeer":
+      return encode_peer_to_peer_script_function(call.token_type, call.payee, call.payee_auth_key, call.amount);
+    case "SetFlags":
+      return encode_set_flags_script_function(call.type_, call.level, call.port, call.index, call.count, call.total);
+    default:
+      throw new Error("Unknown script function call");
+  }
+}
+
+/** Try to recognize a `ScriptFunction` and convert it into a structured object `ScriptFunctionCall`. */
+export function decode_script_function(script: ScriptFunction): ScriptFunctionCall {
+  const helper = SCRIPT_FUNCTION_DECODER_MAP[script.module.name + script.function];
+  if (helper === undefined) {
+    throw new Error("Unknown script function");
+  }
+  return helper(script);
+}
+
+/**
+ * Transfer `amount` of `token_type` to `payee`.
+ *
+ * The account of `payee` is created with `payee_auth_key` if it does not exist.
+ * @param token_type TypeTag value
+ * @param payee address value
+ * @param payee_auth_key vector<u8> value
+ * @param amount u128 value
+ */
+export function encode_peer_to_peer_script_function(token_type: TypeTag, payee: AccountAddress, payee_auth_key: Uint8Array, amount: bigint): ScriptFunction {
+  return {
+    module: { address: "0x1", name: "TransferScripts" },
+    function: "peer_to_peer",
+    ty_args: [token_type],
+    args: [serialize((writer) => writer.writeAddress(payee)), serialize((writer) => writer.writeBytes(payee_auth_key)), serialize((writer) => writer.writeUint(amount, 16))],
+  };
+}
+
+/**
+ * Set the flags, `type` is a reserved word of TypeScript.
+ * @param type bool value
+ * @param level u8 value
+ * @param port u16 value
+ * @param index u32 value
+ * @param count u64 value
+ * @param total u256 value
+ */
+export function encode_set_flags_script_function(type_: boolean, level: number, port: number, index: number, count: bigint, total: bigint): ScriptFunction {
+  return {
+    module: { address: "0x1", name: "FlagScripts" },
+    function: "set_flags",
+    ty_args: [],
+    args: [serialize((writer) => writer.writeBool(type_)), serialize((writer) => writer.writeU8(level)), serialize((writer) => writer.writeUint(port, 2)), serialize((writer) => writer.writeUint(index, 4)), serialize((writer) => writer.writeUint(count, 8)), serialize((writer) => writer.writeUint(total, 32))],
+  };
+}
+
+export function decode_peer_to_peer_script_function(script: ScriptFunction): ScriptFunctionCall {
+  return {
+    type: "PeerToPeer",
+    token_type: script.ty_args[0],
+    payee: deserialize(script.args[0], (reader) => reader.readAddress()),
+    payee_auth_key: deserialize(script.args[1], (reader) => reader.readBytes()),
+    amount: deserialize(script.args[2], (reader) => reader.readUint(16)),
+  };
+}
+
+export function decode_set_flags_script_function(script: ScriptFunction): ScriptFunctionCall {
+  return {
+    type: "SetFlags",
+    type_: deserialize(script.args[0], (reader) => reader.readBool()),
+    level: deserialize(script.args[1], (reader) => reader.readU8()),
+    port: deserialize(script.args[2], (reader) => Number(reader.readUint(2))),
+    index: deserialize(script.args[3], (reader) => Number(reader.readUint(4))),
+    count: deserialize(script.args[4], (reader) => reader.readUint(8)),
+    total: deserialize(script.args[5], (reader) => reader.readUint(32)),
+  };
+}
+
+const SCRIPT_FUNCTION_DECODER_MAP: Record<string, (script: ScriptFunction) => ScriptFunctionCall> = {
+  "TransferScriptspeer_to_peer": decode_peer_to_peer_script_function,
+  "FlagScriptsset_flags": decode_set_flags_script_function,
+};
+
+/** Serialize the script function into the BCS bytes of a `TransactionPayload::ScriptFunction`. */
+export function serialize_script_function_payload(script: ScriptFunction): Uint8Array {
+  const writer = new BcsWriter();
+  writer.writeLen(TRANSACTION_PAYLOAD_SCRIPT_FUNCTION);
+  writer.writeAddress(script.module.address);
+  writer.writeStr(script.module.name);
+  writer.writeStr(script.function);
+  writer.writeLen(script.ty_args.length);
+  script.ty_args.forEach((tag) => writer.writeTypeTag(tag));
+  writer.writeLen(script.args.length);
+  script.args.forEach((arg) => writer.writeBytes(arg));
+  return writer.getBytes();
+}
+
+/** Deserialize the BCS bytes of a `TransactionPayload::ScriptFunction`. */
+export function deserialize_script_function_payload(bytes: Uint8Array): ScriptFunction {
+  const reader = new BcsReader(bytes);
+  if (reader.readLen() !== TRANSACTION_PAYLOAD_SCRIPT_FUNCTION) {
+    throw new Error("Transaction payload not a script function");
+  }
+  const script = {
+    module: { address: reader.readAddress(), name: reader.readStr() },
+    function: reader.readStr(),
+    ty_args: reader.readSeq(() => reader.readTypeTag()),
+    args: reader.readSeq(() => reader.readBytes()),
+  };
+  reader.checkEnd();
+  return script;
+}
+
+const TRANSACTION_PAYLOAD_SCRIPT_FUNCTION = 2;
+const ADDRESS_LENGTH = 16;
+const TYPE_TAG_VECTOR = 6;
+const TYPE_TAG_STRUCT = 7;
+const PRIMITIVE_TYPE_TAGS: Record<string, number> = {
+  bool: 0,
+  u8: 1,
+  u64: 2,
+  u128: 3,
+  address: 4,
+  signer: 5,
+  u16: 8,
+  u32: 9,
+  u256: 10,
+};
+
+function serialize(write: (writer: BcsWriter) => void): Uint8Array {
+  const writer = new BcsWriter();
+  write(writer);
+  return writer.getBytes();
+}
+
+function deserialize<V>(bytes: Uint8Array, read: (reader: BcsReader) => V): V {
+  const reader = new BcsReader(bytes);
+  const value = read(reader);
+  reader.checkEnd();
+  return value;
+}
+
+class BcsWriter {
+  private bytes: number[] = [];
+
+  writeU8(value: number) {
+    if (!Number.isInteger(value) || value < 0 || value > 0xff) {
+      throw new Error(`Invalid u8: ${value}`);
+    }
+    this.bytes.push(value);
+  }
+
+  writeBool(value: boolean) {
+    this.writeU8(value ? 1 : 0);
+  }
+
+  writeUint(value: bigint | number, size: number) {
+    let rest = BigInt(value);
+    if (rest < BigInt(0) || rest >> BigInt(8 * size) !== BigInt(0)) {
+      throw new Error(`Invalid u${8 * size}: ${value}`);
+    }
+    for (let i = 0; i < size; i++) {
+      this.writeU8(Number(rest & BigInt(0xff)));
+      rest >>= BigInt(8);
+    }
+  }
+
+  writeLen(value: number) {
+    let rest = value;
+    while (rest >= 0x80) {
+      this.writeU8((rest & 0x7f) | 0x80);
+      rest = Math.floor(rest / 0x80);
+    }
+    this.writeU8(rest);
+  }
+
+  writeBytes(value: Uint8Array) {
+    this.writeLen(value.length);
+    value.forEach((byte) => this.writeU8(byte));
+  }
+
+  writeStr(value: string) {
+    this.writeBytes(new TextEncoder().encode(value));
+  }
+
+  writeAddress(value: AccountAddress) {
+    let hex = value.startsWith("0x") ? value.slice(2) : value;
+    if (!/^[0-9a-fA-F]*$/.test(hex) || hex.length > ADDRESS_LENGTH * 2) {
+      throw new Error(`Invalid address: ${value}`);
+    }
+    hex = hex.padStart(ADDRESS_LENGTH * 2, "0");
+    for (let i = 0; i < ADDRESS_LENGTH; i++) {
+      this.writeU8(parseInt(hex.slice(i * 2, i * 2 + 2), 16));
+    }
+  }
+
+  writeTypeTag(value: TypeTag) {
+    const tokens = value.match(/[^<>,\s]+|[<>,]/g) ?? [];
+    let pos = 0;
+    const write = () => {
+      const name = tokens[pos++];
+      if (name === undefined || name === "<" || name === ">" || name === ",") {
+        throw new Error(`Invalid type tag: ${value}`);
+      }
+      const typeArgs: (() => void)[] = [];
+      if (tokens[pos] === "<") {
+        pos++;
+        for (;;) {
+          const start = pos;
+          // Check the syntax of the type argument, it is written after the outer type.
+          skip();
+          typeArgs.push(() => {
+            const end = pos;
+            pos = start;
+            write();
+            pos = end;
+          });
+          if (tokens[pos] === ",") {
+            pos++;
+          } else if (tokens[pos] === ">") {
+            pos++;
+            break;
+          } else {
+            throw new Error(`Invalid type tag: ${value}`);
+          }
+        }
+      }
+      if (name in PRIMITIVE_TYPE_TAGS && typeArgs.length === 0) {
+        this.writeLen(PRIMITIVE_TYPE_TAGS[name]);
+      } else if (name === "vector" && typeArgs.length === 1) {
+        this.writeLen(TYPE_TAG_VECTOR);
+        typeArgs[0]();
+      } else {
+        const parts = name.split("::");
+        if (parts.length !== 3) {
+          throw new Error(`Invalid type tag: ${value}`);
+        }
+        this.writeLen(TYPE_TAG_STRUCT);
+        this.writeAddress(parts[0]);
+        this.writeStr(parts[1]);
+        this.writeStr(parts[2]);
+        this.writeLen(typeArgs.length);
+        typeArgs.forEach((writeTypeArg) => writeTypeArg());
+      }
+    };
+    const skip = () => {
+      const name = tokens[pos++];
+      if (name === undefined || name === "<" || name === ">" || name === ",") {
+        throw new Error(`Invalid type tag: ${value}`);
+      }
+      if (tokens[pos] === "<") {
+        pos++;
+        for (;;) {
+          skip();
+          const separator = tokens[pos++];
+          if (separator === ">") {
+            break;
+          } else if (separator !== ",") {
+            throw new Error(`Invalid type tag: ${value}`);
+          }
+        }
+      }
+    };
+    write();
+    if (pos !== tokens.length) {
+      throw new Error(`Invalid type tag: ${value}`);
+    }
+  }
+
+  getBytes(): Uint8Array {
+    return Uint8Array.from(this.bytes);
+  }
+}
+
+class BcsReader {
+  private pos = 0;
+
+  constructor(private readonly bytes: Uint8Array) {}
+
+  readU8(): number {
+    if (this.pos >= this.bytes.length) {
+      throw new Error("Unexpected end of input");
+    }
+    return this.bytes[this.pos++];
+  }
+
+  readBool(): boolean {
+    const value = this.readU8();
+    if (value > 1) {
+      throw new Error(`Invalid bool: ${value}`);
+    }
+    return value === 1;
+  }
+
+  readUint(size: number): bigint {
+    let value = BigInt(0);
+    for (let i = 0; i < size; i++) {
+      value |= BigInt(this.readU8()) << BigInt(8 * i);
+    }
+    return value;
+  }
+
+  readLen(): number {
+    let value = 0;
+    for (let shift = 1; ; shift *= 0x80) {
+      const byte = this.readU8();
+      value += (byte & 0x7f) * shift;
+      if ((byte & 0x80) === 0) {
+        return value;
+      }
+    }
+  }
+
+  readBytes(): Uint8Array {
+    const len = this.readLen();
+    if (this.pos + len > this.bytes.length) {
+      throw new Error("Unexpected end of input");
+    }
+    const value = this.bytes.slice(this.pos, this.pos + len);
+    this.pos += len;
+    return value;
+  }
+
+  readStr(): string {
+    return new TextDecoder().decode(this.readBytes());
+  }
+
+  readSeq<V>(read: () => V): V[] {
+    const len = this.readLen();
+    const values: V[] = [];
+    for (let i = 0; i < len; i++) {
+      values.push(read());
+    }
+    return values;
+  }
+
+  readAddress(): AccountAddress {
+    let hex = "0x";
+    for (let i = 0; i < ADDRESS_LENGTH; i++) {
+      hex += this.readU8().toString(16).padStart(2, "0");
+    }
+    return hex;
+  }
+
+  readTypeTag(): TypeTag {
+    const index = this.readLen();
+    const primitive = Object.keys(PRIMITIVE_TYPE_TAGS).find((name) => PRIMITIVE_TYPE_TAGS[name] === index);
+    if (primitive !== undefined) {
+      return primitive;
+    } else if (index === TYPE_TAG_VECTOR) {
+      return `vector<${this.readTypeTag()}>`;
+    } else if (index === TYPE_TAG_STRUCT) {
+      const name = `${this.readAddress()}::${this.readStr()}::${this.readStr()}`;
+      const typeArgs = this.readSeq(() => this.readTypeTag());
+      return typeArgs.length === 0 ? name : `${name}<${typeArgs.join(", ")}>`;
+    }
+    throw new Error(`Invalid type tag index: ${index}`);
+  }
+
+  checkEnd() {
+    if (this.pos !== this.bytes.length) {
+      throw new Error("Unexpected trailing bytes");
+    }
+  }
+}
@@ -41,3 +41,25 @@ fn test_that_installed_rust_code_compiles() {
     //     .unwrap();
     // assert!(status.success());
 }
+
+#[test]
+fn test_that_typescript_rejects_starcoin_types() {
+    let dir = tempdir().unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_generate-transaction-builders"))
+        .arg("--language")
+        .arg("typescript")
+        .arg("--module-name")
+        .arg("starcoin_stdlib")
+        .arg("--with-starcoin-types")
+        .arg("../../etc/starcoin_types.yml")
+        .arg("--target-source-dir")
+        .arg(dir.path())
+        .arg("../stdlib/compiled/latest/transaction_scripts/abi")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("--with-starcoin-types is not supported by --language typescript"));
+    assert!(std::fs::read_dir(dir.path()).unwrap().next().is_none());
+}
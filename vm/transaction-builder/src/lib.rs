@@ -263,6 +263,12 @@ pub fn encode_transfer_script_by_token_code(
     )
 }
 
+/// The script function of a transaction which cancels the pending transaction of `sender`
+/// with the same sequence number, it's a zero-value STC transfer to the sender itself.
+pub fn encode_cancel_script_function(sender: AccountAddress) -> ScriptFunction {
+    encode_transfer_script_function(sender, 0)
+}
+
 pub fn encode_nft_transfer_script(uuid: NFTUUID, recipient: AccountAddress) -> ScriptFunction {
     ScriptFunction::new(
         ModuleId::new(
@@ -53,11 +53,21 @@ pub enum NativeCostIndex {
     FROMBCS_FROM_BYTES = 44,
     SECP256K1_ECDSA_RECOVER_INTERNAL = 45,
     VECTOR_SPAWN_FROM = 46,
+    BLS12381_VALIDATE_PUBKEY_BASE = 47,
+    BLS12381_VERIFY_SIGNATURE_BASE = 48,
+    BLS12381_VERIFY_SIGNATURE_PER_BYTE = 49,
+    BLS12381_AGGREGATE_VERIFY_BASE = 50,
+    BLS12381_AGGREGATE_VERIFY_PER_PUBKEY = 51,
+    BLS12381_AGGREGATE_VERIFY_PER_BYTE = 52,
+    BLS12381_FAST_AGGREGATE_VERIFY_BASE = 53,
+    BLS12381_FAST_AGGREGATE_VERIFY_PER_PUBKEY = 54,
+    BLS12381_FAST_AGGREGATE_VERIFY_PER_BYTE = 55,
+    BLS12381_VERIFY_PROOF_OF_POSSESSION_BASE = 56,
 }
 
 impl NativeCostIndex {
     //note: should change this value when add new native function.
-    pub const NUMBER_OF_NATIVE_FUNCTIONS: usize = 47;
+    pub const NUMBER_OF_NATIVE_FUNCTIONS: usize = 57;
 }
 
 pub static G_MAX_TRANSACTION_SIZE_IN_BYTES_V1: u64 = 4096 * 10;
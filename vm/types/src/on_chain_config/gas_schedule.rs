@@ -426,6 +426,56 @@ pub fn native_gas_schedule_v4() -> Vec<(String, u64)> {
     natives
 }
 
+// v6_native_table
+pub fn native_gas_schedule_v6() -> Vec<(String, u64)> {
+    let gas_total = |x: u64, y: u64| -> u64 { x + y };
+    let mut natives = native_gas_schedule_v4();
+    let mut natives_delta = vec![
+        (
+            "starcoin_natives.bls12381.validate_pubkey.base".to_string(),
+            gas_total(2000, 1),
+        ),
+        (
+            "starcoin_natives.bls12381.verify_signature.base".to_string(),
+            gas_total(20000, 1),
+        ),
+        (
+            "starcoin_natives.bls12381.verify_signature.per_byte".to_string(),
+            gas_total(8, 1),
+        ),
+        (
+            "starcoin_natives.bls12381.aggregate_verify.base".to_string(),
+            gas_total(10000, 1),
+        ),
+        (
+            "starcoin_natives.bls12381.aggregate_verify.per_pubkey".to_string(),
+            gas_total(20000, 1),
+        ),
+        (
+            "starcoin_natives.bls12381.aggregate_verify.per_byte".to_string(),
+            gas_total(8, 1),
+        ),
+        (
+            "starcoin_natives.bls12381.fast_aggregate_verify.base".to_string(),
+            gas_total(20000, 1),
+        ),
+        (
+            "starcoin_natives.bls12381.fast_aggregate_verify.per_pubkey".to_string(),
+            gas_total(400, 1),
+        ),
+        (
+            "starcoin_natives.bls12381.fast_aggregate_verify.per_byte".to_string(),
+            gas_total(8, 1),
+        ),
+        (
+            "starcoin_natives.bls12381.verify_proof_of_possession.base".to_string(),
+            gas_total(20000, 1),
+        ),
+    ];
+    natives.append(&mut natives_delta);
+    natives
+}
+
 // G_GAS_CONSTANTS_V1
 pub fn txn_gas_schedule_v1() -> Vec<(String, u64)> {
     vec![
@@ -651,6 +701,16 @@ static G_NATIVE_STRS: Lazy<Vec<&str>> = Lazy::new(|| {
         "starcoin_natives.frombcs.base",
         "starcoin_natives.secp256k1.base",
         "move_stdlib.vector.spawn_from.legacy_per_abstract_memory_unit",
+        "starcoin_natives.bls12381.validate_pubkey.base",
+        "starcoin_natives.bls12381.verify_signature.base",
+        "starcoin_natives.bls12381.verify_signature.per_byte",
+        "starcoin_natives.bls12381.aggregate_verify.base",
+        "starcoin_natives.bls12381.aggregate_verify.per_pubkey",
+        "starcoin_natives.bls12381.aggregate_verify.per_byte",
+        "starcoin_natives.bls12381.fast_aggregate_verify.base",
+        "starcoin_natives.bls12381.fast_aggregate_verify.per_pubkey",
+        "starcoin_natives.bls12381.fast_aggregate_verify.per_byte",
+        "starcoin_natives.bls12381.verify_proof_of_possession.base",
     ]
 });
 
@@ -483,12 +483,53 @@ pub fn v5_native_table() -> Vec<GasCost> {
         .collect::<Vec<_>>()
 }
 
-pub static G_LATEST_NATIVE_TABLE: Lazy<Vec<GasCost>> = Lazy::new(|| {
-    let native_table = v5_native_table();
+pub fn v6_native_table() -> Vec<GasCost> {
+    let mut raw_native_table = vec![
+        (N::BLS12381_VALIDATE_PUBKEY_BASE, GasCost::new(2000, 1)),
+        (N::BLS12381_VERIFY_SIGNATURE_BASE, GasCost::new(20000, 1)),
+        (N::BLS12381_VERIFY_SIGNATURE_PER_BYTE, GasCost::new(8, 1)),
+        (N::BLS12381_AGGREGATE_VERIFY_BASE, GasCost::new(10000, 1)),
+        (
+            N::BLS12381_AGGREGATE_VERIFY_PER_PUBKEY,
+            GasCost::new(20000, 1),
+        ),
+        (N::BLS12381_AGGREGATE_VERIFY_PER_BYTE, GasCost::new(8, 1)),
+        (
+            N::BLS12381_FAST_AGGREGATE_VERIFY_BASE,
+            GasCost::new(20000, 1),
+        ),
+        (
+            N::BLS12381_FAST_AGGREGATE_VERIFY_PER_PUBKEY,
+            GasCost::new(400, 1),
+        ),
+        (
+            N::BLS12381_FAST_AGGREGATE_VERIFY_PER_BYTE,
+            GasCost::new(8, 1),
+        ),
+        (
+            N::BLS12381_VERIFY_PROOF_OF_POSSESSION_BASE,
+            GasCost::new(20000, 1),
+        ),
+    ];
+    raw_native_table.sort_by_key(|cost| cost.0 as u64);
+    let mut native_table = v5_native_table();
+    native_table.extend(raw_native_table.into_iter().map(|(_, cost)| cost));
 
     debug_assert!(
         native_table.len() == N::NUMBER_OF_NATIVE_FUNCTIONS,
         "all native functions must be in the cost table"
     );
     native_table
+}
+
+/// The native table of the genesis, the genesis cost tables are fixed, the natives added later
+/// are charged by the gas schedule of their stdlib upgrade.
+pub static G_LATEST_NATIVE_TABLE: Lazy<Vec<GasCost>> = Lazy::new(|| {
+    let native_table = v5_native_table();
+
+    debug_assert!(
+        native_table.len() == N::BLS12381_VALIDATE_PUBKEY_BASE as usize,
+        "all native functions before the BLS12381 natives must be in the cost table"
+    );
+    native_table
 });
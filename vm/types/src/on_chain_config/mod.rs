@@ -32,12 +32,12 @@ pub use self::{
     gas_schedule::{
         instruction_gas_schedule_v1, instruction_gas_schedule_v2, native_gas_schedule_v1,
         native_gas_schedule_v2, native_gas_schedule_v3, native_gas_schedule_v4,
-        txn_gas_schedule_test, txn_gas_schedule_v1, txn_gas_schedule_v2, txn_gas_schedule_v3,
-        GasSchedule, G_GAS_SCHEDULE_GAS_SCHEDULE, G_GAS_SCHEDULE_IDENTIFIER,
+        native_gas_schedule_v6, txn_gas_schedule_test, txn_gas_schedule_v1, txn_gas_schedule_v2,
+        txn_gas_schedule_v3, GasSchedule, G_GAS_SCHEDULE_GAS_SCHEDULE, G_GAS_SCHEDULE_IDENTIFIER,
     },
     genesis_gas_schedule::{
         instruction_table_v1, instruction_table_v2, native_table_v1, native_table_v2,
-        v4_native_table, G_LATEST_INSTRUCTION_TABLE, G_LATEST_NATIVE_TABLE,
+        v4_native_table, v6_native_table, G_LATEST_INSTRUCTION_TABLE, G_LATEST_NATIVE_TABLE,
     },
     move_lang_version::MoveLanguageVersion,
     version::{version_config_type_tag, Version, G_VERSION_CONFIG_IDENTIFIER},
@@ -10,6 +10,7 @@ use move_vm_runtime::native_extensions::NativeContextExtensions;
 use move_vm_runtime::session::Session;
 use starcoin_gas::NativeGasParameters;
 use starcoin_vm_types::errors::{PartialVMResult, VMResult};
+use starcoin_vm_types::genesis_config::StdlibVersion;
 use std::ops::Deref;
 
 pub struct MoveVmExt {
@@ -18,9 +19,12 @@ pub struct MoveVmExt {
 
 impl MoveVmExt {
     // XXX FIXME YSG need add treat_friend_as_private?
-    pub fn new(native_gas_params: NativeGasParameters) -> VMResult<Self> {
+    pub fn new(
+        native_gas_params: NativeGasParameters,
+        stdlib_version: StdlibVersion,
+    ) -> VMResult<Self> {
         Ok(Self {
-            inner: MoveVM::new(natives::starcoin_natives(native_gas_params))?,
+            inner: MoveVM::new(natives::starcoin_natives(native_gas_params, stdlib_version))?,
         })
     }
 
@@ -37,8 +41,9 @@ impl MoveVmExt {
     pub fn update_native_functions(
         &mut self,
         native_gas_params: NativeGasParameters,
+        stdlib_version: StdlibVersion,
     ) -> PartialVMResult<()> {
-        let native_functions = natives::starcoin_natives(native_gas_params);
+        let native_functions = natives::starcoin_natives(native_gas_params, stdlib_version);
         self.inner.update_native_functions(native_functions)
     }
 }
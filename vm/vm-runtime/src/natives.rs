@@ -7,12 +7,36 @@ use move_vm_runtime::native_functions::{
     make_table_from_iter, NativeFunction, NativeFunctionTable,
 };
 use starcoin_gas::NativeGasParameters;
+use starcoin_gas_algebra_ext::ToOnChainGasSchedule;
+use starcoin_vm_types::genesis_config::StdlibVersion;
+use std::collections::BTreeMap;
+
+/// marking of stdlib version which includes the BLS12381 module and its native gas costs.
+pub(crate) const BLS12381_UPGRADE_VERSION_MARK: u64 = 13;
+
+const BLS12381_GAS_KEY_PREFIX: &str = "starcoin_natives.bls12381.";
+
+/// The gas keys of the BLS12381 natives are optional, so the gas schedules before the upgrade
+/// still load, and a missing key is read as 0.
+/// Returns true if the gas schedule prices all the BLS12381 natives, they must not be
+/// registered otherwise, the pairing checks would be free.
+pub(crate) fn has_bls12381_gas_schedule(gas_schedule: &BTreeMap<String, u64>) -> bool {
+    starcoin_natives::GasParameters::zeros()
+        .to_on_chain_gas_schedule()
+        .into_iter()
+        .filter(|(key, _)| key.starts_with(BLS12381_GAS_KEY_PREFIX))
+        .all(|(key, _)| gas_schedule.contains_key(&key))
+}
 
 /// The function returns all native functions supported by Starcoin.
 /// NOTICE:
 /// - mostly re-use natives defined in move-stdlib.
 /// - be careful with the native cost table index used in the implementation
-pub fn starcoin_natives(gas_params: NativeGasParameters) -> NativeFunctionTable {
+/// - the natives added by a stdlib upgrade are only registered from the `stdlib_version` of the upgrade
+pub fn starcoin_natives(
+    gas_params: NativeGasParameters,
+    stdlib_version: StdlibVersion,
+) -> NativeFunctionTable {
     let mut natives = vec![];
 
     macro_rules! add_natives_from_module {
@@ -84,6 +108,12 @@ pub fn starcoin_natives(gas_params: NativeGasParameters) -> NativeFunctionTable
         "Secp256k1",
         starcoin_natives::secp256k1::make_all(gas_params.starcoin_natives.secp256k1)
     );
+    if stdlib_version >= StdlibVersion::Version(BLS12381_UPGRADE_VERSION_MARK) {
+        add_natives_from_module!(
+            "BLS12381",
+            starcoin_natives::bls12381::make_all(gas_params.starcoin_natives.bls12381)
+        );
+    }
 
     let natives = make_table_from_iter(CORE_CODE_ADDRESS, natives);
     natives
@@ -20,7 +20,7 @@ use num_cpus;
 use once_cell::sync::OnceCell;
 use starcoin_config::genesis_config::G_LATEST_GAS_PARAMS;
 use starcoin_crypto::HashValue;
-use starcoin_gas::{NativeGasParameters, StarcoinGasMeter, StarcoinGasParameters};
+use starcoin_gas::{CallFrame, NativeGasParameters, StarcoinGasMeter, StarcoinGasParameters};
 use starcoin_gas_algebra_ext::{
     CostTable, FromOnChainGasSchedule, Gas, GasConstants, GasCost, InitialGasSchedule,
 };
@@ -44,9 +44,9 @@ use starcoin_vm_types::account_config::{
     core_code_address, genesis_address, ModuleUpgradeStrategy, TwoPhaseUpgradeV2Resource,
     G_EPILOGUE_NAME, G_EPILOGUE_V2_NAME, G_PROLOGUE_NAME,
 };
-use starcoin_vm_types::errors::VMResult;
+use starcoin_vm_types::errors::{VMError, VMResult};
 use starcoin_vm_types::file_format::{CompiledModule, CompiledScript};
-use starcoin_vm_types::gas_schedule::G_LATEST_GAS_COST_TABLE;
+use starcoin_vm_types::gas_schedule::{NativeCostIndex, G_LATEST_GAS_COST_TABLE};
 use starcoin_vm_types::genesis_config::StdlibVersion;
 use starcoin_vm_types::identifier::IdentStr;
 use starcoin_vm_types::language_storage::ModuleId;
@@ -86,6 +86,8 @@ pub struct StarcoinVM {
     version: Option<Version>,
     move_version: Option<MoveLanguageVersion>,
     native_params: NativeGasParameters,
+    /// The stdlib version the natives of `move_vm` are registered for.
+    native_stdlib_version: StdlibVersion,
     gas_params: Option<StarcoinGasParameters>,
     gas_schedule: Option<GasSchedule>,
     flexi_dag_config: Option<FlexiDagConfig>,
@@ -103,7 +105,7 @@ impl StarcoinVM {
     pub fn new(metrics: Option<VMMetrics>) -> Self {
         let gas_params = StarcoinGasParameters::initial();
         let native_params = gas_params.natives.clone();
-        let inner = MoveVmExt::new(native_params.clone())
+        let inner = MoveVmExt::new(native_params.clone(), StdlibVersion::Latest)
             .expect("should be able to create Move VM; check if there are duplicated natives");
         Self {
             move_vm: Arc::new(inner),
@@ -111,6 +113,7 @@ impl StarcoinVM {
             version: None,
             move_version: None,
             native_params,
+            native_stdlib_version: StdlibVersion::Latest,
             gas_params: Some(gas_params),
             gas_schedule: None,
             flexi_dag_config: None,
@@ -121,7 +124,7 @@ impl StarcoinVM {
     pub fn new() -> Self {
         let gas_params = StarcoinGasParameters::initial();
         let native_params = gas_params.natives.clone();
-        let inner = MoveVmExt::new(native_params.clone())
+        let inner = MoveVmExt::new(native_params.clone(), StdlibVersion::Latest)
             .expect("should be able to create Move VM; check if there are duplicated natives");
         Self {
             move_vm: Arc::new(inner),
@@ -129,6 +132,7 @@ impl StarcoinVM {
             version: None,
             move_version: None,
             native_params,
+            native_stdlib_version: StdlibVersion::Latest,
             gas_params: Some(gas_params),
             gas_schedule: None,
             flexi_dag_config: None,
@@ -149,25 +153,52 @@ impl StarcoinVM {
             self.load_configs_impl(state)?;
         }
 
+        // the genesis is executed with the latest natives, the same as its gas schedule.
+        let stdlib_version = if state.is_genesis() {
+            StdlibVersion::Latest
+        } else {
+            self.version
+                .clone()
+                .ok_or_else(|| format_err!("failed to load version!"))?
+                .into_stdlib_version()
+        };
         match self.gas_schedule.as_ref() {
             None => {
                 bail!("failed to load gas schedule!");
             }
             Some(gs) => {
-                let gas_params =
-                    StarcoinGasParameters::from_on_chain_gas_schedule(&gs.clone().to_btree_map());
+                let gas_schedule = gs.clone().to_btree_map();
+                let gas_params = StarcoinGasParameters::from_on_chain_gas_schedule(&gas_schedule);
+                let bls12381_version =
+                    StdlibVersion::Version(crate::natives::BLS12381_UPGRADE_VERSION_MARK);
+                // register the natives of the version before the BLS12381 upgrade,
+                // if the gas schedule does not price the BLS12381 natives yet.
+                let stdlib_version = if stdlib_version >= bls12381_version
+                    && !crate::natives::has_bls12381_gas_schedule(&gas_schedule)
+                {
+                    debug!(
+                        "stdlib version: {}, the gas schedule has no BLS12381 costs, skip the BLS12381 natives",
+                        stdlib_version
+                    );
+                    StdlibVersion::Version(crate::natives::BLS12381_UPGRADE_VERSION_MARK - 1)
+                } else {
+                    stdlib_version
+                };
                 if let Some(ref params) = gas_params {
-                    if params.natives != self.native_params {
+                    if params.natives != self.native_params
+                        || stdlib_version != self.native_stdlib_version
+                    {
                         debug!("update native_params");
                         match Arc::get_mut(&mut self.move_vm) {
                             None => {
                                 bail!("failed to get move vm when load config");
                             }
                             Some(mv) => {
-                                mv.update_native_functions(params.clone().natives)?;
+                                mv.update_native_functions(params.clone().natives, stdlib_version)?;
                             }
                         }
                         self.native_params = params.natives.clone();
+                        self.native_stdlib_version = stdlib_version;
                     }
                     self.gas_params = gas_params;
                 }
@@ -240,7 +271,16 @@ impl StarcoinVM {
                         .ok_or_else(|| {
                             anyhow::anyhow!("Expect 0x1::VMConfig::native_schedule() return value")
                         })?;
-                    bcs_ext::from_bytes::<Vec<GasCost>>(&data)?
+                    let mut native_schedule = bcs_ext::from_bytes::<Vec<GasCost>>(&data)?;
+                    // the BLS12381 natives are not charged before the stdlib version includes them,
+                    // so the blocks before the upgrade are replayed with the same gas parameters.
+                    if stdlib_version
+                        < StdlibVersion::Version(crate::natives::BLS12381_UPGRADE_VERSION_MARK)
+                    {
+                        native_schedule
+                            .truncate(NativeCostIndex::BLS12381_VALIDATE_PUBKEY_BASE as usize);
+                    }
+                    native_schedule
                 };
                 let gas_constants = {
                     let data = self
@@ -641,16 +681,22 @@ impl StarcoinVM {
                     init_script.function(),
                     sender
                 );
-                session
-                    .execute_entry_function(
-                        init_script.module(),
-                        init_script.function(),
-                        init_script.ty_args().to_vec(),
-                        init_script.args().to_vec(),
-                        gas_meter,
-                        sender,
-                    )
-                    .map_err(|e| e.into_vm_status())?;
+                gas_meter.trace_enter(
+                    Some(init_script.module()),
+                    init_script.function().as_str(),
+                    init_script.ty_args(),
+                    init_script.args(),
+                );
+                let result = session.execute_entry_function(
+                    init_script.module(),
+                    init_script.function(),
+                    init_script.ty_args().to_vec(),
+                    init_script.args().to_vec(),
+                    gas_meter,
+                    sender,
+                );
+                gas_meter.trace_exit(result.as_ref().err().map(describe_vm_error));
+                result.map_err(|e| e.into_vm_status())?;
             }
             charge_global_write_gas_usage(gas_meter, &session, &txn_data.sender())?;
 
@@ -680,13 +726,14 @@ impl StarcoinVM {
             gas_meter
                 .charge_intrinsic_gas_for_transaction(txn_data.transaction_size())
                 .map_err(|e| e.into_vm_status())?;
-            match payload {
+            let result = match payload {
                 TransactionPayload::Script(script) => {
                     // we only use the ok path, let move vm handle the wrong path.
                     if let Ok(s) = CompiledScript::deserialize(script.code()) {
                         self.check_move_version(s.version() as u64)?;
                     };
                     debug!("TransactionPayload::{:?}", script);
+                    gas_meter.trace_enter(None, "main", script.ty_args(), script.args());
                     session.execute_script(
                         script.code().to_vec(),
                         script.ty_args().to_vec(),
@@ -697,6 +744,12 @@ impl StarcoinVM {
                 }
                 TransactionPayload::ScriptFunction(script_function) => {
                     debug!("TransactionPayload::{:?}", script_function);
+                    gas_meter.trace_enter(
+                        Some(script_function.module()),
+                        script_function.function().as_str(),
+                        script_function.ty_args(),
+                        script_function.args(),
+                    );
                     session.execute_entry_function(
                         script_function.module(),
                         script_function.function(),
@@ -709,8 +762,9 @@ impl StarcoinVM {
                 TransactionPayload::Package(_) => {
                     return Err(VMStatus::Error(StatusCode::UNREACHABLE));
                 }
-            }
-            .map_err(|e|
+            };
+            gas_meter.trace_exit(result.as_ref().err().map(describe_vm_error));
+            result.map_err(|e|
                 {
                     warn!("[VM] execute_script_function error, status_type: {:?}, status_code:{:?}, message:{:?}, location:{:?}", e.status_type(), e.major_status(), e.message(), e.location());
                     e.into_vm_status()
@@ -942,10 +996,20 @@ impl StarcoinVM {
         storage: &S,
         txn: SignedUserTransaction,
     ) -> (VMStatus, TransactionOutput) {
+        let (status, output, _) = self.execute_user_transaction_impl(storage, txn, false);
+        (status, output)
+    }
+
+    fn execute_user_transaction_impl<S: MoveResolverExt + StateView>(
+        &self,
+        storage: &S,
+        txn: SignedUserTransaction,
+        trace: bool,
+    ) -> (VMStatus, TransactionOutput, Option<Vec<CallFrame>>) {
         let txn_data = match TransactionMetadata::new(&txn) {
             Ok(txn_data) => txn_data,
             Err(e) => {
-                return discard_error_vm_status(e);
+                return with_trace(discard_error_vm_status(e), None);
             }
         };
         let gas_params = match self.get_gas_parameters() {
@@ -954,7 +1018,7 @@ impl StarcoinVM {
                 if storage.is_genesis() {
                     &G_LATEST_GAS_PARAMS
                 } else {
-                    return discard_error_vm_status(e);
+                    return with_trace(discard_error_vm_status(e), None);
                 }
             }
         };
@@ -965,13 +1029,16 @@ impl StarcoinVM {
             .into();
         let mut gas_meter = StarcoinGasMeter::new(gas_params.clone(), txn_data.max_gas_amount());
         gas_meter.set_metering(false);
+        if trace {
+            gas_meter.enable_tracing();
+        }
         // check signature
         let signature_checked_txn = match txn.check_signature() {
             Ok(t) => Ok(t),
             Err(_) => Err(VMStatus::Error(StatusCode::INVALID_SIGNATURE)),
         };
 
-        match signature_checked_txn {
+        let status_and_output = match signature_checked_txn {
             Ok(txn) => {
                 let result = match txn.payload() {
                     payload @ TransactionPayload::Script(_)
@@ -1008,7 +1075,20 @@ impl StarcoinVM {
                 }
             }
             Err(e) => discard_error_vm_status(e),
-        }
+        };
+        with_trace(status_and_output, gas_meter.take_trace())
+    }
+
+    /// Re-execute the user transaction on the `storage` with a call tracer,
+    /// the `storage` should be the state before the transaction is executed.
+    pub fn trace_transaction<S: MoveResolverExt + StateView>(
+        &mut self,
+        storage: &S,
+        txn: SignedUserTransaction,
+    ) -> Result<(VMStatus, TransactionOutput, Vec<CallFrame>)> {
+        self.load_configs(storage)?;
+        let (status, output, trace) = self.execute_user_transaction_impl(storage, txn, true);
+        Ok((status, output, trace.unwrap_or_default()))
     }
 
     pub fn dry_run_transaction<S: MoveResolverExt + StateView>(
@@ -1016,6 +1096,26 @@ impl StarcoinVM {
         storage: &S,
         txn: DryRunTransaction,
     ) -> Result<(VMStatus, TransactionOutput)> {
+        let (status, output, _) = self.dry_run_transaction_impl(storage, txn, false)?;
+        Ok((status, output))
+    }
+
+    /// Dry run the transaction with a call tracer, return the call tree of the execution.
+    pub fn dry_run_transaction_with_trace<S: MoveResolverExt + StateView>(
+        &mut self,
+        storage: &S,
+        txn: DryRunTransaction,
+    ) -> Result<(VMStatus, TransactionOutput, Vec<CallFrame>)> {
+        let (status, output, trace) = self.dry_run_transaction_impl(storage, txn, true)?;
+        Ok((status, output, trace.unwrap_or_default()))
+    }
+
+    fn dry_run_transaction_impl<S: MoveResolverExt + StateView>(
+        &mut self,
+        storage: &S,
+        txn: DryRunTransaction,
+        trace: bool,
+    ) -> Result<(VMStatus, TransactionOutput, Option<Vec<CallFrame>>)> {
         // TODO load config by config change event.
         self.load_configs(&storage)?;
 
@@ -1025,7 +1125,7 @@ impl StarcoinVM {
                 if storage.is_genesis() {
                     &G_LATEST_GAS_PARAMS
                 } else {
-                    return Ok(discard_error_vm_status(e));
+                    return Ok(with_trace(discard_error_vm_status(e), None));
                 }
             }
         };
@@ -1034,7 +1134,7 @@ impl StarcoinVM {
             txn.public_key.authentication_key_preimage(),
         ) {
             Ok(txn_data) => txn_data,
-            Err(e) => return Ok(discard_error_vm_status(e)),
+            Err(e) => return Ok(with_trace(discard_error_vm_status(e), None)),
         };
         let session = self
             .move_vm
@@ -1042,6 +1142,9 @@ impl StarcoinVM {
             .into();
         let mut gas_meter = StarcoinGasMeter::new(gas_params.clone(), txn_data.max_gas_amount());
         gas_meter.set_metering(false);
+        if trace {
+            gas_meter.enable_tracing();
+        }
         let result = match txn.raw_txn.payload() {
             payload @ TransactionPayload::Script(_)
             | payload @ TransactionPayload::ScriptFunction(_) => {
@@ -1051,7 +1154,7 @@ impl StarcoinVM {
                 self.execute_package(session, &mut gas_meter, &txn_data, p, storage)
             }
         };
-        Ok(match result {
+        let status_and_output = match result {
             Ok(status_and_output) => status_and_output,
             Err(err) => {
                 let txn_status = TransactionStatus::from(err.clone());
@@ -1061,7 +1164,8 @@ impl StarcoinVM {
                     self.failed_transaction_cleanup(err, &mut gas_meter, &txn_data, storage)
                 }
             }
-        })
+        };
+        Ok(with_trace(status_and_output, gas_meter.take_trace()))
     }
 
     fn check_reconfigure<S: StateView>(
@@ -1088,6 +1192,12 @@ impl StarcoinVM {
         transactions: Vec<Transaction>,
         block_gas_limit: Option<u64>,
     ) -> Result<Vec<(VMStatus, TransactionOutput)>, VMStatus> {
+        let _span = tracing::info_span!(
+            "execute_block_transactions",
+            txns = transactions.len(),
+            block_gas_limit = ?block_gas_limit
+        )
+        .entered();
         let mut data_cache = StateViewCache::new(storage);
         let mut result = vec![];
 
@@ -1534,6 +1644,31 @@ pub fn log_vm_status(
     }
 }
 
+fn with_trace(
+    (status, output): (VMStatus, TransactionOutput),
+    trace: Option<Vec<CallFrame>>,
+) -> (VMStatus, TransactionOutput, Option<Vec<CallFrame>>) {
+    (status, output, trace)
+}
+
+/// Describe the error of the transaction entry execution for the call trace.
+fn describe_vm_error(error: &VMError) -> String {
+    let location = match error.location() {
+        Location::Module(module_id) => module_id.to_string(),
+        location => format!("{:?}", location),
+    };
+    match error.sub_status() {
+        Some(code) if error.major_status() == StatusCode::ABORTED => {
+            let (category, reason) = error_split(code);
+            format!(
+                "ABORTED with code {} (Category: {:?} Reason: {:?}) in {}",
+                code, category, reason, location
+            )
+        }
+        _ => format!("{:?} in {}", error.major_status(), location),
+    }
+}
+
 // Executor external API
 impl VMExecutor for StarcoinVM {
     /// Execute a block of `transactions`. The output vector will have the exact same length as the
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use starcoin_config::genesis_config::G_LATEST_GAS_PARAMS;
 use starcoin_vm_types::access::ModuleAccess;
+use starcoin_vm_types::genesis_config::StdlibVersion;
 use starcoin_vm_types::normalized::Function;
 use std::collections::HashSet;
 use stdlib::load_latest_compiled_modules;
@@ -31,11 +32,13 @@ pub fn test_native_function_matches() -> Result<()> {
         })
         .collect();
 
-    let mut native_function_table =
-        starcoin_vm_runtime::natives::starcoin_natives(G_LATEST_GAS_PARAMS.clone().natives)
-            .iter()
-            .map(|(addr, m_name, f_name, _)| (*addr, m_name.to_string(), f_name.to_string()))
-            .collect::<HashSet<_>>();
+    let mut native_function_table = starcoin_vm_runtime::natives::starcoin_natives(
+        G_LATEST_GAS_PARAMS.clone().natives,
+        StdlibVersion::Latest,
+    )
+    .iter()
+    .map(|(addr, m_name, f_name, _)| (*addr, m_name.to_string(), f_name.to_string()))
+    .collect::<HashSet<_>>();
 
     for f in native_functions {
         assert!(